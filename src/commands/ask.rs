@@ -18,6 +18,7 @@ use colored::Colorize;
 use glob::glob;
 use octomind::config::Config;
 use octomind::session::chat::markdown::{is_markdown_content, MarkdownRenderer};
+use octomind::session::chat::print_citations;
 use octomind::session::{chat_completion_with_provider, Message, ProviderResponse};
 use rustyline::error::ReadlineError;
 use rustyline::{CompletionType, Config as RustylineConfig, EditMode, Editor};
@@ -46,10 +47,32 @@ pub struct AskArgs {
 	/// Output raw text without markdown rendering
 	#[arg(long)]
 	pub raw: bool,
+
+	/// Print only the answer text to stdout, without rendering, colors or citations
+	/// (errors go to stderr); needs the input as an argument or on stdin
+	#[arg(short = 'q', long)]
+	pub quiet: bool,
 }
 
 // Helper function to print content with optional markdown rendering for ask command
-fn print_response(content: &str, use_raw: bool, config: &Config) {
+fn print_response(response: &ProviderResponse, args: &AskArgs, config: &Config) {
+	// --quiet: the answer text and nothing else, so it can be captured as is
+	if args.quiet {
+		println!("{}", response.content);
+		return;
+	}
+
+	print_content(&response.content, args.raw, config);
+
+	// Render sources footer if enabled and provided by the model
+	if config.show_citations {
+		if let Some(ref citations) = response.citations {
+			print_citations(citations);
+		}
+	}
+}
+
+fn print_content(content: &str, use_raw: bool, config: &Config) {
 	if use_raw {
 		// Use plain text output
 		println!("{}", content);
@@ -268,7 +291,7 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 		.unwrap_or_else(|| config.get_effective_model());
 
 	// Simple system prompt for ask command - no mode complexity needed
-	let system_prompt = "You are a helpful assistant.".to_string();
+	let system_prompt = octomind::config::PLAIN_SYSTEM_PROMPT.to_string();
 
 	// Create a clean config with no MCP servers for ask command
 	// This ensures no tools are sent to the API
@@ -296,7 +319,7 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 			&clean_config,
 		)
 		.await?;
-		print_response(&response.content, args.raw, config);
+		print_response(&response, args, config);
 		Ok(())
 	} else if !std::io::stdin().is_terminal() {
 		// Read from stdin if it's being piped
@@ -324,8 +347,11 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 			&clean_config,
 		)
 		.await?;
-		print_response(&response.content, args.raw, config);
+		print_response(&response, args, config);
 		return Ok(());
+	} else if args.quiet {
+		eprintln!("Error: --quiet needs the input as an argument or on stdin.");
+		std::process::exit(1);
 	} else {
 		// Interactive multimode - no argument provided and stdin is a terminal
 		println!(
@@ -360,7 +386,7 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 					.await
 					{
 						Ok(response) => {
-							print_response(&response.content, args.raw, config);
+							print_response(&response, args, config);
 							println!(); // Add spacing between responses
 						}
 						Err(e) => {
@@ -406,6 +432,8 @@ async fn execute_single_query(
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		},
 		Message {
 			role: "user".to_string(),
@@ -419,6 +447,8 @@ async fn execute_single_query(
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		},
 	];
 
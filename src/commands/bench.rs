@@ -0,0 +1,268 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use futures::stream::{self, StreamExt};
+use octomind::config::Config;
+use octomind::session::chat::bench::{run_bench_prompt, BenchRun};
+use std::path::{Path, PathBuf};
+
+/// Separator line between prompts in a prompt file
+const PROMPT_SEPARATOR: &str = "---";
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+	/// File with prompts, separated by lines containing only "---"
+	#[arg(long, value_name = "FILE")]
+	pub prompts: PathBuf,
+
+	/// Comma-separated models to compare (provider:model format)
+	#[arg(long, value_name = "MODELS", value_delimiter = ',', required = true)]
+	pub models: Vec<String>,
+
+	/// Role whose system prompt and tools are used
+	#[arg(long, default_value = "developer")]
+	pub role: String,
+
+	/// Run without MCP tools (model answers from the prompt alone)
+	#[arg(long)]
+	pub no_tools: bool,
+
+	/// Temperature for all runs (defaults to the role temperature)
+	#[arg(long)]
+	pub temperature: Option<f32>,
+
+	/// Maximum number of runs in flight at once
+	#[arg(long, default_value = "2")]
+	pub concurrency: usize,
+
+	/// Write JSON results to this file (default: bench-<timestamp>.json)
+	#[arg(long, short, value_name = "FILE")]
+	pub output: Option<PathBuf>,
+}
+
+// Per-model totals for the comparison table
+#[derive(serde::Serialize, Debug, Default)]
+struct ModelSummary {
+	model: String,
+	runs: usize,
+	failures: usize,
+	avg_latency_ms: u64,
+	input_tokens: u64,
+	output_tokens: u64,
+	tool_calls: u64,
+	total_cost: f64,
+}
+
+pub async fn execute(args: &BenchArgs, config: &Config) -> Result<()> {
+	let prompts = load_prompts(&args.prompts)?;
+	let models: Vec<String> = args
+		.models
+		.iter()
+		.map(|m| m.trim().to_string())
+		.filter(|m| !m.is_empty())
+		.collect();
+	if models.is_empty() {
+		return Err(anyhow::anyhow!("No models given for --models"));
+	}
+	for model in &models {
+		octomind::providers::ProviderFactory::parse_model(model)?;
+	}
+
+	let mut config_for_role = config.get_merged_config_for_role(&args.role);
+	if args.no_tools {
+		config_for_role.mcp.servers.clear();
+	}
+	octomind::config::set_thread_config(&config_for_role);
+
+	// Initialize servers and tool map for the role, same as sessions do
+	if !config_for_role.mcp.servers.is_empty() {
+		if let Err(e) = octomind::mcp::initialize_servers_for_role(&config_for_role).await {
+			eprintln!("Warning: Failed to initialize MCP servers: {}", e);
+		}
+		if let Err(e) = octomind::mcp::tool_map::initialize_tool_map(&config_for_role).await {
+			eprintln!("Warning: Failed to initialize tool map: {}", e);
+		}
+	}
+
+	let total_runs = prompts.len() * models.len();
+	println!(
+		"{}",
+		format!(
+			"Running {} prompts x {} models ({} runs, concurrency {})",
+			prompts.len(),
+			models.len(),
+			total_runs,
+			args.concurrency.max(1)
+		)
+		.bright_cyan()
+	);
+
+	let jobs: Vec<(usize, &String, &String)> = models
+		.iter()
+		.flat_map(|model| {
+			prompts
+				.iter()
+				.enumerate()
+				.map(move |(index, prompt)| (index, model, prompt))
+		})
+		.collect();
+
+	let mut runs: Vec<BenchRun> = stream::iter(jobs)
+		.map(|(index, model, prompt)| {
+			run_bench_prompt(
+				&config_for_role,
+				&args.role,
+				model,
+				prompt,
+				index,
+				args.temperature,
+			)
+		})
+		.buffer_unordered(args.concurrency.max(1))
+		.collect()
+		.await;
+
+	// Keep a stable order regardless of completion order
+	runs.sort_by_key(|run| {
+		(
+			models.iter().position(|m| *m == run.model),
+			run.prompt_index,
+		)
+	});
+
+	let summaries: Vec<ModelSummary> = models.iter().map(|m| summarize(m, &runs)).collect();
+	print_table(&summaries);
+
+	let output_path = args.output.clone().unwrap_or_else(|| {
+		PathBuf::from(format!(
+			"bench-{}.json",
+			chrono::Local::now().format("%Y%m%d-%H%M%S")
+		))
+	});
+	let report = serde_json::json!({
+		"created_at": chrono::Utc::now().to_rfc3339(),
+		"role": args.role,
+		"tools": !config_for_role.mcp.servers.is_empty(),
+		"prompts": prompts,
+		"summary": summaries,
+		"runs": runs,
+	});
+	std::fs::write(&output_path, serde_json::to_string_pretty(&report)?).map_err(|e| {
+		anyhow::anyhow!(
+			"Failed to write results to {}: {}",
+			output_path.display(),
+			e
+		)
+	})?;
+	println!(
+		"{}",
+		format!("Results written to {}", output_path.display()).bright_green()
+	);
+
+	Ok(())
+}
+
+// Read prompts from a file, one prompt per "---" separated block
+fn load_prompts(path: &Path) -> Result<Vec<String>> {
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| anyhow::anyhow!("Failed to read prompts file {}: {}", path.display(), e))?;
+	let prompts = parse_prompts(&content);
+	if prompts.is_empty() {
+		return Err(anyhow::anyhow!("No prompts found in {}", path.display()));
+	}
+	Ok(prompts)
+}
+
+fn parse_prompts(content: &str) -> Vec<String> {
+	let mut prompts = Vec::new();
+	let mut current = Vec::new();
+	for line in content.lines().chain(std::iter::once(PROMPT_SEPARATOR)) {
+		if line.trim() == PROMPT_SEPARATOR {
+			let prompt = current.join("\n").trim().to_string();
+			if !prompt.is_empty() {
+				prompts.push(prompt);
+			}
+			current.clear();
+		} else {
+			current.push(line);
+		}
+	}
+	prompts
+}
+
+fn summarize(model: &str, runs: &[BenchRun]) -> ModelSummary {
+	let model_runs: Vec<&BenchRun> = runs.iter().filter(|r| r.model == model).collect();
+	let count = model_runs.len().max(1) as u64;
+	ModelSummary {
+		model: model.to_string(),
+		runs: model_runs.len(),
+		failures: model_runs.iter().filter(|r| r.error.is_some()).count(),
+		avg_latency_ms: model_runs.iter().map(|r| r.latency_ms).sum::<u64>() / count,
+		input_tokens: model_runs.iter().map(|r| r.input_tokens).sum(),
+		output_tokens: model_runs.iter().map(|r| r.output_tokens).sum(),
+		tool_calls: model_runs.iter().map(|r| r.tool_calls).sum(),
+		total_cost: model_runs.iter().map(|r| r.cost).sum(),
+	}
+}
+
+fn print_table(summaries: &[ModelSummary]) {
+	let model_width = summaries
+		.iter()
+		.map(|s| s.model.len())
+		.max()
+		.unwrap_or(0)
+		.max(5);
+
+	println!();
+	println!(
+		"{}",
+		format!(
+			"{:<width$}  {:>6}  {:>6}  {:>12}  {:>10}  {:>10}  {:>6}  {:>10}",
+			"Model",
+			"Runs",
+			"Failed",
+			"Avg latency",
+			"Input",
+			"Output",
+			"Tools",
+			"Cost",
+			width = model_width
+		)
+		.bold()
+	);
+	for s in summaries {
+		// Pad before colorizing so escape codes do not break the alignment
+		let failed = format!("{:>6}", s.failures);
+		let failed = if s.failures > 0 {
+			failed.bright_red()
+		} else {
+			failed.normal()
+		};
+		println!(
+			"{}  {:>6}  {}  {:>12}  {:>10}  {:>10}  {:>6}  {:>10}",
+			format!("{:<width$}", s.model, width = model_width).bright_cyan(),
+			s.runs,
+			failed,
+			format!("{:.2}s", s.avg_latency_ms as f64 / 1000.0),
+			s.input_tokens,
+			s.output_tokens,
+			s.tool_calls,
+			format!("${:.5}", s.total_cost),
+		);
+	}
+	println!();
+}
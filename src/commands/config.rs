@@ -39,10 +39,26 @@ pub struct ConfigArgs {
 	#[arg(long)]
 	pub mcp_server: Option<String>,
 
+	/// Disable an MCP server in the registry without removing it
+	#[arg(long, value_name = "NAME")]
+	pub disable_server: Option<String>,
+
+	/// Re-enable an MCP server disabled with --disable-server
+	#[arg(long, value_name = "NAME")]
+	pub enable_server: Option<String>,
+
 	/// Set custom system prompt (or 'default' to reset to default)
 	#[arg(long)]
 	pub system: Option<String>,
 
+	/// Load a role's system prompt from a file (or 'default' to use the inline prompt again)
+	#[arg(long, value_name = "PATH")]
+	pub system_file: Option<String>,
+
+	/// Role for --system-file and --show-system
+	#[arg(long, default_value = "developer")]
+	pub role: String,
+
 	/// Enable markdown rendering for AI responses
 	#[arg(long)]
 	pub markdown_enable: Option<bool>,
@@ -59,6 +75,14 @@ pub struct ConfigArgs {
 	#[arg(long)]
 	pub show: bool,
 
+	/// Print the resolved system prompt of --role, with placeholders expanded
+	#[arg(long)]
+	pub show_system: bool,
+
+	/// Export the configuration as a shareable template without credentials (stdout if no PATH)
+	#[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "-")]
+	pub export_template: Option<String>,
+
 	/// Validate configuration without making changes
 	#[arg(long)]
 	pub validate: bool,
@@ -66,6 +90,26 @@ pub struct ConfigArgs {
 	/// Upgrade config file to latest version
 	#[arg(long)]
 	pub upgrade: bool,
+
+	/// Open the config file in $VISUAL/$EDITOR and validate it after saving
+	#[arg(long)]
+	pub edit: bool,
+}
+
+// Print the exact system prompt a session with the role would start with (read-only)
+pub async fn show_system_prompt(args: &ConfigArgs, config: &Config) -> Result<(), anyhow::Error> {
+	if !config.role_map.contains_key(&args.role) {
+		return Err(anyhow::anyhow!(
+			"Role '{}' not found in the configuration",
+			args.role
+		));
+	}
+
+	// No servers are started: external servers contribute only their configured tools
+	let current_dir = std::env::current_dir()?;
+	let prompt = crate::session::create_system_prompt(&current_dir, config, &args.role).await;
+	println!("{}", prompt);
+	Ok(())
 }
 
 // Handle the configuration command
@@ -82,6 +126,19 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 		return Ok(());
 	}
 
+	// If export template flag is set, write the credential-free template and exit
+	if let Some(path) = &args.export_template {
+		let template = config.export_template()?;
+		if path == "-" {
+			print!("{}", template);
+		} else {
+			std::fs::write(path, template)
+				.map_err(|e| anyhow::anyhow!("Failed to write template to {}: {}", path, e))?;
+			println!("Configuration template written to {}", path);
+		}
+		return Ok(());
+	}
+
 	// If validation flag is set, just validate and exit
 	if args.validate {
 		match config.validate() {
@@ -266,6 +323,7 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						"type" => match value.to_lowercase().as_str() {
 							"http" => connection_type = McpConnectionType::Http,
 							"stdin" => connection_type = McpConnectionType::Stdin,
+							"sse" => connection_type = McpConnectionType::Sse,
 							"builtin" => connection_type = McpConnectionType::Builtin,
 							_ => println!("Unknown server type: {}, defaulting to HTTP", value),
 						},
@@ -323,6 +381,14 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						return Ok(());
 					}
 				}
+				McpConnectionType::Sse => {
+					if let Some(url) = url {
+						McpServerConfig::sse(&name, &url, timeout_seconds, Vec::new(), auth_token)
+					} else {
+						println!("Error: URL must be specified for SSE MCP server");
+						return Ok(());
+					}
+				}
 			};
 
 			// Enable MCP if not already enabled - REMOVED: MCP now controlled by server_refs
@@ -339,6 +405,31 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 		}
 	}
 
+	// Toggle the persistent disabled flag on registry servers
+	for (name, disable) in [(&args.disable_server, true), (&args.enable_server, false)] {
+		let Some(name) = name else {
+			continue;
+		};
+		if config.mcp.included_servers.contains(name) {
+			eprintln!(
+				"Error: MCP server '{}' is defined in mcp.servers_file; set disabled = {} there instead",
+				name, disable
+			);
+			return Ok(());
+		}
+		let Some(server) = config.mcp.servers.iter_mut().find(|s| s.name() == name) else {
+			eprintln!("Error: MCP server '{}' not found in the registry", name);
+			return Ok(());
+		};
+		server.set_disabled(disable);
+		println!(
+			"MCP server '{}' {}",
+			name,
+			if disable { "disabled" } else { "enabled" }
+		);
+		modified = true;
+	}
+
 	// Update system prompt if specified
 	if let Some(system_prompt) = &args.system {
 		if system_prompt.to_lowercase() == "default" {
@@ -353,6 +444,33 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 		modified = true;
 	}
 
+	// Point a role's system prompt at a file
+	if let Some(system_file) = &args.system_file {
+		let Some(role) = config.roles.iter_mut().find(|r| r.name == args.role) else {
+			eprintln!("Error: Role '{}' not found in the configuration", args.role);
+			return Ok(());
+		};
+		if system_file.to_lowercase() == "default" {
+			role.config.system_file = None;
+			println!("Role '{}' uses its inline system prompt", args.role);
+		} else {
+			let path = std::path::Path::new(system_file);
+			if !path.is_file() {
+				eprintln!("Error: System prompt file not found: {}", system_file);
+				return Ok(());
+			}
+			// Stored absolute, relative paths in the config resolve against its directory
+			let path = path.canonicalize()?;
+			role.config.system_file = Some(path.to_string_lossy().to_string());
+			println!(
+				"Role '{}' loads its system prompt from {}",
+				args.role,
+				path.display()
+			);
+		}
+		modified = true;
+	}
+
 	// If no modifications were made, create a default config
 	if !modified {
 		// Check if config file already exists
@@ -400,16 +518,9 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 
 	// Show MCP status using the new structure
 	// MCP is enabled per-role based on server_refs, not a global flag
-	let dev_mcp_enabled = config
-		.role_map
-		.get("developer")
-		.map(|r| !r.mcp.server_refs.is_empty())
-		.unwrap_or(false);
-	let ass_mcp_enabled = config
-		.role_map
-		.get("assistant")
-		.map(|r| !r.mcp.server_refs.is_empty())
-		.unwrap_or(false);
+	// (resolved, so roles inheriting the server list count as enabled)
+	let dev_mcp_enabled = !config.get_role_config("developer").1.server_refs.is_empty();
+	let ass_mcp_enabled = !config.get_role_config("assistant").1.server_refs.is_empty();
 
 	println!("MCP status:");
 	println!(
@@ -455,6 +566,13 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						"agent" => println!("  - {} (built-in agent tool) - available", name),
 						_ => println!("  - {} (built-in tools) - available", name),
 					},
+					McpConnectionType::Sse => {
+						println!(
+							"  - {} (SSE: {}) - available",
+							name,
+							server.url().unwrap_or_default()
+						);
+					}
 					McpConnectionType::Http | McpConnectionType::Stdin => {
 						if name == "octocode" {
 							// Check if octocode binary is available
@@ -512,6 +630,71 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 	Ok(())
 }
 
+/// Open the config file in the user's editor, re-validating after every save
+// Runs before the config is loaded so a broken config can still be fixed this way
+pub fn edit_config() -> Result<(), anyhow::Error> {
+	use std::io::Write;
+
+	let config_path = Config::create_default_config()?;
+
+	loop {
+		open_in_editor(&config_path)?;
+
+		match Config::load_from_path(&config_path) {
+			Ok(_) => {
+				println!("✅ Configuration is valid: {}", config_path.display());
+				return Ok(());
+			}
+			Err(e) => {
+				eprintln!("❌ Configuration validation failed: {:#}", e);
+				print!("Reopen the editor to fix it? [Y/n]: ");
+				std::io::stdout().flush()?;
+				let mut answer = String::new();
+				std::io::stdin().read_line(&mut answer)?;
+				if answer.trim().eq_ignore_ascii_case("n") {
+					eprintln!(
+						"⚠️  Invalid configuration left at {}; octomind will not start until it is fixed",
+						config_path.display()
+					);
+					return Err(e);
+				}
+			}
+		}
+	}
+}
+
+// Launch $VISUAL / $EDITOR (which may include arguments, e.g. "code --wait") and wait for it
+fn open_in_editor(path: &std::path::Path) -> Result<(), anyhow::Error> {
+	let editor = std::env::var("VISUAL")
+		.or_else(|_| std::env::var("EDITOR"))
+		.ok()
+		.filter(|e| !e.trim().is_empty())
+		.unwrap_or_else(|| {
+			if cfg!(windows) {
+				"notepad".to_string()
+			} else {
+				"vi".to_string()
+			}
+		});
+
+	let mut parts = editor.split_whitespace();
+	let program = parts.next().unwrap_or("vi");
+	let status = std::process::Command::new(program)
+		.args(parts)
+		.arg(path)
+		.status()
+		.map_err(|e| anyhow::anyhow!("Failed to launch editor '{}': {}", editor, e))?;
+
+	if !status.success() {
+		return Err(anyhow::anyhow!(
+			"Editor '{}' exited with {}",
+			editor,
+			status
+		));
+	}
+	Ok(())
+}
+
 /// Display available markdown themes with descriptions
 fn list_markdown_themes() {
 	println!("🎨 Available Markdown Themes\n");
@@ -647,7 +830,9 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 		config.get_effective_model()
 	);
 	println!("    Layers enabled:  {}", dev_config.enable_layers);
-	if let Some(_system) = dev_system {
+	if let Some(system_file) = &dev_config.system_file {
+		println!("    System prompt:   File ({})", system_file);
+	} else if let Some(_system) = dev_system {
 		println!("    System prompt:   Custom");
 	} else {
 		println!("    System prompt:   Default");
@@ -662,7 +847,9 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 		config.get_effective_model()
 	);
 	println!("    Layers enabled:  {}", ass_config.enable_layers);
-	if let Some(_system) = ass_system {
+	if let Some(system_file) = &ass_config.system_file {
+		println!("    System prompt:   File ({})", system_file);
+	} else if let Some(_system) = ass_system {
 		println!("    System prompt:   Custom");
 	} else {
 		println!("    System prompt:   Default");
@@ -686,22 +873,14 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 	println!("  Developer Role MCP:");
 	println!(
 		"    Server refs:     {}",
-		if dev_mcp.server_refs.is_empty() {
-			"None (MCP disabled)".to_string()
-		} else {
-			dev_mcp.server_refs.join(", ")
-		}
+		format_server_refs(&dev_mcp.server_refs, &config.mcp.servers)
 	);
 
 	// Assistant role MCP
 	println!("  Assistant Role MCP:");
 	println!(
 		"    Server refs:     {}",
-		if ass_mcp.server_refs.is_empty() {
-			"None (MCP disabled)".to_string()
-		} else {
-			ass_mcp.server_refs.join(", ")
-		}
+		format_server_refs(&ass_mcp.server_refs, &config.mcp.servers)
 	);
 	println!();
 
@@ -742,6 +921,25 @@ fn show_env_api_key_status(provider: &str, env_var: &str) {
 	}
 }
 
+/// Format role server references, marking servers disabled in the registry
+fn format_server_refs(server_refs: &[String], servers: &[McpServerConfig]) -> String {
+	if server_refs.is_empty() {
+		return "None (MCP disabled)".to_string();
+	}
+	server_refs
+		.iter()
+		.map(|name| {
+			let disabled = servers.iter().any(|s| s.name() == name && s.is_disabled());
+			if disabled {
+				format!("{} (disabled)", name)
+			} else {
+				name.clone()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
 /// Display MCP server configurations
 fn show_mcp_servers(servers: &Vec<McpServerConfig>) {
 	if servers.is_empty() {
@@ -767,6 +965,13 @@ fn show_mcp_servers(servers: &Vec<McpServerConfig>) {
 				"agent" => println!("      🤖 {} (built-in agent tool)", name),
 				_ => println!("      📦 {} (built-in tools)", name),
 			},
+			McpConnectionType::Sse => {
+				println!(
+					"      🌐 {} (SSE: {})",
+					name,
+					server.url().unwrap_or_default()
+				);
+			}
 			McpConnectionType::Http | McpConnectionType::Stdin => {
 				if name == "octocode" {
 					// Check if octocode binary is available
@@ -792,6 +997,12 @@ fn show_mcp_servers(servers: &Vec<McpServerConfig>) {
 		}
 
 		// Show additional server details if configured
+		if server.is_disabled() {
+			println!(
+				"        Status: disabled (octomind config --enable-server {})",
+				name
+			);
+		}
 		if server.timeout_seconds() != 30 {
 			println!("        Timeout: {} seconds", server.timeout_seconds());
 		}
@@ -0,0 +1,277 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+// How often a followed log is checked for new entries
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Characters of a message, parameter list or tool result shown per entry
+const PREVIEW_CHARS: usize = 160;
+
+#[derive(Args, Debug)]
+pub struct LogsArgs {
+	/// Session whose log to show (default: the most recently active session)
+	#[arg(long, short)]
+	pub session: Option<String>,
+
+	/// Keep watching the log and print entries as they are written
+	#[arg(long, short)]
+	pub follow: bool,
+
+	/// Only show these entry types, e.g. tool_call,tool_result,stats
+	#[arg(long = "type", short = 't', value_name = "TYPE", value_delimiter = ',')]
+	pub types: Vec<String>,
+
+	/// Entries to show from the end of the log before following (0 = whole log)
+	#[arg(long, short = 'n', default_value = "20")]
+	pub lines: usize,
+
+	/// Print entries as raw JSON lines
+	#[arg(long)]
+	pub json: bool,
+}
+
+pub fn execute(args: &LogsArgs) -> Result<()> {
+	let session_name = match &args.session {
+		Some(name) => name.clone(),
+		None => octomind::session::latest_session_name()?
+			.ok_or_else(|| anyhow::anyhow!("No sessions found, nothing to show"))?,
+	};
+	let log_file = octomind::session::logger::get_session_log_path(&session_name)?;
+	if !log_file.exists() {
+		return Err(anyhow::anyhow!(
+			"No log for session '{}' (looked for {})",
+			session_name,
+			log_file.display()
+		));
+	}
+	// On stderr, so --json output can be piped as is
+	eprintln!(
+		"{}",
+		format!("Session '{}': {}", session_name, log_file.display()).dimmed()
+	);
+
+	// Last entries of the log written so far
+	let content = std::fs::read(&log_file)?;
+	let mut position = content.len() as u64;
+	let content = String::from_utf8_lossy(&content);
+	let entries: Vec<&str> = content
+		.lines()
+		.filter(|line| matches_types(line, &args.types))
+		.collect();
+	let start = match args.lines {
+		0 => 0,
+		lines => entries.len().saturating_sub(lines),
+	};
+	for line in &entries[start..] {
+		print_entry(line, args.json);
+	}
+
+	if args.follow {
+		follow_log(&log_file, &mut position, args)?;
+	}
+	Ok(())
+}
+
+// Print entries appended after position until interrupted
+fn follow_log(log_file: &Path, position: &mut u64, args: &LogsArgs) -> Result<()> {
+	let mut pending: Vec<u8> = Vec::new();
+	loop {
+		std::thread::sleep(POLL_INTERVAL);
+
+		let len = std::fs::metadata(log_file)?.len();
+		if len < *position {
+			// The log was rewritten (e.g. by session migrate) - start over
+			*position = 0;
+			pending.clear();
+		}
+		if len == *position {
+			continue;
+		}
+
+		let mut file = File::open(log_file)?;
+		file.seek(SeekFrom::Start(*position))?;
+		let read = file.read_to_end(&mut pending)?;
+		*position += read as u64;
+
+		// Entries are single lines; a line still being written stays pending
+		while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+			let line: Vec<u8> = pending.drain(..=end).collect();
+			let line = String::from_utf8_lossy(&line);
+			let line = line.trim_end();
+			if !line.is_empty() && matches_types(line, &args.types) {
+				print_entry(line, args.json);
+			}
+		}
+	}
+}
+
+// Entry type of a log line: the JSON "type" field, or the prefix of legacy "TYPE: {...}" lines
+fn entry_type(line: &str) -> Option<String> {
+	match serde_json::from_str::<Value>(line) {
+		Ok(entry) => entry
+			.get("type")
+			.and_then(|t| t.as_str())
+			.map(str::to_string),
+		Err(_) => line.split_once(": ").map(|(prefix, _)| prefix.to_string()),
+	}
+}
+
+fn matches_types(line: &str, types: &[String]) -> bool {
+	if types.is_empty() {
+		return true;
+	}
+	match entry_type(line) {
+		Some(entry_type) => types.iter().any(|t| t.eq_ignore_ascii_case(&entry_type)),
+		None => false,
+	}
+}
+
+fn print_entry(line: &str, json: bool) {
+	if json {
+		println!("{}", line);
+		return;
+	}
+	let Ok(entry) = serde_json::from_str::<Value>(line) else {
+		println!("{}", preview(line));
+		return;
+	};
+
+	let entry_type = entry
+		.get("type")
+		.and_then(|t| t.as_str())
+		.unwrap_or("UNKNOWN");
+	let time = entry
+		.get("timestamp")
+		.and_then(|t| t.as_i64())
+		.and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+		.map(|dt| {
+			dt.with_timezone(&chrono::Local)
+				.format("%H:%M:%S")
+				.to_string()
+		})
+		.unwrap_or_else(|| "--:--:--".to_string());
+	let label = format!("{:<13}", entry_type);
+	let label = match entry_type {
+		"USER" => label.bright_blue(),
+		"ASSISTANT" => label.bright_green(),
+		"TOOL_CALL" => label.bright_yellow(),
+		"TOOL_RESULT" => label.yellow(),
+		"STATS" => label.bright_magenta(),
+		"ERROR" => label.bright_red(),
+		_ => label.cyan(),
+	};
+	println!(
+		"{} {} {}",
+		time.dimmed(),
+		label,
+		entry_details(entry_type, &entry)
+	);
+}
+
+// One-line description of an entry's payload
+fn entry_details(entry_type: &str, entry: &Value) -> String {
+	let text = |key: &str| entry.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+	let number = |key: &str| entry.get(key).and_then(|v| v.as_u64()).unwrap_or_default();
+
+	match entry_type {
+		"USER" | "ASSISTANT" | "SYSTEM" => preview(text("content")),
+		"TOOL_CALL" => format!(
+			"{} {}",
+			text("tool_name").bright_white(),
+			preview(
+				&entry
+					.get("parameters")
+					.map(Value::to_string)
+					.unwrap_or_default()
+			)
+		),
+		"TOOL_RESULT" => {
+			let result = match entry.get("result") {
+				Some(Value::String(result)) => preview(result),
+				Some(result) => preview(&result.to_string()),
+				None => "(stored in the tool results directory)".to_string(),
+			};
+			format!(
+				"{} {} {}",
+				text("tool_id"),
+				format!("{}ms", number("execution_time_ms")).dimmed(),
+				result
+			)
+		}
+		"STATS" => format!(
+			"${:.5} | {} input, {} output, {} cached tokens | {} tool calls",
+			entry
+				.get("total_cost")
+				.and_then(|v| v.as_f64())
+				.unwrap_or_default(),
+			number("input_tokens"),
+			number("output_tokens"),
+			number("cached_tokens"),
+			number("tool_calls")
+		),
+		"API_REQUEST" => format!(
+			"model {}",
+			entry
+				.pointer("/data/model")
+				.and_then(|v| v.as_str())
+				.unwrap_or("unknown")
+		),
+		"API_RESPONSE" => match entry.get("usage").filter(|usage| !usage.is_null()) {
+			Some(usage) => format!(
+				"{} prompt, {} output tokens",
+				usage
+					.get("prompt_tokens")
+					.and_then(|v| v.as_u64())
+					.unwrap_or_default(),
+				usage
+					.get("output_tokens")
+					.and_then(|v| v.as_u64())
+					.unwrap_or_default()
+			),
+			None => "no usage reported".to_string(),
+		},
+		"COMMAND" => text("command").to_string(),
+		"CACHE" => format!("{}: {}", text("operation"), text("details")),
+		"ERROR" => preview(text("error")),
+		"RESTORATION_POINT" => preview(text("user_message")),
+		"SUMMARY" => "session saved".to_string(),
+		_ => {
+			let mut payload = entry.clone();
+			if let Some(fields) = payload.as_object_mut() {
+				fields.remove("type");
+				fields.remove("timestamp");
+			}
+			preview(&payload.to_string())
+		}
+	}
+}
+
+// First line of a text, cut to PREVIEW_CHARS
+fn preview(text: &str) -> String {
+	let first_line = text.trim().lines().next().unwrap_or_default();
+	match first_line.char_indices().nth(PREVIEW_CHARS) {
+		Some((end, _)) => format!("{}…", &first_line[..end]),
+		None if first_line.len() < text.trim().len() => format!("{} …", first_line),
+		None => first_line.to_string(),
+	}
+}
@@ -0,0 +1,231 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::*;
+use octomind::config::{Config, McpServerConfig};
+use std::io::{self, Write};
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct McpArgs {
+	#[command(subcommand)]
+	pub command: McpCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum McpCommand {
+	/// Probe a remote HTTP MCP server and add it to the configuration with its tools
+	Add(McpAddArgs),
+
+	/// Check that the parameters schema of every tool a role gets is well-formed
+	ValidateSchemas(McpValidateSchemasArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct McpValidateSchemasArgs {
+	/// Role whose MCP servers are started and checked
+	#[arg(long, default_value = "developer")]
+	pub role: String,
+}
+
+#[derive(Args, Debug)]
+pub struct McpAddArgs {
+	/// URL of the MCP server's JSON-RPC endpoint
+	#[arg(value_name = "URL")]
+	pub url: String,
+
+	/// Server name in the registry (default: derived from the URL host)
+	#[arg(long)]
+	pub name: Option<String>,
+
+	/// Bearer token sent to the server
+	#[arg(long)]
+	pub token: Option<String>,
+
+	/// Request timeout in seconds, also stored in the server entry
+	#[arg(long, default_value = "30")]
+	pub timeout: u64,
+
+	/// Add the server without asking for confirmation
+	#[arg(long, short)]
+	pub yes: bool,
+}
+
+pub async fn execute(args: &McpArgs, config: Config) -> Result<()> {
+	match &args.command {
+		McpCommand::Add(add_args) => add_server(add_args, config).await,
+		McpCommand::ValidateSchemas(validate_args) => {
+			validate_schemas(validate_args, &config).await
+		}
+	}
+}
+
+// Start the role's servers and check the schema of each tool they offer
+// Fails when any schema is malformed, so it can gate CI
+async fn validate_schemas(args: &McpValidateSchemasArgs, config: &Config) -> Result<()> {
+	let config_for_role = config.get_merged_config_for_role(&args.role);
+	octomind::config::set_thread_config(&config_for_role);
+
+	// Initialize servers and tool map for the role, same as sessions do
+	if let Err(e) = octomind::mcp::initialize_servers_for_role(&config_for_role).await {
+		eprintln!("Warning: Failed to initialize MCP servers: {}", e);
+	}
+	if let Err(e) = octomind::mcp::tool_map::initialize_tool_map(&config_for_role).await {
+		eprintln!("Warning: Failed to initialize tool map: {}", e);
+	}
+
+	let functions = octomind::mcp::get_available_functions(&config_for_role).await;
+	if functions.is_empty() {
+		println!("Role '{}' has no MCP tools to check.", args.role);
+		return Ok(());
+	}
+
+	let mut malformed = 0;
+	for function in &functions {
+		let errors = octomind::mcp::tool_schema::check_tool_schema(&function.parameters);
+		if errors.is_empty() {
+			continue;
+		}
+		malformed += 1;
+		let server = octomind::mcp::tool_map::get_tool_server_name(&function.name)
+			.unwrap_or_else(|| "unknown".to_string());
+		println!(
+			"{} {} ({})",
+			"✗".bright_red(),
+			function.name.bright_yellow(),
+			server.bright_blue()
+		);
+		for error in errors {
+			println!("    {}", error);
+		}
+	}
+
+	if malformed == 0 {
+		println!(
+			"{} all {} tool schemas of role '{}' are well-formed",
+			"✓".bright_green(),
+			functions.len(),
+			args.role
+		);
+		return Ok(());
+	}
+	Err(anyhow::anyhow!(
+		"{} of {} tool schemas are malformed - providers may reject requests that include them",
+		malformed,
+		functions.len()
+	))
+}
+
+// Connect to the server, list its tools and write a [[mcp.servers]] entry for it
+async fn add_server(args: &McpAddArgs, mut config: Config) -> Result<()> {
+	let url = url::Url::parse(&args.url)
+		.map_err(|e| anyhow::anyhow!("Invalid MCP server URL '{}': {}", args.url, e))?;
+	let name = match &args.name {
+		Some(name) => name.clone(),
+		None => server_name_from_url(&url)?,
+	};
+	if config.mcp.servers.iter().any(|s| s.name() == name) {
+		return Err(anyhow::anyhow!(
+			"MCP server '{}' already exists in the configuration, choose another --name",
+			name
+		));
+	}
+
+	let mut server = McpServerConfig::remote_http(
+		&name,
+		url.as_str(),
+		args.timeout,
+		Vec::new(),
+		args.token.clone(),
+	);
+
+	println!("Probing {} ...", url.as_str().bright_cyan());
+	let functions = tokio::time::timeout(
+		Duration::from_secs(args.timeout),
+		octomind::mcp::server::get_server_functions(&server),
+	)
+	.await
+	.map_err(|_| anyhow::anyhow!("MCP server did not answer within {}s", args.timeout))?
+	.map_err(|e| anyhow::anyhow!("Failed to list tools from {}: {}", url, e))?;
+
+	if functions.is_empty() {
+		return Err(anyhow::anyhow!(
+			"MCP server at {} reported no tools, nothing to add",
+			url
+		));
+	}
+
+	println!(
+		"{} {} tools:",
+		"✓ Server responded with".bright_green(),
+		functions.len()
+	);
+	for function in &functions {
+		let summary = function.description.lines().next().unwrap_or_default();
+		println!("  {} - {}", function.name.bright_yellow(), summary);
+	}
+
+	let mut tools: Vec<String> = functions.into_iter().map(|f| f.name).collect();
+	tools.sort();
+	if let McpServerConfig::Http {
+		tools: server_tools,
+		..
+	} = &mut server
+	{
+		*server_tools = tools;
+	}
+
+	println!();
+	println!(
+		"{}",
+		toml::to_string_pretty(&serde_json::json!({ "mcp": { "servers": [&server] } }))?
+			.trim_end()
+			.dimmed()
+	);
+
+	if !args.yes {
+		print!("\nAdd MCP server '{}' to the configuration? [y/N]: ", name);
+		io::stdout().flush()?;
+
+		let mut input = String::new();
+		io::stdin().read_line(&mut input)?;
+		let input = input.trim().to_lowercase();
+		if input != "y" && input != "yes" {
+			println!("MCP server not added.");
+			return Ok(());
+		}
+	}
+
+	config.mcp.servers.push(server);
+	config.save()?;
+
+	println!(
+		"Added MCP server '{}'. Add \"{}\" to a role's mcp.server_refs to use it.",
+		name, name
+	);
+	Ok(())
+}
+
+// Registry name from the URL host, e.g. mcp.example.com -> mcp_example_com
+fn server_name_from_url(url: &url::Url) -> Result<String> {
+	let host = url.host_str().ok_or_else(|| {
+		anyhow::anyhow!("MCP server URL '{}' has no host, pass --name", url.as_str())
+	})?;
+	Ok(host
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+		.collect())
+}
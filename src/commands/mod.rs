@@ -13,16 +13,26 @@
 // limitations under the License.
 
 pub mod ask;
+pub mod bench;
 pub mod config;
+pub mod logs;
+pub mod mcp;
+pub mod roles;
 pub mod run;
 pub mod session;
 pub mod shell;
+pub mod tool;
 pub mod vars;
 
 // Re-export all the command structs and enums
 pub use ask::AskArgs;
+pub use bench::BenchArgs;
 pub use config::ConfigArgs;
+pub use logs::LogsArgs;
+pub use mcp::McpArgs;
+pub use roles::RolesArgs;
 pub use run::RunArgs;
-pub use session::SessionArgs;
+pub use session::{SessionArgs, SessionCommand};
 pub use shell::ShellArgs;
+pub use tool::ToolArgs;
 pub use vars::VarsArgs;
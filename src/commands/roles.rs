@@ -0,0 +1,97 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use octomind::config::Config;
+use std::io::{self, IsTerminal, Write};
+
+#[derive(Args, Debug)]
+pub struct RolesArgs {
+	/// Only list the roles, without asking for one to start a session with
+	#[arg(long, short)]
+	pub list: bool,
+}
+
+/// List the configured roles and let the user pick one to start a session with
+/// Returns the picked role, None when only listing or nothing was picked
+pub fn execute(args: &RolesArgs, config: &Config) -> Result<Option<String>> {
+	if config.roles.is_empty() {
+		println!("No roles configured.");
+		return Ok(None);
+	}
+
+	let model = config.get_effective_model();
+	for (index, role) in config.roles.iter().enumerate() {
+		let (role_config, mcp_config, _, _, _) = config.get_role_config(&role.name);
+		println!(
+			"{} {}",
+			format!("{:>3}.", index + 1).dimmed(),
+			role.name.bright_cyan().bold()
+		);
+		if !role.description.is_empty() {
+			println!("     {}", role.description);
+		}
+		println!("     {} {}", "model:".dimmed(), model);
+		let layers = config.get_layer_refs(&role.name);
+		let layers = if !role_config.enable_layers || layers.is_empty() {
+			"off".to_string()
+		} else {
+			layers.join(", ")
+		};
+		println!("     {} {}", "layers:".dimmed(), layers);
+		let servers = if mcp_config.server_refs.is_empty() {
+			"none".to_string()
+		} else {
+			mcp_config.server_refs.join(", ")
+		};
+		println!("     {} {}", "servers:".dimmed(), servers);
+	}
+
+	if args.list || !io::stdin().is_terminal() {
+		return Ok(None);
+	}
+
+	println!();
+	loop {
+		print!(
+			"{}",
+			format!(
+				"Start a session with role [1-{} or name, Enter to quit]: ",
+				config.roles.len()
+			)
+			.bright_cyan()
+		);
+		io::stdout().flush()?;
+
+		let mut input = String::new();
+		io::stdin().read_line(&mut input)?;
+		let input = input.trim();
+		if input.is_empty() {
+			return Ok(None);
+		}
+
+		let picked = match input.parse::<usize>() {
+			Ok(number) => number
+				.checked_sub(1)
+				.and_then(|index| config.roles.get(index)),
+			Err(_) => config.roles.iter().find(|role| role.name == input),
+		};
+		match picked {
+			Some(role) => return Ok(Some(role.name.clone())),
+			None => println!("{}", format!("No role '{}'", input).bright_red()),
+		}
+	}
+}
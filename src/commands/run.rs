@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use clap::Args;
+use octomind::config::RunConfig;
+use octomind::session::chat::{RunOutcome, RunStatus};
+use regex::Regex;
 use std::io::{self, IsTerminal, Read};
 
 #[derive(Args, Debug)]
@@ -21,6 +24,10 @@ pub struct RunArgs {
 	#[arg(value_name = "INPUT")]
 	pub input: Option<String>,
 
+	/// Read the input from a file instead of the argument or stdin
+	#[arg(long, value_name = "PATH", conflicts_with = "input")]
+	pub input_file: Option<std::path::PathBuf>,
+
 	/// Name of the session to start or resume
 	#[arg(long, short)]
 	pub name: Option<String>,
@@ -40,6 +47,38 @@ pub struct RunArgs {
 	/// Session role: developer (default with layers and tools) or assistant (simple chat without tools)
 	#[arg(long, default_value = "developer")]
 	pub role: String,
+
+	/// Send the input straight to the model, without the layer pipeline
+	#[arg(long)]
+	pub skip_layers: bool,
+
+	/// Replay tool results recorded in an existing session (name + args match), executing on a miss
+	#[arg(long, value_name = "SESSION")]
+	pub replay_tools_from: Option<String>,
+
+	/// Record every tool call (name, arguments, result, timing) as numbered files in this directory
+	#[arg(long, value_name = "DIR")]
+	pub trace_tools: Option<String>,
+
+	/// JSON schema file the final response must match (JSON output, re-prompted on mismatch)
+	#[arg(long, value_name = "FILE")]
+	pub response_schema: Option<std::path::PathBuf>,
+
+	/// Exit with a failure code when the final response matches this regex (overrides run.fail_on)
+	#[arg(long, value_name = "REGEX")]
+	pub fail_on: Option<String>,
+
+	/// Stop the whole run after this many seconds (overrides session_max_duration_seconds)
+	#[arg(long, value_name = "SECONDS")]
+	pub max_duration: Option<u64>,
+
+	/// Keep the session in memory only: no session file, log or scratchpad is written
+	#[arg(long, conflicts_with = "resume")]
+	pub no_save: bool,
+
+	/// Override a layer's temperature for this run, e.g. --layer-temp review=0.2 (repeatable)
+	#[arg(long = "layer-temp", value_name = "LAYER=TEMP", value_parser = octomind::config::parse_layer_temperature)]
+	pub layer_temp: Vec<(String, f32)>,
 }
 
 impl RunArgs {
@@ -48,17 +87,71 @@ impl RunArgs {
 		super::SessionArgs {
 			name: self.name.clone(),
 			resume: self.resume.clone(),
+			resume_latest: false,
+			skip_layers: self.skip_layers,
 			model: self.model.clone(),
 			temperature: self.temperature,
 			role: self.role.clone(),
+			replay_tools_from: self.replay_tools_from.clone(),
+			trace_tools: self.trace_tools.clone(),
+			response_schema: self.response_schema.clone(),
+			confirm_tools: None, // Non-interactive: tool calls are never confirmed
+			plain: false,
+			no_save: self.no_save,
+			layer_temp: self.layer_temp.clone(),
+			command: None,
 		}
 	}
 
-	/// Get the actual input, either from parameter or stdin
+	/// Compile the failure pattern from --fail-on or the run.fail_on config
+	pub fn fail_on_regex(&self, run_config: &RunConfig) -> Result<Option<Regex>, anyhow::Error> {
+		let pattern = match self.fail_on.as_ref().or(run_config.fail_on.as_ref()) {
+			Some(pattern) if !pattern.is_empty() => pattern,
+			_ => return Ok(None),
+		};
+		Regex::new(pattern)
+			.map(Some)
+			.map_err(|e| anyhow::anyhow!("Invalid --fail-on regex '{}': {}", pattern, e))
+	}
+
+	/// Process exit code for the outcome of a run (0 on success)
+	pub fn exit_code(outcome: &RunOutcome, fail_on: Option<&Regex>, run_config: &RunConfig) -> i32 {
+		match outcome.status {
+			RunStatus::ApiError => run_config.api_error_exit_code,
+			RunStatus::SpendingLimit => run_config.spending_limit_exit_code,
+			RunStatus::TimeLimit => run_config.time_limit_exit_code,
+			RunStatus::Completed => match fail_on {
+				Some(regex) if regex.is_match(&outcome.final_response) => {
+					eprintln!("Run failed: final response matches --fail-on pattern");
+					run_config.fail_on_exit_code
+				}
+				_ => 0,
+			},
+		}
+	}
+
+	/// Get the actual input, either from parameter, input file or stdin
 	pub fn get_input(&self) -> Result<String, anyhow::Error> {
 		if let Some(input) = &self.input {
 			// Input provided as parameter
 			Ok(input.clone())
+		} else if let Some(path) = &self.input_file {
+			// Input file takes precedence over piped stdin
+			if !path.is_file() {
+				return Err(anyhow::anyhow!("Input file not found: {}", path.display()));
+			}
+			let input = std::fs::read_to_string(path)
+				.map_err(|e| {
+					anyhow::anyhow!("Failed to read input file {}: {}", path.display(), e)
+				})?
+				.trim()
+				.to_string();
+
+			if input.is_empty() {
+				return Err(anyhow::anyhow!("Input file {} is empty", path.display()));
+			}
+
+			Ok(input)
 		} else if !std::io::stdin().is_terminal() {
 			// Read from stdin if it's being piped
 			let mut buffer = String::new();
@@ -73,7 +166,7 @@ impl RunArgs {
 		} else {
 			// No input provided and stdin is a terminal
 			Err(anyhow::anyhow!(
-				"No input provided. Please provide input as a parameter or pipe it via stdin."
+				"No input provided. Please provide input as a parameter, via --input-file or pipe it via stdin."
 			))
 		}
 	}
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use clap::Args;
+use clap::{Args, Subcommand};
+use colored::*;
 
 #[derive(Args, Debug)]
 pub struct SessionArgs {
@@ -24,6 +25,14 @@ pub struct SessionArgs {
 	#[arg(long, short)]
 	pub resume: Option<String>,
 
+	/// Resume the most recently modified session (same as --resume with its name)
+	#[arg(long, conflicts_with_all = ["name", "resume"])]
+	pub resume_latest: bool,
+
+	/// Send the first message straight to the model, without the layer pipeline
+	#[arg(long)]
+	pub skip_layers: bool,
+
 	/// Use a specific model instead of the one configured in config (runtime only, not saved)
 	#[arg(long)]
 	pub model: Option<String>,
@@ -35,8 +44,207 @@ pub struct SessionArgs {
 	/// Session role: developer (default with layers and tools) or assistant (simple chat without tools)
 	#[arg(long, default_value = "developer")]
 	pub role: String,
+
+	/// Replay tool results recorded in an existing session (name + args match), executing on a miss
+	#[arg(long, value_name = "SESSION")]
+	pub replay_tools_from: Option<String>,
+
+	/// Record every tool call (name, arguments, result, timing) as numbered files in this directory
+	#[arg(long, value_name = "DIR")]
+	pub trace_tools: Option<String>,
+
+	/// JSON schema file the final responses must match (JSON output, re-prompted on mismatch)
+	#[arg(long, value_name = "FILE")]
+	pub response_schema: Option<std::path::PathBuf>,
+
+	/// Ask before executing tool calls: first (default when given), always or off
+	#[arg(
+		long,
+		value_name = "MODE",
+		num_args = 0..=1,
+		require_equals = true,
+		default_missing_value = "first"
+	)]
+	pub confirm_tools: Option<octomind::session::chat::response::tool_execution::ToolConfirmMode>,
+
+	/// Bare model for comparisons: no layers, minimal system prompt and no tools (overrides the role)
+	#[arg(long)]
+	pub plain: bool,
+
+	/// Keep the session in memory only: no session file, log, scratchpad or input history is written
+	#[arg(long, conflicts_with_all = ["resume", "resume_latest"])]
+	pub no_save: bool,
+
+	/// Override a layer's temperature for this session, e.g. --layer-temp review=0.2 (repeatable)
+	#[arg(long = "layer-temp", value_name = "LAYER=TEMP", value_parser = octomind::config::parse_layer_temperature)]
+	pub layer_temp: Vec<(String, f32)>,
+
+	#[command(subcommand)]
+	pub command: Option<SessionCommand>,
 }
 
-// No execute function here since it's handled directly by the session::chat module
+#[derive(Subcommand, Debug)]
+pub enum SessionCommand {
+	/// Upgrade stored sessions to the current session storage format
+	Migrate(SessionMigrateArgs),
+	/// Export a stored session as JSON (session info and messages)
+	Export(SessionExportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SessionMigrateArgs {
+	/// Only migrate this session (default: all stored sessions)
+	#[arg(long, short)]
+	pub name: Option<String>,
+
+	/// Report what would change without rewriting any session files
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SessionExportArgs {
+	/// Session to export
+	#[arg(long, short)]
+	pub name: String,
+
+	/// Write the export to this file (default: stdout)
+	#[arg(long, short)]
+	pub output: Option<String>,
+
+	/// Attach the full result of each tool call to its tool message (inline or sidecar storage),
+	/// next to the context-sized content the model saw
+	#[arg(long)]
+	pub full_results: bool,
+}
+
+impl SessionArgs {
+	/// Arguments of `octomind session --role <role>` with everything else at its default
+	pub fn for_role(role: &str) -> Self {
+		Self {
+			name: None,
+			resume: None,
+			resume_latest: false,
+			skip_layers: false,
+			model: None,
+			temperature: 0.7,
+			role: role.to_string(),
+			replay_tools_from: None,
+			trace_tools: None,
+			response_schema: None,
+			confirm_tools: None,
+			plain: false,
+			no_save: false,
+			layer_temp: Vec::new(),
+			command: None,
+		}
+	}
+}
+
+// Interactive sessions are handled directly by the session::chat module
 // The module is accessed in main.rs via:
 // session::chat::run_interactive_session(session_args, &store, &config).await?
+
+// Execute `session migrate`
+pub fn migrate(args: &SessionMigrateArgs) -> Result<(), anyhow::Error> {
+	use octomind::session::migrations;
+
+	let reports = if let Some(name) = &args.name {
+		let path = octomind::session::get_sessions_dir()?.join(format!("{}.jsonl", name));
+		if !path.exists() {
+			return Err(anyhow::anyhow!("Session '{}' not found", name));
+		}
+		vec![migrations::migrate_session_file(&path, args.dry_run)?]
+	} else {
+		migrations::migrate_all_sessions(args.dry_run)?
+	};
+
+	if reports.is_empty() {
+		println!("No stored sessions found.");
+		return Ok(());
+	}
+
+	let mut migrated = 0;
+	for report in &reports {
+		if report.is_up_to_date() {
+			println!("{} {}", "✓".green(), report.name);
+			continue;
+		}
+
+		migrated += 1;
+		println!(
+			"{} {} (version {} → {})",
+			"↑".bright_yellow(),
+			report.name.bright_cyan(),
+			report.from_version,
+			migrations::CURRENT_SESSION_VERSION
+		);
+		for change in &report.changes {
+			println!("    - {}", change);
+		}
+		if let Some(backup) = &report.backup_path {
+			println!("    {}", format!("backup: {}", backup.display()).dimmed());
+		}
+	}
+
+	println!();
+	if args.dry_run {
+		println!(
+			"Dry run: {} of {} sessions would be migrated (no files changed)",
+			migrated,
+			reports.len()
+		);
+	} else {
+		println!("Migrated {} of {} sessions", migrated, reports.len());
+	}
+
+	Ok(())
+}
+
+/// Export a stored session, optionally with the full tool results kept in its log
+pub fn export(args: &SessionExportArgs) -> Result<(), anyhow::Error> {
+	let path = octomind::session::get_sessions_dir()?.join(format!("{}.jsonl", args.name));
+	if !path.exists() {
+		return Err(anyhow::anyhow!("Session '{}' not found", args.name));
+	}
+	let session = octomind::session::load_session(&path)?;
+
+	let full_results = if args.full_results {
+		Some(octomind::session::logger::load_tool_results(&args.name)?)
+	} else {
+		None
+	};
+
+	let mut messages = Vec::with_capacity(session.messages.len());
+	for message in &session.messages {
+		let mut value = serde_json::to_value(message)?;
+		let full_result = full_results
+			.as_ref()
+			.zip(message.tool_call_id.as_ref())
+			.and_then(|(results, tool_id)| results.get(tool_id));
+		if let Some(result) = full_result {
+			value["full_result"] = result.clone();
+		}
+		messages.push(value);
+	}
+
+	let export = serde_json::json!({
+		"session": session.info,
+		"messages": messages,
+	});
+	let content = serde_json::to_string_pretty(&export)?;
+
+	match &args.output {
+		Some(output) => {
+			std::fs::write(output, content)?;
+			println!(
+				"Exported session '{}' ({} messages) to {}",
+				args.name.bright_cyan(),
+				session.messages.len(),
+				output
+			);
+		}
+		None => println!("{}", content),
+	}
+	Ok(())
+}
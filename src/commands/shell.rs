@@ -158,6 +158,8 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		},
 		Message {
 			role: "user".to_string(),
@@ -171,6 +173,8 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		},
 	];
 
@@ -0,0 +1,107 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use octomind::config::Config;
+use octomind::mcp::McpToolCall;
+
+#[derive(Args, Debug)]
+pub struct ToolArgs {
+	/// Name of the tool to invoke (as exposed to the model)
+	#[arg(value_name = "NAME")]
+	pub name: String,
+
+	/// Tool arguments as a JSON object
+	#[arg(long, default_value = "{}")]
+	pub args: String,
+
+	/// Role whose MCP servers should be used to resolve the tool
+	#[arg(long, default_value = "developer")]
+	pub role: String,
+
+	/// Print the raw McpToolResult as JSON
+	#[arg(long)]
+	pub json: bool,
+}
+
+pub async fn execute(args: &ToolArgs, config: &Config) -> Result<()> {
+	// Validate arguments before starting any servers
+	let parameters: serde_json::Value = serde_json::from_str(&args.args)
+		.map_err(|e| anyhow::anyhow!("Invalid --args JSON: {}", e))?;
+	if !parameters.is_object() {
+		return Err(anyhow::anyhow!("--args must be a JSON object"));
+	}
+
+	let config_for_role = config.get_merged_config_for_role(&args.role);
+	octomind::config::set_thread_config(&config_for_role);
+
+	// Initialize servers and tool map for the role, same as sessions do
+	if let Err(e) = octomind::mcp::initialize_servers_for_role(&config_for_role).await {
+		eprintln!("Warning: Failed to initialize MCP servers: {}", e);
+	}
+	if let Err(e) = octomind::mcp::tool_map::initialize_tool_map(&config_for_role).await {
+		eprintln!("Warning: Failed to initialize tool map: {}", e);
+	}
+
+	// Make sure the tool is actually available for this role
+	let available = octomind::mcp::get_available_functions(&config_for_role).await;
+	if !available.iter().any(|f| f.name == args.name) {
+		let mut names: Vec<&str> = available.iter().map(|f| f.name.as_str()).collect();
+		names.sort_unstable();
+		return Err(anyhow::anyhow!(
+			"Tool '{}' not found for role '{}'. Available tools: {}",
+			args.name,
+			args.role,
+			names.join(", ")
+		));
+	}
+
+	let mut calls = vec![McpToolCall {
+		tool_name: args.name.clone(),
+		parameters,
+		tool_id: String::new(),
+	}];
+	octomind::mcp::ensure_tool_call_ids(&mut calls);
+
+	let (result, tool_time_ms) =
+		octomind::mcp::execute_tool_call(&calls[0], &config_for_role, None).await?;
+
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&result)?);
+		return Ok(());
+	}
+
+	let is_error = result
+		.result
+		.get("isError")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+	let status = if is_error {
+		"✗ error".bright_red()
+	} else {
+		"✓ ok".bright_green()
+	};
+	println!(
+		"{} {} ({}ms)",
+		result.tool_name.bright_cyan().bold(),
+		status,
+		tool_time_ms
+	);
+	println!("──────────────────");
+	println!("{}", octomind::mcp::extract_mcp_content(&result.result));
+
+	Ok(())
+}
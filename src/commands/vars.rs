@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use crate::session::helper_functions::get_all_placeholders;
+use crate::session::placeholders::{find_placeholder, unknown_placeholders};
 use anyhow::Result;
 use clap::Args;
 use colored::*;
 use octomind::config::Config;
 use std::env;
+use std::path::Path;
 
 #[derive(Args)]
 pub struct VarsArgs {
@@ -28,10 +30,19 @@ pub struct VarsArgs {
 	/// Show full expanded values for placeholders
 	#[arg(short, long)]
 	pub expand: bool,
+
+	/// Report unknown placeholders in configured prompts and the instructions file
+	#[arg(short, long)]
+	pub lint: bool,
 }
 
-pub async fn execute(args: &VarsArgs, _config: &Config) -> Result<()> {
+pub async fn execute(args: &VarsArgs, config: &Config) -> Result<()> {
 	let current_dir = env::current_dir()?;
+
+	if args.lint {
+		return lint(config, &current_dir);
+	}
+
 	let placeholders = get_all_placeholders(&current_dir).await;
 
 	println!("{}", "Available placeholders:".bright_blue().bold());
@@ -97,22 +108,15 @@ pub async fn execute(args: &VarsArgs, _config: &Config) -> Result<()> {
 			}
 			println!();
 		} else {
-			// Show just a brief description
-			let description = match placeholder.as_str() {
-				"%{DATE}" => "Current date and time with timezone",
-				"%{SHELL}" => "Current shell name and version",
-				"%{OS}" => "Operating system information",
-				"%{BINARIES}" => "Available development tools and their versions",
-				"%{CWD}" => "Current working directory",
-				"%{ROLE}" => "Current session role (developer, assistant, etc.)",
-				"%{SYSTEM}" => "Complete system information (date, shell, OS, binaries, CWD)",
-				"%{CONTEXT}" => "Project context information (README, git status, git tree)",
-				"%{GIT_STATUS}" => "Git repository status",
-				"%{GIT_TREE}" => "Git file tree",
-				"%{README}" => "Project README content",
-				_ => "Project context variable",
-			};
-			println!(" - {}", description.dimmed());
+			// Show the registry description and an example value
+			match find_placeholder(placeholder) {
+				Some(info) => {
+					let kind = if info.is_async { " (async)" } else { "" };
+					println!(" - {}{}", info.description.dimmed(), kind.dimmed());
+					println!("    {} {}", "e.g.".dimmed(), info.example);
+				}
+				None => println!(" - {}", "Project context variable".dimmed()),
+			}
 		}
 	}
 
@@ -127,3 +131,46 @@ pub async fn execute(args: &VarsArgs, _config: &Config) -> Result<()> {
 
 	Ok(())
 }
+
+// Check every configured template plus the project instructions file for unknown placeholders
+fn lint(config: &Config, current_dir: &Path) -> Result<()> {
+	let mut sources: Vec<(String, String, Vec<String>)> = config
+		.placeholder_templates()
+		.into_iter()
+		.map(|(source, template, extra)| (source, template.to_string(), extra))
+		.collect();
+
+	if !config.custom_instructions_file_name.is_empty() {
+		let path = current_dir.join(&config.custom_instructions_file_name);
+		if let Ok(content) = std::fs::read_to_string(&path) {
+			sources.push((path.display().to_string(), content, Vec::new()));
+		}
+	}
+
+	let mut issues = 0;
+	for (source, template, extra) in &sources {
+		let extra: Vec<&str> = extra.iter().map(|k| k.as_str()).collect();
+		let unknown = unknown_placeholders(template, &extra);
+		if unknown.is_empty() {
+			continue;
+		}
+		issues += unknown.len();
+		println!("{}", source.bright_yellow());
+		for token in unknown {
+			println!("  {} {}", "unknown placeholder".bright_red(), token);
+		}
+	}
+
+	if issues == 0 {
+		println!(
+			"{}",
+			format!("No unknown placeholders in {} templates", sources.len()).bright_green()
+		);
+		return Ok(());
+	}
+	Err(anyhow::anyhow!(
+		"Found {} unknown placeholder{} (run `octomind vars` to list available placeholders)",
+		issues,
+		if issues == 1 { "" } else { "s" }
+	))
+}
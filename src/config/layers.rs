@@ -23,6 +23,15 @@ pub struct LayersConfig {
 	pub layers: std::collections::HashMap<String, LayerConfig>,
 }
 
+/// Named, reusable layer pipeline that roles can reference via `layer_preset`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LayerPreset {
+	/// Preset name referenced by roles (e.g., "review")
+	pub name: String,
+	/// Ordered layer names from the global layers registry
+	pub layers: Vec<String>,
+}
+
 /// Role-specific layers configuration with layer_refs (similar to MCP)
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RoleLayersConfig {
@@ -17,9 +17,115 @@ use std::fs;
 
 use super::Config;
 
+// Environment variables a provider reads its credentials and account settings from
+fn provider_env_vars(provider: &str) -> &'static [&'static str] {
+	match provider {
+		"openrouter" => &["OPENROUTER_API_KEY"],
+		"openai" => &["OPENAI_API_KEY"],
+		"anthropic" => &["ANTHROPIC_API_KEY"],
+		"google" => &["GOOGLE_APPLICATION_CREDENTIALS", "GOOGLE_PROJECT_ID"],
+		"amazon" => &["AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"],
+		"cloudflare" => &["CLOUDFLARE_API_TOKEN", "CLOUDFLARE_ACCOUNT_ID"],
+		"deepseek" => &["DEEPSEEK_API_KEY"],
+		_ => &[],
+	}
+}
+
+// Names of the $VAR / ${VAR} references in a value
+fn env_var_references(value: &str) -> Vec<String> {
+	let mut names = Vec::new();
+	let mut rest = value;
+	while let Some(start) = rest.find('$') {
+		rest = &rest[start + 1..];
+		let name: String = match rest.strip_prefix('{') {
+			Some(braced) => braced.chars().take_while(|c| *c != '}').collect(),
+			None => rest
+				.chars()
+				.take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+				.collect(),
+		};
+		if !name.is_empty() {
+			names.push(name);
+		}
+	}
+	names
+}
+
 impl Config {
 	fn initialize_config(&mut self) {}
 
+	/// Merge server definitions from mcp.servers_file into the server registry
+	fn load_servers_file(&mut self) -> Result<()> {
+		let Some(servers_file) = self.mcp.servers_file.clone() else {
+			return Ok(());
+		};
+
+		let path = self
+			.resolve_config_relative_path(&servers_file)
+			.context("Failed to resolve mcp.servers_file")?;
+
+		let content = fs::read_to_string(&path).context(format!(
+			"Failed to read MCP servers file {}",
+			path.display()
+		))?;
+		let included: super::McpServersFile = toml::from_str(&content).context(format!(
+			"Failed to parse MCP servers file {}",
+			path.display()
+		))?;
+
+		for server in included.servers {
+			server
+				.validate()
+				.map_err(|e| anyhow::anyhow!(e))
+				.and_then(|_| Self::validate_mcp_server(&server))
+				.context(format!("Invalid MCP server in {}", path.display()))?;
+			if self.mcp.servers.iter().any(|s| s.name() == server.name()) {
+				return Err(anyhow::anyhow!(
+					"MCP server '{}' from {} is already defined",
+					server.name(),
+					path.display()
+				));
+			}
+			self.mcp.included_servers.push(server.name().to_string());
+			self.mcp.servers.push(server);
+		}
+
+		Ok(())
+	}
+
+	/// Resolve a path from the config: ~/ is the home directory, relative paths
+	/// are relative to the config file directory
+	pub fn resolve_config_relative_path(&self, path: &str) -> Result<std::path::PathBuf> {
+		if let Some(rest) = path.strip_prefix("~/") {
+			return Ok(dirs::home_dir()
+				.context("Failed to resolve home directory")?
+				.join(rest));
+		}
+		let path = std::path::PathBuf::from(path);
+		Ok(match self.config_path.as_ref().and_then(|p| p.parent()) {
+			Some(dir) if path.is_relative() => dir.join(path),
+			_ => path,
+		})
+	}
+
+	/// System prompt template for a role: the contents of its system_file when set,
+	/// otherwise its inline system prompt
+	pub fn get_role_system_prompt(&self, role: &str) -> Result<Option<String>> {
+		let (role_config, _, _, _, system) = self.get_role_config(role);
+		match &role_config.system_file {
+			Some(system_file) => {
+				let path = self.resolve_config_relative_path(system_file)?;
+				let content = fs::read_to_string(&path).context(format!(
+					"Failed to read system_file {} for role '{}'",
+					path.display(),
+					role
+				))?;
+				Ok(Some(content))
+			}
+			None => Ok(system.cloned()),
+		}
+	}
+
 	pub fn ensure_octomind_dir() -> Result<std::path::PathBuf> {
 		// Use the system-wide directory
 		crate::directories::get_octomind_data_dir()
@@ -107,6 +213,9 @@ impl Config {
 		// Initialize the configuration
 		config.initialize_config();
 
+		// Merge MCP servers from an included file
+		config.load_servers_file()?;
+
 		// Build role map from roles array
 		config.build_role_map();
 
@@ -119,6 +228,17 @@ impl Config {
 		Ok(config)
 	}
 
+	/// Load the config file again during a session, keeping the
+	/// command-line adjustments made to this config (--plain, --layer-temp)
+	pub fn reload(&self) -> Result<Self> {
+		let mut config = Self::load()?;
+		if let Some(role) = &self.plain_role {
+			config.apply_plain_mode(role);
+		}
+		config.apply_layer_temperatures(&self.layer_temperatures)?;
+		Ok(config)
+	}
+
 	/// REMOVED: No more default_with_env - config must be complete and explicit
 	/// All defaults are now in the template file
 	///
@@ -142,9 +262,9 @@ impl Config {
 			))?;
 		}
 
-		// Serialize to TOML
-		let config_str =
-			toml::to_string_pretty(self).context("Failed to serialize configuration to TOML")?;
+		// Serialize to TOML (without servers merged from mcp.servers_file)
+		let config_str = toml::to_string_pretty(&self.create_clean_copy_for_saving())
+			.context("Failed to serialize configuration to TOML")?;
 
 		// Write to file
 		fs::write(&config_path, config_str).context(format!(
@@ -169,6 +289,9 @@ impl Config {
 		// Initialize the configuration
 		config.initialize_config();
 
+		// Merge MCP servers from an included file
+		config.load_servers_file()?;
+
 		// Build role map from roles array
 		config.build_role_map();
 
@@ -191,9 +314,9 @@ impl Config {
 			))?;
 		}
 
-		// Serialize to TOML
-		let config_str =
-			toml::to_string_pretty(self).context("Failed to serialize configuration to TOML")?;
+		// Serialize to TOML (without servers merged from mcp.servers_file)
+		let config_str = toml::to_string_pretty(&self.create_clean_copy_for_saving())
+			.context("Failed to serialize configuration to TOML")?;
 
 		// Write to file
 		fs::write(path, config_str)
@@ -206,11 +329,113 @@ impl Config {
 	/// Create a clean copy of the config for saving (removes runtime-only fields)
 	pub fn create_clean_copy_for_saving(&self) -> Self {
 		// Only remove servers that are marked as runtime-only or temporary
-		// (Currently there are no runtime-only servers, so we keep all servers)
+		// Servers merged from mcp.servers_file stay in that file
 
 		// Keep the MCP section to show what's actually available
 
-		self.clone()
+		let mut clean = self.clone();
+		let included = std::mem::take(&mut clean.mcp.included_servers);
+		clean
+			.mcp
+			.servers
+			.retain(|server| !included.iter().any(|name| name == server.name()));
+		clean
+	}
+
+	/// Shareable template of the config: the clean copy without credentials, headed by
+	/// comments listing the environment variables it needs
+	pub fn export_template(&self) -> Result<String> {
+		let mut template = self.create_clean_copy_for_saving();
+		let mut removed = Vec::new();
+		for server in &mut template.mcp.servers {
+			let fields = server.strip_secrets();
+			if !fields.is_empty() {
+				removed.push(format!("{}: {}", server.name(), fields.join(", ")));
+			}
+		}
+
+		let body = toml::to_string_pretty(&template)
+			.context("Failed to serialize configuration to TOML")?;
+
+		let mut header = String::from(
+			"# Octomind configuration template\n# Exported without credentials - import it as config.toml\n",
+		);
+		let env_vars = template.required_env_vars();
+		if !env_vars.is_empty() {
+			header.push_str(
+				"#\n# Required environment variables (or [secrets] entries for API keys):\n",
+			);
+			for (name, used_by) in &env_vars {
+				header.push_str(&format!("#   {} - {}\n", name, used_by));
+			}
+		}
+		if !removed.is_empty() {
+			header.push_str(
+				"#\n# Credentials removed from MCP servers (set them after importing):\n",
+			);
+			for line in &removed {
+				header.push_str(&format!("#   {}\n", line));
+			}
+		}
+
+		Ok(format!("{}\n{}", header, body))
+	}
+
+	// Environment variables the config relies on: API keys of the providers its models use
+	// and variables referenced in MCP server headers, with what uses them
+	fn required_env_vars(&self) -> Vec<(String, String)> {
+		let layer_models = self
+			.layers
+			.iter()
+			.chain(self.commands.iter())
+			.flatten()
+			.filter_map(|layer| layer.model.as_deref());
+		let agent_models = self
+			.agents
+			.iter()
+			.filter_map(|agent| agent.fallback_model.as_deref());
+		let models = [
+			self.model.as_str(),
+			self.default_layer_model.as_str(),
+			self.rate_limit_fallback_model.as_str(),
+		]
+		.into_iter()
+		.chain(layer_models)
+		.chain(agent_models)
+		.chain(self.context_models.iter().map(|entry| entry.model.as_str()))
+		.filter(|model| !model.is_empty());
+
+		let mut env_vars = std::collections::BTreeMap::new();
+		for model in models {
+			if let Ok((provider, _)) = crate::providers::ProviderFactory::parse_model(model) {
+				for name in provider_env_vars(&provider.to_lowercase()) {
+					env_vars
+						.entry(name.to_string())
+						.or_insert_with(|| format!("{} provider", provider));
+				}
+			}
+		}
+
+		for server in &self.mcp.servers {
+			if server.connection_type() == super::McpConnectionType::Builtin
+				&& server.name() == "web"
+			{
+				for name in self.web.api_key_names() {
+					env_vars
+						.entry(name)
+						.or_insert_with(|| "web server search tools".to_string());
+				}
+			}
+			for value in server.headers().into_iter().flat_map(|h| h.values()) {
+				for name in env_var_references(value) {
+					env_vars
+						.entry(name)
+						.or_insert_with(|| format!("MCP server '{}' headers", server.name()));
+				}
+			}
+		}
+
+		env_vars.into_iter().collect()
 	}
 
 	/// Update configuration with a closure and save
@@ -319,6 +544,15 @@ impl Config {
 	}
 }
 
+// Default config template, parsed and with the role map built, for tests across the crate
+#[cfg(test)]
+pub(crate) fn get_test_default_config() -> Config {
+	let mut config: Config = toml::from_str(include_str!("../../config-templates/default.toml"))
+		.expect("Failed to parse default config");
+	config.build_role_map();
+	config
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -373,7 +607,10 @@ tools = []
 		assert!(config.role_map.contains_key("tester"));
 
 		let tester_role = config.role_map.get("tester").unwrap();
-		assert_eq!(tester_role.mcp.server_refs, vec!["octocode", "clt"]);
+		assert_eq!(
+			tester_role.mcp.as_ref().unwrap().server_refs,
+			vec!["octocode", "clt"]
+		);
 		assert!(!tester_role.config.enable_layers);
 
 		// Test get_role_config for custom role
@@ -413,4 +650,474 @@ tools = []
 		assert!(!server_names.contains(&"developer")); // Should not be included
 		assert!(!server_names.contains(&"filesystem")); // Should not be included
 	}
+
+	#[test]
+	fn test_role_default_mcp_inheritance() {
+		let test_config = get_test_config_with_custom_role()
+			.replace(
+				"mcp = { server_refs = [\"octocode\", \"clt\"], allowed_tools = [] }\n",
+				"",
+			)
+			.replace(
+				"default_server_refs = []",
+				"default_server_refs = [\"clt\"]",
+			)
+			.replace(
+				"default_allowed_tools = []",
+				"default_allowed_tools = [\"clt_*\"]",
+			);
+
+		let mut config: Config = toml::from_str(&test_config).expect("Failed to parse test config");
+		config.build_role_map();
+
+		// Role without an mcp section inherits the defaults
+		let (_, mcp_config, _, _, _) = config.get_role_config("tester");
+		assert_eq!(mcp_config.server_refs, vec!["clt"]);
+		assert_eq!(mcp_config.allowed_tools, vec!["clt_*"]);
+
+		// Roles with their own mcp section keep it
+		let (_, mcp_config, _, _, _) = config.get_role_config("assistant");
+		assert_eq!(mcp_config.server_refs, vec!["filesystem"]);
+		assert_eq!(mcp_config.allowed_tools, vec!["list_files"]);
+	}
+
+	#[test]
+	fn test_default_layer_model_fallback() {
+		let test_config = get_test_config_with_custom_role().replace(
+			"default_layer_model = \"\"",
+			"default_layer_model = \"openrouter:openai/gpt-4.1-mini\"",
+		);
+		let mut config: Config = toml::from_str(&test_config).expect("Failed to parse test config");
+		config.layers = Some(vec![
+			crate::session::layers::LayerConfig::create_system_layer("custom"),
+			crate::session::layers::LayerConfig::create_system_layer("query_processor"),
+		]);
+		config.build_role_map();
+
+		let layers = config.layers.as_ref().unwrap();
+		// Layer without a model uses default_layer_model instead of the session model
+		assert_eq!(
+			layers[0].get_effective_model("openrouter:anthropic/claude-sonnet-4"),
+			"openrouter:openai/gpt-4.1-mini"
+		);
+		// A layer's own model always wins
+		assert_eq!(
+			layers[1].get_effective_model("openrouter:anthropic/claude-sonnet-4"),
+			"openrouter:openai/gpt-4.1-nano"
+		);
+
+		// Without default_layer_model the session model is used
+		config.default_layer_model = String::new();
+		config.build_role_map();
+		assert_eq!(
+			config.layers.as_ref().unwrap()[0]
+				.get_effective_model("openrouter:anthropic/claude-sonnet-4"),
+			"openrouter:anthropic/claude-sonnet-4"
+		);
+	}
+
+	#[test]
+	fn test_layer_preset_expansion() {
+		let mut test_config = get_test_config_with_custom_role();
+		test_config = test_config.replace(
+			"name = \"tester\"\n",
+			"name = \"tester\"\nlayer_preset = \"review\"\n",
+		);
+		test_config.push_str(
+			r#"
+[[layer_presets]]
+name = "review"
+layers = ["query_processor", "context_generator"]
+"#,
+		);
+
+		let mut config: Config = toml::from_str(&test_config).expect("Failed to parse test config");
+		config.build_role_map();
+		assert!(config.validate().is_ok());
+
+		// Preset layers are expanded into the role's layer_refs
+		assert_eq!(
+			config.get_layer_refs("tester"),
+			&vec![
+				"query_processor".to_string(),
+				"context_generator".to_string()
+			]
+		);
+
+		// Unknown preset reference fails validation
+		config
+			.roles
+			.iter_mut()
+			.find(|r| r.name == "tester")
+			.unwrap()
+			.layer_preset = Some("missing".to_string());
+		assert!(config.validate().is_err());
+
+		// Preset referencing an unknown layer fails validation
+		config
+			.roles
+			.iter_mut()
+			.find(|r| r.name == "tester")
+			.unwrap()
+			.layer_preset = None;
+		config.layer_presets[0]
+			.layers
+			.push("nonexistent".to_string());
+		assert!(config.validate().is_err());
+	}
+
+	#[test]
+	fn test_mcp_servers_file_include() {
+		let dir =
+			std::env::temp_dir().join(format!("octomind-servers-file-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(
+			dir.join("servers.toml"),
+			r#"
+[[servers]]
+name = "team_tools"
+type = "stdin"
+command = "team-mcp"
+args = []
+timeout_seconds = 60
+tools = []
+"#,
+		)
+		.unwrap();
+
+		let mut config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		config.config_path = Some(dir.join("config.toml"));
+		config.mcp.servers_file = Some("servers.toml".to_string());
+		config.load_servers_file().unwrap();
+
+		assert!(config.mcp.servers.iter().any(|s| s.name() == "team_tools"));
+		assert_eq!(config.mcp.included_servers, vec!["team_tools"]);
+		// Included servers are not written back to the main config
+		let clean = config.create_clean_copy_for_saving();
+		assert!(!clean.mcp.servers.iter().any(|s| s.name() == "team_tools"));
+		assert_eq!(clean.mcp.servers_file.as_deref(), Some("servers.toml"));
+
+		// Redefining a server from the config is an error naming the included file
+		fs::write(
+			dir.join("servers.toml"),
+			"[[servers]]\nname = \"developer\"\ntype = \"builtin\"\ntimeout_seconds = 30\ntools = []\n",
+		)
+		.unwrap();
+		let mut config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		config.config_path = Some(dir.join("config.toml"));
+		config.mcp.servers_file = Some("servers.toml".to_string());
+		let error = config.load_servers_file().unwrap_err().to_string();
+		assert!(error.contains("servers.toml"));
+
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn test_role_system_file() {
+		let dir = std::env::temp_dir().join(format!("octomind-system-file-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(dir.join("tester.md"), "You are a file-based tester.").unwrap();
+
+		let mut config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		config.config_path = Some(dir.join("config.toml"));
+		config.build_role_map();
+		assert_eq!(
+			config.get_role_system_prompt("tester").unwrap().as_deref(),
+			Some("You are a test assistant.")
+		);
+
+		// The file takes precedence over the inline prompt and resolves next to the config
+		config
+			.roles
+			.iter_mut()
+			.find(|r| r.name == "tester")
+			.unwrap()
+			.config
+			.system_file = Some("tester.md".to_string());
+		config.build_role_map();
+		assert!(config.validate().is_ok());
+		assert_eq!(
+			config.get_role_system_prompt("tester").unwrap().as_deref(),
+			Some("You are a file-based tester.")
+		);
+
+		fs::remove_file(dir.join("tester.md")).unwrap();
+		assert!(config.get_role_system_prompt("tester").is_err());
+		assert!(config.validate().is_err());
+
+		fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn test_model_validation() {
+		let mut config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		config.build_role_map();
+		assert!(config.validate().is_ok());
+
+		for model in ["", "  ", "claude-sonnet-4", "unknown:model", "openrouter:"] {
+			config.model = model.to_string();
+			assert!(
+				config.validate().is_err(),
+				"model '{}' should be rejected",
+				model
+			);
+		}
+	}
+
+	#[test]
+	fn test_layer_failure_policy() {
+		use crate::config::LayerFailurePolicy;
+
+		for (value, policy) in [
+			("continue", LayerFailurePolicy::Continue),
+			("abort", LayerFailurePolicy::Abort),
+			("retry", LayerFailurePolicy::Retry),
+		] {
+			let config_content = get_test_config_with_custom_role().replace(
+				"layer_failure_policy = \"continue\"",
+				&format!("layer_failure_policy = \"{}\"", value),
+			);
+			let mut config: Config =
+				toml::from_str(&config_content).expect("Failed to parse test config");
+			config.build_role_map();
+			assert_eq!(
+				config
+					.get_role_config_struct("developer")
+					.layer_failure_policy,
+				policy
+			);
+			// Roles without the setting keep the historical behavior
+			assert_eq!(
+				config.get_role_config_struct("tester").layer_failure_policy,
+				LayerFailurePolicy::Continue
+			);
+		}
+
+		let config_content = get_test_config_with_custom_role().replace(
+			"layer_failure_policy = \"continue\"",
+			"layer_failure_policy = \"skip\"",
+		);
+		assert!(toml::from_str::<Config>(&config_content).is_err());
+	}
+
+	#[test]
+	fn test_disabled_server_skipped_for_role() {
+		let config_content = get_test_config_with_custom_role().replace(
+			"name = \"developer\"\ntype = \"builtin\"",
+			"name = \"developer\"\ntype = \"builtin\"\ndisabled = true",
+		);
+		let mut config: Config =
+			toml::from_str(&config_content).expect("Failed to parse test config");
+		config.build_role_map();
+
+		let developer = config
+			.mcp
+			.servers
+			.iter()
+			.find(|s| s.name() == "developer")
+			.unwrap();
+		assert!(developer.is_disabled());
+
+		// Still referenced by the role, but not part of the merged config
+		let (_, role_mcp, _, _, _) = config.get_role_config("developer");
+		assert!(role_mcp.server_refs.contains(&"developer".to_string()));
+		let merged = config.get_merged_config_for_role("developer");
+		assert!(!merged.mcp.servers.iter().any(|s| s.name() == "developer"));
+
+		// The flag is only written when set
+		let mut enabled = developer.clone();
+		enabled.set_disabled(false);
+		let serialized = toml::to_string(&enabled).unwrap();
+		assert!(!serialized.contains("disabled"));
+	}
+
+	#[test]
+	fn test_openrouter_routing() {
+		let config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		assert!(config.openrouter.allow_fallbacks);
+		assert!(config.openrouter.only.is_empty());
+
+		// Deterministic routing pinned to one upstream
+		let config_content = get_test_config_with_custom_role().replace(
+			"allow_fallbacks = true",
+			"allow_fallbacks = false\nonly = [\"Anthropic\"]",
+		);
+		let config: Config = toml::from_str(&config_content).expect("Failed to parse test config");
+		assert!(!config.openrouter.allow_fallbacks);
+		assert_eq!(config.openrouter.only, vec!["Anthropic"]);
+
+		// Fallbacks stay enabled when the section is absent
+		let config_content = get_test_config_with_custom_role()
+			.replace("[openrouter]", "")
+			.replace("allow_fallbacks = true", "");
+		let config: Config = toml::from_str(&config_content).expect("Failed to parse test config");
+		assert_eq!(
+			config.openrouter,
+			crate::config::OpenRouterConfig::default()
+		);
+		assert!(config.openrouter.allow_fallbacks);
+	}
+
+	#[test]
+	fn test_plain_mode() {
+		let mut config: Config = toml::from_str(&get_test_config_with_custom_role())
+			.expect("Failed to parse test config");
+		config.build_role_map();
+		assert!(config.get_enable_layers("developer"));
+
+		config.apply_plain_mode("developer");
+		assert_eq!(config.plain_role.as_deref(), Some("developer"));
+		let (role_config, mcp_config, _, _, system) = config.get_role_config("developer");
+		assert!(!role_config.enable_layers);
+		assert_eq!(
+			system.map(String::as_str),
+			Some(crate::config::PLAIN_SYSTEM_PROMPT)
+		);
+		assert!(mcp_config.server_refs.is_empty());
+		assert!(config
+			.get_merged_config_for_role("developer")
+			.mcp
+			.servers
+			.is_empty());
+		assert!(config.custom_instructions_file_name.is_empty());
+
+		// Other roles keep their configuration
+		let (_, tester_mcp, _, _, _) = config.get_role_config("tester");
+		assert!(!tester_mcp.server_refs.is_empty());
+	}
+
+	#[test]
+	fn test_parse_layer_temperature() {
+		use crate::config::parse_layer_temperature;
+
+		assert_eq!(
+			parse_layer_temperature("query_processor=0.1"),
+			Ok(("query_processor".to_string(), 0.1))
+		);
+		assert_eq!(
+			parse_layer_temperature(" review = 2 "),
+			Ok(("review".to_string(), 2.0))
+		);
+		assert!(parse_layer_temperature("review").is_err());
+		assert!(parse_layer_temperature("=0.5").is_err());
+		assert!(parse_layer_temperature("review=warm").is_err());
+		assert!(parse_layer_temperature("review=2.5").is_err());
+		assert!(parse_layer_temperature("review=-0.1").is_err());
+	}
+
+	#[test]
+	fn test_apply_layer_temperatures() {
+		let mut config = get_test_default_config();
+		config
+			.apply_layer_temperatures(&[("context_generator".to_string(), 0.6)])
+			.unwrap();
+		let layer = config
+			.layers
+			.iter()
+			.flatten()
+			.find(|layer| layer.name == "context_generator")
+			.unwrap();
+		assert_eq!(layer.temperature, 0.6);
+		assert_eq!(
+			config.layer_temperatures,
+			vec![("context_generator".to_string(), 0.6)]
+		);
+
+		// Unknown names fail and list the configured layers
+		let error = config
+			.apply_layer_temperatures(&[("missing".to_string(), 0.2)])
+			.unwrap_err()
+			.to_string();
+		assert!(error.contains("Unknown layer 'missing'"));
+		assert!(error.contains("query_processor, context_generator"));
+	}
+
+	#[test]
+	fn test_role_welcome_optional() {
+		let config_content = get_test_config_with_custom_role()
+			.replace("welcome = \"Hello! Test tester role.\"\n", "");
+		let mut config: Config =
+			toml::from_str(&config_content).expect("Failed to parse test config");
+		config.build_role_map();
+
+		assert!(config.get_role_config_struct("tester").welcome.is_empty());
+		assert!(!config
+			.get_role_config_struct("developer")
+			.welcome
+			.is_empty());
+	}
+
+	#[test]
+	fn test_export_template_strips_credentials() {
+		let mut config_content = get_test_config_with_custom_role();
+		config_content.push_str(
+			r#"
+[[mcp.servers]]
+name = "remote"
+type = "http"
+url = "https://mcp.example.com"
+auth_token = "secret-token"
+headers = { "Authorization" = "Bearer literal", "X-Api-Key" = "${REMOTE_KEY}", "X-Version" = "2" }
+timeout_seconds = 30
+tools = []
+"#,
+		);
+		let config: Config = toml::from_str(&config_content).expect("Failed to parse test config");
+
+		let template = config.export_template().unwrap();
+		assert!(!template.contains("secret-token"));
+		assert!(!template.contains("Bearer literal"));
+		assert!(template.contains("${REMOTE_KEY}"));
+		assert!(template.contains("X-Version"));
+		assert!(template.contains("#   remote: auth_token, headers.Authorization"));
+		assert!(template.contains("#   REMOTE_KEY - MCP server 'remote' headers"));
+		assert!(template.contains("#   OPENROUTER_API_KEY - openrouter provider"));
+
+		// The template, comments included, is a loadable config
+		let imported: Config = toml::from_str(&template).expect("Template should parse");
+		let remote = imported
+			.mcp
+			.servers
+			.iter()
+			.find(|s| s.name() == "remote")
+			.unwrap();
+		assert_eq!(remote.auth_token(), None);
+	}
+
+	#[test]
+	fn test_tool_timeouts() {
+		let mut config_content = get_test_config_with_custom_role();
+		config_content.push_str(
+			r#"
+[[mcp.servers]]
+name = "builder"
+type = "stdin"
+command = "builder-mcp"
+args = []
+timeout_seconds = 30
+tool_timeouts = { build = 300 }
+tools = []
+"#,
+		);
+		let config: Config = toml::from_str(&config_content).expect("Failed to parse test config");
+		assert!(config.validate().is_ok());
+
+		let builder = config
+			.mcp
+			.servers
+			.iter()
+			.find(|s| s.name() == "builder")
+			.unwrap();
+		assert_eq!(builder.tool_timeout_seconds("build"), 300);
+		assert_eq!(builder.tool_timeout_seconds("lint"), 30);
+
+		let invalid = config_content.replace("build = 300", "build = 0");
+		let config: Config = toml::from_str(&invalid).expect("Failed to parse test config");
+		assert!(config.validate().is_err());
+	}
 }
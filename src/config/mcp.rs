@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Type-specific MCP server configuration using tagged enums
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -23,14 +24,26 @@ pub enum McpServerConfig {
 		name: String,
 		timeout_seconds: u64,
 		tools: Vec<String>,
+		// Kept in the registry but never started or offered to roles
+		#[serde(default, skip_serializing_if = "is_false")]
+		disabled: bool,
 	},
 	#[serde(rename = "http")]
 	Http {
 		name: String,
 		#[serde(flatten)]
 		connection: HttpConnection,
+		// Extra request headers (values support $VAR / ${VAR} env expansion)
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		headers: HashMap<String, String>,
 		timeout_seconds: u64,
+		// Per-tool timeouts in seconds, overriding timeout_seconds for those tools
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		tool_timeouts: HashMap<String, u64>,
 		tools: Vec<String>,
+		// Kept in the registry but never started or offered to roles
+		#[serde(default, skip_serializing_if = "is_false")]
+		disabled: bool,
 	},
 	#[serde(rename = "stdin")]
 	Stdin {
@@ -38,8 +51,60 @@ pub enum McpServerConfig {
 		command: String,
 		args: Vec<String>,
 		timeout_seconds: u64,
+		// Per-tool timeouts in seconds, overriding timeout_seconds for those tools
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		tool_timeouts: HashMap<String, u64>,
 		tools: Vec<String>,
+		// Seconds between JSON-RPC ping checks while idle (0 = disabled)
+		#[serde(default, skip_serializing_if = "is_zero")]
+		heartbeat_seconds: u64,
+		// Kept in the registry but never started or offered to roles
+		#[serde(default, skip_serializing_if = "is_false")]
+		disabled: bool,
 	},
+	// Remote server using the MCP SSE transport: responses and server-initiated messages
+	// arrive on an event stream from `url`, requests are POSTed to the endpoint it announces
+	#[serde(rename = "sse")]
+	Sse {
+		name: String,
+		url: String,
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		auth_token: Option<String>,
+		// Extra request headers (values support $VAR / ${VAR} env expansion)
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		headers: HashMap<String, String>,
+		timeout_seconds: u64,
+		// Per-tool timeouts in seconds, overriding timeout_seconds for those tools
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		tool_timeouts: HashMap<String, u64>,
+		tools: Vec<String>,
+		// Kept in the registry but never started or offered to roles
+		#[serde(default, skip_serializing_if = "is_false")]
+		disabled: bool,
+	},
+}
+
+fn is_zero(value: &u64) -> bool {
+	*value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+	!*value
+}
+
+// Headers that usually carry credentials
+fn is_credential_header(name: &str) -> bool {
+	let name = name.to_lowercase();
+	[
+		"authorization",
+		"cookie",
+		"token",
+		"key",
+		"secret",
+		"password",
+	]
+	.iter()
+	.any(|part| name.contains(part))
 }
 
 // HTTP connection variants - remote vs local
@@ -61,11 +126,13 @@ pub enum HttpConnection {
 }
 
 // Legacy connection type enum for backward compatibility in some functions
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum McpConnectionType {
 	Builtin,
 	Stdin,
 	Http,
+	Sse,
 }
 
 impl McpServerConfig {
@@ -75,6 +142,7 @@ impl McpServerConfig {
 			McpServerConfig::Builtin { name, .. } => name,
 			McpServerConfig::Http { name, .. } => name,
 			McpServerConfig::Stdin { name, .. } => name,
+			McpServerConfig::Sse { name, .. } => name,
 		}
 	}
 
@@ -84,6 +152,7 @@ impl McpServerConfig {
 			McpServerConfig::Builtin { .. } => McpConnectionType::Builtin,
 			McpServerConfig::Http { .. } => McpConnectionType::Http,
 			McpServerConfig::Stdin { .. } => McpConnectionType::Stdin,
+			McpServerConfig::Sse { .. } => McpConnectionType::Sse,
 		}
 	}
 
@@ -99,30 +168,53 @@ impl McpServerConfig {
 			McpServerConfig::Stdin {
 				timeout_seconds, ..
 			} => *timeout_seconds,
+			McpServerConfig::Sse {
+				timeout_seconds, ..
+			} => *timeout_seconds,
 		}
 	}
 
+	/// Get per-tool timeout overrides for external servers (if available)
+	pub fn tool_timeouts(&self) -> Option<&HashMap<String, u64>> {
+		match self {
+			McpServerConfig::Http { tool_timeouts, .. }
+			| McpServerConfig::Stdin { tool_timeouts, .. }
+			| McpServerConfig::Sse { tool_timeouts, .. } => Some(tool_timeouts),
+			McpServerConfig::Builtin { .. } => None,
+		}
+	}
+
+	/// Timeout for one tool: its tool_timeouts entry, or the server timeout
+	pub fn tool_timeout_seconds(&self, tool_name: &str) -> u64 {
+		self.tool_timeouts()
+			.and_then(|timeouts| timeouts.get(tool_name))
+			.copied()
+			.unwrap_or_else(|| self.timeout_seconds())
+	}
+
 	/// Get tools list regardless of variant
 	pub fn tools(&self) -> &[String] {
 		match self {
 			McpServerConfig::Builtin { tools, .. } => tools,
 			McpServerConfig::Http { tools, .. } => tools,
 			McpServerConfig::Stdin { tools, .. } => tools,
+			McpServerConfig::Sse { tools, .. } => tools,
 		}
 	}
 
-	/// Get URL for HTTP servers (if available)
+	/// Get URL for remote HTTP and SSE servers (if available)
 	pub fn url(&self) -> Option<&str> {
 		match self {
 			McpServerConfig::Http {
 				connection: HttpConnection::Remote { url, .. },
 				..
-			} => Some(url),
+			}
+			| McpServerConfig::Sse { url, .. } => Some(url),
 			_ => None,
 		}
 	}
 
-	/// Get auth token for HTTP servers (if available)
+	/// Get auth token for HTTP and SSE servers (if available)
 	pub fn auth_token(&self) -> Option<&str> {
 		match self {
 			McpServerConfig::Http {
@@ -132,7 +224,18 @@ impl McpServerConfig {
 			| McpServerConfig::Http {
 				connection: HttpConnection::Local { auth_token, .. },
 				..
-			} => auth_token.as_deref(),
+			}
+			| McpServerConfig::Sse { auth_token, .. } => auth_token.as_deref(),
+			_ => None,
+		}
+	}
+
+	/// Get custom request headers for HTTP and SSE servers (if available)
+	pub fn headers(&self) -> Option<&HashMap<String, String>> {
+		match self {
+			McpServerConfig::Http { headers, .. } | McpServerConfig::Sse { headers, .. } => {
+				Some(headers)
+			}
 			_ => None,
 		}
 	}
@@ -149,6 +252,36 @@ impl McpServerConfig {
 		}
 	}
 
+	/// Get heartbeat interval for stdin servers (0 = disabled)
+	pub fn heartbeat_seconds(&self) -> u64 {
+		match self {
+			McpServerConfig::Stdin {
+				heartbeat_seconds, ..
+			} => *heartbeat_seconds,
+			_ => 0,
+		}
+	}
+
+	/// Check if the server is disabled in the registry
+	pub fn is_disabled(&self) -> bool {
+		match self {
+			McpServerConfig::Builtin { disabled, .. } => *disabled,
+			McpServerConfig::Http { disabled, .. } => *disabled,
+			McpServerConfig::Stdin { disabled, .. } => *disabled,
+			McpServerConfig::Sse { disabled, .. } => *disabled,
+		}
+	}
+
+	/// Enable or disable the server without removing it from the registry
+	pub fn set_disabled(&mut self, value: bool) {
+		match self {
+			McpServerConfig::Builtin { disabled, .. } => *disabled = value,
+			McpServerConfig::Http { disabled, .. } => *disabled = value,
+			McpServerConfig::Stdin { disabled, .. } => *disabled = value,
+			McpServerConfig::Sse { disabled, .. } => *disabled = value,
+		}
+	}
+
 	/// Get args for command-based servers (if available)
 	pub fn args(&self) -> &[String] {
 		match self {
@@ -167,6 +300,7 @@ impl McpServerConfig {
 			name: name.to_string(),
 			timeout_seconds,
 			tools,
+			disabled: false,
 		}
 	}
 
@@ -184,8 +318,11 @@ impl McpServerConfig {
 				url: url.to_string(),
 				auth_token,
 			},
+			headers: HashMap::new(),
 			timeout_seconds,
+			tool_timeouts: HashMap::new(),
 			tools,
+			disabled: false,
 		}
 	}
 
@@ -205,8 +342,11 @@ impl McpServerConfig {
 				args,
 				auth_token,
 			},
+			headers: HashMap::new(),
 			timeout_seconds,
+			tool_timeouts: HashMap::new(),
 			tools,
+			disabled: false,
 		}
 	}
 
@@ -223,8 +363,65 @@ impl McpServerConfig {
 			command: command.to_string(),
 			args,
 			timeout_seconds,
+			tool_timeouts: HashMap::new(),
+			tools,
+			heartbeat_seconds: 0,
+			disabled: false,
+		}
+	}
+
+	/// Create an SSE server configuration
+	pub fn sse(
+		name: &str,
+		url: &str,
+		timeout_seconds: u64,
+		tools: Vec<String>,
+		auth_token: Option<String>,
+	) -> Self {
+		Self::Sse {
+			name: name.to_string(),
+			url: url.to_string(),
+			auth_token,
+			headers: HashMap::new(),
+			timeout_seconds,
+			tool_timeouts: HashMap::new(),
 			tools,
+			disabled: false,
+		}
+	}
+
+	/// Remove credentials before the config is shared: the auth token and literal values
+	/// of credential headers (values referencing $VAR / ${VAR} are kept)
+	/// Returns the removed fields
+	pub fn strip_secrets(&mut self) -> Vec<String> {
+		let (auth_token, headers) = match self {
+			McpServerConfig::Http {
+				connection:
+					HttpConnection::Remote { auth_token, .. } | HttpConnection::Local { auth_token, .. },
+				headers,
+				..
+			}
+			| McpServerConfig::Sse {
+				auth_token,
+				headers,
+				..
+			} => (auth_token, headers),
+			_ => return Vec::new(),
+		};
+
+		let mut removed = Vec::new();
+		if auth_token.take().is_some() {
+			removed.push("auth_token".to_string());
 		}
+		headers.retain(|name, value| {
+			let keep = value.contains('$') || !is_credential_header(name);
+			if !keep {
+				removed.push(format!("headers.{}", name));
+			}
+			keep
+		});
+		removed.sort();
+		removed
 	}
 
 	/// Validate the server configuration
@@ -262,6 +459,14 @@ impl McpServerConfig {
 					return Err("Stdin server command cannot be empty".to_string());
 				}
 			}
+			McpServerConfig::Sse { name, url, .. } => {
+				if name.is_empty() {
+					return Err("SSE server name cannot be empty".to_string());
+				}
+				if url.is_empty() {
+					return Err("SSE server URL cannot be empty".to_string());
+				}
+			}
 		}
 		Ok(())
 	}
@@ -270,10 +475,131 @@ impl McpServerConfig {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct McpConfig {
 	// Server registry - array of server configurations (consistent with layers)
+	#[serde(default)]
 	pub servers: Vec<McpServerConfig>,
 
 	// Tool filtering - allows limiting tools across all enabled servers
 	pub allowed_tools: Vec<String>,
+
+	// TOML file with more [[servers]] entries, merged into the registry at load time
+	// Relative paths are resolved against the directory of the config file
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub servers_file: Option<String>,
+
+	// Names of the servers merged from servers_file (never written back to the config)
+	#[serde(skip)]
+	pub included_servers: Vec<String>,
+}
+
+// Settings of the builtin developer server tools ([developer] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeveloperConfig {
+	// Run shell tool commands in a container instead of on the host (unset = host)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub shell_sandbox: Option<ShellSandboxConfig>,
+	// Commands run at session start whose output is added as the first user message,
+	// e.g. "cargo build 2>&1 | tail -40"
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub context_commands: Vec<String>,
+	// Ask before running context_commands (skipped without a terminal to ask on)
+	#[serde(default = "default_confirm_context_commands")]
+	pub confirm_context_commands: bool,
+}
+
+fn default_confirm_context_commands() -> bool {
+	true
+}
+
+// Config files written before [developer] existed get the template values
+impl Default for DeveloperConfig {
+	fn default() -> Self {
+		Self {
+			shell_sandbox: None,
+			context_commands: Vec::new(),
+			confirm_context_commands: default_confirm_context_commands(),
+		}
+	}
+}
+
+// Container the shell tool runs commands in: the working directory is mounted read-write
+// at the same path, the rest of the container filesystem is read-only
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShellSandboxConfig {
+	// Container runtime CLI used to start the container
+	#[serde(default)]
+	pub runtime: SandboxRuntime,
+	// Image the commands run in, e.g. "rust:1.85"
+	pub image: String,
+	// Allow network access from the container (none by default)
+	#[serde(default)]
+	pub network: bool,
+	// Extra arguments passed to `<runtime> run`, e.g. ["--memory", "2g"]
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub extra_args: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SandboxRuntime {
+	#[default]
+	#[serde(rename = "docker")]
+	Docker,
+	#[serde(rename = "podman")]
+	Podman,
+}
+
+impl SandboxRuntime {
+	/// Name of the runtime CLI binary
+	pub fn command(&self) -> &'static str {
+		match self {
+			SandboxRuntime::Docker => "docker",
+			SandboxRuntime::Podman => "podman",
+		}
+	}
+}
+
+// Settings of the builtin web server search tools ([web] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebConfig {
+	// Names of the secrets holding Brave Search API keys, used round-robin
+	// (resolved like provider keys: environment first, then [secrets]; empty = BRAVE_API_KEY)
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub api_key_vars: Vec<String>,
+	// Seconds a key is skipped after the API rate limited it (429)
+	#[serde(default = "default_key_cooldown_seconds")]
+	pub key_cooldown_seconds: u64,
+}
+
+fn default_key_cooldown_seconds() -> u64 {
+	60
+}
+
+// Config files written before [web] existed get the template values
+impl Default for WebConfig {
+	fn default() -> Self {
+		Self {
+			api_key_vars: Vec::new(),
+			key_cooldown_seconds: default_key_cooldown_seconds(),
+		}
+	}
+}
+
+impl WebConfig {
+	/// Secret names of the configured Brave Search API keys
+	pub fn api_key_names(&self) -> Vec<String> {
+		if self.api_key_vars.is_empty() {
+			vec!["BRAVE_API_KEY".to_string()]
+		} else {
+			self.api_key_vars.clone()
+		}
+	}
+}
+
+// Contents of an mcp.servers_file include
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpServersFile {
+	#[serde(default)]
+	pub servers: Vec<McpServerConfig>,
 }
 
 // Role-specific MCP configuration with server_refs
@@ -297,6 +623,7 @@ impl RoleMcpConfig {
 
 	/// Get enabled servers from the global registry for this role
 	/// Now works with array format (consistent with layers)
+	/// Servers marked disabled in the registry are skipped
 	pub fn get_enabled_servers(&self, global_servers: &[McpServerConfig]) -> Vec<McpServerConfig> {
 		if self.server_refs.is_empty() {
 			return Vec::new();
@@ -306,6 +633,10 @@ impl RoleMcpConfig {
 		for server_name in &self.server_refs {
 			// Find server by name in the array
 			if let Some(server_config) = global_servers.iter().find(|s| s.name() == *server_name) {
+				// Disabled servers stay referenced but are not started
+				if server_config.is_disabled() {
+					continue;
+				}
 				let mut server = server_config.clone();
 				// Apply role-specific tool filtering if specified
 				if !self.allowed_tools.is_empty() {
@@ -316,35 +647,68 @@ impl RoleMcpConfig {
 						McpServerConfig::Builtin {
 							name,
 							timeout_seconds,
+							disabled,
 							..
 						} => McpServerConfig::Builtin {
 							name,
 							timeout_seconds,
 							tools: filtered_tools,
+							disabled,
 						},
 						McpServerConfig::Http {
 							name,
 							connection,
+							headers,
 							timeout_seconds,
+							tool_timeouts,
+							disabled,
 							..
 						} => McpServerConfig::Http {
 							name,
 							connection,
+							headers,
 							timeout_seconds,
+							tool_timeouts,
 							tools: filtered_tools,
+							disabled,
 						},
 						McpServerConfig::Stdin {
 							name,
 							command,
 							args,
 							timeout_seconds,
+							tool_timeouts,
+							heartbeat_seconds,
+							disabled,
 							..
 						} => McpServerConfig::Stdin {
 							name,
 							command,
 							args,
 							timeout_seconds,
+							tool_timeouts,
+							tools: filtered_tools,
+							heartbeat_seconds,
+							disabled,
+						},
+						McpServerConfig::Sse {
+							name,
+							url,
+							auth_token,
+							headers,
+							timeout_seconds,
+							tool_timeouts,
+							disabled,
+							..
+						} => McpServerConfig::Sse {
+							name,
+							url,
+							auth_token,
+							headers,
+							timeout_seconds,
+							tool_timeouts,
 							tools: filtered_tools,
+							disabled,
 						},
 					};
 				}
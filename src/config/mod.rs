@@ -24,6 +24,7 @@ pub mod mcp;
 pub mod migrations;
 pub mod providers;
 pub mod roles;
+pub mod secrets;
 pub mod validation;
 
 // Tests removed - strict configuration mode doesn't support Default implementations
@@ -34,6 +35,7 @@ pub use layers::*;
 pub use mcp::*;
 pub use providers::*;
 pub use roles::*;
+pub use secrets::{SecretsConfig, SecretsSource};
 
 // Agent configuration
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -43,11 +45,258 @@ pub struct AgentConfig {
 
 	// Tool description for this specific agent
 	pub description: String,
+
+	// What to do when the agent run fails
+	#[serde(default)]
+	pub on_failure: AgentFailureMode,
+
+	// Number of extra attempts when on_failure = "retry"
+	#[serde(default)]
+	pub max_retries: u32,
+
+	// Model for a single extra attempt when on_failure = "fallback_model"
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fallback_model: Option<String>,
+}
+
+// Operational logging configuration ([logging] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct LoggingConfig {
+	#[serde(default)]
+	pub format: LogFormat,
+	// Where full tool results are persisted for session logs
+	#[serde(default)]
+	pub tool_results: ToolResultStorage,
+	// Longest string value of logged tool call parameters in bytes (0 = log in full)
+	// Calls logged with truncated parameters are skipped by --replay-tools-from
+	#[serde(default)]
+	pub max_param_bytes: usize,
+}
+
+// Pricing of a model in USD per 1M tokens ([model_pricing] entries)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+	pub input: f64,
+	pub output: f64,
+}
+
+// Session model from a context size on ([[context_models]] entries)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContextModel {
+	// Context size in tokens from which this model is used
+	pub min_tokens: usize,
+	// Model to switch to ("provider:model")
+	pub model: String,
+}
+
+// Slash command that expands into a prompt ([[custom_commands]] entries)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CustomCommand {
+	// Command name without the slash, e.g. "review" for /review
+	pub name: String,
+	// Prompt sent as the user message, with placeholders; %{ARGS} is the text after the command
+	pub template: String,
+	// Shown in /help
+	#[serde(default)]
+	pub description: String,
+}
+
+// Exit codes and failure detection for `octomind run` ([run] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RunConfig {
+	// Regex matched against the final assistant message; a match fails the run (see --fail-on)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fail_on: Option<String>,
+	// Exit code when the API call or a tool follow-up fails
+	#[serde(default = "default_api_error_exit_code")]
+	pub api_error_exit_code: i32,
+	// Exit code when continuing past max_session_spending_threshold is declined
+	#[serde(default = "default_spending_limit_exit_code")]
+	pub spending_limit_exit_code: i32,
+	// Exit code when the final message matches fail_on
+	#[serde(default = "default_fail_on_exit_code")]
+	pub fail_on_exit_code: i32,
+	// Exit code when the run is stopped by session_max_duration_seconds
+	#[serde(default = "default_time_limit_exit_code")]
+	pub time_limit_exit_code: i32,
+}
+
+fn default_api_error_exit_code() -> i32 {
+	1
+}
+
+fn default_spending_limit_exit_code() -> i32 {
+	2
+}
+
+fn default_fail_on_exit_code() -> i32 {
+	3
+}
+
+fn default_time_limit_exit_code() -> i32 {
+	4
+}
+
+impl Default for RunConfig {
+	fn default() -> Self {
+		Self {
+			fail_on: None,
+			api_error_exit_code: default_api_error_exit_code(),
+			spending_limit_exit_code: default_spending_limit_exit_code(),
+			fail_on_exit_code: default_fail_on_exit_code(),
+			time_limit_exit_code: default_time_limit_exit_code(),
+		}
+	}
+}
+
+fn default_rate_limit_fallback_after() -> u32 {
+	2
+}
+
+fn default_scratchpad_prompt_max_bytes() -> usize {
+	4000
+}
+
+fn default_octocode_integration() -> bool {
+	true
+}
+
+fn default_exit_summary_min_messages() -> usize {
+	6
+}
+
+fn default_force_exit_grace_seconds() -> u64 {
+	3
+}
+
+fn default_file_tree_max_depth() -> usize {
+	3
+}
+
+fn default_file_tree_max_files() -> usize {
+	200
+}
+
+fn default_dedupe_similarity_threshold() -> f64 {
+	0.97
+}
+
+fn default_auto_compact_threshold() -> f64 {
+	0.8
+}
+
+// Storage of full tool results in session logs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ToolResultStorage {
+	// Stored inside the session file (historical behavior)
+	#[default]
+	#[serde(rename = "inline")]
+	Inline,
+	// Stored in a sidecar directory next to the session file, referenced by tool id
+	#[serde(rename = "sidecar")]
+	Sidecar,
+}
+
+// Log output format
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum LogFormat {
+	// Session logs only (historical behavior)
+	#[default]
+	#[serde(rename = "text")]
+	Text,
+	// Additionally emit one JSON event per line to the logs directory
+	#[serde(rename = "json")]
+	Json,
+}
+
+// Template syntax of role system prompts and welcome messages
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TemplateEngine {
+	// %{NAME} placeholder substitution only (historical behavior)
+	#[default]
+	#[serde(rename = "simple")]
+	Simple,
+	// Additionally {{#if}}, {{#unless}}, {{#each}} blocks and {{variables}}, see session::prompt_template
+	#[serde(rename = "handlebars")]
+	Handlebars,
+}
+
+// Tool calls written into the response content, for models without native function calling
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ContentToolCallFormat {
+	// Only native tool calls are executed (historical behavior)
+	#[default]
+	#[serde(rename = "none")]
+	None,
+	// <tool_call>{"name": ..., "arguments": {...}}</tool_call>
+	#[serde(rename = "xml")]
+	Xml,
+	// A fenced ```tool_call block holding the same JSON object (or an array of them)
+	#[serde(rename = "fenced")]
+	Fenced,
+	// Either of the above
+	#[serde(rename = "auto")]
+	Auto,
+}
+
+// What the session does when the role's layers fail on a message
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum LayerFailurePolicy {
+	// Send the original input to the main model (historical behavior)
+	#[default]
+	#[serde(rename = "continue")]
+	Continue,
+	// Stop: the message is not sent (run mode exits with an error)
+	#[serde(rename = "abort")]
+	Abort,
+	// Run the layers once more, then continue with the original input if they fail again
+	#[serde(rename = "retry")]
+	Retry,
+}
+
+// Agent failure handling strategy
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum AgentFailureMode {
+	// Return the error to the parent (historical behavior)
+	#[default]
+	#[serde(rename = "propagate")]
+	Propagate,
+	// Re-run the agent up to max_retries times
+	#[serde(rename = "retry")]
+	Retry,
+	// Re-run the agent once with fallback_model
+	#[serde(rename = "fallback_model")]
+	FallbackModel,
 }
 
 // Current config version - increment when making breaking changes
 pub const CURRENT_CONFIG_VERSION: u32 = 1;
 
+// System prompt of the bare model (ask command, --plain sessions)
+pub const PLAIN_SYSTEM_PROMPT: &str = "You are a helpful assistant.";
+
+/// Parse a --layer-temp value: the layer name and a temperature from 0.0 to 2.0
+pub fn parse_layer_temperature(value: &str) -> Result<(String, f32), String> {
+	let (layer, temperature) = value
+		.split_once('=')
+		.ok_or_else(|| format!("expected LAYER=TEMP, got '{}'", value))?;
+	let layer = layer.trim();
+	if layer.is_empty() {
+		return Err(format!("missing layer name in '{}'", value));
+	}
+	let temperature: f32 = temperature
+		.trim()
+		.parse()
+		.map_err(|_| format!("invalid temperature '{}'", temperature))?;
+	if !(0.0..=2.0).contains(&temperature) {
+		return Err(format!(
+			"temperature {} is out of range (0.0 to 2.0)",
+			temperature
+		));
+	}
+	Ok((layer.to_string(), temperature))
+}
+
 // Type alias to simplify the complex return type for get_role_config
 type RoleConfigResult<'a> = (
 	&'a RoleConfig,
@@ -81,7 +330,50 @@ impl LogLevel {
 	}
 }
 
-// REMOVED: All default functions - config must be complete and explicit
+/// Ordering applied to tool definitions before they are sent to the provider
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ToolDefinitionOrder {
+	/// Server/config order as gathered (historical behavior)
+	#[default]
+	#[serde(rename = "config")]
+	Config,
+	#[serde(rename = "alphabetical")]
+	Alphabetical,
+	/// Grouped by tool category, config order within a group
+	#[serde(rename = "category")]
+	Category,
+}
+
+/// Layout of the tools section appended to the system prompt
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PromptToolListing {
+	/// One list in tool definition order (historical behavior)
+	#[default]
+	#[serde(rename = "flat")]
+	Flat,
+	/// Under a header per tool category, categories in order of first appearance
+	#[serde(rename = "category")]
+	Category,
+}
+
+/// Formatting of tool results whose content parses as JSON
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ToolResultJsonMode {
+	/// Content passed through unchanged (historical behavior)
+	#[default]
+	#[serde(rename = "raw")]
+	Raw,
+	#[serde(rename = "pretty")]
+	Pretty,
+	/// Minified to save tokens
+	#[serde(rename = "compact")]
+	Compact,
+}
+
+// Config must be complete and explicit: config-templates/default.toml sets every field,
+// and fields that existed in the first config version have no defaults.
+// Fields added since then carry #[serde(default)] with the same value as the template,
+// so config files written by earlier versions keep loading
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
@@ -99,23 +391,259 @@ pub struct Config {
 
 	// System-wide configuration settings (not role-specific)
 	pub mcp_response_warning_threshold: usize,
+	// Warn when a single model completion exceeds this many output tokens (0 = disabled)
+	#[serde(default)]
+	pub completion_warning_tokens: u64,
 	pub max_request_tokens_threshold: usize,
+	// Maximum tokens for a single user message before it is trimmed (0 = no limit)
+	#[serde(default)]
+	pub max_user_message_tokens: usize,
 	pub enable_auto_truncation: bool,
 	pub cache_tokens_threshold: u64,
 	pub cache_timeout_seconds: u64,
 	pub enable_markdown_rendering: bool,
 	// Markdown theme for styling
 	pub markdown_theme: String,
+	// Copy each final assistant response to the system clipboard (interactive sessions)
+	#[serde(default)]
+	pub copy_response_to_clipboard: bool,
+	// Elide tool parameter strings longer than this in the display (0 = show full values)
+	#[serde(default)]
+	pub tool_param_preview_len: usize,
+	// Render a "Sources:" footer when the provider returns citations/annotations
+	#[serde(default)]
+	pub show_citations: bool,
 	// Session spending threshold in USD - if > 0, prompt user when exceeded
 	pub max_session_spending_threshold: f64,
+	// Wall-clock limit for a whole `run` invocation in seconds (0 = no limit, see --max-duration)
+	#[serde(default)]
+	pub session_max_duration_seconds: u64,
+	// Seconds a second Ctrl+C waits for the session save and server cleanup before exiting
+	// anyway (0 = exit immediately without saving)
+	#[serde(default = "default_force_exit_grace_seconds")]
+	pub force_exit_grace_seconds: u64,
+	// Template syntax of role system prompts and welcome messages ("simple" or "handlebars")
+	#[serde(default)]
+	pub template_engine: TemplateEngine,
+
+	// Summarize the session with a model on /exit and at the end of `run`, shown on resume
+	#[serde(default)]
+	pub exit_summary: bool,
+
+	// Model for the exit summary (empty = default_layer_model, then the session model)
+	#[serde(default)]
+	pub exit_summary_model: String,
+
+	// Sessions with fewer user and assistant messages are not summarized
+	#[serde(default = "default_exit_summary_min_messages")]
+	pub exit_summary_min_messages: usize,
+
+	// Summarize older turns into a checkpoint before a request once the context
+	// reaches auto_compact_threshold of the model's context window
+	#[serde(default)]
+	pub auto_compact: bool,
+
+	// Fraction of the context window (0.0-1.0) that triggers auto_compact
+	#[serde(default = "default_auto_compact_threshold")]
+	pub auto_compact_threshold: f64,
+
+	// Keep this many most recent tool results in full; older ones that an assistant
+	// message already followed are replaced with a one-line summary (0 = keep all)
+	#[serde(default)]
+	pub tool_result_retention: usize,
+
+	// Collapse runs of several tool calls older than the last three user turns into a
+	// one-line summary in front of the assistant message that ends them
+	#[serde(default)]
+	pub compact_tool_churn: bool,
 
 	// Use long-term (1h) caching for system messages (strict: must be in config)
 	pub use_long_system_cache: bool,
 
+	// Start sessions with system prompt, instructions and welcome in a fixed order and
+	// cache them as one block, so the prefix stays identical between sessions
+	#[serde(default)]
+	pub stable_cache_prefix: bool,
+
+	// Retry the follow-up request once when a model reports tool_calls but returns none
+	#[serde(default)]
+	pub retry_on_empty_tool_calls: bool,
+
+	// Rebuild the system prompt / instructions message when their source files change
+	#[serde(default)]
+	pub auto_reload_prompt: bool,
+
+	// Attach snippets of files and symbols mentioned in user messages before sending
+	#[serde(default)]
+	pub auto_context: bool,
+
+	// Append the git branch, ahead/behind and changed files to the system prompt (runs git)
+	#[serde(default)]
+	pub include_git_status: bool,
+
+	// Append a bounded tree of the project files to the system prompt of new sessions
+	#[serde(default)]
+	pub inject_file_tree: bool,
+
+	// Directory levels listed in the injected file tree (0 = no limit)
+	#[serde(default = "default_file_tree_max_depth")]
+	pub file_tree_max_depth: usize,
+
+	// Files listed in the injected file tree, the rest are counted
+	#[serde(default = "default_file_tree_max_files")]
+	pub file_tree_max_files: usize,
+
+	// Add the session scratchpad (scratchpad_write notes) to the system message of every request
+	#[serde(default)]
+	pub scratchpad_in_prompt: bool,
+
+	// Scratchpad bytes added to the system message, longer notes are cut
+	#[serde(default = "default_scratchpad_prompt_max_bytes")]
+	pub scratchpad_prompt_max_bytes: usize,
+
+	// Show session cost, total tokens and context usage in the interactive prompt
+	#[serde(default)]
+	pub prompt_status: bool,
+
+	// octocode hints at developer session start and the octocode auto-commit after /done
+	#[serde(default = "default_octocode_integration")]
+	pub octocode_integration: bool,
+
+	// Replace a tool result identical to an earlier one in the session with a short reference
+	#[serde(default)]
+	pub dedupe_tool_results: bool,
+
+	// Embedding model ("provider:model") that also finds near-duplicate tool results (empty = exact only)
+	#[serde(default)]
+	pub dedupe_similarity_model: String,
+
+	// Cosine similarity from which a tool result counts as a near-duplicate
+	#[serde(default = "default_dedupe_similarity_threshold")]
+	pub dedupe_similarity_threshold: f64,
+
+	// Interactive sessions: ask before executing the first tool call (see --confirm-tools)
+	#[serde(default)]
+	pub confirm_first_tool_call: bool,
+
+	// Include a unified diff in text_editor str_replace/insert results
+	#[serde(default)]
+	pub show_edit_diffs: bool,
+
+	// Trim trailing whitespace on edited lines, keep the file's line endings and ensure a final newline
+	#[serde(default)]
+	pub normalize_edits: bool,
+
+	// Extensions or file names never normalized (unset = built-in list: Markdown, Makefiles, patches)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub normalize_edits_skip: Option<Vec<String>>,
+
+	// MCP server references and tool filter for roles that do not define their own mcp
+	#[serde(default)]
+	pub default_server_refs: Vec<String>,
+
+	#[serde(default)]
+	pub default_allowed_tools: Vec<String>,
+
+	// Maximum automatic continuations when a response is cut off by length (0 = disabled)
+	#[serde(default)]
+	pub auto_continue_on_length: u32,
+
+	// Model for layers and commands that do not set their own (empty = session model)
+	#[serde(default)]
+	pub default_layer_model: String,
+
+	// Model the session switches to after repeated rate limit (HTTP 429) errors (empty = disabled)
+	#[serde(default)]
+	pub rate_limit_fallback_model: String,
+
+	// Consecutive rate limited requests before switching to rate_limit_fallback_model
+	#[serde(default = "default_rate_limit_fallback_after")]
+	pub rate_limit_fallback_after: u32,
+
+	// Seconds on the fallback model before switching back (0 = rest of the session)
+	#[serde(default)]
+	pub rate_limit_restore_seconds: u64,
+
+	// Session model by context size: the entry with the highest min_tokens reached (empty = disabled)
+	#[serde(default)]
+	pub context_models: Vec<ContextModel>,
+
+	// Retries one turn may make across provider and agent retries (0 = unlimited)
+	#[serde(default)]
+	pub max_retries_per_turn: u32,
+
+	// Ordering of tool definitions sent to the provider
+	#[serde(default)]
+	pub tool_definition_order: ToolDefinitionOrder,
+
+	// Tools always sent first (in listed order), regardless of tool_definition_order
+	#[serde(default)]
+	pub pinned_tools: Vec<String>,
+
+	// Layout of the tools section in the system prompt
+	#[serde(default)]
+	pub prompt_tool_listing: PromptToolListing,
+
+	// Sort the system prompt tools by name (within each category when grouped)
+	#[serde(default)]
+	pub sort_prompt_tools: bool,
+
+	// Reformatting of JSON tool results before they are added to the conversation
+	#[serde(default)]
+	pub tool_result_json_mode: ToolResultJsonMode,
+
+	// Per-tool overrides of tool_result_json_mode, keyed by tool name
+	#[serde(default)]
+	pub tool_result_json_modes: HashMap<String, ToolResultJsonMode>,
+
+	// Model pricing for cost estimates, keyed by "provider:model" (overrides built-in prices)
+	#[serde(default)]
+	pub model_pricing: HashMap<String, ModelPricing>,
+
 	// Agent configurations - array of agent definitions
 	#[serde(default)]
 	pub agents: Vec<AgentConfig>,
 
+	// Maximum number of agent_* tools running at once; extra calls wait their turn (0 = unlimited)
+	#[serde(default)]
+	pub agent_max_concurrency: usize,
+
+	// Operational logging settings
+	#[serde(default)]
+	pub logging: LoggingConfig,
+
+	// Where provider API keys are resolved from when not set in the environment
+	#[serde(default)]
+	pub secrets: SecretsConfig,
+
+	// Exit codes of the run command by outcome
+	#[serde(default)]
+	pub run: RunConfig,
+
+	// Upstream provider routing for openrouter: models
+	#[serde(default)]
+	pub openrouter: OpenRouterConfig,
+
+	// API base URL overrides of the other providers with fixed endpoints
+	#[serde(default)]
+	pub openai: ProviderEndpointConfig,
+	#[serde(default)]
+	pub anthropic: ProviderEndpointConfig,
+	#[serde(default)]
+	pub deepseek: ProviderEndpointConfig,
+
+	// User and metadata tags sent with provider requests for cost attribution
+	#[serde(default)]
+	pub attribution: AttributionConfig,
+
+	// Builtin developer server tool settings (shell sandbox)
+	#[serde(default)]
+	pub developer: DeveloperConfig,
+
+	// Builtin web server search settings (API key rotation)
+	#[serde(default)]
+	pub web: WebConfig,
+
 	// REMOVED: Providers configuration - API keys now only from ENV variables for security
 
 	// Role configurations - array format like layers
@@ -132,21 +660,45 @@ pub struct Config {
 	// Global command configurations (fallback for roles) - array format consistent with layers
 	pub commands: Option<Vec<crate::session::layers::LayerConfig>>,
 
+	// Slash commands that expand a prompt template into the user message
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub custom_commands: Vec<CustomCommand>,
+
 	// Global layer configurations - array of layer definitions
 	pub layers: Option<Vec<crate::session::layers::LayerConfig>>,
 
+	// Named layer pipelines that roles can reference via `layer_preset`
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub layer_presets: Vec<LayerPreset>,
+
 	// Legacy system prompt field for backward compatibility
 	pub system: Option<String>,
 
+	// Role's content_tool_call_format (not serialized - set by get_merged_config_for_role)
+	#[serde(skip)]
+	pub content_tool_call_format: ContentToolCallFormat,
+
+	// JSON schema session responses must match (--response-schema or /schema, runtime only)
+	#[serde(skip)]
+	pub response_schema: Option<serde_json::Value>,
+
 	#[serde(skip)]
 	config_path: Option<PathBuf>,
+
+	// Role reduced by --plain, applied again when the config is reloaded
+	#[serde(skip)]
+	plain_role: Option<String>,
+
+	// Layer temperatures from --layer-temp, applied again when the config is reloaded
+	#[serde(skip)]
+	layer_temperatures: Vec<(String, f32)>,
 }
 
 impl McpConfig {
 	/// Check if this config should be skipped during serialization
 	/// This helps avoid writing empty [mcp] sections when only internal servers exist
 	pub fn is_default_for_serialization(&self) -> bool {
-		self.servers.is_empty() && self.allowed_tools.is_empty()
+		self.servers.is_empty() && self.allowed_tools.is_empty() && self.servers_file.is_none()
 	}
 
 	/// Get all servers from the registry (for populating role configs)
@@ -178,35 +730,68 @@ impl McpConfig {
 					McpServerConfig::Builtin {
 						timeout_seconds,
 						tools,
+						disabled,
 						..
 					} => McpServerConfig::Builtin {
 						name,
 						timeout_seconds,
 						tools,
+						disabled,
 					},
 					McpServerConfig::Http {
 						connection,
+						headers,
 						timeout_seconds,
+						tool_timeouts,
 						tools,
+						disabled,
 						..
 					} => McpServerConfig::Http {
 						name,
 						connection,
+						headers,
 						timeout_seconds,
+						tool_timeouts,
 						tools,
+						disabled,
 					},
 					McpServerConfig::Stdin {
 						command,
 						args,
 						timeout_seconds,
+						tool_timeouts,
 						tools,
+						heartbeat_seconds,
+						disabled,
 						..
 					} => McpServerConfig::Stdin {
 						name,
 						command,
 						args,
 						timeout_seconds,
+						tool_timeouts,
 						tools,
+						heartbeat_seconds,
+						disabled,
+					},
+					McpServerConfig::Sse {
+						url,
+						auth_token,
+						headers,
+						timeout_seconds,
+						tool_timeouts,
+						tools,
+						disabled,
+						..
+					} => McpServerConfig::Sse {
+						name,
+						url,
+						auth_token,
+						headers,
+						timeout_seconds,
+						tool_timeouts,
+						tools,
+						disabled,
 					},
 				}
 			})
@@ -215,6 +800,8 @@ impl McpConfig {
 		Self {
 			servers: servers_vec,
 			allowed_tools: allowed_tools.unwrap_or_default(),
+			servers_file: None,
+			included_servers: Vec::new(),
 		}
 	}
 }
@@ -276,10 +863,14 @@ impl Config {
 	/// Get configuration for a specific role
 	/// Returns: (role_config, role_mcp_config, layers, commands, system_prompt)
 	pub fn get_role_config(&self, role: &str) -> RoleConfigResult<'_> {
+		static DEFAULT_MCP_CONFIG: RoleMcpConfig = RoleMcpConfig {
+			server_refs: Vec::new(),
+			allowed_tools: Vec::new(),
+		};
 		if let Some(role_config) = self.role_map.get(role) {
 			(
 				&role_config.config,
-				&role_config.mcp,
+				role_config.mcp.as_ref().unwrap_or(&DEFAULT_MCP_CONFIG),
 				self.layers.as_ref(),
 				self.commands.as_ref(),
 				role_config.config.system.as_ref(),
@@ -289,12 +880,11 @@ impl Config {
 			static DEFAULT_ROLE_CONFIG: RoleConfig = RoleConfig {
 				enable_layers: false,
 				system: None,
+				system_file: None,
 				welcome: String::new(), // Empty welcome for unknown roles
 				temperature: 0.7,       // Fallback temperature for unknown roles
-			};
-			static DEFAULT_MCP_CONFIG: RoleMcpConfig = RoleMcpConfig {
-				server_refs: Vec::new(),
-				allowed_tools: Vec::new(),
+				content_tool_call_format: ContentToolCallFormat::None,
+				layer_failure_policy: LayerFailurePolicy::Continue,
 			};
 			(
 				&DEFAULT_ROLE_CONFIG,
@@ -309,7 +899,7 @@ impl Config {
 	/// Get a merged config for a specific role (for backward compatibility)
 	/// This creates a new Config with role-specific settings merged into system-wide settings
 	pub fn get_merged_config_for_role(&self, mode: &str) -> Config {
-		let (_role_config, role_mcp_config, _role_layers_config, commands, system_prompt) =
+		let (role_config, role_mcp_config, _role_layers_config, commands, system_prompt) =
 			self.get_role_config(mode);
 
 		let mut merged = self.clone();
@@ -335,6 +925,8 @@ impl Config {
 		merged.mcp = McpConfig {
 			servers: enabled_servers, // Only role-enabled servers (with runtime injection)
 			allowed_tools: role_mcp_config.allowed_tools.clone(),
+			servers_file: None,
+			included_servers: Vec::new(),
 		};
 
 		// Role-specific layers (only enabled via layer_refs) - NOT USED ANYWHERE
@@ -343,6 +935,7 @@ impl Config {
 
 		merged.commands = commands.cloned();
 		merged.system = system_prompt.cloned();
+		merged.content_tool_call_format = role_config.content_tool_call_format;
 
 		merged
 	}
@@ -399,13 +992,120 @@ impl Config {
 		result
 	}
 
+	/// Reduce a role to the bare model for this invocation (--plain): no layers,
+	/// a minimal system prompt, no tools and no custom instructions file
+	pub fn apply_plain_mode(&mut self, role: &str) {
+		if let Some(role) = self.roles.iter_mut().find(|r| r.name == role) {
+			role.config.enable_layers = false;
+			role.config.system = Some(PLAIN_SYSTEM_PROMPT.to_string());
+			role.config.system_file = None;
+			role.mcp = Some(RoleMcpConfig::default());
+		}
+		self.custom_instructions_file_name.clear();
+		self.plain_role = Some(role.to_string());
+		self.build_role_map();
+	}
+
+	/// Override layer temperatures for this invocation (--layer-temp LAYER=TEMP)
+	/// Fails on names that are not in the layers registry, listing the known ones
+	pub fn apply_layer_temperatures(&mut self, overrides: &[(String, f32)]) -> anyhow::Result<()> {
+		for (name, temperature) in overrides {
+			match self
+				.layers
+				.iter_mut()
+				.flatten()
+				.find(|layer| layer.name == *name)
+			{
+				Some(layer) => layer.temperature = *temperature,
+				None => {
+					let known: Vec<&str> = self
+						.layers
+						.iter()
+						.flatten()
+						.map(|layer| layer.name.as_str())
+						.collect();
+					return Err(anyhow::anyhow!(
+						"Unknown layer '{}' in --layer-temp (configured layers: {})",
+						name,
+						if known.is_empty() {
+							"none".to_string()
+						} else {
+							known.join(", ")
+						}
+					));
+				}
+			}
+		}
+		self.layer_temperatures.extend_from_slice(overrides);
+		Ok(())
+	}
+
 	/// Build the internal role map from the roles array for fast lookup
+	/// default_layer_model is applied to layer and command configs first
+	/// Layer presets referenced by roles are expanded into layer_refs here,
+	/// and roles without their own mcp section inherit the default server refs and tools
 	pub fn build_role_map(&mut self) {
+		// Layers and commands without their own model fall back to default_layer_model
+		let default_layer_model =
+			(!self.default_layer_model.is_empty()).then(|| self.default_layer_model.clone());
+		for layer in self
+			.layers
+			.iter_mut()
+			.flatten()
+			.chain(self.commands.iter_mut().flatten())
+		{
+			layer.default_model = default_layer_model.clone();
+		}
+
 		self.role_map.clear();
 		for role in &self.roles {
-			self.role_map.insert(role.name.clone(), role.clone());
+			let mut role = role.clone();
+			if role.mcp.is_none() {
+				role.mcp = Some(RoleMcpConfig::with_server_refs_and_tools(
+					self.default_server_refs.clone(),
+					self.default_allowed_tools.clone(),
+				));
+			}
+			if let Some(preset_name) = &role.layer_preset {
+				if let Some(preset) = self.get_layer_preset(preset_name) {
+					// Preset layers run first, followed by any role-specific layer_refs
+					let mut layer_refs = preset.layers.clone();
+					for layer_name in &role.layer_refs {
+						if !layer_refs.contains(layer_name) {
+							layer_refs.push(layer_name.clone());
+						}
+					}
+					role.layer_refs = layer_refs;
+				}
+			}
+			self.role_map.insert(role.name.clone(), role);
 		}
 	}
+
+	/// JSON result mode for a tool (per-tool override, then the global mode)
+	pub fn get_tool_result_json_mode(&self, tool_name: &str) -> ToolResultJsonMode {
+		self.tool_result_json_modes
+			.get(tool_name)
+			.copied()
+			.unwrap_or(self.tool_result_json_mode)
+	}
+
+	/// Pricing for a "provider:model" string: [model_pricing] first, then the provider's built-in prices
+	pub fn get_model_pricing(&self, model: &str) -> Option<ModelPricing> {
+		if let Some(pricing) = self.model_pricing.get(model) {
+			return Some(*pricing);
+		}
+		let (provider, model_name) =
+			crate::providers::ProviderFactory::get_provider_for_model(model).ok()?;
+		provider
+			.get_model_pricing(&model_name)
+			.map(|(input, output)| ModelPricing { input, output })
+	}
+
+	/// Get a layer preset by name
+	pub fn get_layer_preset(&self, name: &str) -> Option<&LayerPreset> {
+		self.layer_presets.iter().find(|preset| preset.name == name)
+	}
 }
 
 // Logging macros for different log levels
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // Provider configurations - ONLY contain API keys and provider-specific settings
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -31,11 +32,52 @@ pub struct ProvidersConfig {
 	pub deepseek: ProviderConfig,
 }
 
-// Legacy OpenRouterConfig for backward compatibility
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// OpenRouter upstream routing ([openrouter] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct OpenRouterConfig {
-	pub model: String,
-	pub api_key: Option<String>,
+	// Let OpenRouter reroute a request to another upstream provider when the preferred one fails
+	#[serde(default = "default_allow_fallbacks")]
+	pub allow_fallbacks: bool,
+	// Upstream providers allowed to serve requests, e.g. ["Anthropic"] (empty = any)
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub only: Vec<String>,
+	// API base URL, e.g. for a corporate gateway (None = https://openrouter.ai/api/v1)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub base_url: Option<String>,
+}
+
+fn default_allow_fallbacks() -> bool {
+	true
+}
+
+// Config files written before [openrouter] existed get the template values
+impl Default for OpenRouterConfig {
+	fn default() -> Self {
+		Self {
+			allow_fallbacks: default_allow_fallbacks(),
+			only: Vec::new(),
+			base_url: None,
+		}
+	}
+}
+
+// API endpoint of a provider ([openai], [anthropic] and [deepseek] sections)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ProviderEndpointConfig {
+	// API base URL, e.g. for a proxy or a regional endpoint (None = the provider's own)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub base_url: Option<String>,
 }
 
-// REMOVED: Default implementations - all config must be explicit
+// Tags sent with provider requests for cost attribution ([attribution] section)
+// Only openrouter: models pass them on, other providers ignore them
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AttributionConfig {
+	// End user the requests are made for, sent as the request "user" field (empty = none)
+	#[serde(default)]
+	pub user: String,
+	// Key/value tags sent as the request "metadata" object, e.g. project = "billing-api"
+	// (sorted, so every request carries them in the same order)
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub metadata: BTreeMap<String, String>,
+}
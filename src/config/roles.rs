@@ -24,10 +24,24 @@ pub struct RoleConfig {
 	pub enable_layers: bool,
 	// Custom system prompt
 	pub system: Option<String>,
+	// Load the system prompt from this file instead of `system`, read at session start
+	// (relative to the config file directory; ~/ is supported)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub system_file: Option<String>,
 	// Custom welcome message with variable support
+	// Empty or omitted: the session starts without an assistant welcome turn
+	#[serde(default)]
 	pub welcome: String,
 	// Temperature for AI responses (0.0 to 1.0) - STRICT: must be in config
 	pub temperature: f32,
+	// Execute tool calls the model writes into its content ("none", "xml", "fenced", "auto"),
+	// for models without native function calling
+	#[serde(default)]
+	pub content_tool_call_format: crate::config::ContentToolCallFormat,
+	// When the layers fail on a message: "continue" with the original input, "abort"
+	// the message or "retry" the layers once
+	#[serde(default)]
+	pub layer_failure_policy: crate::config::LayerFailurePolicy,
 }
 
 // REMOVED: Default implementations - all config must be explicit
@@ -39,17 +53,26 @@ pub struct Role {
 	// Role name (e.g., "developer", "assistant", "tester")
 	pub name: String,
 
+	// Short description shown by `octomind roles`
+	#[serde(default, skip_serializing_if = "String::is_empty")]
+	pub description: String,
+
 	// Flattened role configuration
 	#[serde(flatten)]
 	pub config: RoleConfig,
 
-	// MCP configuration for this role
-	#[serde(default)]
-	pub mcp: RoleMcpConfig,
+	// MCP configuration for this role - when omitted, the role inherits
+	// default_server_refs / default_allowed_tools (resolved in build_role_map)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mcp: Option<RoleMcpConfig>,
 
 	// Layer references - list of layer names to use for this role
 	#[serde(default)]
 	pub layer_refs: Vec<String>,
+
+	// Named layer preset - expanded into layer_refs when the role map is built
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub layer_preset: Option<String>,
 }
 
 // REMOVED: Default implementations - all config must be explicit
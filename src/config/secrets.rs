@@ -0,0 +1,159 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Secret resolution for provider API keys
+// Resolution order: explicit environment variable first, then the configured source
+// (gitignored secrets file or OS keychain)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Service name used for keychain entries (account = secret name, e.g. OPENROUTER_API_KEY)
+const KEYCHAIN_SERVICE: &str = "octomind";
+
+// Secrets configuration ([secrets] section)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SecretsConfig {
+	#[serde(default)]
+	pub source: SecretsSource,
+	// Path to the secrets file (defaults to secrets.toml in the config directory)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub file: Option<String>,
+}
+
+// Where to look for secrets not present in the environment
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SecretsSource {
+	// Environment variables only (historical behavior)
+	#[default]
+	#[serde(rename = "env")]
+	Env,
+	// Flat TOML file of NAME = "value" pairs
+	#[serde(rename = "file")]
+	File,
+	// OS keychain (macOS Keychain, Secret Service on Linux)
+	#[serde(rename = "keychain")]
+	Keychain,
+}
+
+// Process-wide secrets settings, set once at startup
+static SECRETS_CONFIG: OnceLock<SecretsConfig> = OnceLock::new();
+
+// Cached secrets file contents (loaded lazily on first lookup)
+static SECRETS_FILE: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Configure secret resolution (call once at startup)
+pub fn init_secrets(config: &SecretsConfig) {
+	let _ = SECRETS_CONFIG.set(config.clone());
+}
+
+/// Resolve a secret by name: environment variable first, then the configured source
+pub fn get_secret(name: &str) -> Option<String> {
+	if let Ok(value) = std::env::var(name) {
+		return Some(value);
+	}
+
+	let config = SECRETS_CONFIG.get()?;
+	let value = match config.source {
+		SecretsSource::Env => None,
+		SecretsSource::File => load_secrets_file(config).get(name).cloned(),
+		SecretsSource::Keychain => read_keychain(name),
+	};
+
+	value.filter(|v| !v.is_empty())
+}
+
+/// Resolve a required secret, with an error naming where it was looked up
+pub fn require_secret(name: &str, what: &str) -> anyhow::Result<String> {
+	get_secret(name).ok_or_else(|| {
+		let source = match SECRETS_CONFIG.get().map(|c| c.source) {
+			Some(SecretsSource::File) => "environment or secrets file",
+			Some(SecretsSource::Keychain) => "environment or keychain",
+			_ => "environment variable",
+		};
+		anyhow::anyhow!("{} not found in {}: {}", what, source, name)
+	})
+}
+
+fn secrets_file_path(config: &SecretsConfig) -> Option<PathBuf> {
+	match &config.file {
+		Some(path) => {
+			if let Some(rest) = path.strip_prefix("~/") {
+				dirs::home_dir().map(|home| home.join(rest))
+			} else {
+				Some(PathBuf::from(path))
+			}
+		}
+		None => crate::directories::get_config_dir()
+			.ok()
+			.map(|dir| dir.join("secrets.toml")),
+	}
+}
+
+fn load_secrets_file(config: &SecretsConfig) -> &'static HashMap<String, String> {
+	SECRETS_FILE.get_or_init(|| {
+		let Some(path) = secrets_file_path(config) else {
+			return HashMap::new();
+		};
+		match std::fs::read_to_string(&path) {
+			Ok(content) => {
+				toml::from_str::<HashMap<String, String>>(&content).unwrap_or_else(|e| {
+					crate::log_error!("Failed to parse secrets file {}: {}", path.display(), e);
+					HashMap::new()
+				})
+			}
+			Err(e) => {
+				crate::log_debug!("Secrets file {} not readable: {}", path.display(), e);
+				HashMap::new()
+			}
+		}
+	})
+}
+
+// Read a secret from the OS keychain using the platform CLI
+fn read_keychain(name: &str) -> Option<String> {
+	let output = if cfg!(target_os = "macos") {
+		std::process::Command::new("security")
+			.args([
+				"find-generic-password",
+				"-s",
+				KEYCHAIN_SERVICE,
+				"-a",
+				name,
+				"-w",
+			])
+			.output()
+	} else if cfg!(target_os = "linux") {
+		std::process::Command::new("secret-tool")
+			.args(["lookup", "service", KEYCHAIN_SERVICE, "account", name])
+			.output()
+	} else {
+		crate::log_debug!("Keychain secrets are not supported on this platform");
+		return None;
+	};
+
+	match output {
+		Ok(output) if output.status.success() => {
+			let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+			Some(value)
+		}
+		Ok(_) => None,
+		Err(e) => {
+			crate::log_debug!("Keychain lookup for {} failed: {}", name, e);
+			None
+		}
+	}
+}
@@ -31,6 +31,219 @@ impl Config {
 			self.validate_layers(layers)?;
 		}
 
+		// Validate layer presets and role references to them - STRICT
+		self.validate_layer_presets()?;
+
+		// Validate agent failure handling settings - STRICT
+		self.validate_agents()?;
+
+		// Model prices must not be negative - STRICT
+		for (model, pricing) in &self.model_pricing {
+			if pricing.input < 0.0 || pricing.output < 0.0 {
+				return Err(anyhow!(
+					"Invalid model_pricing for '{}': prices cannot be negative",
+					model
+				));
+			}
+		}
+
+		// Rate limit fallback must name a provider:model and switch after at least one error - STRICT
+		if !self.rate_limit_fallback_model.is_empty() {
+			crate::providers::ProviderFactory::parse_model(&self.rate_limit_fallback_model)
+				.map_err(|e| anyhow!("Invalid rate_limit_fallback_model: {}", e))?;
+			if self.rate_limit_fallback_after == 0 {
+				return Err(anyhow!("rate_limit_fallback_after must be at least 1"));
+			}
+		}
+
+		// Context models must name a provider:model, one per context size - STRICT
+		for (index, entry) in self.context_models.iter().enumerate() {
+			crate::providers::ProviderFactory::parse_model(&entry.model)
+				.map_err(|e| anyhow!("Invalid context_models model '{}': {}", entry.model, e))?;
+			if self.context_models[..index]
+				.iter()
+				.any(|other| other.min_tokens == entry.min_tokens)
+			{
+				return Err(anyhow!(
+					"context_models has more than one entry with min_tokens = {}",
+					entry.min_tokens
+				));
+			}
+		}
+
+		// Attribution tags need a name - STRICT
+		if self
+			.attribution
+			.metadata
+			.keys()
+			.any(|key| key.trim().is_empty())
+		{
+			return Err(anyhow!("attribution.metadata keys cannot be empty"));
+		}
+
+		// Provider base URLs must be http(s) URLs - STRICT
+		let base_urls = [
+			("openrouter", &self.openrouter.base_url),
+			("openai", &self.openai.base_url),
+			("anthropic", &self.anthropic.base_url),
+			("deepseek", &self.deepseek.base_url),
+		];
+		for (provider, base_url) in base_urls {
+			if let Some(base_url) = base_url {
+				let valid = url::Url::parse(base_url)
+					.is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+				if !valid {
+					return Err(anyhow!(
+						"Invalid {}.base_url '{}': expected an http(s) URL such as https://gateway.example.com/v1",
+						provider,
+						base_url
+					));
+				}
+			}
+		}
+
+		// A shell sandbox needs an image to run commands in - STRICT
+		if let Some(sandbox) = &self.developer.shell_sandbox {
+			if sandbox.image.trim().is_empty() {
+				return Err(anyhow!("developer.shell_sandbox.image cannot be empty"));
+			}
+		}
+
+		// Context commands must be actual commands - STRICT
+		if self
+			.developer
+			.context_commands
+			.iter()
+			.any(|command| command.trim().is_empty())
+		{
+			return Err(anyhow!(
+				"developer.context_commands cannot contain empty commands"
+			));
+		}
+
+		// Custom commands need a unique name that is no built-in command - STRICT
+		for (index, command) in self.custom_commands.iter().enumerate() {
+			let name = command.name.as_str();
+			if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '/') {
+				return Err(anyhow!(
+					"Invalid custom command name '{}': use a single word without the slash, e.g. \"review\"",
+					name
+				));
+			}
+			if crate::session::chat::custom_commands::is_builtin_command(name) {
+				return Err(anyhow!(
+					"Custom command '{}' has the name of a built-in command",
+					name
+				));
+			}
+			if self.custom_commands[..index]
+				.iter()
+				.any(|other| other.name == name)
+			{
+				return Err(anyhow!(
+					"Custom command '{}' is defined more than once",
+					name
+				));
+			}
+			if command.template.trim().is_empty() {
+				return Err(anyhow!("Custom command '{}' has an empty template", name));
+			}
+		}
+
+		// Web search key names must be usable secret names - STRICT
+		if self
+			.web
+			.api_key_vars
+			.iter()
+			.any(|name| name.trim().is_empty())
+		{
+			return Err(anyhow!("web.api_key_vars cannot contain empty names"));
+		}
+
+		// Exit summary model must name a provider:model - STRICT
+		if !self.exit_summary_model.is_empty() {
+			crate::providers::ProviderFactory::parse_model(&self.exit_summary_model)
+				.map_err(|e| anyhow!("Invalid exit_summary_model: {}", e))?;
+		}
+
+		// Near-duplicate detection needs an embeddings provider and a similarity in (0, 1] - STRICT
+		if !self.dedupe_similarity_model.is_empty() {
+			crate::providers::EmbeddingProviderFactory::get_provider_for_model(
+				&self.dedupe_similarity_model,
+			)
+			.map_err(|e| anyhow!("Invalid dedupe_similarity_model: {}", e))?;
+		}
+		if self.dedupe_similarity_threshold <= 0.0 || self.dedupe_similarity_threshold > 1.0 {
+			return Err(anyhow!(
+				"dedupe_similarity_threshold must be greater than 0.0 and at most 1.0, got {}",
+				self.dedupe_similarity_threshold
+			));
+		}
+
+		// The scratchpad prompt section needs room for notes - STRICT
+		if self.scratchpad_in_prompt && self.scratchpad_prompt_max_bytes == 0 {
+			return Err(anyhow!(
+				"scratchpad_prompt_max_bytes must be at least 1 when scratchpad_in_prompt is enabled"
+			));
+		}
+
+		// The injected file tree must list at least one file - STRICT
+		if self.inject_file_tree && self.file_tree_max_files == 0 {
+			return Err(anyhow!(
+				"file_tree_max_files must be at least 1 when inject_file_tree is enabled"
+			));
+		}
+
+		// Role system prompt files must be readable - STRICT
+		for role in &self.roles {
+			if let Some(system_file) = &role.config.system_file {
+				let path = self.resolve_config_relative_path(system_file)?;
+				if !path.is_file() {
+					return Err(anyhow!(
+						"Role '{}' system_file not found: {}",
+						role.name,
+						path.display()
+					));
+				}
+			}
+		}
+
+		// Auto-compaction triggers at a fraction of the context window - STRICT
+		if self.auto_compact_threshold <= 0.0 || self.auto_compact_threshold > 1.0 {
+			return Err(anyhow!(
+				"auto_compact_threshold must be greater than 0.0 and at most 1.0, got {}",
+				self.auto_compact_threshold
+			));
+		}
+
+		// Run failure pattern must be a valid regex - STRICT
+		if let Some(pattern) = &self.run.fail_on {
+			regex::Regex::new(pattern)
+				.map_err(|e| anyhow!("Invalid run.fail_on regex '{}': {}", pattern, e))?;
+		}
+
+		// Prompts must only reference known placeholders - STRICT
+		for (source, template, extra_known) in self.placeholder_templates() {
+			let extra_known: Vec<&str> = extra_known.iter().map(|k| k.as_str()).collect();
+			crate::session::placeholders::validate_placeholders(template, &extra_known, &source)?;
+		}
+
+		// Role prompts must be valid templates with template_engine = "handlebars" - STRICT
+		if self.template_engine == crate::config::TemplateEngine::Handlebars {
+			for role in &self.roles {
+				if let Some(system) = &role.config.system {
+					crate::session::prompt_template::validate_template(
+						system,
+						&format!("Role '{}' system prompt", role.name),
+					)?;
+				}
+				crate::session::prompt_template::validate_template(
+					&role.config.welcome,
+					&format!("Role '{}' welcome message", role.name),
+				)?;
+			}
+		}
+
 		// STRICT: Validate required fields are not empty
 		self.validate_required_fields()?;
 
@@ -39,8 +252,22 @@ impl Config {
 
 	/// Validate that all required fields are present and not empty
 	fn validate_required_fields(&self) -> Result<()> {
-		if self.model.is_empty() {
-			return Err(anyhow!("Model field cannot be empty"));
+		if self.model.trim().is_empty() {
+			return Err(anyhow!(
+				"Model field cannot be empty - set model = \"provider:model\" (e.g. \"openrouter:anthropic/claude-sonnet-4\")"
+			));
+		}
+
+		// Every role's effective model must name a known provider and a model
+		for role in &self.roles {
+			let model = self.get_model(&role.name);
+			crate::providers::ProviderFactory::parse_model(&model)
+				.and_then(|(provider, _)| {
+					crate::providers::ProviderFactory::create_provider(&provider)
+				})
+				.map_err(|e| {
+					anyhow!("Invalid model '{}' for role '{}': {}", model, role.name, e)
+				})?;
 		}
 
 		if self.markdown_theme.is_empty() {
@@ -109,42 +336,183 @@ impl Config {
 	fn validate_mcp_config(&self) -> Result<()> {
 		// Validate server configurations
 		for server_config in &self.mcp.servers {
-			let server_name = &server_config.name();
-			// Validate timeout
-			if server_config.timeout_seconds() == 0 {
+			Self::validate_mcp_server(server_config)?;
+		}
+
+		Ok(())
+	}
+
+	/// Validate a single MCP server definition (also used for mcp.servers_file includes)
+	pub(crate) fn validate_mcp_server(
+		server_config: &crate::config::McpServerConfig,
+	) -> Result<()> {
+		let server_name = &server_config.name();
+		// Validate timeout
+		if server_config.timeout_seconds() == 0 {
+			return Err(anyhow!(
+				"Server '{}' has invalid timeout: 0. Must be greater than 0",
+				server_name
+			));
+		}
+
+		if server_config.timeout_seconds() > 3600 {
+			// 1 hour max
+			return Err(anyhow!(
+				"Server '{}' timeout too high: {} seconds. Maximum allowed: 3600 (1 hour)",
+				server_name,
+				server_config.timeout_seconds()
+			));
+		}
+
+		// Per-tool timeouts follow the same limits
+		for (tool, timeout) in server_config.tool_timeouts().into_iter().flatten() {
+			if *timeout == 0 || *timeout > 3600 {
 				return Err(anyhow!(
-					"Server '{}' has invalid timeout: 0. Must be greater than 0",
-					server_name
+					"Server '{}' has invalid timeout for tool '{}': {}. Must be between 1 and 3600 seconds",
+					server_name,
+					tool,
+					timeout
 				));
 			}
+		}
 
-			if server_config.timeout_seconds() > 3600 {
-				// 1 hour max
+		// SSE servers connect to a remote event stream
+		if server_config.connection_type() == crate::config::McpConnectionType::Sse {
+			let url = server_config.url().unwrap_or_default();
+			if !url.starts_with("http://") && !url.starts_with("https://") {
 				return Err(anyhow!(
-					"Server '{}' timeout too high: {} seconds. Maximum allowed: 3600 (1 hour)",
+					"SSE server '{}' needs an http:// or https:// url, got '{}'",
 					server_name,
-					server_config.timeout_seconds()
+					url
+				));
+			}
+		}
+
+		// Validate external server configuration
+		if matches!(
+			server_config.connection_type(),
+			crate::config::McpConnectionType::Http
+		) {
+			if server_config.url().is_none() && server_config.command().is_none() {
+				return Err(anyhow!(
+					"External server '{}' must have either 'url' or 'command' specified",
+					server_name
 				));
 			}
 
-			// Validate external server configuration
-			if matches!(
-				server_config.connection_type(),
-				crate::config::McpConnectionType::Http
-			) {
-				if server_config.url().is_none() && server_config.command().is_none() {
+			if server_config.url().is_some() && server_config.command().is_some() {
+				return Err(anyhow!(
+					"External server '{}' cannot have both 'url' and 'command' specified",
+					server_name
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn validate_layer_presets(&self) -> Result<()> {
+		let mut seen = std::collections::HashSet::new();
+		for preset in &self.layer_presets {
+			if preset.name.is_empty() {
+				return Err(anyhow!("Layer preset has empty name"));
+			}
+			if !seen.insert(preset.name.as_str()) {
+				return Err(anyhow!("Duplicate layer preset name '{}'", preset.name));
+			}
+
+			// Every layer in the preset must exist in the global layers registry
+			for layer_name in &preset.layers {
+				let exists = self
+					.layers
+					.as_ref()
+					.is_some_and(|layers| layers.iter().any(|l| l.name == *layer_name));
+				if !exists {
+					return Err(anyhow!(
+						"Layer preset '{}' references unknown layer '{}'",
+						preset.name,
+						layer_name
+					));
+				}
+			}
+		}
+
+		// Role preset references must resolve
+		for role in &self.roles {
+			if let Some(preset_name) = &role.layer_preset {
+				if self.get_layer_preset(preset_name).is_none() {
 					return Err(anyhow!(
-						"External server '{}' must have either 'url' or 'command' specified",
-						server_name
+						"Role '{}' references unknown layer preset '{}'",
+						role.name,
+						preset_name
 					));
 				}
+			}
+		}
+
+		Ok(())
+	}
 
-				if server_config.url().is_some() && server_config.command().is_some() {
+	/// Configured templates that go through placeholder resolution
+	/// Returns (source description, template, extra known names such as layer parameters)
+	pub fn placeholder_templates(&self) -> Vec<(String, &str, Vec<String>)> {
+		let mut templates = Vec::new();
+		for role in &self.roles {
+			if let Some(system) = &role.config.system {
+				templates.push((
+					format!("Role '{}' system prompt", role.name),
+					system.as_str(),
+					Vec::new(),
+				));
+			}
+			templates.push((
+				format!("Role '{}' welcome message", role.name),
+				role.config.welcome.as_str(),
+				Vec::new(),
+			));
+		}
+
+		for command in &self.custom_commands {
+			templates.push((
+				format!("Custom command '{}' template", command.name),
+				command.template.as_str(),
+				vec!["ARGS".to_string()],
+			));
+		}
+
+		let layers = self.layers.iter().flatten().map(|l| ("Layer", l));
+		let commands = self.commands.iter().flatten().map(|l| ("Command", l));
+		for (kind, layer) in layers.chain(commands) {
+			if let Some(system_prompt) = &layer.system_prompt {
+				templates.push((
+					format!("{} '{}' system prompt", kind, layer.name),
+					system_prompt.as_str(),
+					layer.parameters.keys().cloned().collect(),
+				));
+			}
+		}
+
+		templates
+	}
+
+	fn validate_agents(&self) -> Result<()> {
+		for agent in &self.agents {
+			match agent.on_failure {
+				crate::config::AgentFailureMode::Retry if agent.max_retries == 0 => {
+					return Err(anyhow!(
+						"Agent '{}' uses on_failure = \"retry\" but max_retries is 0",
+						agent.name
+					));
+				}
+				crate::config::AgentFailureMode::FallbackModel
+					if agent.fallback_model.as_deref().unwrap_or("").is_empty() =>
+				{
 					return Err(anyhow!(
-						"External server '{}' cannot have both 'url' and 'command' specified",
-						server_name
+						"Agent '{}' uses on_failure = \"fallback_model\" but fallback_model is not set",
+						agent.name
 					));
 				}
+				_ => {}
 			}
 		}
 
@@ -17,6 +17,41 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Active configuration profile (set once at startup via `--profile`)
+static ACTIVE_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select a named configuration profile for this process
+///
+/// With a profile set, the config is loaded from `config/<profile>.toml` and
+/// sessions are stored under `sessions/<profile>/`.
+pub fn set_active_profile(profile: &str) -> Result<()> {
+	validate_profile_name(profile)?;
+	ACTIVE_PROFILE
+		.set(profile.to_string())
+		.map_err(|_| anyhow::anyhow!("Configuration profile is already set"))
+}
+
+/// Get the active configuration profile, if any
+pub fn get_active_profile() -> Option<&'static str> {
+	ACTIVE_PROFILE.get().map(|p| p.as_str())
+}
+
+/// Profile names become file and directory names, so keep them simple
+fn validate_profile_name(profile: &str) -> Result<()> {
+	if profile.is_empty()
+		|| !profile
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+	{
+		return Err(anyhow::anyhow!(
+			"Invalid profile name '{}': use letters, digits, '-' or '_'",
+			profile
+		));
+	}
+	Ok(())
+}
 
 /// Get the system-wide data directory for octomind
 ///
@@ -72,7 +107,12 @@ pub fn get_config_dir() -> Result<PathBuf> {
 /// Get the sessions directory path
 pub fn get_sessions_dir() -> Result<PathBuf> {
 	let data_dir = get_octomind_data_dir()?;
-	let sessions_dir = data_dir.join("sessions");
+	let mut sessions_dir = data_dir.join("sessions");
+
+	// Keep each profile's sessions separate
+	if let Some(profile) = get_active_profile() {
+		sessions_dir = sessions_dir.join(profile);
+	}
 
 	if !sessions_dir.exists() {
 		fs::create_dir_all(&sessions_dir)?;
@@ -105,15 +145,21 @@ pub fn get_cache_dir() -> Result<PathBuf> {
 	Ok(cache_dir)
 }
 
-/// Get the default configuration file path
+/// Get the configuration file path (`config.toml`, or `<profile>.toml` when a profile is active)
 pub fn get_config_file_path() -> Result<PathBuf> {
 	let config_dir = get_config_dir()?;
-	Ok(config_dir.join("config.toml"))
+	match get_active_profile() {
+		Some(profile) => Ok(config_dir.join(format!("{}.toml", profile))),
+		None => Ok(config_dir.join("config.toml")),
+	}
 }
 
 /// Display information about the data directory locations
 pub fn print_directory_info() -> Result<()> {
 	println!("Octomind Data Directories:");
+	if let Some(profile) = get_active_profile() {
+		println!("  Profile:      {}", profile);
+	}
 	println!("  Data Dir:     {}", get_octomind_data_dir()?.display());
 	println!("  Config Dir:   {}", get_config_dir()?.display());
 	println!("  Sessions Dir: {}", get_sessions_dir()?.display());
@@ -153,4 +199,13 @@ mod tests {
 		let config_path = get_config_file_path().unwrap();
 		assert!(config_path.to_string_lossy().ends_with("config.toml"));
 	}
+
+	#[test]
+	fn test_validate_profile_name() {
+		assert!(validate_profile_name("work").is_ok());
+		assert!(validate_profile_name("personal_2-gpt").is_ok());
+		assert!(validate_profile_name("").is_err());
+		assert!(validate_profile_name("../work").is_err());
+		assert!(validate_profile_name("my profile").is_err());
+	}
 }
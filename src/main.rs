@@ -26,6 +26,10 @@ mod commands;
 #[command(version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "Octomind is a smart AI developer assistant with configurable MCP support")]
 struct CliArgs {
+	/// Use a named configuration profile (config/<PROFILE>.toml, sessions kept per profile)
+	#[arg(long, global = true, value_name = "PROFILE")]
+	profile: Option<String>,
+
 	#[command(subcommand)]
 	command: Commands,
 }
@@ -50,6 +54,21 @@ enum Commands {
 	/// Show all available placeholder variables and their values
 	Vars(commands::VarsArgs),
 
+	/// Invoke a single MCP tool directly and print its result (bypasses the model)
+	Tool(commands::ToolArgs),
+
+	/// Benchmark models on a fixed set of prompts (latency, tokens, cost)
+	Bench(commands::BenchArgs),
+
+	/// Manage external MCP servers in the configuration
+	Mcp(commands::McpArgs),
+
+	/// List the configured roles and start a session with the one picked
+	Roles(commands::RolesArgs),
+
+	/// Show a session's log, optionally following new entries as they are written
+	Logs(commands::LogsArgs),
+
 	/// Generate shell completion scripts
 	Completion {
 		/// The shell to generate completion for
@@ -62,9 +81,41 @@ enum Commands {
 async fn main() -> Result<(), anyhow::Error> {
 	let args = CliArgs::parse();
 
+	// Select the configuration profile before anything resolves config/session paths
+	if let Some(profile) = &args.profile {
+		octomind::directories::set_active_profile(profile)?;
+	}
+
+	// Editing must work even when the current config fails to load
+	if let Commands::Config(config_args) = &args.command {
+		if config_args.edit {
+			return commands::config::edit_config();
+		}
+	}
+
 	// Load configuration
 	let config = Config::load()?;
 
+	// Configure structured event logging
+	octomind::session::logger::set_log_format(config.logging.format);
+	octomind::session::logger::set_tool_result_storage(config.logging.tool_results);
+	octomind::session::logger::set_max_param_bytes(config.logging.max_param_bytes);
+
+	// Configure secret resolution for provider API keys
+	octomind::config::secrets::init_secrets(&config.secrets);
+
+	// Roles: list them and continue as `session --role <picked>`
+	let args = match args.command {
+		Commands::Roles(roles_args) => match commands::roles::execute(&roles_args, &config)? {
+			Some(role) => CliArgs {
+				command: Commands::Session(commands::SessionArgs::for_role(&role)),
+				..args
+			},
+			None => return Ok(()),
+		},
+		_ => args,
+	};
+
 	// Setup cleanup for MCP server processes when the program exits
 	let result = run_with_cleanup(args, config).await;
 
@@ -73,13 +124,53 @@ async fn main() -> Result<(), anyhow::Error> {
 		eprintln!("Warning: Error cleaning up MCP servers: {}", e);
 	}
 
-	result
+	// Non-zero exit code from the run command outcome
+	let exit_code = result?;
+	if exit_code != 0 {
+		std::process::exit(exit_code);
+	}
+
+	Ok(())
 }
 
-async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::Error> {
+// Returns the process exit code (only the run command reports failures this way)
+async fn run_with_cleanup(args: CliArgs, mut config: Config) -> Result<i32, anyhow::Error> {
+	// --plain: bare model for this session, before any MCP server is started for the role
+	if let Commands::Session(session_args) = &args.command {
+		if session_args.plain {
+			config.apply_plain_mode(&session_args.role);
+		}
+	}
+
+	// --layer-temp: layer temperature overrides for this invocation
+	let layer_temps: &[(String, f32)] = match &args.command {
+		Commands::Session(session_args) => &session_args.layer_temp,
+		Commands::Run(run_args) => &run_args.layer_temp,
+		_ => &[],
+	};
+	config.apply_layer_temperatures(layer_temps)?;
+
+	// --response-schema: load the schema before anything runs, so a bad file fails fast
+	let response_schema = match &args.command {
+		Commands::Session(session_args) => session_args.response_schema.as_ref(),
+		Commands::Run(run_args) => run_args.response_schema.as_ref(),
+		_ => None,
+	};
+	if let Some(path) = response_schema {
+		config.response_schema = Some(octomind::session::response_schema::load_schema(path)?);
+	}
+
+	// --no-save: keep the session in memory only
+	let no_save = match &args.command {
+		Commands::Session(session_args) => session_args.no_save,
+		Commands::Run(run_args) => run_args.no_save,
+		_ => false,
+	};
+	octomind::session::set_ephemeral(no_save);
+
 	// Initialize MCP servers and tool map once at startup for commands that need them
 	match &args.command {
-		Commands::Session(session_args) => {
+		Commands::Session(session_args) if session_args.command.is_none() => {
 			// For session command, initialize MCP servers based on the role
 			let role = &session_args.role;
 			let config_for_role = config.get_merged_config_for_role(role);
@@ -96,6 +187,16 @@ async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::E
 				eprintln!("Warning: Failed to initialize tool map: {}", e);
 				// Continue anyway - will fall back to building tool map on each use
 			}
+
+			// Step 3: Load recorded tool results for replay mode
+			if let Some(replay_session) = &session_args.replay_tools_from {
+				load_tool_replay(replay_session)?;
+			}
+
+			// Step 4: Record tool I/O to a trace directory
+			if let Some(trace_dir) = &session_args.trace_tools {
+				enable_tool_trace(trace_dir)?;
+			}
 		}
 		Commands::Run(run_args) => {
 			// For run command, initialize MCP servers based on the role
@@ -113,6 +214,16 @@ async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::E
 				eprintln!("Warning: Failed to initialize tool map: {}", e);
 				// Continue anyway - will fall back to building tool map on each use
 			}
+
+			// Step 3: Load recorded tool results for replay mode
+			if let Some(replay_session) = &run_args.replay_tools_from {
+				load_tool_replay(replay_session)?;
+			}
+
+			// Step 4: Record tool I/O to a trace directory
+			if let Some(trace_dir) = &run_args.trace_tools {
+				enable_tool_trace(trace_dir)?;
+			}
 		}
 		_ => {
 			// Other commands don't need MCP servers
@@ -121,21 +232,47 @@ async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::E
 
 	// Execute the appropriate command
 	match &args.command {
-		Commands::Config(config_args) => commands::config::execute(config_args, config)?,
-		Commands::Session(session_args) => {
-			session::chat::run_interactive_session(session_args, &config).await?
+		Commands::Config(config_args) if config_args.show_system => {
+			commands::config::show_system_prompt(config_args, &config).await?
 		}
+		Commands::Config(config_args) => commands::config::execute(config_args, config)?,
+		Commands::Session(session_args) => match &session_args.command {
+			Some(commands::SessionCommand::Migrate(migrate_args)) => {
+				commands::session::migrate(migrate_args)?
+			}
+			Some(commands::SessionCommand::Export(export_args)) => {
+				commands::session::export(export_args)?
+			}
+			None => session::chat::run_interactive_session(session_args, &config).await?,
+		},
 		Commands::Run(run_args) => {
+			// Fail fast on an invalid --fail-on pattern, before any API call
+			let fail_on = run_args.fail_on_regex(&config.run)?;
+			if let Some(max_duration) = run_args.max_duration {
+				config.session_max_duration_seconds = max_duration;
+			}
 			// Get input from parameter or stdin
 			let input = run_args.get_input()?;
 			// Convert RunArgs to SessionArgs and run non-interactively
 			let session_args = run_args.to_session_args();
-			session::chat::run_interactive_session_with_input(&session_args, &config, &input)
-				.await?
+			let outcome =
+				session::chat::run_interactive_session_with_input(&session_args, &config, &input)
+					.await?;
+			return Ok(commands::RunArgs::exit_code(
+				&outcome,
+				fail_on.as_ref(),
+				&config.run,
+			));
 		}
 		Commands::Ask(ask_args) => commands::ask::execute(ask_args, &config).await?,
 		Commands::Shell(shell_args) => commands::shell::execute(shell_args, &config).await?,
 		Commands::Vars(vars_args) => commands::vars::execute(vars_args, &config).await?,
+		Commands::Tool(tool_args) => commands::tool::execute(tool_args, &config).await?,
+		Commands::Bench(bench_args) => commands::bench::execute(bench_args, &config).await?,
+		Commands::Mcp(mcp_args) => commands::mcp::execute(mcp_args, config).await?,
+		Commands::Logs(logs_args) => commands::logs::execute(logs_args)?,
+		// Handled before MCP servers start, a picked role continues as a session
+		Commands::Roles(_) => {}
 		Commands::Completion { shell } => {
 			let mut app = CliArgs::command();
 			let name = app.get_name().to_string();
@@ -143,5 +280,28 @@ async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::E
 		}
 	}
 
+	Ok(0)
+}
+
+// Load tool results from a previous session so identical tool calls are replayed
+fn load_tool_replay(session_name: &str) -> Result<(), anyhow::Error> {
+	let (count, truncated) = octomind::mcp::replay::load_replay_session(session_name)?;
+	eprintln!(
+		"Tool replay enabled: {} recorded results from session '{}'",
+		count, session_name
+	);
+	if truncated > 0 {
+		eprintln!(
+			"Warning: {} recorded tool calls have truncated parameters ([logging] max_param_bytes) and cannot be replayed",
+			truncated
+		);
+	}
+	Ok(())
+}
+
+// Record each tool call's arguments, result and timing as numbered files
+fn enable_tool_trace(dir: &str) -> Result<(), anyhow::Error> {
+	let path = octomind::mcp::trace::init_tool_trace(dir)?;
+	eprintln!("Tool trace enabled: writing tool I/O to {}", path.display());
 	Ok(())
 }
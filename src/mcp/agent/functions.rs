@@ -18,8 +18,12 @@ use crate::mcp::{McpFunction, McpToolCall, McpToolResult};
 use crate::session::layers::{GenericLayer, Layer};
 use anyhow::Result;
 use serde_json::json;
-use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// Shared limiter for concurrently running agents, rebuilt when the configured limit changes
+static AGENT_LIMITER: Mutex<Option<(usize, Arc<Semaphore>)>> = Mutex::new(None);
 
 // Get all available agent functions based on config
 pub fn get_all_functions(config: &crate::config::Config) -> Vec<McpFunction> {
@@ -48,7 +52,7 @@ pub fn get_all_functions(config: &crate::config::Config) -> Vec<McpFunction> {
 pub async fn execute_agent_command(
 	call: &McpToolCall,
 	config: &crate::config::Config,
-	_cancellation_token: Option<Arc<AtomicBool>>,
+	cancellation_token: Option<Arc<AtomicBool>>,
 ) -> Result<McpToolResult> {
 	// Extract layer name from tool name (agent_<layer_name>)
 	let layer_name = call
@@ -63,7 +67,7 @@ pub async fn execute_agent_command(
 		.ok_or_else(|| anyhow::anyhow!("Agent tool requires 'task' parameter"))?;
 
 	// Verify this agent is configured
-	let _agent_config = config
+	let agent_config = config
 		.agents
 		.iter()
 		.find(|agent| agent.name == layer_name)
@@ -80,8 +84,26 @@ pub async fn execute_agent_command(
 	}
 	.ok_or_else(|| anyhow::anyhow!("Layer '{}' not found in configuration", layer_name))?;
 
+	let operation_cancelled =
+		cancellation_token.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+
+	// Wait for a free agent slot (held until this agent finishes)
+	let _permit = acquire_agent_slot(
+		config.agent_max_concurrency,
+		&agent_config.name,
+		&operation_cancelled,
+	)
+	.await?;
+
 	// Process task through the layer using the provider system
-	let result = process_layer_as_agent(&layer_config, task, config).await?;
+	let result = process_agent_with_failure_handling(
+		agent_config,
+		layer_config,
+		task,
+		config,
+		&operation_cancelled,
+	)
+	.await?;
 
 	// Return MCP-compliant result
 	Ok(McpToolResult::success(
@@ -91,11 +113,156 @@ pub async fn execute_agent_command(
 	))
 }
 
+// Acquire a slot from the shared agent limiter (None when concurrency is unlimited)
+// Waiting stops as soon as the operation is cancelled
+async fn acquire_agent_slot(
+	max_concurrency: usize,
+	agent_name: &str,
+	operation_cancelled: &Arc<AtomicBool>,
+) -> Result<Option<OwnedSemaphorePermit>> {
+	if max_concurrency == 0 {
+		return Ok(None);
+	}
+
+	let semaphore = {
+		let mut limiter = AGENT_LIMITER.lock().unwrap();
+		match limiter.as_ref() {
+			Some((limit, semaphore)) if *limit == max_concurrency => semaphore.clone(),
+			_ => {
+				let semaphore = Arc::new(Semaphore::new(max_concurrency));
+				*limiter = Some((max_concurrency, semaphore.clone()));
+				semaphore
+			}
+		}
+	};
+
+	if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+		return Ok(Some(permit));
+	}
+
+	crate::log_debug!(
+		"Agent '{}' queued: {} agents already running",
+		agent_name,
+		max_concurrency
+	);
+
+	tokio::select! {
+		permit = semaphore.acquire_owned() => Ok(Some(permit?)),
+		_ = async {
+			while !operation_cancelled.load(Ordering::SeqCst) {
+				tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+			}
+		} => Err(anyhow::anyhow!("Agent '{}' cancelled while waiting for a free slot", agent_name)),
+	}
+}
+
+// Run the agent layer applying the configured failure behavior
+// Non-default paths are reported in the result so the parent knows what happened
+async fn process_agent_with_failure_handling(
+	agent_config: &crate::config::AgentConfig,
+	layer_config: crate::session::layers::LayerConfig,
+	task: &str,
+	config: &crate::config::Config,
+	operation_cancelled: &Arc<AtomicBool>,
+) -> Result<String> {
+	use crate::config::AgentFailureMode;
+
+	let first_error =
+		match process_layer_as_agent(&layer_config, task, config, operation_cancelled).await {
+			Ok(result) => return Ok(result),
+			Err(e) => e,
+		};
+
+	match agent_config.on_failure {
+		AgentFailureMode::Propagate => Err(first_error),
+		AgentFailureMode::Retry => {
+			let mut last_error = first_error;
+			for attempt in 1..=agent_config.max_retries {
+				if operation_cancelled.load(Ordering::SeqCst) {
+					break;
+				}
+				if let Err(e) = crate::session::chat::retry_budget::take_retry(&format!(
+					"agent '{}'",
+					agent_config.name
+				)) {
+					return Err(anyhow::anyhow!(
+						"Agent '{}' failed: {}. {}",
+						agent_config.name,
+						last_error,
+						e
+					));
+				}
+				crate::log_info!(
+					"Agent '{}' failed ({}), retry {}/{}",
+					agent_config.name,
+					last_error,
+					attempt,
+					agent_config.max_retries
+				);
+				match process_layer_as_agent(&layer_config, task, config, operation_cancelled).await
+				{
+					Ok(result) => {
+						return Ok(format!(
+							"[agent '{}' succeeded on retry {}/{}]\n\n{}",
+							agent_config.name, attempt, agent_config.max_retries, result
+						));
+					}
+					Err(e) => last_error = e,
+				}
+			}
+			Err(anyhow::anyhow!(
+				"Agent '{}' failed after {} retries: {}",
+				agent_config.name,
+				agent_config.max_retries,
+				last_error
+			))
+		}
+		AgentFailureMode::FallbackModel => {
+			let Some(fallback_model) = agent_config.fallback_model.clone() else {
+				return Err(first_error);
+			};
+			if let Err(e) = crate::session::chat::retry_budget::take_retry(&format!(
+				"agent '{}' with fallback model {}",
+				agent_config.name, fallback_model
+			)) {
+				return Err(anyhow::anyhow!(
+					"Agent '{}' failed: {}. {}",
+					agent_config.name,
+					first_error,
+					e
+				));
+			}
+			crate::log_info!(
+				"Agent '{}' failed ({}), falling back to model {}",
+				agent_config.name,
+				first_error,
+				fallback_model
+			);
+			let mut fallback_layer = layer_config;
+			fallback_layer.model = Some(fallback_model.clone());
+			match process_layer_as_agent(&fallback_layer, task, config, operation_cancelled).await {
+				Ok(result) => Ok(format!(
+					"[agent '{}' used fallback model {} after error: {}]\n\n{}",
+					agent_config.name, fallback_model, first_error, result
+				)),
+				Err(e) => Err(anyhow::anyhow!(
+					"Agent '{}' failed with fallback model {}: {} (original error: {})",
+					agent_config.name,
+					fallback_model,
+					e,
+					first_error
+				)),
+			}
+		}
+	}
+}
+
 // Process layer as agent using isolated session with full layer processing
 async fn process_layer_as_agent(
 	layer_config: &crate::session::layers::LayerConfig,
 	task: &str,
 	config: &crate::config::Config,
+	operation_cancelled: &Arc<AtomicBool>,
 ) -> Result<String> {
 	// Create isolated session for agent
 	let agent_session = crate::session::Session::new(
@@ -107,10 +274,9 @@ async fn process_layer_as_agent(
 	// Create GenericLayer from config (reuse existing pattern)
 	let layer = GenericLayer::new(layer_config.clone());
 
-	// Process task through layer with full MCP tools support
-	let operation_cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+	// Process task through layer with full MCP tools support (shares the parent's cancellation)
 	let result = layer
-		.process(task, &agent_session, config, operation_cancelled)
+		.process(task, &agent_session, config, operation_cancelled.clone())
 		.await?;
 
 	// Collect all messages from agent session in chronological order
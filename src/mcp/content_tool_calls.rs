@@ -0,0 +1,166 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tool calls written into the response content (content_tool_call_format), for models
+// without native function calling. Their results go back as a user message, since such
+// models have no tool role either
+
+use super::McpToolCall;
+use crate::config::ContentToolCallFormat;
+use regex::Regex;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+// Tool ids of calls parsed from content start with this, to tell them from native calls
+pub const CONTENT_TOOL_CALL_ID_PREFIX: &str = "content_call_";
+
+fn xml_pattern() -> &'static Regex {
+	static PATTERN: OnceLock<Regex> = OnceLock::new();
+	PATTERN.get_or_init(|| Regex::new(r"(?s)<tool_call>\s*(.*?)\s*</tool_call>").unwrap())
+}
+
+fn fenced_pattern() -> &'static Regex {
+	static PATTERN: OnceLock<Regex> = OnceLock::new();
+	PATTERN.get_or_init(|| Regex::new(r"(?s)```tool_call[ \t]*\r?\n(.*?)```").unwrap())
+}
+
+/// Tool calls in the content, in order of appearance
+pub fn parse_content_tool_calls(content: &str, format: ContentToolCallFormat) -> Vec<McpToolCall> {
+	let patterns: &[&Regex] = match format {
+		ContentToolCallFormat::None => return Vec::new(),
+		ContentToolCallFormat::Xml => &[xml_pattern()],
+		ContentToolCallFormat::Fenced => &[fenced_pattern()],
+		ContentToolCallFormat::Auto => &[xml_pattern(), fenced_pattern()],
+	};
+
+	let mut blocks: Vec<(usize, &str)> = patterns
+		.iter()
+		.flat_map(|pattern| pattern.captures_iter(content))
+		.filter_map(|captures| {
+			let body = captures.get(1)?;
+			Some((body.start(), body.as_str()))
+		})
+		.collect();
+	blocks.sort_by_key(|(start, _)| *start);
+
+	let mut calls = Vec::new();
+	for (_, body) in blocks {
+		let Ok(value) = serde_json::from_str::<Value>(body.trim()) else {
+			crate::log_debug!(
+				"Skipping content tool call that is not valid JSON: {}",
+				body
+			);
+			continue;
+		};
+		let entries = match value {
+			Value::Array(entries) => entries,
+			entry => vec![entry],
+		};
+		for entry in entries {
+			match tool_call_from_json(&entry) {
+				Some(call) => calls.push(call),
+				None => crate::log_debug!("Skipping content tool call without a name: {}", entry),
+			}
+		}
+	}
+	calls
+}
+
+// {"name": ..., "arguments": {...}} - also accepts tool_name and parameters/input,
+// and arguments given as a JSON string
+fn tool_call_from_json(entry: &Value) -> Option<McpToolCall> {
+	let tool_name = ["name", "tool_name", "tool"]
+		.iter()
+		.find_map(|key| entry.get(key)?.as_str())?
+		.to_string();
+	let parameters = ["arguments", "parameters", "input"]
+		.iter()
+		.find_map(|key| entry.get(key))
+		.cloned()
+		.unwrap_or_else(|| Value::Object(Default::default()));
+	let parameters = match parameters {
+		Value::String(text) => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+		parameters => parameters,
+	};
+	Some(McpToolCall {
+		tool_name,
+		parameters,
+		// Unique per session: tool results are stored and replayed by tool id
+		tool_id: format!(
+			"{}{}",
+			CONTENT_TOOL_CALL_ID_PREFIX,
+			uuid::Uuid::new_v4().simple()
+		),
+	})
+}
+
+/// Whether a tool call was parsed from content rather than returned natively
+pub fn is_content_tool_call(tool_id: &str) -> bool {
+	tool_id.starts_with(CONTENT_TOOL_CALL_ID_PREFIX)
+}
+
+/// A tool result as sent back to the model in a user message
+pub fn format_content_tool_result(tool_name: &str, content: &str) -> String {
+	format!(
+		"<tool_result name=\"{}\">\n{}\n</tool_result>",
+		tool_name, content
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_xml_tool_calls() {
+		let content = "Let me look.\n<tool_call>\n{\"name\": \"list_files\", \"arguments\": {\"directory\": \"src\"}}\n</tool_call>\nand\n<tool_call>{\"tool_name\": \"shell\", \"parameters\": \"{\\\"command\\\": \\\"ls\\\"}\"}</tool_call>";
+		let calls = parse_content_tool_calls(content, ContentToolCallFormat::Xml);
+		assert_eq!(calls.len(), 2);
+		assert_eq!(calls[0].tool_name, "list_files");
+		assert_eq!(calls[0].parameters["directory"], "src");
+		assert!(is_content_tool_call(&calls[0].tool_id));
+		assert_ne!(calls[0].tool_id, calls[1].tool_id);
+		assert_eq!(calls[1].tool_name, "shell");
+		assert_eq!(calls[1].parameters["command"], "ls");
+		assert!(is_content_tool_call(&calls[1].tool_id));
+
+		// Later responses get new ids, so stored results of earlier turns are kept
+		let again = parse_content_tool_calls(content, ContentToolCallFormat::Xml);
+		assert_ne!(again[0].tool_id, calls[0].tool_id);
+
+		// Not parsed unless the role enables the format
+		assert!(parse_content_tool_calls(content, ContentToolCallFormat::None).is_empty());
+		assert!(parse_content_tool_calls(content, ContentToolCallFormat::Fenced).is_empty());
+	}
+
+	#[test]
+	fn test_parse_fenced_tool_calls() {
+		let content = "Two calls:\n```tool_call\n[{\"name\": \"a\"}, {\"name\": \"b\", \"input\": {\"x\": 1}}]\n```\n```json\n{\"name\": \"not_a_call\"}\n```\n<tool_call>{\"name\": \"c\"}</tool_call>";
+		let calls = parse_content_tool_calls(content, ContentToolCallFormat::Fenced);
+		let names: Vec<&str> = calls.iter().map(|c| c.tool_name.as_str()).collect();
+		assert_eq!(names, vec!["a", "b"]);
+		assert_eq!(calls[1].parameters["x"], 1);
+
+		// Auto takes both formats in order of appearance
+		let calls = parse_content_tool_calls(content, ContentToolCallFormat::Auto);
+		let names: Vec<&str> = calls.iter().map(|c| c.tool_name.as_str()).collect();
+		assert_eq!(names, vec!["a", "b", "c"]);
+	}
+
+	#[test]
+	fn test_invalid_content_tool_calls_are_skipped() {
+		let content = "<tool_call>not json</tool_call><tool_call>{\"arguments\": {}}</tool_call>";
+		assert!(parse_content_tool_calls(content, ContentToolCallFormat::Xml).is_empty());
+	}
+}
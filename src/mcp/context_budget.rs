@@ -0,0 +1,104 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Context budget of large tool responses - the main session records how full its
+// context window is before running tools, and the large response check scales
+// mcp_response_warning_threshold to the room that is left
+
+use std::sync::Mutex;
+
+static CONTEXT_USAGE: Mutex<Option<ContextUsage>> = Mutex::new(None);
+
+// A single result may take up to this share (1/N) of the remaining context...
+const REMAINING_SHARE_DIVISOR: usize = 4;
+// ...but never more than this multiple of the configured threshold
+const MAX_THRESHOLD_MULTIPLIER: usize = 4;
+// Near a full context the threshold goes down to 1/N of the configured one...
+const MIN_THRESHOLD_DIVISOR: usize = 4;
+// ...or this many tokens if that is more (but never above the configured threshold)
+const MIN_THRESHOLD_TOKENS: usize = 1000;
+
+/// Estimated conversation tokens against the model's context window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextUsage {
+	pub used_tokens: usize,
+	pub context_window: usize,
+}
+
+impl ContextUsage {
+	pub fn remaining_tokens(&self) -> usize {
+		self.context_window.saturating_sub(self.used_tokens)
+	}
+}
+
+/// Record the context usage for the next tool calls (None = unknown, e.g. layer tools)
+pub fn set_context_usage(usage: Option<ContextUsage>) {
+	*CONTEXT_USAGE.lock().unwrap() = usage.filter(|usage| usage.context_window > 0);
+}
+
+/// Context usage recorded before the current tool calls
+pub fn context_usage() -> Option<ContextUsage> {
+	*CONTEXT_USAGE.lock().unwrap()
+}
+
+/// Token count above which a tool result needs confirmation: a share of the remaining
+/// context, capped at a multiple of the configured threshold and never below a floor,
+/// so a full context does not flag every result. Without a known context window the
+/// configured threshold applies as is
+pub fn adaptive_threshold(configured: usize, usage: Option<ContextUsage>) -> usize {
+	match usage {
+		Some(usage) => (usage.remaining_tokens() / REMAINING_SHARE_DIVISOR)
+			.min(configured.saturating_mul(MAX_THRESHOLD_MULTIPLIER))
+			.max(threshold_floor(configured)),
+		None => configured,
+	}
+}
+
+// Lowest adaptive threshold, also the least a confirmed oversized result is cut to
+fn threshold_floor(configured: usize) -> usize {
+	(configured / MIN_THRESHOLD_DIVISOR)
+		.max(MIN_THRESHOLD_TOKENS)
+		.min(configured)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_adaptive_threshold() {
+		let usage = |used_tokens| {
+			Some(ContextUsage {
+				used_tokens,
+				context_window: 200_000,
+			})
+		};
+
+		// Plenty of room allows larger outputs, up to 4x the configured threshold
+		assert_eq!(adaptive_threshold(10_000, usage(0)), 40_000);
+		assert_eq!(adaptive_threshold(10_000, usage(100_000)), 25_000);
+		// Nearly full context warns earlier than configured
+		assert_eq!(adaptive_threshold(10_000, usage(180_000)), 5_000);
+		assert_eq!(adaptive_threshold(10_000, usage(190_000)), 2_500);
+		// ...but not below a quarter of the configured threshold, even when full or over
+		assert_eq!(adaptive_threshold(10_000, usage(198_000)), 2_500);
+		assert_eq!(adaptive_threshold(10_000, usage(200_000)), 2_500);
+		assert_eq!(adaptive_threshold(10_000, usage(250_000)), 2_500);
+		// Small thresholds floor at 1000 tokens, or the configured value if lower
+		assert_eq!(adaptive_threshold(2_000, usage(250_000)), 1_000);
+		assert_eq!(adaptive_threshold(500, usage(250_000)), 500);
+		// Unknown context window keeps the configured threshold
+		assert_eq!(adaptive_threshold(10_000, None), 10_000);
+	}
+}
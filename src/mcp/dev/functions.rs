@@ -15,9 +15,14 @@
 // Function definitions for the Developer MCP provider
 
 use super::super::McpFunction;
+use super::scratchpad::{get_scratchpad_read_function, get_scratchpad_write_function};
 use super::shell::get_shell_function;
 
 // Get all available developer functions
 pub fn get_all_functions() -> Vec<McpFunction> {
-	vec![get_shell_function()]
+	vec![
+		get_shell_function(),
+		get_scratchpad_read_function(),
+		get_scratchpad_write_function(),
+	]
 }
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 // Developer MCP provider - modular structure
-// Handles shell execution and other development tools
+// Handles shell execution (optionally sandboxed), the session scratchpad and other development tools
 
 pub mod functions;
+pub mod sandbox;
+pub mod scratchpad;
 pub mod shell;
 
 // Re-export main functionality
 pub use functions::get_all_functions;
+pub use scratchpad::{execute_scratchpad_read, execute_scratchpad_write};
 pub use shell::execute_shell_command;
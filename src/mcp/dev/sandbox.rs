@@ -0,0 +1,151 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Container sandbox for the shell tool ([developer] shell_sandbox)
+// Every command runs in a fresh container that is removed when it exits
+
+use crate::config::{SandboxRuntime, ShellSandboxConfig};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::process::Command as TokioCommand;
+
+static CONTAINER_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Unique container name for one command, so a cancelled command can be stopped
+pub fn container_name() -> String {
+	format!(
+		"octomind-shell-{}-{}",
+		std::process::id(),
+		CONTAINER_COUNTER.fetch_add(1, Ordering::Relaxed) + 1
+	)
+}
+
+/// Command that runs a shell command in the sandbox container
+pub fn sandbox_command(
+	command: &str,
+	sandbox: &ShellSandboxConfig,
+	workdir: &Path,
+	name: &str,
+) -> TokioCommand {
+	let mut cmd = TokioCommand::new(sandbox.runtime.command());
+	cmd.args(sandbox_args(command, sandbox, workdir, name));
+	cmd
+}
+
+// Arguments of `<runtime> run`: the working directory read-write at the same path,
+// everything else read-only apart from a /tmp tmpfs
+fn sandbox_args(
+	command: &str,
+	sandbox: &ShellSandboxConfig,
+	workdir: &Path,
+	name: &str,
+) -> Vec<String> {
+	let workdir = workdir.to_string_lossy().to_string();
+	let mut args: Vec<String> = vec![
+		"run".into(),
+		"--rm".into(),
+		"--name".into(),
+		name.into(),
+		"--read-only".into(),
+		"--tmpfs".into(),
+		"/tmp".into(),
+		"--volume".into(),
+		format!("{0}:{0}:rw", workdir),
+		"--workdir".into(),
+		workdir.clone(),
+	];
+	if !sandbox.network {
+		args.extend(["--network".into(), "none".into()]);
+	}
+	// Files written to the working directory keep the owner of the host directory
+	match sandbox.runtime {
+		SandboxRuntime::Podman => args.push("--userns=keep-id".into()),
+		SandboxRuntime::Docker => {
+			#[cfg(unix)]
+			{
+				use std::os::unix::fs::MetadataExt;
+				if let Ok(metadata) = std::fs::metadata(&workdir) {
+					args.extend([
+						"--user".into(),
+						format!("{}:{}", metadata.uid(), metadata.gid()),
+					]);
+				}
+			}
+		}
+	}
+	args.extend(sandbox.extra_args.iter().cloned());
+	args.extend([
+		sandbox.image.clone(),
+		"sh".into(),
+		"-c".into(),
+		command.into(),
+	]);
+	args
+}
+
+/// Stop the container of a cancelled command (killing the runtime CLI leaves it running)
+pub fn stop_container(sandbox: &ShellSandboxConfig, name: &str) {
+	let _ = std::process::Command::new(sandbox.runtime.command())
+		.args(["kill", name])
+		.output();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sandbox_args() {
+		let sandbox = ShellSandboxConfig {
+			runtime: SandboxRuntime::Podman,
+			image: "alpine:3".to_string(),
+			network: false,
+			extra_args: vec!["--memory".to_string(), "2g".to_string()],
+		};
+		let args = sandbox_args("ls -la", &sandbox, Path::new("/work/project"), "box");
+		assert_eq!(
+			args,
+			vec![
+				"run",
+				"--rm",
+				"--name",
+				"box",
+				"--read-only",
+				"--tmpfs",
+				"/tmp",
+				"--volume",
+				"/work/project:/work/project:rw",
+				"--workdir",
+				"/work/project",
+				"--network",
+				"none",
+				"--userns=keep-id",
+				"--memory",
+				"2g",
+				"alpine:3",
+				"sh",
+				"-c",
+				"ls -la",
+			]
+		);
+
+		// Network access leaves out --network none
+		let sandbox = ShellSandboxConfig {
+			network: true,
+			..sandbox
+		};
+		let args = sandbox_args("ls", &sandbox, Path::new("/work"), "box");
+		assert!(!args.contains(&"none".to_string()));
+	}
+}
@@ -0,0 +1,238 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Scratchpad functionality for the Developer MCP provider
+// Notes the agent keeps in a per-session file that survives context truncation
+
+use super::super::{McpFunction, McpToolCall, McpToolResult};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+// Upper bound for the scratchpad file so it stays a set of notes
+const MAX_SCRATCHPAD_BYTES: usize = 64 * 1024;
+
+// Session whose scratchpad the tools use, None outside a session
+static SCRATCHPAD_SESSION: RwLock<Option<String>> = RwLock::new(None);
+
+// Notes of a --no-save session, which never get a scratchpad file
+static EPHEMERAL_NOTES: RwLock<String> = RwLock::new(String::new());
+
+/// Use the scratchpad of this session (called when a session starts or is switched)
+pub fn set_session(session_name: &str) {
+	*SCRATCHPAD_SESSION.write().unwrap() = Some(session_name.to_string());
+}
+
+/// Scratchpad file of a session, next to its session log
+pub fn get_scratchpad_path(session_name: &str) -> Result<PathBuf> {
+	let sessions_dir = crate::directories::get_sessions_dir()?;
+	Ok(sessions_dir.join(format!("{}.scratchpad.md", session_name)))
+}
+
+fn current_scratchpad_path() -> Result<PathBuf> {
+	let session = SCRATCHPAD_SESSION.read().unwrap().clone();
+	match session {
+		Some(session_name) => get_scratchpad_path(&session_name),
+		None => Err(anyhow!("The scratchpad is only available in a session")),
+	}
+}
+
+// Notes of the current session, empty when the scratchpad does not exist yet
+fn load_notes() -> Result<String> {
+	let path = current_scratchpad_path()?;
+	if crate::session::is_ephemeral() {
+		return Ok(EPHEMERAL_NOTES.read().unwrap().clone());
+	}
+	match std::fs::read_to_string(&path) {
+		Ok(notes) => Ok(notes),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+		Err(e) => Err(anyhow!("Failed to read scratchpad: {}", e)),
+	}
+}
+
+fn store_notes(notes: &str) -> Result<()> {
+	let path = current_scratchpad_path()?;
+	if crate::session::is_ephemeral() {
+		*EPHEMERAL_NOTES.write().unwrap() = notes.to_string();
+		return Ok(());
+	}
+	std::fs::write(&path, notes).map_err(|e| anyhow!("Failed to write scratchpad: {}", e))
+}
+
+/// Notes in the current session's scratchpad, empty when there are none
+pub fn read_notes() -> String {
+	load_notes().unwrap_or_default()
+}
+
+/// System prompt section with the scratchpad notes, cut to max_bytes
+/// None when the scratchpad is empty
+pub fn prompt_section(max_bytes: usize) -> Option<String> {
+	format_prompt_section(&read_notes(), max_bytes)
+}
+
+fn format_prompt_section(notes: &str, max_bytes: usize) -> Option<String> {
+	let notes = notes.trim();
+	if notes.is_empty() {
+		return None;
+	}
+
+	let mut section = String::from(
+		"\n\n==== SCRATCHPAD ====\nYour notes from scratchpad_write, kept across turns:\n\n",
+	);
+	if notes.len() > max_bytes {
+		let mut end = max_bytes;
+		while !notes.is_char_boundary(end) {
+			end -= 1;
+		}
+		section.push_str(&notes[..end]);
+		section.push_str(&format!(
+			"\n[... {} more bytes, use scratchpad_read for all notes]",
+			notes.len() - end
+		));
+	} else {
+		section.push_str(notes);
+	}
+	section.push_str("\n==== END SCRATCHPAD ====");
+	Some(section)
+}
+
+// Define the scratchpad_read function for the MCP protocol
+pub fn get_scratchpad_read_function() -> McpFunction {
+	McpFunction {
+		name: "scratchpad_read".to_string(),
+		description: "Read your scratchpad: notes for this session saved with scratchpad_write.
+
+The scratchpad is kept in a file and is not affected by context truncation or summarization,
+so read it when resuming a long task or after earlier messages were dropped."
+			.to_string(),
+		parameters: json!({
+			"type": "object",
+			"properties": {}
+		}),
+	}
+}
+
+// Define the scratchpad_write function for the MCP protocol
+pub fn get_scratchpad_write_function() -> McpFunction {
+	McpFunction {
+		name: "scratchpad_write".to_string(),
+		description: format!(
+			"Save notes to your scratchpad for this session.
+
+Use it as working memory for multi-step tasks: the plan, decisions made, progress and
+open questions. Notes persist across turns and survive context truncation.
+Keep them short and current - replace outdated notes instead of piling up history.
+The scratchpad is limited to {} KB.",
+			MAX_SCRATCHPAD_BYTES / 1024
+		),
+		parameters: json!({
+			"type": "object",
+			"properties": {
+				"content": {
+					"type": "string",
+					"description": "Notes to save"
+				},
+				"mode": {
+					"type": "string",
+					"enum": ["replace", "append"],
+					"default": "replace",
+					"description": "replace overwrites the scratchpad, append adds to the end"
+				}
+			},
+			"required": ["content"]
+		}),
+	}
+}
+
+// Execute scratchpad_read
+pub async fn execute_scratchpad_read(call: &McpToolCall) -> Result<McpToolResult> {
+	let notes = load_notes()?;
+
+	let content = if notes.trim().is_empty() {
+		"The scratchpad is empty.".to_string()
+	} else {
+		notes
+	};
+	Ok(McpToolResult::success(
+		"scratchpad_read".to_string(),
+		call.tool_id.clone(),
+		content,
+	))
+}
+
+// Execute scratchpad_write
+pub async fn execute_scratchpad_write(call: &McpToolCall) -> Result<McpToolResult> {
+	let content = match call.parameters.get("content") {
+		Some(Value::String(content)) => content.clone(),
+		_ => return Err(anyhow!("Missing or invalid 'content' parameter")),
+	};
+	let append = match call.parameters.get("mode").and_then(|m| m.as_str()) {
+		None | Some("replace") => false,
+		Some("append") => true,
+		Some(other) => {
+			return Err(anyhow!(
+				"Invalid 'mode' parameter '{}': expected replace or append",
+				other
+			))
+		}
+	};
+
+	let notes = if append {
+		let mut notes = load_notes().unwrap_or_default();
+		if !notes.is_empty() && !notes.ends_with('\n') {
+			notes.push('\n');
+		}
+		notes.push_str(&content);
+		notes
+	} else {
+		content
+	};
+
+	if notes.len() > MAX_SCRATCHPAD_BYTES {
+		return Ok(McpToolResult::error(
+			"scratchpad_write".to_string(),
+			call.tool_id.clone(),
+			format!(
+				"Scratchpad would be {} bytes, the limit is {} bytes. Replace it with shorter notes.",
+				notes.len(),
+				MAX_SCRATCHPAD_BYTES
+			),
+		));
+	}
+
+	store_notes(&notes)?;
+	Ok(McpToolResult::success(
+		"scratchpad_write".to_string(),
+		call.tool_id.clone(),
+		format!("Scratchpad saved ({} bytes).", notes.len()),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_format_prompt_section() {
+		assert_eq!(format_prompt_section("  \n", 100), None);
+
+		let section = format_prompt_section("plan: step 1\n", 100).unwrap();
+		assert!(section.contains("plan: step 1\n==== END SCRATCHPAD ===="));
+
+		// Long notes are cut on a char boundary
+		let section = format_prompt_section("ééé", 3).unwrap();
+		assert!(section.contains("\n\né\n[... 4 more bytes"));
+	}
+}
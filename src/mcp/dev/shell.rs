@@ -15,6 +15,8 @@
 // Shell execution functionality for the Developer MCP provider
 
 use super::super::{McpFunction, McpToolCall, McpToolResult};
+use super::sandbox;
+use crate::config::ShellSandboxConfig;
 use anyhow::{anyhow, Result};
 use serde_json::{json, Value};
 use std::fs::OpenOptions;
@@ -113,9 +115,10 @@ may show ignored or hidden files. For example *do not* use `find` or `ls -r`
 	}
 }
 
-// Execute a shell command
+// Execute a shell command, in the sandbox container when one is configured
 pub async fn execute_shell_command(
 	call: &McpToolCall,
+	sandbox: Option<&ShellSandboxConfig>,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<McpToolResult> {
 	use std::sync::atomic::Ordering;
@@ -137,8 +140,15 @@ pub async fn execute_shell_command(
 	// Add command to shell history before execution
 	let _ = add_to_shell_history(&command);
 
+	// Container of a sandboxed command, stopped on cancellation
+	let container = sandbox.map(|config| (config, sandbox::container_name()));
+
 	// Use tokio::process::Command for better cancellation support
-	let mut cmd = if cfg!(target_os = "windows") {
+	let mut cmd = if let Some((sandbox, name)) = &container {
+		let workdir = std::env::current_dir()
+			.map_err(|e| anyhow!("Failed to get the working directory: {}", e))?;
+		sandbox::sandbox_command(&command, sandbox, &workdir, name)
+	} else if cfg!(target_os = "windows") {
 		let mut cmd = TokioCommand::new("cmd");
 		cmd.args(["/C", &command]);
 		cmd
@@ -155,9 +165,14 @@ pub async fn execute_shell_command(
 		.kill_on_drop(true); // CRITICAL: Kill process when dropped
 
 	// Spawn the process
-	let child = cmd
-		.spawn()
-		.map_err(|e| anyhow!("Failed to spawn command: {}", e))?;
+	let child = cmd.spawn().map_err(|e| match &container {
+		Some((sandbox, _)) => anyhow!(
+			"Failed to start the {} shell sandbox: {}",
+			sandbox.runtime.command(),
+			e
+		),
+		None => anyhow!("Failed to spawn command: {}", e),
+	})?;
 
 	// Get the process ID for potential killing
 	let child_id = child.id();
@@ -229,6 +244,9 @@ Error: {}",
 		}
 		cancelled = cancellation_future => {
 			if cancelled {
+				if let Some((sandbox, name)) = &container {
+					sandbox::stop_container(sandbox, name);
+				}
 				// Try to kill the process using system commands if we have the PID
 				if let Some(pid) = child_id {
 					#[cfg(unix)]
@@ -0,0 +1,130 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Binary file detection - keeps raw bytes of non-text files out of the conversation
+
+use anyhow::Result;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+/// Number of leading bytes inspected to decide whether a file is binary
+pub const BINARY_SAMPLE_SIZE: usize = 8192;
+
+/// Metadata reported instead of the content of a binary file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryFileInfo {
+	pub size: u64,
+	pub file_type: &'static str,
+}
+
+impl BinaryFileInfo {
+	/// Short human readable description, e.g. "binary file (png image), 2048 bytes"
+	pub fn describe(&self) -> String {
+		format!("binary file ({}), {} bytes", self.file_type, self.size)
+	}
+}
+
+/// Heuristic: a sample is binary if it contains a null byte or invalid UTF-8
+/// A multi-byte character cut off at the end of the sample is not treated as invalid
+pub fn looks_binary(sample: &[u8]) -> bool {
+	if sample.contains(&0) {
+		return true;
+	}
+	match std::str::from_utf8(sample) {
+		Ok(_) => false,
+		// error_len() is None when the input just ends mid-character
+		Err(e) => e.error_len().is_some(),
+	}
+}
+
+/// Guess the file type from well-known magic numbers
+pub fn detect_binary_type(sample: &[u8]) -> &'static str {
+	const SIGNATURES: &[(&[u8], &str)] = &[
+		(b"\x89PNG\r\n\x1a\n", "png image"),
+		(b"\xff\xd8\xff", "jpeg image"),
+		(b"GIF87a", "gif image"),
+		(b"GIF89a", "gif image"),
+		(b"%PDF", "pdf document"),
+		(b"PK\x03\x04", "zip archive"),
+		(b"\x1f\x8b", "gzip archive"),
+		(b"\x7fELF", "elf executable"),
+		(b"MZ", "windows executable"),
+		(b"\xcf\xfa\xed\xfe", "mach-o executable"),
+		(b"\xca\xfe\xba\xbe", "mach-o universal binary"),
+		(b"\0asm", "webassembly module"),
+		(b"SQLite format 3\0", "sqlite database"),
+	];
+
+	if sample.len() >= 12 && &sample[..4] == b"RIFF" && &sample[8..12] == b"WEBP" {
+		return "webp image";
+	}
+
+	SIGNATURES
+		.iter()
+		.find(|(magic, _)| sample.starts_with(magic))
+		.map(|(_, file_type)| *file_type)
+		.unwrap_or("unknown binary")
+}
+
+/// Inspect the start of a file; returns metadata when it appears to be binary
+pub async fn check_binary_file(path: &Path) -> Result<Option<BinaryFileInfo>> {
+	let mut file = tokio::fs::File::open(path).await?;
+	let size = file.metadata().await?.len();
+
+	let mut sample = Vec::with_capacity(BINARY_SAMPLE_SIZE);
+	(&mut file)
+		.take(BINARY_SAMPLE_SIZE as u64)
+		.read_to_end(&mut sample)
+		.await?;
+
+	if looks_binary(&sample) {
+		Ok(Some(BinaryFileInfo {
+			size,
+			file_type: detect_binary_type(&sample),
+		}))
+	} else {
+		Ok(None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_looks_binary() {
+		assert!(!looks_binary(b""));
+		assert!(!looks_binary(b"fn main() {}\n"));
+		assert!(!looks_binary("héllo wörld".as_bytes()));
+
+		// Null bytes and invalid UTF-8 are binary
+		assert!(looks_binary(b"abc\0def"));
+		assert!(looks_binary(b"abc\xff\xfedef"));
+
+		// A multi-byte character cut off by the sample boundary is still text
+		let text = "abcé".as_bytes();
+		assert!(!looks_binary(&text[..text.len() - 1]));
+	}
+
+	#[test]
+	fn test_detect_binary_type() {
+		assert_eq!(
+			detect_binary_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+			"png image"
+		);
+		assert_eq!(detect_binary_type(b"\x7fELF\x02\x01"), "elf executable");
+		assert_eq!(detect_binary_type(b"RIFF\0\0\0\0WEBPVP8 "), "webp image");
+		assert_eq!(detect_binary_type(b"\x00\x01\x02"), "unknown binary");
+	}
+}
@@ -140,8 +140,12 @@ pub fn detect_language(ext: &str) -> &str {
 // Main execution functions
 
 // Execute a text editor command following modern text editor specifications
+// With show_diff, str_replace and insert results include a unified diff of the change
+// With normalize, written content is cleaned up (see normalize::normalize_edit)
 pub async fn execute_text_editor(
 	call: &McpToolCall,
+	show_diff: bool,
+	normalize: Option<&super::normalize::EditNormalization>,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<McpToolResult> {
 	use std::sync::atomic::Ordering;
@@ -237,7 +241,7 @@ pub async fn execute_text_editor(
 				Some(Value::String(txt)) => txt.clone(),
 				_ => return Err(anyhow!("Missing or invalid 'file_text' parameter for create command")),
 			};
-			file_ops::create_file_spec(call, Path::new(&path), &file_text).await
+			file_ops::create_file_spec(call, Path::new(&path), &file_text, normalize).await
 		},
 		"str_replace" => {
 			// Check for cancellation before str_replace operation
@@ -259,7 +263,7 @@ pub async fn execute_text_editor(
 				Some(Value::String(s)) => s.clone(),
 				_ => return Err(anyhow!("Missing or invalid 'new_str' parameter")),
 			};
-			text_editing::str_replace_spec(call, Path::new(&path), &old_str, &new_str).await
+			text_editing::str_replace_spec(call, Path::new(&path), &old_str, &new_str, show_diff, normalize).await
 		},
 		"insert" => {
 			// Check for cancellation before insert operation
@@ -281,7 +285,7 @@ pub async fn execute_text_editor(
 				Some(Value::String(s)) => s.clone(),
 				_ => return Err(anyhow!("Missing or invalid 'new_str' parameter for insert command")),
 			};
-			text_editing::insert_text_spec(call, Path::new(&path), insert_line, &new_str).await
+			text_editing::insert_text_spec(call, Path::new(&path), insert_line, &new_str, show_diff, normalize).await
 		},
 		"line_replace" => {
 			// Check for cancellation before line_replace operation
@@ -310,7 +314,7 @@ pub async fn execute_text_editor(
 				Some(Value::String(s)) => s.clone(),
 				_ => return Err(anyhow!("Missing or invalid 'new_str' parameter for line_replace command")),
 			};
-			text_editing::line_replace_spec(call, Path::new(&path), view_range, &new_str).await
+			text_editing::line_replace_spec(call, Path::new(&path), view_range, &new_str, normalize).await
 		},
 		"undo_edit" => {
 			// Check for cancellation before undo_edit operation
@@ -344,7 +348,7 @@ pub async fn execute_text_editor(
 				_ => return Err(anyhow!("Missing or invalid 'operations' parameter for batch_edit command - must be an array")),
 			};
 
-			text_editing::batch_edit_spec(call, operations).await
+			text_editing::batch_edit_spec(call, operations, normalize).await
 		},
 		_ => Err(anyhow!("Invalid command: {}. Allowed commands are: view, view_many, create, str_replace, insert, line_replace, undo_edit, batch_edit", command)),
 	}
@@ -0,0 +1,128 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Edit diffs - unified diff of a single text_editor change (show_edit_diffs)
+
+/// Unchanged lines shown around the changed block
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Build a unified diff between the old and new content of a file
+/// Edits touch one contiguous region, so the diff is a single hunk covering
+/// everything between the common leading and trailing lines
+/// Returns an empty string when the content is unchanged
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+	let old_lines: Vec<&str> = old.lines().collect();
+	let new_lines: Vec<&str> = new.lines().collect();
+
+	let (prefix, suffix) = common_prefix_suffix(&old_lines, &new_lines);
+
+	let old_end = old_lines.len() - suffix;
+	let new_end = new_lines.len() - suffix;
+	if prefix == old_end && prefix == new_end {
+		return String::new();
+	}
+
+	let start = prefix.saturating_sub(DIFF_CONTEXT_LINES);
+	let old_hunk_end = (old_end + DIFF_CONTEXT_LINES).min(old_lines.len());
+	let new_hunk_end = (new_end + DIFF_CONTEXT_LINES).min(new_lines.len());
+
+	let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+	diff.push_str(&format!(
+		"@@ -{} +{} @@\n",
+		hunk_range(start, old_hunk_end - start),
+		hunk_range(start, new_hunk_end - start)
+	));
+	for line in &old_lines[start..prefix] {
+		diff.push_str(&format!(" {}\n", line));
+	}
+	for line in &old_lines[prefix..old_end] {
+		diff.push_str(&format!("-{}\n", line));
+	}
+	for line in &new_lines[prefix..new_end] {
+		diff.push_str(&format!("+{}\n", line));
+	}
+	for line in &old_lines[old_end..old_hunk_end] {
+		diff.push_str(&format!(" {}\n", line));
+	}
+	diff
+}
+
+/// Number of common leading and trailing lines of two versions of a file
+/// The two never overlap, so the lines between them are the edited region
+pub fn common_prefix_suffix(old_lines: &[&str], new_lines: &[&str]) -> (usize, usize) {
+	let prefix = old_lines
+		.iter()
+		.zip(new_lines.iter())
+		.take_while(|(a, b)| a == b)
+		.count();
+	let max_suffix = old_lines.len().min(new_lines.len()) - prefix;
+	let suffix = old_lines
+		.iter()
+		.rev()
+		.zip(new_lines.iter().rev())
+		.take(max_suffix)
+		.take_while(|(a, b)| a == b)
+		.count();
+	(prefix, suffix)
+}
+
+// Hunk header range: 1-based start line, an empty range points at the line before it
+fn hunk_range(start: usize, len: usize) -> String {
+	if len == 0 {
+		format!("{},0", start)
+	} else {
+		format!("{},{}", start + 1, len)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_unified_diff_replacement() {
+		let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+		let new = "a\nb\nc\nd\nE\nf\ng\nh\n";
+		assert_eq!(
+			unified_diff("src/x.txt", old, new),
+			"--- a/src/x.txt\n+++ b/src/x.txt\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+		);
+	}
+
+	#[test]
+	fn test_unified_diff_insert_and_unchanged() {
+		assert_eq!(
+			unified_diff("x", "a\nb\n", "new\na\nb\n"),
+			"--- a/x\n+++ b/x\n@@ -1,2 +1,3 @@\n+new\n a\n b\n"
+		);
+		assert_eq!(unified_diff("x", "a\nb\n", "a\nb\n"), "");
+
+		// Repeated lines must not be counted in both prefix and suffix
+		assert_eq!(
+			unified_diff("x", "a\na\n", "a\na\na\n"),
+			"--- a/x\n+++ b/x\n@@ -1,2 +1,3 @@\n a\n a\n+a\n"
+		);
+	}
+
+	#[test]
+	fn test_common_prefix_suffix() {
+		assert_eq!(
+			common_prefix_suffix(&["a", "b", "c"], &["a", "x", "c"]),
+			(1, 1)
+		);
+		assert_eq!(common_prefix_suffix(&["a", "b"], &["a", "b"]), (2, 0));
+		assert_eq!(common_prefix_suffix(&["a", "a"], &["a", "a", "a"]), (2, 0));
+		assert_eq!(common_prefix_suffix(&[], &["a"]), (0, 0));
+	}
+}
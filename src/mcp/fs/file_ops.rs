@@ -15,6 +15,7 @@
 // File operations module - handling file viewing, creation, and basic manipulation
 
 use super::super::{McpToolCall, McpToolResult};
+use super::binary::check_binary_file;
 use super::core::detect_language;
 use anyhow::{anyhow, Result};
 use serde_json::json;
@@ -100,6 +101,23 @@ pub async fn view_file_spec(
 		});
 	}
 
+	// Report binary files by metadata instead of dumping raw bytes
+	if let Some(binary) = check_binary_file(path)
+		.await
+		.map_err(|e| anyhow!("Permission denied. Cannot read file: {}", e))?
+	{
+		return Ok(McpToolResult {
+			tool_name: "text_editor".to_string(),
+			tool_id: call.tool_id.clone(),
+			result: json!({
+				"content": format!("Content not shown: {}", binary.describe()),
+				"binary": true,
+				"size": binary.size,
+				"file_type": binary.file_type
+			}),
+		});
+	}
+
 	// Read the file content
 	let content = tokio_fs::read_to_string(path)
 		.await
@@ -178,6 +196,7 @@ pub async fn create_file_spec(
 	call: &McpToolCall,
 	path: &Path,
 	content: &str,
+	normalize: Option<&super::normalize::EditNormalization>,
 ) -> Result<McpToolResult> {
 	// Check if file already exists
 	if path.exists() {
@@ -201,6 +220,7 @@ pub async fn create_file_spec(
 	}
 
 	// Write the content to the file
+	let content = &super::normalize::apply(normalize, path, "", content.to_string());
 	tokio_fs::write(path, content)
 		.await
 		.map_err(|e| anyhow!("Permission denied. Cannot write to file: {}", e))?;
@@ -255,13 +275,13 @@ pub async fn view_many_files_spec(call: &McpToolCall, paths: &[String]) -> Resul
 		};
 
 		// Check if file is binary
-		if let Ok(sample) = tokio_fs::read(&path).await {
-			let sample_size = sample.len().min(512);
-			let null_count = sample[..sample_size].iter().filter(|&&b| b == 0).count();
-			if null_count > sample_size / 10 {
-				failures.push(format!("Binary file skipped: {}", path_display));
-				continue;
-			}
+		if let Ok(Some(binary)) = check_binary_file(path).await {
+			failures.push(format!(
+				"Binary file skipped: {} ({})",
+				path_display,
+				binary.describe()
+			));
+			continue;
 		}
 
 		// Read file content with error handling
@@ -350,13 +370,13 @@ pub async fn view_many_files(call: &McpToolCall, paths: &[String]) -> Result<Mcp
 		};
 
 		// Check if file is binary
-		if let Ok(sample) = tokio_fs::read(&path).await {
-			let sample_size = sample.len().min(512);
-			let null_count = sample[..sample_size].iter().filter(|&&b| b == 0).count();
-			if null_count > sample_size / 10 {
-				failures.push(format!("Binary file skipped: {}", path_display));
-				continue;
-			}
+		if let Ok(Some(binary)) = check_binary_file(path).await {
+			failures.push(format!(
+				"Binary file skipped: {} ({})",
+				path_display,
+				binary.describe()
+			));
+			continue;
 		}
 
 		// Read file content with error handling
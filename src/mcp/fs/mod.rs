@@ -15,10 +15,13 @@
 // File System MCP provider - modular structure
 // Handles file operations
 
+pub mod binary;
 pub mod core;
+pub mod diff;
 pub mod directory;
 pub mod file_ops;
 pub mod functions;
+pub mod normalize;
 pub mod text_editing;
 
 // Re-export main functionality
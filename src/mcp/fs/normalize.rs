@@ -0,0 +1,169 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Edit normalization - whitespace cleanup of text_editor writes (normalize_edits)
+
+use std::path::Path;
+
+/// Extensions and file names where trailing whitespace is significant
+pub const DEFAULT_SKIP: &[&str] = &[
+	"md",
+	"markdown",
+	"mdx",
+	"diff",
+	"patch",
+	"mk",
+	"Makefile",
+	"makefile",
+	"GNUmakefile",
+];
+
+/// Normalization applied to files written by text_editor
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditNormalization {
+	/// Extensions ("md") or file names ("Makefile") left untouched
+	pub skip: Vec<String>,
+}
+
+impl EditNormalization {
+	/// Normalization settings from the config, None when normalize_edits is off
+	pub fn from_config(config: &crate::config::Config) -> Option<Self> {
+		if !config.normalize_edits {
+			return None;
+		}
+		let skip = match &config.normalize_edits_skip {
+			Some(skip) => skip.clone(),
+			None => DEFAULT_SKIP.iter().map(|s| s.to_string()).collect(),
+		};
+		Some(Self { skip })
+	}
+
+	/// Whether files at this path are normalized
+	pub fn applies_to(&self, path: &Path) -> bool {
+		let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+		let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+		!self
+			.skip
+			.iter()
+			.any(|s| s == file_name || (!extension.is_empty() && s == extension))
+	}
+}
+
+/// Content to write for an edit: normalized when enabled for the path, unchanged otherwise
+pub fn apply(normalize: Option<&EditNormalization>, path: &Path, old: &str, new: String) -> String {
+	match normalize {
+		Some(normalize) if normalize.applies_to(path) => normalize_edit(old, &new),
+		_ => new,
+	}
+}
+
+/// Normalize the edited region of a file
+/// - trailing whitespace is trimmed on edited lines only
+/// - edited lines use the file's existing line ending (CRLF or LF)
+/// - the file ends with a newline
+///
+/// Edits touch one contiguous region, found from the common leading and trailing lines;
+/// lines outside it are written back byte for byte
+pub fn normalize_edit(old: &str, new: &str) -> String {
+	if new.is_empty() {
+		return String::new();
+	}
+
+	let crlf = old.matches("\r\n").count() * 2 > old.matches('\n').count();
+	let old_lines: Vec<&str> = split_lines(old);
+	let new_lines: Vec<&str> = split_lines(new);
+
+	let (prefix, suffix) = super::diff::common_prefix_suffix(&old_lines, &new_lines);
+	let edited_end = new_lines.len() - suffix;
+
+	let mut result = String::with_capacity(new.len() + 1);
+	for (index, line) in new_lines.iter().enumerate() {
+		if (prefix..edited_end).contains(&index) {
+			result.push_str(line.trim_end());
+			if crlf {
+				result.push('\r');
+			}
+		} else {
+			result.push_str(line);
+		}
+		result.push('\n');
+	}
+
+	// An unedited last line without a newline still gets the file's line ending
+	if crlf && !result.ends_with("\r\n") {
+		result.insert(result.len() - 1, '\r');
+	}
+	result
+}
+
+// Lines split on '\n' only, keeping any '\r' so unedited lines stay byte-exact
+fn split_lines(content: &str) -> Vec<&str> {
+	if content.is_empty() {
+		return Vec::new();
+	}
+	content
+		.strip_suffix('\n')
+		.unwrap_or(content)
+		.split('\n')
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_normalize_edit_trims_edited_lines_only() {
+		let old = "keep  \nold\nend\n";
+		let new = "keep  \nnew line   \n\tsecond\t\nend\n";
+		assert_eq!(
+			normalize_edit(old, new),
+			"keep  \nnew line\n\tsecond\nend\n"
+		);
+
+		// Final newline is added
+		assert_eq!(normalize_edit("a\n", "a\nb  "), "a\nb\n");
+	}
+
+	#[test]
+	fn test_normalize_edit_preserves_crlf() {
+		let old = "one\r\ntwo\r\nthree\r\n";
+		let new = "one\r\n2 \nextra\r\nthree\r\n";
+		assert_eq!(normalize_edit(old, new), "one\r\n2\r\nextra\r\nthree\r\n");
+
+		// LF files stay LF
+		assert_eq!(normalize_edit("a\nb\n", "a\nc \nb\n"), "a\nc\nb\n");
+	}
+
+	#[test]
+	fn test_skip_whitespace_significant_files() {
+		let normalize = EditNormalization {
+			skip: DEFAULT_SKIP.iter().map(|s| s.to_string()).collect(),
+		};
+		assert!(normalize.applies_to(Path::new("src/main.rs")));
+		assert!(!normalize.applies_to(Path::new("README.md")));
+		assert!(!normalize.applies_to(Path::new("build/Makefile")));
+
+		let new = "line  \n".to_string();
+		assert_eq!(
+			apply(Some(&normalize), Path::new("x.md"), "", new.clone()),
+			new
+		);
+		assert_eq!(apply(None, Path::new("x.rs"), "", new.clone()), new);
+		assert_eq!(
+			apply(Some(&normalize), Path::new("x.rs"), "", new),
+			"line\n"
+		);
+	}
+}
@@ -16,6 +16,8 @@
 
 use super::super::{McpToolCall, McpToolResult};
 use super::core::save_file_history;
+use super::diff::unified_diff;
+use super::normalize::{self, EditNormalization};
 use anyhow::{anyhow, Result};
 use serde_json::{json, Value};
 use std::path::Path;
@@ -27,6 +29,8 @@ pub async fn str_replace_spec(
 	path: &Path,
 	old_str: &str,
 	new_str: &str,
+	show_diff: bool,
+	normalize: Option<&EditNormalization>,
 ) -> Result<McpToolResult> {
 	if !path.exists() {
 		return Ok(McpToolResult {
@@ -71,20 +75,30 @@ pub async fn str_replace_spec(
 	save_file_history(path).await?;
 
 	// Replace the string
-	let new_content = content.replace(old_str, new_str);
+	let new_content =
+		normalize::apply(normalize, path, &content, content.replace(old_str, new_str));
 
 	// Write the new content
-	tokio_fs::write(path, new_content)
+	tokio_fs::write(path, &new_content)
 		.await
 		.map_err(|e| anyhow!("Permission denied. Cannot write to file: {}", e))?;
 
+	let mut result = json!({
+		"content": "Successfully replaced text at exactly one location.",
+		"path": path.to_string_lossy()
+	});
+	if show_diff {
+		result["diff"] = json!(unified_diff(
+			&path.to_string_lossy(),
+			&content,
+			&new_content
+		));
+	}
+
 	Ok(McpToolResult {
 		tool_name: "text_editor".to_string(),
 		tool_id: call.tool_id.clone(),
-		result: json!({
-			"content": "Successfully replaced text at exactly one location.",
-			"path": path.to_string_lossy()
-		}),
+		result,
 	})
 }
 
@@ -94,6 +108,8 @@ pub async fn insert_text_spec(
 	path: &Path,
 	insert_line: usize,
 	new_str: &str,
+	show_diff: bool,
+	normalize: Option<&EditNormalization>,
 ) -> Result<McpToolResult> {
 	if !path.exists() {
 		return Ok(McpToolResult {
@@ -143,20 +159,30 @@ pub async fn insert_text_spec(
 	} else {
 		new_content
 	};
+	let final_content = normalize::apply(normalize, path, &content, final_content);
 
 	// Write the new content
-	tokio_fs::write(path, final_content)
+	tokio_fs::write(path, &final_content)
 		.await
 		.map_err(|e| anyhow!("Permission denied. Cannot write to file: {}", e))?;
 
+	let mut result = json!({
+		"content": format!("Successfully inserted {} lines at line {}", new_str.lines().count(), insert_line),
+		"path": path.to_string_lossy(),
+		"lines_inserted": new_str.lines().count()
+	});
+	if show_diff {
+		result["diff"] = json!(unified_diff(
+			&path.to_string_lossy(),
+			&content,
+			&final_content
+		));
+	}
+
 	Ok(McpToolResult {
 		tool_name: "text_editor".to_string(),
 		tool_id: call.tool_id.clone(),
-		result: json!({
-			"content": format!("Successfully inserted {} lines at line {}", new_str.lines().count(), insert_line),
-			"path": path.to_string_lossy(),
-			"lines_inserted": new_str.lines().count()
-		}),
+		result,
 	})
 }
 
@@ -166,6 +192,7 @@ pub async fn line_replace_spec(
 	path: &Path,
 	view_range: (usize, usize),
 	new_str: &str,
+	normalize: Option<&EditNormalization>,
 ) -> Result<McpToolResult> {
 	if !path.exists() {
 		return Ok(McpToolResult {
@@ -273,6 +300,7 @@ pub async fn line_replace_spec(
 	} else {
 		new_content
 	};
+	let final_content = normalize::apply(normalize, path, &file_content, final_content);
 
 	// Write the new content
 	tokio_fs::write(path, final_content)
@@ -343,7 +371,11 @@ pub async fn line_replace_spec(
 
 // Batch edit operations - perform multiple text editing operations in a single call
 // This is recommended for making changes across multiple files or multiple non-interconnected modifications
-pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result<McpToolResult> {
+pub async fn batch_edit_spec(
+	call: &McpToolCall,
+	operations: &[Value],
+	normalize: Option<&EditNormalization>,
+) -> Result<McpToolResult> {
 	let mut results = Vec::new();
 	let mut successful_operations = 0;
 	let mut failed_operations = 0;
@@ -433,7 +465,7 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 					}
 				};
 
-				str_replace_spec(&temp_call, path, old_str, new_str).await
+				str_replace_spec(&temp_call, path, old_str, new_str, false, normalize).await
 			}
 			"insert" => {
 				let insert_line = match operation_obj.get("insert_line").and_then(|v| v.as_u64()) {
@@ -466,7 +498,7 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 					}
 				};
 
-				insert_text_spec(&temp_call, path, insert_line, new_str).await
+				insert_text_spec(&temp_call, path, insert_line, new_str, false, normalize).await
 			}
 			"line_replace" => {
 				let view_range = match operation_obj.get("view_range").and_then(|v| v.as_array()) {
@@ -514,7 +546,7 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 					}
 				};
 
-				line_replace_spec(&temp_call, path, view_range, new_str).await
+				line_replace_spec(&temp_call, path, view_range, new_str, normalize).await
 			}
 			_ => {
 				failed_operations += 1;
@@ -26,6 +26,7 @@ static HEALTH_MONITOR_RUNNING: AtomicBool = AtomicBool::new(false);
 
 // Health monitoring configuration
 const HEALTH_CHECK_INTERVAL_SECONDS: u64 = 30; // Check every 30 seconds
+const HEARTBEAT_TIMEOUT_SECONDS: u64 = 10; // Max wait for a heartbeat reply
 
 /// Start the background health monitoring task
 pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Error> {
@@ -51,7 +52,7 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 		.filter(|server| {
 			matches!(
 				server.connection_type(),
-				McpConnectionType::Http | McpConnectionType::Stdin
+				McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse
 			)
 		})
 		.cloned()
@@ -78,6 +79,7 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 							"http-remote"
 						}
 					}
+					McpConnectionType::Sse => "sse",
 					McpConnectionType::Builtin => "builtin",
 				};
 				format!("{}({})", s.name(), server_type)
@@ -86,6 +88,19 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 			.join(", ")
 	);
 
+	// Opt-in protocol heartbeats for stdin servers (heartbeat_seconds > 0)
+	for server in &external_servers {
+		let heartbeat_seconds = server.heartbeat_seconds();
+		if heartbeat_seconds > 0 && server.connection_type() == McpConnectionType::Stdin {
+			crate::log_debug!(
+				"Heartbeat enabled for server '{}' (every {}s)",
+				server.name(),
+				heartbeat_seconds
+			);
+			tokio::spawn(run_heartbeat(server.clone(), heartbeat_seconds));
+		}
+	}
+
 	// Spawn the monitoring task
 	tokio::spawn(async move {
 		let mut check_interval = interval(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECONDS));
@@ -114,6 +129,47 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 	Ok(())
 }
 
+/// Periodically ping a stdin server while the health monitor is running
+async fn run_heartbeat(server: McpServerConfig, heartbeat_seconds: u64) {
+	let mut heartbeat_interval = interval(Duration::from_secs(heartbeat_seconds));
+	// The first tick completes immediately - the server was just started
+	heartbeat_interval.tick().await;
+
+	loop {
+		heartbeat_interval.tick().await;
+
+		if !HEALTH_MONITOR_RUNNING.load(Ordering::SeqCst) {
+			break;
+		}
+
+		if let Err(e) = check_server_heartbeat(&server).await {
+			crate::log_debug!("Heartbeat error for server '{}': {}", server.name(), e);
+		}
+	}
+}
+
+/// Ping an idle stdin server; an unanswered ping marks it unresponsive and restarts it
+async fn check_server_heartbeat(server: &McpServerConfig) -> Result<(), anyhow::Error> {
+	match process::ping_stdin_server(server.name(), HEARTBEAT_TIMEOUT_SECONDS).await {
+		process::HeartbeatStatus::Responsive => {
+			crate::log_debug!("Heartbeat: server '{}' is responsive", server.name());
+			Ok(())
+		}
+		process::HeartbeatStatus::Skipped => Ok(()),
+		process::HeartbeatStatus::Unresponsive(pid) => {
+			crate::log_info!(
+				"MCP server '{}' did not answer heartbeat within {}s - restarting",
+				server.name(),
+				HEARTBEAT_TIMEOUT_SECONDS
+			);
+			process::kill_unresponsive_server(server.name(), pid);
+
+			// Same restart path (attempt limit and cooldown) as a dead process
+			check_server_health_and_restart_if_dead(server).await
+		}
+	}
+}
+
 /// Stop the background health monitoring task
 pub fn stop_health_monitor() {
 	if HEALTH_MONITOR_RUNNING
@@ -155,6 +211,14 @@ async fn check_server_health_and_restart_if_dead(
 				}
 			}
 		}
+		McpConnectionType::Sse => {
+			// SSE server - running while its event stream is open
+			if super::sse::is_connected(server.name()) {
+				ServerHealth::Running
+			} else {
+				ServerHealth::Dead
+			}
+		}
 		McpConnectionType::Builtin => {
 			// Builtin servers are always running
 			ServerHealth::Running
@@ -289,6 +353,7 @@ async fn restart_dead_server(server: &McpServerConfig) -> Result<(), anyhow::Err
 	let can_restart = match server.connection_type() {
 		McpConnectionType::Stdin => true, // Stdin servers can always be restarted
 		McpConnectionType::Http => server.command().is_some(), // Only local HTTP servers can be restarted
+		McpConnectionType::Sse => true,   // SSE servers are reconnected
 		McpConnectionType::Builtin => false, // Builtin servers don't need restart
 	};
 
@@ -305,7 +370,12 @@ async fn restart_dead_server(server: &McpServerConfig) -> Result<(), anyhow::Err
 		server.name()
 	);
 
-	match process::ensure_server_running(server).await {
+	let result = match server.connection_type() {
+		McpConnectionType::Sse => super::sse::ensure_connected(server).await,
+		_ => process::ensure_server_running(server).await,
+	};
+
+	match result {
 		Ok(_) => {
 			crate::log_info!(
 				"Health monitor successfully restarted dead server '{}'",
@@ -342,6 +412,7 @@ async fn verify_server_responsiveness(server: &McpServerConfig) -> bool {
 			// Don't make HTTP requests - failed responses are normal
 			process::is_server_running(server.name())
 		}
+		McpConnectionType::Sse => super::sse::is_connected(server.name()),
 		McpConnectionType::Builtin => {
 			// Built-in servers are always "running"
 			true
@@ -365,7 +436,7 @@ pub async fn force_health_check(config: &Config) -> Result<(), anyhow::Error> {
 		.filter(|server| {
 			matches!(
 				server.connection_type(),
-				McpConnectionType::Http | McpConnectionType::Stdin
+				McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse
 			)
 		})
 		.cloned()
@@ -24,7 +24,10 @@ use std::sync::{Arc, RwLock};
 use uuid;
 
 // Modules
+pub mod content_tool_calls;
+pub mod context_budget;
 pub mod tool_map;
+pub mod tool_schema;
 
 // Cache for internal server function definitions (static during session)
 lazy_static::lazy_static! {
@@ -37,7 +40,10 @@ pub mod dev;
 pub mod fs;
 pub mod health_monitor;
 pub mod process;
+pub mod replay;
 pub mod server;
+pub mod sse;
+pub mod trace;
 pub mod web;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,11 +202,13 @@ pub fn guess_tool_category(tool_name: &str) -> &'static str {
 	}
 }
 
-// Parse a model's response to extract tool calls - kept for backward compatibility
-pub fn parse_tool_calls(_content: &str) -> Vec<McpToolCall> {
-	// This function is kept for backward compatibility but is no longer used directly
-	// as we now prefer to pass tool calls directly as structs
-	Vec::new()
+// Parse tool calls the model wrote into its content - the fallback when a response has no
+// native tool calls. Nothing is parsed unless the role sets content_tool_call_format
+pub fn parse_tool_calls(
+	content: &str,
+	format: crate::config::ContentToolCallFormat,
+) -> Vec<McpToolCall> {
+	content_tool_calls::parse_content_tool_calls(content, format)
 }
 
 // Structure to represent tool responses for OpenAI/Claude format
@@ -237,6 +245,61 @@ pub fn ensure_tool_call_ids(calls: &mut [McpToolCall]) {
 	}
 }
 
+/// Serializable status of a configured MCP server, for embedding applications
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerStatus {
+	pub name: String,
+	#[serde(rename = "type")]
+	pub server_type: McpConnectionType,
+	/// Endpoint of remote HTTP servers
+	pub url: Option<String>,
+	/// None until an external server has been started or health checked
+	pub health: Option<process::ServerHealth>,
+	pub restart_count: u32,
+	/// Last health check, seconds since the Unix epoch
+	pub last_check: Option<u64>,
+	/// Tools provided by the server, None until the tool map is initialized
+	pub tool_count: Option<usize>,
+}
+
+/// Status of every MCP server in the (role-merged) config, without starting or contacting servers
+pub fn server_statuses(config: &crate::config::Config) -> Vec<McpServerStatus> {
+	let report = process::get_server_status_report();
+	config
+		.mcp
+		.servers
+		.iter()
+		.map(|server| {
+			let (health, restart_info) = match server.connection_type() {
+				// Built-in servers run in-process and are always available
+				McpConnectionType::Builtin => (
+					Some(process::ServerHealth::Running),
+					process::ServerRestartInfo::default(),
+				),
+				McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
+					match report.get(server.name()) {
+						Some((health, restart_info)) => (Some(*health), restart_info.clone()),
+						None => (None, process::ServerRestartInfo::default()),
+					}
+				}
+			};
+			McpServerStatus {
+				name: server.name().to_string(),
+				server_type: server.connection_type(),
+				url: server.url().map(|url| url.to_string()),
+				health,
+				restart_count: restart_info.restart_count,
+				last_check: restart_info.last_health_check.and_then(|time| {
+					time.duration_since(std::time::UNIX_EPOCH)
+						.ok()
+						.map(|d| d.as_secs())
+				}),
+				tool_count: tool_map::get_tool_count_for_server(server.name()),
+			}
+		})
+		.collect()
+}
+
 // Initialize all servers for a specific mode/role ONCE at startup
 pub async fn initialize_servers_for_role(config: &crate::config::Config) -> Result<()> {
 	// Only initialize if MCP has any servers configured
@@ -254,7 +317,9 @@ pub async fn initialize_servers_for_role(config: &crate::config::Config) -> Resu
 
 	for server in &enabled_servers {
 		// Only initialize external servers that need to be started
-		if let McpConnectionType::Http | McpConnectionType::Stdin = server.connection_type() {
+		if let McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse =
+			server.connection_type()
+		{
 			crate::log_debug!("Initializing external server: {}", server.name());
 
 			// Check if server is already running to avoid double initialization
@@ -361,7 +426,7 @@ pub async fn get_available_functions(config: &crate::config::Config) -> Vec<McpF
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				// CRITICAL FIX: For external servers, use cached function discovery
 				// This avoids spawning servers during system prompt creation
 				match server::get_server_functions_cached(&server).await {
@@ -383,7 +448,75 @@ pub async fn get_available_functions(config: &crate::config::Config) -> Vec<McpF
 		}
 	}
 
-	functions
+	order_tool_definitions(
+		functions,
+		config.tool_definition_order,
+		&config.pinned_tools,
+	)
+}
+
+// Apply configured ordering to tool definitions: pinned tools first (in pinned order),
+// then the rest sorted according to the selected strategy (stable within groups)
+pub fn order_tool_definitions(
+	functions: Vec<McpFunction>,
+	order: crate::config::ToolDefinitionOrder,
+	pinned_tools: &[String],
+) -> Vec<McpFunction> {
+	use crate::config::ToolDefinitionOrder;
+
+	let (mut pinned, mut rest): (Vec<McpFunction>, Vec<McpFunction>) = functions
+		.into_iter()
+		.partition(|func| pinned_tools.contains(&func.name));
+
+	pinned.sort_by_key(|func| pinned_tools.iter().position(|name| *name == func.name));
+
+	match order {
+		ToolDefinitionOrder::Config => {}
+		ToolDefinitionOrder::Alphabetical => rest.sort_by(|a, b| a.name.cmp(&b.name)),
+		ToolDefinitionOrder::Category => rest.sort_by_key(|func| guess_tool_category(&func.name)),
+	}
+
+	pinned.extend(rest);
+	pinned
+}
+
+// Tools section of the system prompt, flat or with a header per category.
+// Categories keep the order of their first tool, so pinned tools still lead
+pub fn format_prompt_tool_listing(
+	functions: &[McpFunction],
+	listing: crate::config::PromptToolListing,
+	sort: bool,
+) -> String {
+	use crate::config::PromptToolListing;
+
+	let mut groups: Vec<(&str, Vec<&McpFunction>)> = Vec::new();
+	for function in functions {
+		let category = match listing {
+			PromptToolListing::Flat => "",
+			PromptToolListing::Category => guess_tool_category(&function.name),
+		};
+		match groups.iter_mut().find(|(name, _)| *name == category) {
+			Some((_, group)) => group.push(function),
+			None => groups.push((category, vec![function])),
+		}
+	}
+
+	let mut listing = String::from("You have access to the following tools:");
+	for (category, mut group) in groups {
+		if sort {
+			group.sort_by(|a, b| a.name.cmp(&b.name));
+		}
+		if !category.is_empty() {
+			listing.push_str(&format!("\n\n### {} tools", category));
+		}
+		for function in group {
+			listing.push_str(&format!(
+				"\n\n- {} - {}",
+				function.name, function.description
+			));
+		}
+	}
+	listing
 }
 
 // Helper function to filter tools based on patterns
@@ -476,6 +609,13 @@ pub fn clear_internal_function_cache() {
 	}
 }
 
+// Clear internal function cache entries of one built-in server (all tool filters)
+pub fn clear_internal_function_cache_for_server(server_type: &str) {
+	let mut cache = INTERNAL_FUNCTION_CACHE.write().unwrap();
+	let prefix = format!("{}_", server_type);
+	cache.retain(|key, _| !key.starts_with(&prefix));
+}
+
 // Execute a tool call
 pub async fn execute_tool_call(
 	call: &McpToolCall,
@@ -506,6 +646,12 @@ pub async fn execute_tool_call(
 		}
 	}
 
+	// Replay mode: reuse a recorded result for identical calls, execute on a miss
+	if let Some(replayed) = replay::lookup(call) {
+		crate::log_info!("Replaying recorded result for tool '{}'", call.tool_name);
+		return Ok((replayed, 0));
+	}
+
 	// Track tool execution time
 	let tool_start = std::time::Instant::now();
 
@@ -563,7 +709,7 @@ pub async fn build_tool_server_map(
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				// For external servers, get their actual functions
 				match server::get_server_functions_cached(&server).await {
 					Ok(functions) => filter_tools_by_patterns(functions, server.tools()),
@@ -640,9 +786,22 @@ async fn try_execute_tool_call(
 								"Executing shell command via developer server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								dev::execute_shell_command(call, cancellation_token.clone())
-									.await?;
+							let mut result = dev::execute_shell_command(
+								call,
+								config.developer.shell_sandbox.as_ref(),
+								cancellation_token.clone(),
+							)
+							.await?;
+							result.tool_id = call.tool_id.clone();
+							return Ok(result);
+						}
+						"scratchpad_read" => {
+							let mut result = dev::execute_scratchpad_read(call).await?;
+							result.tool_id = call.tool_id.clone();
+							return Ok(result);
+						}
+						"scratchpad_write" => {
+							let mut result = dev::execute_scratchpad_write(call).await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -659,8 +818,14 @@ async fn try_execute_tool_call(
 								"Executing text_editor via filesystem server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								fs::execute_text_editor(call, cancellation_token.clone()).await?;
+							let normalize = fs::normalize::EditNormalization::from_config(config);
+							let mut result = fs::execute_text_editor(
+								call,
+								config.show_edit_diffs,
+								normalize.as_ref(),
+								cancellation_token.clone(),
+							)
+							.await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -710,8 +875,12 @@ async fn try_execute_tool_call(
 								"Executing web_search via web server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								web::execute_web_search(call, cancellation_token.clone()).await?;
+							let mut result = web::execute_web_search(
+								call,
+								&config.web,
+								cancellation_token.clone(),
+							)
+							.await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -720,8 +889,12 @@ async fn try_execute_tool_call(
 								"Executing image_search via web server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								web::execute_image_search(call, cancellation_token.clone()).await?;
+							let mut result = web::execute_image_search(
+								call,
+								&config.web,
+								cancellation_token.clone(),
+							)
+							.await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -730,8 +903,12 @@ async fn try_execute_tool_call(
 								"Executing video_search via web server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								web::execute_video_search(call, cancellation_token.clone()).await?;
+							let mut result = web::execute_video_search(
+								call,
+								&config.web,
+								cancellation_token.clone(),
+							)
+							.await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -740,8 +917,12 @@ async fn try_execute_tool_call(
 								"Executing news_search via web server '{}'",
 								target_server.name()
 							);
-							let mut result =
-								web::execute_news_search(call, cancellation_token.clone()).await?;
+							let mut result = web::execute_news_search(
+								call,
+								&config.web,
+								cancellation_token.clone(),
+							)
+							.await?;
 							result.tool_id = call.tool_id.clone();
 							return Ok(result);
 						}
@@ -770,7 +951,7 @@ async fn try_execute_tool_call(
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				// Execute on external server
 				match server::execute_tool_call(call, target_server, cancellation_token.clone())
 					.await
@@ -801,13 +982,21 @@ async fn try_execute_tool_call(
 }
 
 // Helper function to handle large response warnings
+// The threshold adapts to the context room left, see context_budget::adaptive_threshold
 async fn handle_large_response(
 	result: McpToolResult,
 	config: &crate::config::Config,
 ) -> Result<McpToolResult> {
+	if config.mcp_response_warning_threshold == 0 {
+		return Ok(result);
+	}
+	let usage = context_budget::context_usage();
+	let threshold =
+		context_budget::adaptive_threshold(config.mcp_response_warning_threshold, usage);
+
 	// Check if result is large - warn user if it exceeds threshold
 	let estimated_tokens = crate::session::estimate_tokens(&format!("{}", result.result));
-	if estimated_tokens > config.mcp_response_warning_threshold {
+	if estimated_tokens > threshold {
 		// Create a modified result that warns about the size
 		use colored::Colorize;
 
@@ -816,6 +1005,9 @@ async fn handle_large_response(
 			crate::session::chat::response::get_tool_server_name_async(&result.tool_name, config)
 				.await;
 
+		// Keep the live tool status line from drawing over the prompt
+		crate::session::chat::set_tool_status_paused(true);
+
 		println!(
 			"{}",
 			format!(
@@ -835,6 +1027,18 @@ async fn handle_large_response(
 			"{}",
 			"This may consume significant tokens and impact your usage limits.".bright_yellow()
 		);
+		if let Some(usage) = usage {
+			println!(
+				"{}",
+				format!(
+					"Context: {} of {} tokens used, {} left",
+					usage.used_tokens,
+					usage.context_window,
+					usage.remaining_tokens()
+				)
+				.bright_yellow()
+			);
+		}
 
 		// Ask user for confirmation before proceeding
 		print!(
@@ -845,6 +1049,7 @@ async fn handle_large_response(
 
 		let mut input = String::new();
 		std::io::stdin().read_line(&mut input).unwrap_or_default();
+		crate::session::chat::set_tool_status_paused(false);
 
 		if !input.trim().to_lowercase().starts_with('y') {
 			// User declined large output. Return an MCP-compliant error result instead of
@@ -865,6 +1070,29 @@ async fn handle_large_response(
 			));
 		}
 
+		// An output that does not fit into the context left is cut to the threshold
+		if let Some(usage) = usage.filter(|usage| estimated_tokens > usage.remaining_tokens()) {
+			println!(
+				"{}",
+				format!(
+					"Output does not fit into the {} tokens of context left, keeping the first {} tokens...",
+					usage.remaining_tokens(),
+					threshold
+				)
+				.bright_yellow()
+			);
+			let content = extract_mcp_content(&result.result);
+			return Ok(McpToolResult::success(
+				result.tool_name.clone(),
+				result.tool_id.clone(),
+				format!(
+					"{}\n\n[Output truncated from {} tokens to fit the remaining context]",
+					crate::session::truncate_to_tokens(&content, threshold),
+					estimated_tokens
+				),
+			));
+		}
+
 		// User confirmed, continue with original result
 		println!("{}", "Proceeding with full output...".bright_green());
 	}
@@ -912,3 +1140,102 @@ pub async fn execute_tool_calls(
 
 	results
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::ToolDefinitionOrder;
+
+	fn function(name: &str) -> McpFunction {
+		McpFunction {
+			name: name.to_string(),
+			description: String::new(),
+			parameters: json!({}),
+		}
+	}
+
+	fn names(functions: &[McpFunction]) -> Vec<&str> {
+		functions.iter().map(|f| f.name.as_str()).collect()
+	}
+
+	#[test]
+	fn test_order_tool_definitions() {
+		let functions = vec![
+			function("web_search"),
+			function("text_editor"),
+			function("shell"),
+			function("list_files"),
+		];
+
+		// Config order is preserved
+		let ordered = order_tool_definitions(functions.clone(), ToolDefinitionOrder::Config, &[]);
+		assert_eq!(
+			names(&ordered),
+			vec!["web_search", "text_editor", "shell", "list_files"]
+		);
+
+		// Alphabetical with a pinned tool first
+		let pinned = vec!["shell".to_string()];
+		let ordered = order_tool_definitions(
+			functions.clone(),
+			ToolDefinitionOrder::Alphabetical,
+			&pinned,
+		);
+		assert_eq!(
+			names(&ordered),
+			vec!["shell", "list_files", "text_editor", "web_search"]
+		);
+
+		// Category groups tools, keeping config order within a group
+		let ordered = order_tool_definitions(functions, ToolDefinitionOrder::Category, &[]);
+		assert_eq!(
+			names(&ordered),
+			vec!["text_editor", "shell", "list_files", "web_search"]
+		);
+	}
+
+	#[test]
+	fn test_format_prompt_tool_listing() {
+		use crate::config::PromptToolListing;
+
+		let functions = vec![
+			function("web_search"),
+			function("text_editor"),
+			function("shell"),
+			function("list_files"),
+			function("semantic_search"),
+		];
+
+		let flat = format_prompt_tool_listing(&functions, PromptToolListing::Flat, false);
+		assert_eq!(
+			flat,
+			"You have access to the following tools:\n\n- web_search - \n\n- text_editor - \n\n- shell - \n\n- list_files - \n\n- semantic_search - "
+		);
+
+		// Categories in order of first appearance, sorted by name within each
+		let grouped = format_prompt_tool_listing(&functions, PromptToolListing::Category, true);
+		assert_eq!(
+			grouped,
+			"You have access to the following tools:\n\n### search tools\n\n- semantic_search - \n\n- web_search - \n\n### developer tools\n\n- text_editor - \n\n### external tools\n\n- shell - \n\n### filesystem tools\n\n- list_files - "
+		);
+	}
+
+	#[test]
+	fn test_server_statuses_serialize() {
+		let mut config = crate::config::loading::get_test_default_config();
+		config.mcp.servers = vec![crate::config::McpServerConfig::builtin(
+			"developer",
+			30,
+			vec![],
+		)];
+
+		let statuses = server_statuses(&config);
+		assert_eq!(statuses.len(), 1);
+		let value = serde_json::to_value(&statuses[0]).unwrap();
+		assert_eq!(value["name"], "developer");
+		assert_eq!(value["type"], "builtin");
+		assert_eq!(value["health"], "running");
+		assert_eq!(value["restart_count"], 0);
+		assert!(value["url"].is_null());
+	}
+}
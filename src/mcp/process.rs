@@ -18,7 +18,7 @@ use super::{McpFunction, McpToolCall, McpToolResult};
 use crate::config::{HttpConnection, McpConnectionType, McpServerConfig};
 use anyhow::Result;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::process::{Child, Command, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -27,7 +27,8 @@ use std::time::{Duration, Instant, SystemTime};
 use tokio::time::sleep;
 
 // Server health status tracking
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ServerHealth {
 	Running,
 	Dead,
@@ -73,6 +74,104 @@ lazy_static::lazy_static! {
 	Arc::new(RwLock::new(HashMap::new()));
 }
 
+// Lines of stderr kept per server, and the longest line stored
+const STDERR_BUFFER_LINES: usize = 200;
+const STDERR_LINE_MAX_CHARS: usize = 1000;
+// Lines of stderr appended to a failed tool call
+const STDERR_TAIL_LINES: usize = 20;
+
+// Bounded ring buffer of the most recent stderr lines from a server process
+#[derive(Debug, Default)]
+pub struct StderrBuffer {
+	lines: VecDeque<String>,
+}
+
+impl StderrBuffer {
+	pub fn push(&mut self, line: &str) {
+		let line = if line.chars().count() > STDERR_LINE_MAX_CHARS {
+			let truncated: String = line.chars().take(STDERR_LINE_MAX_CHARS).collect();
+			format!("{}…", truncated)
+		} else {
+			line.to_string()
+		};
+		if self.lines.len() == STDERR_BUFFER_LINES {
+			self.lines.pop_front();
+		}
+		self.lines.push_back(line);
+	}
+
+	/// Last `count` lines, oldest first
+	pub fn tail(&self, count: usize) -> Vec<String> {
+		let skip = self.lines.len().saturating_sub(count);
+		self.lines.iter().skip(skip).cloned().collect()
+	}
+}
+
+// Captured stderr per server, kept across restarts so crash output stays visible
+lazy_static::lazy_static! {
+	static ref SERVER_STDERR: RwLock<HashMap<String, Arc<Mutex<StderrBuffer>>>> =
+		RwLock::new(HashMap::new());
+}
+
+// Drain the child's stderr into the server's ring buffer on a background thread
+// Reading it also keeps a chatty server from blocking on a full pipe
+fn capture_server_stderr(server_name: &str, child: &mut Child) {
+	let Some(stderr) = child.stderr.take() else {
+		return;
+	};
+	let buffer = SERVER_STDERR
+		.write()
+		.unwrap()
+		.entry(server_name.to_string())
+		.or_default()
+		.clone();
+	let name = server_name.to_string();
+	let spawned = std::thread::Builder::new()
+		.name(format!("mcp-stderr-{}", name))
+		.spawn(move || {
+			let reader = BufReader::new(stderr);
+			for line in reader.split(b'\n') {
+				let Ok(line) = line else {
+					break;
+				};
+				let line = String::from_utf8_lossy(&line);
+				buffer.lock().unwrap().push(line.trim_end());
+			}
+			crate::log_debug!("stderr of MCP server '{}' closed", name);
+		});
+	if let Err(e) = spawned {
+		crate::log_debug!(
+			"Failed to start stderr capture for MCP server '{}': {}",
+			server_name,
+			e
+		);
+	}
+}
+
+/// Most recent stderr lines captured from a server process, oldest first
+pub fn get_server_stderr(server_name: &str, max_lines: usize) -> Vec<String> {
+	SERVER_STDERR
+		.read()
+		.unwrap()
+		.get(server_name)
+		.map(|buffer| buffer.lock().unwrap().tail(max_lines))
+		.unwrap_or_default()
+}
+
+// Error text with the server's recent stderr appended, if it printed anything
+fn with_stderr_tail(server_name: &str, message: String) -> String {
+	let tail = get_server_stderr(server_name, STDERR_TAIL_LINES);
+	if tail.is_empty() {
+		return message;
+	}
+	format!(
+		"{}\n\nLast stderr output from MCP server '{}':\n{}",
+		message,
+		server_name,
+		tail.join("\n")
+	)
+}
+
 // Structure to hold either an HTTP or stdin-based server process
 pub enum ServerProcess {
 	Http(Child),
@@ -264,8 +363,8 @@ async fn start_server_once_if_needed(server: &McpServerConfig) -> Result<String>
 				match server.connection_type() {
 					McpConnectionType::Http => return get_server_url(server),
 					McpConnectionType::Stdin => return Ok("stdin://".to_string() + server_id),
-					McpConnectionType::Builtin => {
-						unreachable!("Builtin servers should not use this function")
+					McpConnectionType::Builtin | McpConnectionType::Sse => {
+						unreachable!("Builtin and SSE servers have no local process")
 					}
 				}
 			} else {
@@ -360,6 +459,13 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 				url
 			));
 		}
+		McpServerConfig::Sse { url, .. } => {
+			return Err(anyhow::anyhow!(
+				"SSE server '{}' should not be started as local process (URL: {})",
+				server.name(),
+				url
+			));
+		}
 		McpServerConfig::Builtin { .. } => {
 			return Err(anyhow::anyhow!(
 				"Builtin server '{}' should not be started as external process",
@@ -403,9 +509,10 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 				"🚀 Starting MCP server (HTTP mode, signal-isolated): {}",
 				server.name()
 			);
-			let child = cmd.spawn().map_err(|e| {
+			let mut child = cmd.spawn().map_err(|e| {
 				anyhow::anyhow!("Failed to start MCP server '{}': {}", server.name(), e)
 			})?;
+			capture_server_stderr(server.name(), &mut child);
 
 			// Add to the registry
 			{
@@ -472,6 +579,7 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 			let child_stdout = child.stdout.take().ok_or_else(|| {
 				anyhow::anyhow!("Failed to open stdout for MCP server: {}", server.name())
 			})?;
+			capture_server_stderr(server.name(), &mut child);
 
 			// Create buffered reader/writer
 			let writer = BufWriter::new(child_stdin);
@@ -526,11 +634,14 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 					);
 				}
 
-				return Err(anyhow::anyhow!(
-					"Failed to initialize stdin MCP server '{}': {}",
+				return Err(anyhow::anyhow!(with_stderr_tail(
 					server.name(),
-					e
-				));
+					format!(
+						"Failed to initialize stdin MCP server '{}': {}",
+						server.name(),
+						e
+					)
+				)));
 			}
 
 			// Return a pseudo-URL for stdin-based servers
@@ -539,6 +650,9 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 			// println!("MCP server started and initialized (stdin mode): {} at {}", server.name(), stdin_url);
 			Ok(stdin_url)
 		}
+		McpConnectionType::Sse => Err(anyhow::anyhow!(
+			"SSE servers should not use process management"
+		)),
 		McpConnectionType::Builtin => Err(anyhow::anyhow!(
 			"Builtin servers should not use process management"
 		)),
@@ -967,7 +1081,7 @@ pub async fn execute_stdin_tool_call(
 		server.name(),
 		&message,
 		1,
-		server.timeout_seconds(),
+		server.tool_timeout_seconds(&call.tool_name),
 		cancellation_token,
 	)
 	.await
@@ -979,7 +1093,7 @@ pub async fn execute_stdin_tool_call(
 			return Ok(McpToolResult::error(
 				call.tool_name.clone(),
 				call.tool_id.clone(),
-				format!("Error executing tool: {}", e),
+				with_stderr_tail(server.name(), format!("Error executing tool: {}", e)),
 			));
 		}
 	};
@@ -1006,7 +1120,10 @@ pub async fn execute_stdin_tool_call(
 		return Ok(McpToolResult::error(
 			call.tool_name.clone(),
 			call.tool_id.clone(),
-			format!("{} (code: {})", error_message, error_code),
+			with_stderr_tail(
+				server.name(),
+				format!("{} (code: {})", error_message, error_code),
+			),
 		));
 	}
 
@@ -1063,6 +1180,115 @@ pub fn stop_all_servers() -> Result<()> {
 	Ok(())
 }
 
+// Result of a heartbeat ping on a stdin server
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeartbeatStatus {
+	Responsive,
+	// Server busy with a request, shut down or not registered - nothing was sent
+	Skipped,
+	// No reply within the timeout; carries the process ID so it can be terminated
+	Unresponsive(u32),
+}
+
+// Send a JSON-RPC ping to an idle stdin server and wait for any reply
+// Never queues behind a real request: a busy server is skipped rather than timed out
+pub async fn ping_stdin_server(server_name: &str, timeout_seconds: u64) -> HeartbeatStatus {
+	let server_process = match SERVER_PROCESSES.read().unwrap().get(server_name) {
+		Some(process) => process.clone(),
+		None => return HeartbeatStatus::Skipped,
+	};
+
+	// Capture the PID up front - a hung read keeps the process locked afterwards
+	let pid = match server_process.try_lock() {
+		Ok(process) => match &*process {
+			ServerProcess::Stdin {
+				child, is_shutdown, ..
+			} if !is_shutdown.load(Ordering::SeqCst) => child.id(),
+			_ => return HeartbeatStatus::Skipped,
+		},
+		Err(_) => return HeartbeatStatus::Skipped,
+	};
+
+	let ping = tokio::task::spawn_blocking(move || -> Option<Result<()>> {
+		let mut process = server_process.try_lock().ok()?;
+		let ServerProcess::Stdin {
+			reader,
+			writer,
+			next_id,
+			..
+		} = &mut *process
+		else {
+			return None;
+		};
+
+		Some(send_ping(
+			reader,
+			writer,
+			next_id.fetch_add(1, Ordering::SeqCst),
+		))
+	});
+
+	match tokio::time::timeout(Duration::from_secs(timeout_seconds), ping).await {
+		Ok(Ok(None)) => HeartbeatStatus::Skipped,
+		Ok(Ok(Some(Ok(())))) => HeartbeatStatus::Responsive,
+		Ok(Ok(Some(Err(e)))) => {
+			crate::log_debug!("Heartbeat for server '{}' failed: {}", server_name, e);
+			HeartbeatStatus::Unresponsive(pid)
+		}
+		Ok(Err(_)) | Err(_) => HeartbeatStatus::Unresponsive(pid),
+	}
+}
+
+fn send_ping(
+	reader: &mut BufReader<std::process::ChildStdout>,
+	writer: &mut BufWriter<std::process::ChildStdin>,
+	id: u64,
+) -> Result<()> {
+	let request = json!({
+		"jsonrpc": "2.0",
+		"id": id,
+		"method": "ping"
+	});
+	writeln!(writer, "{}", request)?;
+	writer.flush()?;
+
+	// Any JSON-RPC reply (even "method not found") proves the server is responsive
+	let mut response = String::new();
+	if reader.read_line(&mut response)? == 0 {
+		return Err(anyhow::anyhow!("Server closed connection"));
+	}
+	serde_json::from_str::<Value>(&response)?;
+	Ok(())
+}
+
+// Terminate an unresponsive stdin server and remove it from the registry
+// Killing by PID also unblocks a read that still holds the process lock
+pub fn kill_unresponsive_server(server_name: &str, pid: u32) {
+	#[cfg(unix)]
+	{
+		let _ = Command::new("kill")
+			.args(["-KILL", &pid.to_string()])
+			.output();
+	}
+	#[cfg(windows)]
+	{
+		let _ = Command::new("taskkill")
+			.args(["/F", "/PID", &pid.to_string()])
+			.output();
+	}
+
+	if let Err(e) = cleanup_server_process(server_name) {
+		crate::log_debug!("Cleanup after killing server '{}': {}", server_name, e);
+	}
+
+	let mut restart_info_guard = SERVER_RESTART_INFO.write().unwrap();
+	let info = restart_info_guard
+		.entry(server_name.to_string())
+		.or_default();
+	info.health_status = ServerHealth::Dead;
+	info.consecutive_failures += 1;
+}
+
 // Cleanup a specific server process (helper function)
 pub fn cleanup_server_process(server_name: &str) -> Result<()> {
 	let mut processes = SERVER_PROCESSES.write().unwrap();
@@ -1225,3 +1451,28 @@ async fn try_communicate_with_stdin_server(
 	}
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_stderr_buffer_is_bounded() {
+		let mut buffer = StderrBuffer::default();
+		for i in 0..STDERR_BUFFER_LINES + 5 {
+			buffer.push(&format!("line {}", i));
+		}
+		assert_eq!(buffer.lines.len(), STDERR_BUFFER_LINES);
+		assert_eq!(
+			buffer.tail(2),
+			vec![
+				format!("line {}", STDERR_BUFFER_LINES + 3),
+				format!("line {}", STDERR_BUFFER_LINES + 4)
+			]
+		);
+		assert_eq!(buffer.tail(usize::MAX).len(), STDERR_BUFFER_LINES);
+
+		buffer.push(&"x".repeat(STDERR_LINE_MAX_CHARS * 2));
+		assert_eq!(buffer.tail(1)[0].chars().count(), STDERR_LINE_MAX_CHARS + 1);
+	}
+}
@@ -0,0 +1,162 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tool Replay - reuse tool results recorded in a previous session
+//!
+//! When enabled via `--replay-tools-from <session>`, tool calls whose name and
+//! parameters match a call recorded in the source session log return the stored
+//! result instead of executing. Misses fall back to real execution.
+//!
+//! Identical calls recorded several times are replayed in recorded order; once
+//! exhausted, the last recorded result keeps being returned.
+//!
+//! Calls logged with truncated parameters (`[logging] max_param_bytes`) can never
+//! match and are skipped.
+
+use super::{McpToolCall, McpToolResult};
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Global replay cache - only populated when replay mode is requested
+static REPLAY_CACHE: OnceLock<Arc<RwLock<Vec<ReplayEntry>>>> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+struct ReplayEntry {
+	tool_name: String,
+	parameters: Value,
+	result: Value,
+	used: bool,
+}
+
+/// Load recorded tool calls and results from an existing session log
+///
+/// # Returns
+/// * `Ok((count, truncated))` with the number of replayable tool results and of calls
+///   skipped because their parameters were truncated in the log
+/// * `Err(...)` if the session log cannot be read
+pub fn load_replay_session(session_name: &str) -> Result<(usize, usize)> {
+	let log_file = crate::session::logger::get_session_log_path(session_name)?;
+	if !log_file.exists() {
+		return Err(anyhow::anyhow!(
+			"Session '{}' not found for tool replay",
+			session_name
+		));
+	}
+
+	let reader = BufReader::new(File::open(&log_file)?);
+
+	// tool_id -> (tool_name, parameters), in recorded order
+	let mut calls: Vec<(String, String, Value)> = Vec::new();
+	let mut results: HashMap<String, Value> = HashMap::new();
+	let mut truncated = 0;
+
+	for line in reader.lines() {
+		let line = line?;
+		let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+			continue;
+		};
+
+		match entry.get("type").and_then(|t| t.as_str()) {
+			Some("TOOL_CALL") if has_truncated_parameters(&entry) => truncated += 1,
+			Some("TOOL_CALL") => {
+				let tool_id = entry.get("tool_id").and_then(|v| v.as_str());
+				let tool_name = entry.get("tool_name").and_then(|v| v.as_str());
+				if let (Some(tool_id), Some(tool_name)) = (tool_id, tool_name) {
+					calls.push((
+						tool_id.to_string(),
+						tool_name.to_string(),
+						entry.get("parameters").cloned().unwrap_or(Value::Null),
+					));
+				}
+			}
+			Some("TOOL_RESULT") => {
+				// The first result logged for an id is the raw tool output;
+				// later ones are the message-level copies
+				let Some(tool_id) = entry.get("tool_id").and_then(|v| v.as_str()) else {
+					continue;
+				};
+				if !results.contains_key(tool_id) {
+					if let Some(result) =
+						crate::session::logger::load_tool_result(session_name, &entry)
+					{
+						results.insert(tool_id.to_string(), result);
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let entries: Vec<ReplayEntry> = calls
+		.into_iter()
+		.filter_map(|(tool_id, tool_name, parameters)| {
+			results.remove(&tool_id).map(|result| ReplayEntry {
+				tool_name,
+				parameters,
+				result,
+				used: false,
+			})
+		})
+		.collect();
+
+	let count = entries.len();
+	let cache = REPLAY_CACHE.get_or_init(|| Arc::new(RwLock::new(Vec::new())));
+	*cache.write().unwrap() = entries;
+
+	crate::log_debug!(
+		"Loaded {} replayable tool results from session '{}' ({} truncated calls skipped)",
+		count,
+		session_name,
+		truncated
+	);
+
+	Ok((count, truncated))
+}
+
+/// Whether a TOOL_CALL log entry was written with truncated parameters
+pub fn has_truncated_parameters(entry: &Value) -> bool {
+	entry
+		.get("parameters_truncated")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false)
+}
+
+/// Look up a recorded result for a tool call (name + parameters must match)
+pub fn lookup(call: &McpToolCall) -> Option<McpToolResult> {
+	let cache = REPLAY_CACHE.get()?;
+	let mut entries = cache.write().unwrap();
+
+	let matches = |entry: &ReplayEntry| {
+		entry.tool_name == call.tool_name && entry.parameters == call.parameters
+	};
+
+	// Prefer the next unused recording, then fall back to the last one
+	let index = entries
+		.iter()
+		.position(|entry| !entry.used && matches(entry))
+		.or_else(|| entries.iter().rposition(matches))?;
+
+	let entry = &mut entries[index];
+	entry.used = true;
+
+	Some(McpToolResult {
+		tool_name: call.tool_name.clone(),
+		tool_id: call.tool_id.clone(),
+		result: entry.result.clone(),
+	})
+}
@@ -18,7 +18,7 @@ use super::process;
 use super::{McpFunction, McpToolCall, McpToolResult};
 use crate::config::{Config, McpConnectionType, McpServerConfig};
 use anyhow::Result;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use reqwest::Client;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -57,6 +57,92 @@ pub fn create_initialize_request() -> Value {
 	})
 }
 
+// Build HTTP request headers for an external server: JSON content type,
+// optional bearer auth and any custom headers from the server config
+pub(super) fn build_request_headers(server: &McpServerConfig) -> Result<HeaderMap> {
+	let mut headers = HeaderMap::new();
+	headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+	// Add auth token if present
+	if let Some(token) = server.auth_token() {
+		headers.insert(
+			AUTHORIZATION,
+			HeaderValue::from_str(&format!("Bearer {}", token))?,
+		);
+	}
+
+	// Merge custom headers, expanding environment variables in values
+	if let Some(custom) = server.headers() {
+		for (name, value) in custom {
+			let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+				anyhow::anyhow!(
+					"Invalid header name '{}' for MCP server '{}': {}",
+					name,
+					server.name(),
+					e
+				)
+			})?;
+			let expanded = expand_env_vars(value);
+			let header_value = HeaderValue::from_str(&expanded).map_err(|e| {
+				anyhow::anyhow!(
+					"Invalid value for header '{}' on MCP server '{}': {}",
+					name,
+					server.name(),
+					e
+				)
+			})?;
+			headers.insert(header_name, header_value);
+		}
+	}
+
+	Ok(headers)
+}
+
+// Expand $VAR and ${VAR} references using the current environment
+// Unset variables expand to an empty string
+fn expand_env_vars(value: &str) -> String {
+	let mut result = String::with_capacity(value.len());
+	let mut chars = value.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		if c != '$' {
+			result.push(c);
+			continue;
+		}
+
+		let var_name = if chars.peek() == Some(&'{') {
+			chars.next();
+			let mut name = String::new();
+			for ch in chars.by_ref() {
+				if ch == '}' {
+					break;
+				}
+				name.push(ch);
+			}
+			name
+		} else {
+			let mut name = String::new();
+			while let Some(&ch) = chars.peek() {
+				if ch.is_ascii_alphanumeric() || ch == '_' {
+					name.push(ch);
+					chars.next();
+				} else {
+					break;
+				}
+			}
+			name
+		};
+
+		if var_name.is_empty() {
+			result.push('$');
+		} else {
+			result.push_str(&std::env::var(&var_name).unwrap_or_default());
+		}
+	}
+
+	result
+}
+
 fn create_tools_call_request(tool_name: &str, parameters: &Value) -> Value {
 	json!({
 		"jsonrpc": "2.0",
@@ -128,17 +214,8 @@ pub async fn get_server_functions(server: &McpServerConfig) -> Result<Vec<McpFun
 			// Create a client
 			let client = Client::new();
 
-			// Prepare headers
-			let mut headers = HeaderMap::new();
-			headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-			// Add auth token if present
-			if let Some(token) = server.auth_token() {
-				headers.insert(
-					AUTHORIZATION,
-					HeaderValue::from_str(&format!("Bearer {}", token))?,
-				);
-			}
+			// Prepare headers (content type, auth token and custom headers)
+			let headers = build_request_headers(server)?;
 
 			// MCP uses JSON-RPC over HTTP with POST requests
 			let schema_url = server_url; // Use base URL for JSON-RPC
@@ -189,6 +266,14 @@ pub async fn get_server_functions(server: &McpServerConfig) -> Result<Vec<McpFun
 			process::ensure_server_running(server).await?;
 			process::get_stdin_server_functions(server).await
 		}
+		McpConnectionType::Sse => {
+			crate::log_debug!(
+				"Making JSON-RPC tools/list request to SSE server '{}'",
+				server.name()
+			);
+			let jsonrpc_response = super::sse::list_tools(server).await?;
+			parse_tools_from_jsonrpc_response(&jsonrpc_response, server)
+		}
 		McpConnectionType::Builtin => {
 			// Built-in servers don't need external processes
 			Err(anyhow::anyhow!(
@@ -257,11 +342,11 @@ pub async fn get_server_functions_cached(server: &McpServerConfig) -> Result<Vec
 // Helper function to get fallback functions when server is not running
 fn get_fallback_functions(server: &McpServerConfig) -> Result<Vec<McpFunction>> {
 	if !server.tools().is_empty() {
-		// For remote HTTP servers, don't show "server not started" since they're external
-		let description_suffix = if server.connection_type()
-			== crate::config::McpConnectionType::Http
-			&& server.url().is_some()
-			&& server.command().is_none()
+		// For remote HTTP and SSE servers, don't show "server not started" since they're external
+		let description_suffix = if server.connection_type() == McpConnectionType::Sse
+			|| (server.connection_type() == McpConnectionType::Http
+				&& server.url().is_some()
+				&& server.command().is_none())
 		{
 			"(remote server)"
 		} else {
@@ -292,6 +377,11 @@ fn get_fallback_functions(server: &McpServerConfig) -> Result<Vec<McpFunction>>
 // Optimized server running check that doesn't hold locks for long
 // This function now requires server config to properly handle remote HTTP servers
 fn is_server_running_for_cache_check(server: &McpServerConfig) -> bool {
+	// SSE servers are running while their event stream is open
+	if server.connection_type() == McpConnectionType::Sse {
+		return super::sse::is_connected(server.name());
+	}
+
 	// For remote HTTP servers (have URL but no command), consider them always available
 	if server.connection_type() == McpConnectionType::Http
 		&& server.url().is_some()
@@ -346,6 +436,16 @@ pub fn clear_function_cache_for_server(server_name: &str) {
 	}
 }
 
+// Re-probe a server's function definitions and replace the cached ones
+// (picks up a changed tool set without restarting the session)
+pub async fn refresh_server_functions(server: &McpServerConfig) -> Result<Vec<McpFunction>> {
+	clear_function_cache_for_server(server.name());
+	let functions = get_server_functions(server).await?;
+	let mut cache = FUNCTION_CACHE.write().unwrap();
+	cache.insert(server.name().to_string(), functions.clone());
+	Ok(functions)
+}
+
 // Clear all cached functions (useful for cleanup)
 pub fn clear_all_function_cache() {
 	let mut cache = FUNCTION_CACHE.write().unwrap();
@@ -440,6 +540,21 @@ pub fn is_server_already_running_with_config(server: &crate::config::McpServerCo
 
 			is_process_running
 		}
+		McpConnectionType::Sse => {
+			// The stream reader marks the server dead when the stream closes
+			let is_connected = super::sse::is_connected(server.name());
+			{
+				let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+				let info = restart_info_guard
+					.entry(server.name().to_string())
+					.or_default();
+				if is_connected {
+					info.health_status = process::ServerHealth::Running;
+				}
+				info.last_health_check = Some(std::time::SystemTime::now());
+			}
+			is_connected
+		}
 	}
 }
 
@@ -592,23 +707,16 @@ async fn execute_tool_call_internal(
 			// Handle local vs remote servers for HTTP mode
 			let server_url = get_server_base_url(server).await?;
 
-			// Create a client with configured timeout
+			// Create a client with the tool's timeout (tool_timeouts or the server timeout)
 			let client = Client::builder()
-				.timeout(std::time::Duration::from_secs(server.timeout_seconds()))
+				.timeout(std::time::Duration::from_secs(
+					server.tool_timeout_seconds(&call.tool_name),
+				))
 				.build()
 				.unwrap_or_else(|_| Client::new());
 
-			// Prepare headers
-			let mut headers = HeaderMap::new();
-			headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-			// Add auth token if present
-			if let Some(token) = server.auth_token() {
-				headers.insert(
-					AUTHORIZATION,
-					HeaderValue::from_str(&format!("Bearer {}", token))?,
-				);
-			}
+			// Prepare headers (content type, auth token and custom headers)
+			let headers = build_request_headers(server)?;
 
 			// Use base URL for JSON-RPC tool execution
 			let execute_url = server_url;
@@ -670,6 +778,27 @@ async fn execute_tool_call_internal(
 			// For stdin-based servers, use the stdin communication channel with cancellation support
 			process::execute_stdin_tool_call(call, server, cancellation_token).await
 		}
+		McpConnectionType::Sse => {
+			// Sent over POST, the response arrives on the event stream
+			let result =
+				super::sse::call_tool(server, tool_name, parameters, cancellation_token).await?;
+
+			let output = if let Some(error) = result.get("error") {
+				json!({
+					"error": true,
+					"success": false,
+					"message": error.get("message").and_then(|m| m.as_str()).unwrap_or("Server error")
+				})
+			} else {
+				result.get("result").cloned().unwrap_or(json!("No result"))
+			};
+
+			Ok(McpToolResult::success(
+				tool_name.clone(),
+				call.tool_id.clone(),
+				serde_json::to_string_pretty(&output).unwrap_or_else(|_| output.to_string()),
+			))
+		}
 		McpConnectionType::Builtin => {
 			// Built-in servers should not use this function
 			Err(anyhow::anyhow!(
@@ -720,6 +849,7 @@ async fn get_server_base_url(server: &McpServerConfig) -> Result<String> {
 				Err(anyhow::anyhow!("Invalid server configuration: command not specified for stdin-based server '{}'", server.name()))
 			}
 		}
+		McpConnectionType::Sse => super::sse::ensure_connected(server).await,
 		McpConnectionType::Builtin => {
 			// Built-in servers don't have URLs
 			Err(anyhow::anyhow!("Built-in servers don't have URLs"))
@@ -758,6 +888,9 @@ pub fn cleanup_servers() -> Result<()> {
 	// Stop the health monitor first
 	crate::mcp::health_monitor::stop_health_monitor();
 
+	// Close SSE event streams
+	super::sse::disconnect_all();
+
 	// Then stop all server processes
 	process::stop_all_servers()
 }
@@ -0,0 +1,632 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// SSE MCP transport - server messages arrive on an event stream, requests are sent with POST
+
+use super::process::{self, ServerHealth};
+use super::server::{build_request_headers, create_initialize_request};
+use crate::config::McpServerConfig;
+use anyhow::Result;
+use reqwest::header::{HeaderValue, ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::oneshot;
+
+// How long the server has to announce its POST endpoint after the stream opens
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Value>>>>;
+
+// Open event stream to one server
+struct SseConnection {
+	endpoint: String,
+	pending: PendingRequests,
+	next_id: AtomicU64,
+	connected: Arc<AtomicBool>,
+	reader: tokio::task::JoinHandle<()>,
+}
+
+lazy_static::lazy_static! {
+	static ref SSE_CONNECTIONS: RwLock<HashMap<String, Arc<SseConnection>>> =
+		RwLock::new(HashMap::new());
+	// Serializes connects so concurrent first uses open a single stream
+	static ref CONNECT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::new(());
+}
+
+/// A dispatched server-sent event
+#[derive(Debug, Clone, PartialEq)]
+pub struct SseEvent {
+	/// Event type, "message" when the event has no event field
+	pub event: String,
+	/// Data lines joined with '\n'
+	pub data: String,
+}
+
+/// Incremental text/event-stream parser
+/// Chunks may split lines and events anywhere; events are returned once complete
+#[derive(Debug, Default)]
+pub struct SseParser {
+	buffer: Vec<u8>,
+	event: Option<String>,
+	data: Vec<String>,
+}
+
+impl SseParser {
+	/// Feed the next chunk of the stream, returning every event it completes
+	pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+		self.buffer.extend_from_slice(chunk);
+
+		let mut events = Vec::new();
+		while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+			let raw: Vec<u8> = self.buffer.drain(..=end).collect();
+			let text = String::from_utf8_lossy(&raw[..end]).into_owned();
+			let line = text.strip_suffix('\r').unwrap_or(&text);
+
+			// A blank line dispatches the event
+			if line.is_empty() {
+				let event = self.event.take();
+				if !self.data.is_empty() {
+					events.push(SseEvent {
+						event: event.unwrap_or_else(|| "message".to_string()),
+						data: self.data.join("\n"),
+					});
+					self.data.clear();
+				}
+				continue;
+			}
+
+			// Comment lines are keep-alives
+			if line.starts_with(':') {
+				continue;
+			}
+
+			let (field, value) = match line.split_once(':') {
+				Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+				None => (line, ""),
+			};
+			match field {
+				"event" => self.event = Some(value.to_string()),
+				"data" => self.data.push(value.to_string()),
+				// id and retry are not used by MCP
+				_ => {}
+			}
+		}
+		events
+	}
+}
+
+// Resolve the endpoint announced by the server against the stream URL
+// Requests carry the server's auth headers, so the endpoint must stay on the stream's origin
+fn resolve_endpoint(stream_url: &str, endpoint: &str) -> Result<String> {
+	let base = url::Url::parse(stream_url)
+		.map_err(|e| anyhow::anyhow!("Invalid SSE URL '{}': {}", stream_url, e))?;
+	let resolved = base
+		.join(endpoint)
+		.map_err(|e| anyhow::anyhow!("Invalid SSE endpoint '{}': {}", endpoint, e))?;
+	if resolved.scheme() != base.scheme()
+		|| resolved.host_str() != base.host_str()
+		|| resolved.port_or_known_default() != base.port_or_known_default()
+	{
+		return Err(anyhow::anyhow!(
+			"SSE endpoint '{}' is not on the same origin as {}",
+			endpoint,
+			stream_url
+		));
+	}
+	Ok(resolved.to_string())
+}
+
+fn set_health(server_name: &str, health: ServerHealth) {
+	let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+	let info = restart_info_guard
+		.entry(server_name.to_string())
+		.or_default();
+	info.health_status = health;
+	info.last_health_check = Some(SystemTime::now());
+}
+
+// Hand a JSON-RPC message from the stream to the request waiting for it
+fn dispatch_message(server_name: &str, data: &str, pending: &PendingRequests) {
+	let message: Value = match serde_json::from_str(data) {
+		Ok(message) => message,
+		Err(e) => {
+			crate::log_debug!(
+				"Invalid JSON message from SSE server '{}': {}",
+				server_name,
+				e
+			);
+			return;
+		}
+	};
+
+	match message.get("id").and_then(|id| id.as_u64()) {
+		Some(id) if message.get("method").is_none() => {
+			if let Some(sender) = pending.lock().unwrap().remove(&id) {
+				let _ = sender.send(message);
+			}
+		}
+		_ => {
+			// Notifications (progress, logging) and server requests are not acted on
+			crate::log_debug!(
+				"Message from SSE server '{}': {}",
+				server_name,
+				message
+					.get("method")
+					.and_then(|m| m.as_str())
+					.unwrap_or("unknown")
+			);
+		}
+	}
+}
+
+// Read the event stream until it closes, then mark the server dead
+async fn read_events(
+	server_name: String,
+	mut response: Response,
+	pending: PendingRequests,
+	connected: Arc<AtomicBool>,
+	endpoint_tx: oneshot::Sender<String>,
+) {
+	let mut parser = SseParser::default();
+	let mut endpoint_tx = Some(endpoint_tx);
+
+	loop {
+		let chunk = match response.chunk().await {
+			Ok(Some(chunk)) => chunk,
+			Ok(None) => break,
+			Err(e) => {
+				crate::log_debug!("SSE stream error for server '{}': {}", server_name, e);
+				break;
+			}
+		};
+
+		for event in parser.feed(&chunk) {
+			match event.event.as_str() {
+				"endpoint" => {
+					if let Some(sender) = endpoint_tx.take() {
+						let _ = sender.send(event.data.trim().to_string());
+					}
+				}
+				"message" => dispatch_message(&server_name, &event.data, &pending),
+				other => {
+					crate::log_debug!(
+						"Ignoring '{}' event from SSE server '{}'",
+						other,
+						server_name
+					);
+				}
+			}
+		}
+	}
+
+	crate::log_info!("SSE stream for server '{}' closed", server_name);
+	connected.store(false, Ordering::SeqCst);
+	// Dropping the senders fails every request still waiting for a response
+	pending.lock().unwrap().clear();
+	set_health(&server_name, ServerHealth::Dead);
+}
+
+// Open the event stream, wait for the POST endpoint and run the MCP handshake
+async fn connect(server: &McpServerConfig) -> Result<Arc<SseConnection>> {
+	let url = server
+		.url()
+		.ok_or_else(|| anyhow::anyhow!("SSE server '{}' has no url configured", server.name()))?;
+
+	let mut headers = build_request_headers(server)?;
+	headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+
+	crate::log_debug!(
+		"Opening SSE stream for server '{}' at {}",
+		server.name(),
+		url
+	);
+	let response = Client::new().get(url).headers(headers).send().await?;
+	if !response.status().is_success() {
+		return Err(anyhow::anyhow!(
+			"Failed to open SSE stream for MCP server '{}': {}",
+			server.name(),
+			response.status()
+		));
+	}
+
+	let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+	let connected = Arc::new(AtomicBool::new(true));
+	let (endpoint_tx, endpoint_rx) = oneshot::channel();
+	let reader = tokio::spawn(read_events(
+		server.name().to_string(),
+		response,
+		pending.clone(),
+		connected.clone(),
+		endpoint_tx,
+	));
+
+	let endpoint = match tokio::time::timeout(ENDPOINT_TIMEOUT, endpoint_rx).await {
+		Ok(Ok(endpoint)) => resolve_endpoint(url, &endpoint)?,
+		Ok(Err(_)) => {
+			return Err(anyhow::anyhow!(
+				"SSE server '{}' closed the stream before announcing its endpoint",
+				server.name()
+			));
+		}
+		Err(_) => {
+			reader.abort();
+			return Err(anyhow::anyhow!(
+				"SSE server '{}' did not announce its endpoint within {}s",
+				server.name(),
+				ENDPOINT_TIMEOUT.as_secs()
+			));
+		}
+	};
+	crate::log_debug!("SSE server '{}' endpoint: {}", server.name(), endpoint);
+
+	let connection = Arc::new(SseConnection {
+		endpoint,
+		pending,
+		next_id: AtomicU64::new(1),
+		connected,
+		reader,
+	});
+
+	let initialize_params = create_initialize_request()["params"].clone();
+	let handshake = async {
+		let response = request(
+			server,
+			&connection,
+			"initialize",
+			initialize_params,
+			server.timeout_seconds(),
+			None,
+		)
+		.await?;
+		if let Some(error) = response.get("error") {
+			return Err(anyhow::anyhow!("initialize failed: {}", error));
+		}
+		post(
+			server,
+			&connection.endpoint,
+			&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+			server.timeout_seconds(),
+		)
+		.await?;
+		Ok::<(), anyhow::Error>(())
+	};
+	if let Err(e) = handshake.await {
+		connection.reader.abort();
+		return Err(e);
+	}
+
+	Ok(connection)
+}
+
+async fn post(
+	server: &McpServerConfig,
+	endpoint: &str,
+	body: &Value,
+	timeout_seconds: u64,
+) -> Result<Response> {
+	let client = Client::builder()
+		.timeout(Duration::from_secs(timeout_seconds))
+		.build()
+		.unwrap_or_else(|_| Client::new());
+	let response = client
+		.post(endpoint)
+		.headers(build_request_headers(server)?)
+		.json(body)
+		.send()
+		.await?;
+
+	if !response.status().is_success() {
+		let status = response.status();
+		let error_text = response.text().await.unwrap_or_default();
+		return Err(anyhow::anyhow!(
+			"MCP server '{}' rejected the request: {}, {}",
+			server.name(),
+			status,
+			error_text
+		));
+	}
+	Ok(response)
+}
+
+// Response to request `id` carried in the POST response body itself, when the server
+// answers inline (JSON or an event stream) instead of on the SSE stream
+async fn inline_response(server_name: &str, response: Response, id: u64) -> Option<Value> {
+	let is_event_stream = response
+		.headers()
+		.get(CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.is_some_and(|value| value.starts_with("text/event-stream"));
+	let body = response.bytes().await.ok()?;
+
+	let messages: Vec<Value> = if is_event_stream {
+		let mut parser = SseParser::default();
+		let mut events = parser.feed(&body);
+		// Flush an event not terminated by a blank line
+		events.extend(parser.feed(b"\n\n"));
+		events
+			.into_iter()
+			.filter(|event| event.event == "message")
+			.filter_map(|event| serde_json::from_str(&event.data).ok())
+			.collect()
+	} else {
+		serde_json::from_slice(&body).ok().into_iter().collect()
+	};
+
+	let found = messages
+		.into_iter()
+		.find(|message| message.get("id").and_then(|i| i.as_u64()) == Some(id));
+	if found.is_some() {
+		crate::log_debug!(
+			"SSE server '{}' answered request {} inline",
+			server_name,
+			id
+		);
+	}
+	found
+}
+
+// Send a JSON-RPC request and wait for its response on the stream
+async fn request(
+	server: &McpServerConfig,
+	connection: &SseConnection,
+	method: &str,
+	params: Value,
+	timeout_seconds: u64,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<Value> {
+	let id = connection.next_id.fetch_add(1, Ordering::SeqCst);
+	let (sender, mut receiver) = oneshot::channel();
+	connection.pending.lock().unwrap().insert(id, sender);
+
+	let body = json!({
+		"jsonrpc": "2.0",
+		"id": id,
+		"method": method,
+		"params": params
+	});
+	let response = match post(server, &connection.endpoint, &body, timeout_seconds).await {
+		Ok(response) => response,
+		Err(e) => {
+			connection.pending.lock().unwrap().remove(&id);
+			return Err(e);
+		}
+	};
+	if let Some(message) = inline_response(server.name(), response, id).await {
+		connection.pending.lock().unwrap().remove(&id);
+		return Ok(message);
+	}
+
+	let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_seconds);
+	loop {
+		if let Some(ref token) = cancellation_token {
+			if token.load(Ordering::SeqCst) {
+				connection.pending.lock().unwrap().remove(&id);
+				return Err(anyhow::anyhow!("External tool execution cancelled"));
+			}
+		}
+		if tokio::time::Instant::now() >= deadline {
+			connection.pending.lock().unwrap().remove(&id);
+			return Err(anyhow::anyhow!(
+				"MCP server '{}' did not answer '{}' within {}s",
+				server.name(),
+				method,
+				timeout_seconds
+			));
+		}
+
+		match tokio::time::timeout(Duration::from_millis(100), &mut receiver).await {
+			Ok(Ok(message)) => return Ok(message),
+			Ok(Err(_)) => {
+				return Err(anyhow::anyhow!(
+					"SSE stream for MCP server '{}' closed while waiting for '{}'",
+					server.name(),
+					method
+				));
+			}
+			Err(_) => continue,
+		}
+	}
+}
+
+// Connection for the server, opening the stream if there is none or it closed
+async fn get_connection(server: &McpServerConfig) -> Result<Arc<SseConnection>> {
+	let _guard = CONNECT_LOCK.lock().await;
+
+	if let Some(connection) = SSE_CONNECTIONS.read().unwrap().get(server.name()) {
+		if connection.connected.load(Ordering::SeqCst) {
+			return Ok(connection.clone());
+		}
+	}
+
+	match connect(server).await {
+		Ok(connection) => {
+			SSE_CONNECTIONS
+				.write()
+				.unwrap()
+				.insert(server.name().to_string(), connection.clone());
+			{
+				let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+				let info = restart_info_guard
+					.entry(server.name().to_string())
+					.or_default();
+				info.health_status = ServerHealth::Running;
+				info.restart_count += 1; // Track that we connected it
+				info.last_restart_time = Some(SystemTime::now());
+				info.last_health_check = Some(SystemTime::now());
+				info.consecutive_failures = 0;
+			}
+			Ok(connection)
+		}
+		Err(e) => {
+			{
+				let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+				let info = restart_info_guard
+					.entry(server.name().to_string())
+					.or_default();
+				info.health_status = ServerHealth::Failed;
+				info.consecutive_failures += 1;
+			}
+			Err(e)
+		}
+	}
+}
+
+/// Open the event stream if needed, returning the server URL
+pub async fn ensure_connected(server: &McpServerConfig) -> Result<String> {
+	get_connection(server).await?;
+	Ok(server.url().unwrap_or_default().to_string())
+}
+
+/// Whether the server has an open event stream
+pub fn is_connected(server_name: &str) -> bool {
+	SSE_CONNECTIONS
+		.read()
+		.unwrap()
+		.get(server_name)
+		.is_some_and(|connection| connection.connected.load(Ordering::SeqCst))
+}
+
+/// tools/list response from the server
+pub async fn list_tools(server: &McpServerConfig) -> Result<Value> {
+	let connection = get_connection(server).await?;
+	request(
+		server,
+		&connection,
+		"tools/list",
+		json!({}),
+		server.timeout_seconds(),
+		None,
+	)
+	.await
+}
+
+/// tools/call response from the server
+pub async fn call_tool(
+	server: &McpServerConfig,
+	tool_name: &str,
+	parameters: &Value,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<Value> {
+	let connection = get_connection(server).await?;
+	request(
+		server,
+		&connection,
+		"tools/call",
+		json!({
+			"name": tool_name,
+			"arguments": parameters
+		}),
+		server.tool_timeout_seconds(tool_name),
+		cancellation_token,
+	)
+	.await
+}
+
+/// Close the event stream for one server
+pub fn disconnect(server_name: &str) {
+	if let Some(connection) = SSE_CONNECTIONS.write().unwrap().remove(server_name) {
+		connection.reader.abort();
+		connection.connected.store(false, Ordering::SeqCst);
+		crate::log_debug!("Closed SSE stream for server '{}'", server_name);
+	}
+}
+
+/// Close every event stream
+pub fn disconnect_all() {
+	let names: Vec<String> = SSE_CONNECTIONS.read().unwrap().keys().cloned().collect();
+	for name in names {
+		disconnect(&name);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sse_parser_reassembles_split_events() {
+		let mut parser = SseParser::default();
+
+		// Event split across chunks, CRLF line endings and a keep-alive comment
+		assert!(parser.feed(b": ping\r\n\r\nevent: endp").is_empty());
+		assert_eq!(
+			parser.feed(b"oint\r\ndata: /messages?session=1\r\n\r\n"),
+			vec![SseEvent {
+				event: "endpoint".to_string(),
+				data: "/messages?session=1".to_string(),
+			}]
+		);
+
+		// Multi-line data is joined, events without a type are messages
+		let events = parser.feed(b"data: {\"id\": 1,\ndata: \"result\": {}}\n\ndata: x\n");
+		assert_eq!(
+			events,
+			vec![SseEvent {
+				event: "message".to_string(),
+				data: "{\"id\": 1,\n\"result\": {}}".to_string(),
+			}]
+		);
+		assert_eq!(parser.feed(b"\n").len(), 1);
+	}
+
+	#[test]
+	fn test_resolve_endpoint() {
+		assert_eq!(
+			resolve_endpoint("https://mcp.example.com/sse", "/messages?session=1").unwrap(),
+			"https://mcp.example.com/messages?session=1"
+		);
+		assert_eq!(
+			resolve_endpoint(
+				"https://mcp.example.com:443/sse",
+				"https://mcp.example.com/m"
+			)
+			.unwrap(),
+			"https://mcp.example.com/m"
+		);
+
+		// Endpoints on another origin would receive the auth headers
+		assert!(
+			resolve_endpoint("https://mcp.example.com/sse", "https://other.example.com/m").is_err()
+		);
+		assert!(
+			resolve_endpoint("https://mcp.example.com/sse", "http://mcp.example.com/m").is_err()
+		);
+		assert!(
+			resolve_endpoint("https://mcp.example.com/sse", "//mcp.example.com:8443/m").is_err()
+		);
+	}
+
+	#[test]
+	fn test_dispatch_message_routes_by_id() {
+		let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+		let (sender, mut receiver) = oneshot::channel();
+		pending.lock().unwrap().insert(7, sender);
+
+		// Notifications are not responses
+		dispatch_message(
+			"test",
+			r#"{"jsonrpc":"2.0","method":"notifications/progress","params":{}}"#,
+			&pending,
+		);
+		assert!(receiver.try_recv().is_err());
+
+		dispatch_message("test", r#"{"jsonrpc":"2.0","id":7,"result":{}}"#, &pending);
+		assert_eq!(receiver.try_recv().unwrap()["id"], 7);
+		assert!(pending.lock().unwrap().is_empty());
+	}
+}
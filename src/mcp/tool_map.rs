@@ -162,6 +162,63 @@ pub fn get_all_tool_names() -> Vec<String> {
 	state.tool_to_server.keys().cloned().collect()
 }
 
+/// Count the tools a server provides in the initialized tool map
+///
+/// # Arguments
+/// * `server_name` - The name of the server
+///
+/// # Returns
+/// * Number of tools mapped to the server if initialized
+/// * `None` if the tool map is not initialized
+pub fn get_tool_count_for_server(server_name: &str) -> Option<usize> {
+	let tool_map_state = TOOL_MAP.get()?;
+	let state = tool_map_state.read().unwrap();
+	if !state.initialized {
+		return None;
+	}
+
+	Some(
+		state
+			.tool_to_server
+			.values()
+			.filter(|server| server.name() == server_name)
+			.count(),
+	)
+}
+
+/// Get the names of the tools a server provides in the initialized tool map
+///
+/// # Arguments
+/// * `server_name` - The name of the server
+///
+/// # Returns
+/// * Sorted tool names, empty if the tool map is not initialized
+pub fn get_tool_names_for_server(server_name: &str) -> Vec<String> {
+	let Some(tool_map_state) = TOOL_MAP.get() else {
+		return Vec::new();
+	};
+	let state = tool_map_state.read().unwrap();
+	let mut names: Vec<String> = state
+		.tool_to_server
+		.iter()
+		.filter(|(_, server)| server.name() == server_name)
+		.map(|(name, _)| name.clone())
+		.collect();
+	names.sort();
+	names
+}
+
+/// Rebuild the global tool map even when the configuration is unchanged
+///
+/// Used after server function caches were cleared, so a changed tool set
+/// is picked up without restarting the session.
+pub async fn rebuild_tool_map(config: &Config) -> Result<()> {
+	if let Some(tool_map_state) = TOOL_MAP.get() {
+		tool_map_state.write().unwrap().initialized = false;
+	}
+	initialize_tool_map(config).await
+}
+
 /// Internal function to build the tool-to-server mapping
 ///
 /// This is the same logic as the original `build_tool_server_map()` function,
@@ -207,7 +264,7 @@ async fn build_tool_server_map_internal(
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				// For external servers, get their actual functions
 				match crate::mcp::server::get_server_functions_cached(&server).await {
 					Ok(functions) => {
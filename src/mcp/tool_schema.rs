@@ -0,0 +1,226 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Well-formedness checks of tool parameter schemas (`mcp validate-schemas`)
+// A malformed inputSchema from a server makes providers reject the whole request,
+// so the schemas are checked the way providers read them
+
+use serde_json::Value;
+
+const SCHEMA_TYPES: [&str; 7] = [
+	"object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+/// Problems of a tool's parameters schema, empty when it is well-formed
+pub fn check_tool_schema(parameters: &Value) -> Vec<String> {
+	let mut errors = Vec::new();
+	match parameters.as_object() {
+		None => errors.push(format!(
+			"$: parameters must be a JSON object, got {}",
+			kind(parameters)
+		)),
+		Some(schema) => {
+			if schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+				errors.push("$: parameters must have \"type\": \"object\"".to_string());
+			}
+			check_at(parameters, "$", &mut errors);
+		}
+	}
+	errors
+}
+
+fn check_at(schema: &Value, path: &str, errors: &mut Vec<String>) {
+	let Some(schema) = schema.as_object() else {
+		// `true`/`false` schemas are valid anywhere below the root
+		if !schema.is_boolean() {
+			errors.push(format!(
+				"{}: a schema must be an object, got {}",
+				path,
+				kind(schema)
+			));
+		}
+		return;
+	};
+
+	let types: Vec<&str> = match schema.get("type") {
+		None => Vec::new(),
+		Some(Value::String(t)) => vec![t.as_str()],
+		Some(Value::Array(ts)) if ts.iter().all(|t| t.is_string()) => {
+			ts.iter().filter_map(|t| t.as_str()).collect()
+		}
+		Some(other) => {
+			errors.push(format!(
+				"{}: \"type\" must be a string or an array of strings, got {}",
+				path,
+				kind(other)
+			));
+			Vec::new()
+		}
+	};
+	for t in &types {
+		if !SCHEMA_TYPES.contains(t) {
+			errors.push(format!("{}: unknown type \"{}\"", path, t));
+		}
+	}
+
+	match schema.get("properties") {
+		None => {}
+		Some(Value::Object(properties)) => {
+			for (name, property) in properties {
+				check_at(property, &format!("{}.{}", path, name), errors);
+			}
+		}
+		Some(other) => errors.push(format!(
+			"{}: \"properties\" must be an object, got {}",
+			path,
+			kind(other)
+		)),
+	}
+
+	match schema.get("required") {
+		None => {}
+		Some(Value::Array(required)) => {
+			let properties = schema.get("properties").and_then(|p| p.as_object());
+			for name in required {
+				match name.as_str() {
+					None => errors.push(format!(
+						"{}: \"required\" entries must be strings, got {}",
+						path,
+						kind(name)
+					)),
+					Some(name) if properties.is_some_and(|p| !p.contains_key(name)) => {
+						errors.push(format!(
+							"{}: required property '{}' is not in \"properties\"",
+							path, name
+						))
+					}
+					Some(_) => {}
+				}
+			}
+		}
+		Some(other) => errors.push(format!(
+			"{}: \"required\" must be an array, got {}",
+			path,
+			kind(other)
+		)),
+	}
+
+	match schema.get("items") {
+		None if types.contains(&"array") => {
+			errors.push(format!("{}: array schema has no \"items\"", path))
+		}
+		None => {}
+		Some(Value::Array(items)) => {
+			for (index, item) in items.iter().enumerate() {
+				check_at(item, &format!("{}.items[{}]", path, index), errors);
+			}
+		}
+		Some(items) => check_at(items, &format!("{}[]", path), errors),
+	}
+
+	match schema.get("additionalProperties") {
+		None | Some(Value::Bool(_)) => {}
+		Some(additional @ Value::Object(_)) => check_at(
+			additional,
+			&format!("{}.additionalProperties", path),
+			errors,
+		),
+		Some(other) => errors.push(format!(
+			"{}: \"additionalProperties\" must be a boolean or a schema, got {}",
+			path,
+			kind(other)
+		)),
+	}
+
+	match schema.get("enum") {
+		None => {}
+		Some(Value::Array(values)) if !values.is_empty() => {}
+		Some(_) => errors.push(format!("{}: \"enum\" must be a non-empty array", path)),
+	}
+
+	for key in ["anyOf", "oneOf", "allOf"] {
+		match schema.get(key) {
+			None => {}
+			Some(Value::Array(options)) if !options.is_empty() => {
+				for (index, option) in options.iter().enumerate() {
+					check_at(option, &format!("{}.{}[{}]", path, key, index), errors);
+				}
+			}
+			Some(_) => errors.push(format!("{}: \"{}\" must be a non-empty array", path, key)),
+		}
+	}
+}
+
+fn kind(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "a boolean",
+		Value::Number(_) => "a number",
+		Value::String(_) => "a string",
+		Value::Array(_) => "an array",
+		Value::Object(_) => "an object",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_well_formed_schema() {
+		let schema = json!({
+			"type": "object",
+			"properties": {
+				"path": {"type": "string"},
+				"lines": {"type": "array", "items": {"type": ["integer", "null"]}},
+				"mode": {"enum": ["a", "b"]},
+				"extra": {"anyOf": [{"type": "string"}, true]}
+			},
+			"required": ["path"],
+			"additionalProperties": false
+		});
+		assert!(check_tool_schema(&schema).is_empty());
+		assert!(check_tool_schema(&json!({"type": "object"})).is_empty());
+	}
+
+	#[test]
+	fn test_malformed_schema() {
+		assert_eq!(
+			check_tool_schema(&json!("object")),
+			vec!["$: parameters must be a JSON object, got a string"]
+		);
+
+		let schema = json!({
+			"properties": {
+				"query": {"type": "text"},
+				"ids": {"type": "array"},
+				"opts": "string"
+			},
+			"required": ["query", "missing"],
+			"enum": []
+		});
+		assert_eq!(
+			check_tool_schema(&schema),
+			vec![
+				"$: parameters must have \"type\": \"object\"",
+				"$.ids: array schema has no \"items\"",
+				"$.opts: a schema must be an object, got a string",
+				"$.query: unknown type \"text\"",
+				"$: required property 'missing' is not in \"properties\"",
+				"$: \"enum\" must be a non-empty array",
+			]
+		);
+	}
+}
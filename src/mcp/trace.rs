@@ -0,0 +1,109 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tool Trace - record tool I/O to a directory
+//!
+//! When enabled via `--trace-tools <dir>`, every completed or failed tool call
+//! is written as a numbered JSON file (`0001_<tool>.json`, ...) containing the
+//! tool name, arguments, result or error, and execution time.
+
+use super::{McpToolCall, McpToolResult};
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Trace directory - only set when trace mode is requested
+static TRACE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Sequence number for trace files
+static TRACE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Enable tool tracing into the given directory (created if missing)
+pub fn init_tool_trace(dir: &str) -> Result<PathBuf> {
+	let path = PathBuf::from(dir);
+	std::fs::create_dir_all(&path).map_err(|e| {
+		anyhow::anyhow!(
+			"Failed to create tool trace directory '{}': {}",
+			path.display(),
+			e
+		)
+	})?;
+
+	// Continue numbering after any trace files already in the directory
+	let existing = std::fs::read_dir(&path)?
+		.filter_map(|entry| entry.ok())
+		.filter_map(|entry| {
+			let name = entry.file_name().to_string_lossy().to_string();
+			name.split('_').next().and_then(|n| n.parse::<usize>().ok())
+		})
+		.max()
+		.unwrap_or(0);
+	TRACE_COUNTER.store(existing, Ordering::SeqCst);
+
+	let _ = TRACE_DIR.set(path.clone());
+	Ok(path)
+}
+
+/// Record a finished tool call; failures to write are logged and otherwise ignored
+pub fn record(call: &McpToolCall, outcome: &Result<(McpToolResult, u64)>, execution_time_ms: u64) {
+	let Some(dir) = TRACE_DIR.get() else {
+		return;
+	};
+
+	let sequence = TRACE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+	let timestamp = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs();
+
+	let mut entry = serde_json::json!({
+		"sequence": sequence,
+		"timestamp": timestamp,
+		"tool_name": call.tool_name,
+		"tool_id": call.tool_id,
+		"parameters": call.parameters,
+		"execution_time_ms": execution_time_ms,
+	});
+	match outcome {
+		Ok((result, _)) => {
+			entry["status"] = "success".into();
+			entry["result"] = result.result.clone();
+		}
+		Err(e) => {
+			entry["status"] = "error".into();
+			entry["error"] = e.to_string().into();
+		}
+	}
+
+	let safe_name: String = call
+		.tool_name
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	let file = dir.join(format!("{:04}_{}.json", sequence, safe_name));
+
+	let written = serde_json::to_string_pretty(&entry)
+		.map_err(anyhow::Error::from)
+		.and_then(|content| std::fs::write(&file, content).map_err(anyhow::Error::from));
+	if let Err(e) = written {
+		crate::log_debug!("Failed to write tool trace '{}': {}", file.display(), e);
+	}
+}
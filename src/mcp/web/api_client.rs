@@ -15,8 +15,61 @@
 // Shared Brave API client functionality
 
 use super::super::{McpToolCall, McpToolResult};
+use crate::config::WebConfig;
 use anyhow::{anyhow, Result};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Round-robin position and rate limit cooldowns of the API keys, by secret name
+#[derive(Debug, Default)]
+struct KeyRotation {
+	next: usize,
+	cooldowns: HashMap<String, Instant>,
+}
+
+impl KeyRotation {
+	// Index of the key to use next: round-robin over the keys that are not cooling down,
+	// or the key whose cooldown ends first when all of them are
+	fn pick(&mut self, names: &[&str], now: Instant) -> usize {
+		let count = names.len();
+		let start = self.next % count;
+		let index = (0..count)
+			.map(|offset| (start + offset) % count)
+			.find(|&index| !matches!(self.cooldowns.get(names[index]), Some(until) if *until > now))
+			.or_else(|| (0..count).min_by_key(|&index| self.cooldowns.get(names[index]).copied()))
+			.unwrap_or(start);
+		self.next = index + 1;
+		index
+	}
+
+	fn cool_down(&mut self, name: &str, until: Instant) {
+		self.cooldowns.insert(name.to_string(), until);
+	}
+}
+
+// Key rotation shared by all searches of the process
+fn key_rotation() -> &'static Mutex<KeyRotation> {
+	static ROTATION: OnceLock<Mutex<KeyRotation>> = OnceLock::new();
+	ROTATION.get_or_init(|| Mutex::new(KeyRotation::default()))
+}
+
+// Brave Search API keys of [web] api_key_vars that are set, as (secret name, key)
+pub fn resolve_api_keys(web: &WebConfig) -> Result<Vec<(String, String)>> {
+	let names = web.api_key_names();
+	let keys: Vec<(String, String)> = names
+		.iter()
+		.filter_map(|name| Some((name.clone(), crate::config::secrets::get_secret(name)?)))
+		.collect();
+	if keys.is_empty() {
+		return Err(anyhow!(
+			"No Brave Search API key found: {} not set",
+			names.join(", ")
+		));
+	}
+	Ok(keys)
+}
 
 // Helper function to extract and validate query parameter
 pub fn extract_and_validate_query(call: &McpToolCall) -> Result<String> {
@@ -40,20 +93,53 @@ pub fn extract_and_validate_query(call: &McpToolCall) -> Result<String> {
 	Ok(query)
 }
 
-// Helper function to make Brave API requests
+// Helper function to make Brave API requests, rotating through the API keys
+// A rate limited (429) key cools down and the request is retried with the next key
 pub async fn make_brave_api_request(
 	client: &reqwest::Client,
 	url: &str,
-	api_key: &str,
+	api_keys: &[(String, String)],
+	web: &WebConfig,
 	search_type: &str,
 ) -> Result<Value> {
-	let request = client
-		.get(url)
-		.header("Accept", "application/json")
-		.header("Accept-Encoding", "gzip")
-		.header("X-Subscription-Token", api_key);
+	let names: Vec<&str> = api_keys.iter().map(|(name, _)| name.as_str()).collect();
+	for attempt in 0..api_keys.len() {
+		let index = key_rotation().lock().unwrap().pick(&names, Instant::now());
+		let (name, api_key) = &api_keys[index];
+		let request = client
+			.get(url)
+			.header("Accept", "application/json")
+			.header("Accept-Encoding", "gzip")
+			.header("X-Subscription-Token", api_key);
+		let response = request.send().await.map_err(|e| {
+			anyhow!(
+				"Failed to send {} request to Brave Search API: {}",
+				search_type,
+				e
+			)
+		})?;
+
+		if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+			crate::log_debug!(
+				"Brave Search API key {} is rate limited, skipping it for {}s",
+				name,
+				web.key_cooldown_seconds
+			);
+			key_rotation().lock().unwrap().cool_down(
+				name,
+				Instant::now() + Duration::from_secs(web.key_cooldown_seconds),
+			);
+			if attempt + 1 < api_keys.len() {
+				continue;
+			}
+		}
+		return handle_brave_api_response(response, search_type).await;
+	}
 
-	make_brave_api_request_with_builder(request, search_type).await
+	Err(anyhow!(
+		"No Brave Search API key to send the {} request with",
+		search_type
+	))
 }
 
 // Helper function to make Brave API requests with a pre-built request
@@ -116,3 +202,32 @@ pub fn create_api_error_result(
 		format!("Failed to execute {} search: {}", search_type, error),
 	)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_key_rotation() {
+		let names = ["KEY_A", "KEY_B", "KEY_C"];
+		let now = Instant::now();
+		let mut rotation = KeyRotation::default();
+
+		// Round-robin
+		assert_eq!(rotation.pick(&names, now), 0);
+		assert_eq!(rotation.pick(&names, now), 1);
+		assert_eq!(rotation.pick(&names, now), 2);
+		assert_eq!(rotation.pick(&names, now), 0);
+
+		// A rate limited key is skipped until its cooldown ends
+		rotation.cool_down("KEY_B", now + Duration::from_secs(60));
+		assert_eq!(rotation.pick(&names, now), 2);
+		assert_eq!(rotation.pick(&names, now), 0);
+		assert_eq!(rotation.pick(&names, now + Duration::from_secs(61)), 1);
+
+		// All keys cooling down: the one that is free first
+		rotation.cool_down("KEY_A", now + Duration::from_secs(30));
+		rotation.cool_down("KEY_C", now + Duration::from_secs(90));
+		assert_eq!(rotation.pick(&names, now), 0);
+	}
+}
@@ -16,10 +16,11 @@
 
 use super::super::{McpFunction, McpToolCall, McpToolResult};
 use super::api_client::{
-	create_api_error_result, extract_and_validate_query, make_brave_api_request,
+	create_api_error_result, extract_and_validate_query, make_brave_api_request, resolve_api_keys,
 };
 use super::formatters::format_news_results;
-use anyhow::{anyhow, Result};
+use crate::config::WebConfig;
+use anyhow::Result;
 use serde_json::json;
 
 // Define the news_search function for the MCP protocol
@@ -103,6 +104,7 @@ Examples:
 // Execute a news search using Brave Search API
 pub async fn execute_news_search(
 	call: &McpToolCall,
+	web: &WebConfig,
 	_cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<McpToolResult> {
 	// Extract and validate query
@@ -119,8 +121,7 @@ pub async fn execute_news_search(
 	};
 
 	// Get API key from environment
-	let api_key = std::env::var("BRAVE_API_KEY")
-		.map_err(|_| anyhow!("BRAVE_API_KEY environment variable is not set"))?;
+	let api_keys = resolve_api_keys(web)?;
 
 	// Extract optional parameters with defaults
 	let count = call
@@ -175,7 +176,7 @@ pub async fn execute_news_search(
 	let client = reqwest::Client::new();
 
 	// Make the API request
-	let search_result = match make_brave_api_request(&client, &url, &api_key, "news").await {
+	let search_result = match make_brave_api_request(&client, &url, &api_keys, web, "news").await {
 		Ok(result) => result,
 		Err(e) => {
 			return Ok(create_api_error_result(
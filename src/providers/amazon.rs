@@ -52,14 +52,10 @@ const PRICING: &[(&str, f64, f64)] = &[
 
 /// Calculate cost for Amazon Bedrock models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
 }
 
 /// Amazon Bedrock provider implementation
@@ -85,14 +81,12 @@ impl AmazonBedrockProvider {
 
 	/// Get AWS access key ID
 	fn get_aws_access_key_id(&self) -> Result<String> {
-		env::var("AWS_ACCESS_KEY_ID")
-			.map_err(|_| anyhow::anyhow!("AWS_ACCESS_KEY_ID not found in environment"))
+		crate::config::secrets::require_secret("AWS_ACCESS_KEY_ID", "AWS access key ID")
 	}
 
 	/// Get AWS secret access key
 	fn get_aws_secret_access_key(&self) -> Result<String> {
-		env::var("AWS_SECRET_ACCESS_KEY")
-			.map_err(|_| anyhow::anyhow!("AWS_SECRET_ACCESS_KEY not found in environment"))
+		crate::config::secrets::require_secret("AWS_SECRET_ACCESS_KEY", "AWS secret access key")
 	}
 
 	/// Convert Bedrock model ID to full model name for API calls
@@ -219,6 +213,10 @@ impl AiProvider for AmazonBedrockProvider {
 		32_768 - 2_048
 	}
 
+	fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+		super::model_pricing(PRICING, model)
+	}
+
 	async fn chat_completion(
 		&self,
 		messages: &[Message],
@@ -385,11 +383,11 @@ impl AiProvider for AmazonBedrockProvider {
 				.get("message")
 				.and_then(|m| m.as_str())
 				.unwrap_or(&response_text);
-			return Err(anyhow::anyhow!(
-				"Amazon Bedrock API error ({}): {}",
+			return Err(super::ProviderHttpError::new(
 				status,
-				error_message
-			));
+				format!("Amazon Bedrock API error ({}): {}", status, error_message),
+			)
+			.into());
 		}
 
 		// Extract content and tool calls based on model family
@@ -522,6 +520,7 @@ impl AiProvider for AmazonBedrockProvider {
 				output_tokens: completion_tokens,
 				total_tokens,
 				cached_tokens: 0, // Amazon Bedrock doesn't support caching yet
+				cache_write_tokens: 0,
 				cost,
 				request_time_ms: Some(api_time_ms), // Track API timing for Amazon
 			})
@@ -559,6 +558,8 @@ impl AiProvider for AmazonBedrockProvider {
 			exchange,
 			tool_calls,
 			finish_reason,
+			citations: None,
+			refusal: None,
 		})
 	}
 }
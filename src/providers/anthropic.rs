@@ -21,7 +21,6 @@ use crate::session::Message;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
 
 /// Anthropic pricing constants (per 1M tokens in USD)
 /// Source: https://docs.anthropic.com/en/docs/about-claude/models/overview (as of January 2025)
@@ -61,49 +60,43 @@ struct CacheTokenUsage {
 /// - regular_input_tokens: charged at normal price
 /// - output_tokens: charged at normal price
 fn calculate_cost_with_cache(model: &str, usage: CacheTokenUsage) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			// Regular input tokens at normal price
-			let regular_input_cost =
-				(usage.regular_input_tokens as f64 / 1_000_000.0) * input_price;
-
-			// Cache creation tokens at 1.25x price (25% more expensive) for 5m cache
-			let cache_creation_cost =
-				(usage.cache_creation_tokens as f64 / 1_000_000.0) * input_price * 1.25;
-
-			// Cache creation tokens at 2x price (100% more expensive) for 1h cache
-			let cache_creation_cost_1h =
-				(usage.cache_creation_tokens_1h as f64 / 1_000_000.0) * input_price * 2.0;
-
-			// Cache read tokens at 0.1x price (90% cheaper)
-			let cache_read_cost =
-				(usage.cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.1;
-
-			// Output tokens at normal price (never cached)
-			let output_cost = (usage.output_tokens as f64 / 1_000_000.0) * output_price;
-
-			let total_cost = regular_input_cost
-				+ cache_creation_cost
-				+ cache_creation_cost_1h
-				+ cache_read_cost
-				+ output_cost;
-
-			// Debug: Log detailed cost calculation breakdown
-			crate::log_debug!(
-				"Anthropic detailed cost calculation for {}: Regular input: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 5m: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 1h: ${:.8} ({} tokens @ ${:.2}/1M), Cache read: ${:.8} ({} tokens @ ${:.2}/1M), Output: ${:.8} ({} tokens @ ${:.2}/1M), Total: ${:.8}",
-				model,
-				regular_input_cost, usage.regular_input_tokens, input_price,
-				cache_creation_cost, usage.cache_creation_tokens, input_price * 1.25,
-				cache_creation_cost_1h, usage.cache_creation_tokens_1h, input_price * 2.0,
-				cache_read_cost, usage.cache_read_tokens, input_price * 0.1,
-				output_cost, usage.output_tokens, output_price,
-				total_cost
-			);
-
-			return Some(total_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+	// Regular input tokens at normal price
+	let regular_input_cost = (usage.regular_input_tokens as f64 / 1_000_000.0) * input_price;
+
+	// Cache creation tokens at 1.25x price (25% more expensive) for 5m cache
+	let cache_creation_cost =
+		(usage.cache_creation_tokens as f64 / 1_000_000.0) * input_price * 1.25;
+
+	// Cache creation tokens at 2x price (100% more expensive) for 1h cache
+	let cache_creation_cost_1h =
+		(usage.cache_creation_tokens_1h as f64 / 1_000_000.0) * input_price * 2.0;
+
+	// Cache read tokens at 0.1x price (90% cheaper)
+	let cache_read_cost = (usage.cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.1;
+
+	// Output tokens at normal price (never cached)
+	let output_cost = (usage.output_tokens as f64 / 1_000_000.0) * output_price;
+
+	let total_cost = regular_input_cost
+		+ cache_creation_cost
+		+ cache_creation_cost_1h
+		+ cache_read_cost
+		+ output_cost;
+
+	// Debug: Log detailed cost calculation breakdown
+	crate::log_debug!(
+		"Anthropic detailed cost calculation for {}: Regular input: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 5m: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 1h: ${:.8} ({} tokens @ ${:.2}/1M), Cache read: ${:.8} ({} tokens @ ${:.2}/1M), Output: ${:.8} ({} tokens @ ${:.2}/1M), Total: ${:.8}",
+		model,
+		regular_input_cost, usage.regular_input_tokens, input_price,
+		cache_creation_cost, usage.cache_creation_tokens, input_price * 1.25,
+		cache_creation_cost_1h, usage.cache_creation_tokens_1h, input_price * 2.0,
+		cache_read_cost, usage.cache_read_tokens, input_price * 0.1,
+		output_cost, usage.output_tokens, output_price,
+		total_cost
+	);
+
+	Some(total_cost)
 }
 
 /// Anthropic provider implementation
@@ -123,7 +116,7 @@ impl AnthropicProvider {
 
 // Constants
 const ANTHROPIC_API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
-const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_API_BASE: &str = "https://api.anthropic.com/v1";
 
 /// Message format for the Anthropic API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -144,14 +137,8 @@ impl AiProvider for AnthropicProvider {
 	}
 
 	fn get_api_key(&self, _config: &Config) -> Result<String> {
-		// API keys now only from environment variables for security
-		match env::var(ANTHROPIC_API_KEY_ENV) {
-			Ok(key) => Ok(key),
-			Err(_) => Err(anyhow::anyhow!(
-				"Anthropic API key not found in environment variable: {}",
-				ANTHROPIC_API_KEY_ENV
-			)),
-		}
+		// Environment variable first, then the configured secrets source
+		crate::config::secrets::require_secret(ANTHROPIC_API_KEY_ENV, "Anthropic API key")
 	}
 
 	fn supports_caching(&self, _model: &str) -> bool {
@@ -198,6 +185,10 @@ impl AiProvider for AnthropicProvider {
 		100_000
 	}
 
+	fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+		super::model_pricing(PRICING, model)
+	}
+
 	async fn chat_completion(
 		&self,
 		messages: &[Message],
@@ -312,7 +303,11 @@ impl AiProvider for AnthropicProvider {
 
 		// Create the HTTP request
 		let request_future = client
-			.post(ANTHROPIC_API_URL)
+			.post(super::endpoint_url(
+				config.anthropic.base_url.as_deref(),
+				ANTHROPIC_API_BASE,
+				"messages",
+			))
 			.header("x-api-key", api_key)
 			.header("Content-Type", "application/json")
 			.header("anthropic-version", "2023-06-01")
@@ -385,7 +380,11 @@ impl AiProvider for AnthropicProvider {
 			}
 
 			let full_error = error_details.join(" | ");
-			return Err(anyhow::anyhow!("Anthropic API error: {}", full_error));
+			return Err(super::ProviderHttpError::new(
+				status,
+				format!("Anthropic API error: {}", full_error),
+			)
+			.into());
 		}
 
 		// Extract content from response
@@ -519,6 +518,7 @@ impl AiProvider for AnthropicProvider {
 				cached_tokens, // Only cache_read_input_tokens are truly "cached"
 				cost,          // Pre-calculated with proper cache pricing
 				request_time_ms: Some(api_time_ms), // Track API timing for Anthropic
+				cache_write_tokens: cache_creation_5m_tokens + cache_creation_1h_tokens,
 			})
 		} else {
 			None
@@ -534,6 +534,9 @@ impl AiProvider for AnthropicProvider {
 			None
 		};
 
+		// Extract citations attached to text content blocks
+		let citations = super::extract_citations(&response_json);
+
 		// Create exchange record
 		let mut exchange = ProviderExchange::new(request_body, response_json, usage, self.name());
 
@@ -547,6 +550,8 @@ impl AiProvider for AnthropicProvider {
 			exchange,
 			tool_calls,
 			finish_reason,
+			citations,
+			refusal: None,
 		})
 	}
 }
@@ -21,7 +21,6 @@ use crate::session::Message;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
 
 /// Cloudflare Workers AI pricing constants (per 1M tokens in USD)
 /// Source: https://developers.cloudflare.com/workers-ai/platform/pricing/ (as of January 2025)
@@ -56,18 +55,18 @@ const PRICING: &[(&str, f64, f64)] = &[
 	("hermes-2-pro-mistral-7b", 0.125, 0.125),
 ];
 
+// Pricing of models missing from PRICING (roughly similar to small models)
+const DEFAULT_PRICING: (f64, f64) = (0.125, 0.125);
+
+fn model_pricing(model: &str) -> (f64, f64) {
+	super::model_pricing(PRICING, model).unwrap_or(DEFAULT_PRICING)
+}
+
 /// Calculate cost for Cloudflare Workers AI models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	// Default pricing for unknown models (roughly similar to small models)
-	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * 0.125;
-	let output_cost = (completion_tokens as f64 / 1_000_000.0) * 0.125;
+	let (input_price, output_price) = model_pricing(model);
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
 	Some(input_cost + output_cost)
 }
 
@@ -87,14 +86,12 @@ impl CloudflareWorkersAiProvider {
 
 	/// Get Cloudflare API token
 	fn get_api_token(&self) -> Result<String> {
-		env::var("CLOUDFLARE_API_TOKEN")
-			.map_err(|_| anyhow::anyhow!("CLOUDFLARE_API_TOKEN not found in environment"))
+		crate::config::secrets::require_secret("CLOUDFLARE_API_TOKEN", "Cloudflare API token")
 	}
 
 	/// Get Cloudflare Account ID
 	fn get_account_id(&self) -> Result<String> {
-		env::var("CLOUDFLARE_ACCOUNT_ID")
-			.map_err(|_| anyhow::anyhow!("CLOUDFLARE_ACCOUNT_ID not found in environment"))
+		crate::config::secrets::require_secret("CLOUDFLARE_ACCOUNT_ID", "Cloudflare account ID")
 	}
 
 	/// Convert model name to full Cloudflare model identifier
@@ -233,6 +230,10 @@ impl AiProvider for CloudflareWorkersAiProvider {
 		16_384 - 1_024
 	}
 
+	fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+		Some(model_pricing(model))
+	}
+
 	async fn chat_completion(
 		&self,
 		messages: &[Message],
@@ -355,10 +356,11 @@ impl AiProvider for CloudflareWorkersAiProvider {
 			}
 
 			let full_error = error_details.join(" | ");
-			return Err(anyhow::anyhow!(
-				"Cloudflare Workers AI API error: {}",
-				full_error
-			));
+			return Err(super::ProviderHttpError::new(
+				status,
+				format!("Cloudflare Workers AI API error: {}", full_error),
+			)
+			.into());
 		}
 
 		// Check for success in response
@@ -474,6 +476,7 @@ impl AiProvider for CloudflareWorkersAiProvider {
 			output_tokens: estimated_completion_tokens,
 			total_tokens,
 			cached_tokens: 0, // Cloudflare Workers AI doesn't support caching yet
+			cache_write_tokens: 0,
 			cost,
 			request_time_ms: Some(api_time_ms), // Track API timing for Cloudflare
 		});
@@ -497,6 +500,8 @@ impl AiProvider for CloudflareWorkersAiProvider {
 			exchange,
 			tool_calls,
 			finish_reason,
+			citations: None,
+			refusal: None,
 		})
 	}
 }
@@ -21,7 +21,6 @@ use crate::session::Message;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
 
 /// DeepSeek pricing constants (per 1M tokens in USD)
 /// Update according to https://platform.deepseek.com/pricing if needed
@@ -34,14 +33,10 @@ const PRICING: &[(&str, f64, f64)] = &[
 
 /// Calculate cost for DeepSeek models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-    for (pricing_model, input_price, output_price) in PRICING {
-        if model.contains(pricing_model) {
-            let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-            let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-            return Some(input_cost + output_cost);
-        }
-    }
-    None
+    let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+    let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+    let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+    Some(input_cost + output_cost)
 }
 
 /// Check if a model supports the temperature parameter
@@ -66,7 +61,7 @@ impl DeepSeekProvider {
 
 // Constants
 const DEEPSEEK_API_KEY_ENV: &str = "DEEPSEEK_API_KEY";
-const DEEPSEEK_API_URL: &str = "https://api.deepseek.com/v1/chat/completions";
+const DEEPSEEK_API_BASE: &str = "https://api.deepseek.com/v1";
 
 /// Message format for the DeepSeek API (compatible with OpenAI format)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,14 +82,8 @@ impl AiProvider for DeepSeekProvider {
     }
 
     fn get_api_key(&self, _config: &Config) -> Result<String> {
-        // API keys from environment variable
-        match env::var(DEEPSEEK_API_KEY_ENV) {
-            Ok(key) => Ok(key),
-            Err(_) => Err(anyhow::anyhow!(
-                "DeepSeek API key not found in environment variable: {}",
-                DEEPSEEK_API_KEY_ENV
-            )),
-        }
+        // Environment variable first, then the configured secrets source
+        crate::config::secrets::require_secret(DEEPSEEK_API_KEY_ENV, "DeepSeek API key")
     }
 
     fn supports_caching(&self, _model: &str) -> bool {
@@ -113,6 +102,10 @@ impl AiProvider for DeepSeekProvider {
         8_192 // fallback
     }
 
+    fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+        super::model_pricing(PRICING, model)
+    }
+
     async fn chat_completion(
         &self,
         messages: &[Message],
@@ -152,7 +145,11 @@ impl AiProvider for DeepSeekProvider {
 
         // Make the actual API request
         let response = client
-            .post(DEEPSEEK_API_URL)
+            .post(super::endpoint_url(
+                config.deepseek.base_url.as_deref(),
+                DEEPSEEK_API_BASE,
+                "chat/completions",
+            ))
             .header("Authorization", format!("Bearer {}", api_key))
             .header("Content-Type", "application/json")
             .json(&request_body)
@@ -198,7 +195,11 @@ impl AiProvider for DeepSeekProvider {
                 error_details.push(format!("Raw response: {}", response_text));
             }
             let full_error = error_details.join(" | ");
-            return Err(anyhow::anyhow!("DeepSeek API error: {}", full_error));
+            return Err(super::ProviderHttpError::new(
+                status,
+                format!("DeepSeek API error: {}", full_error),
+            )
+            .into());
         }
 
         // Extract content
@@ -252,6 +253,7 @@ impl AiProvider for DeepSeekProvider {
                 output_tokens: completion_tokens,
                 total_tokens,
                 cached_tokens: 0,
+                cache_write_tokens: 0,
                 cost,
                 request_time_ms: Some(api_time_ms),
             })
@@ -267,6 +269,8 @@ impl AiProvider for DeepSeekProvider {
             exchange,
             tool_calls,
             finish_reason,
+            citations: None,
+            refusal: None,
         })
     }
 }
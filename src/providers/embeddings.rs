@@ -0,0 +1,95 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Embedding providers for semantic similarity (e.g. near-duplicate tool results)
+
+use super::{OpenAiProvider, ProviderExchange, ProviderFactory};
+use crate::config::Config;
+use anyhow::Result;
+
+/// Embeddings of a batch of texts, in input order
+#[derive(Debug, Clone)]
+pub struct EmbeddingResponse {
+	pub embeddings: Vec<Vec<f32>>,
+	pub exchange: ProviderExchange,
+}
+
+/// Trait that providers offering an embeddings API implement
+#[async_trait::async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+	/// Get the provider name (e.g., "openai")
+	fn name(&self) -> &str;
+
+	/// Embed the texts with the given embedding model
+	async fn embed(
+		&self,
+		texts: &[String],
+		model: &str,
+		config: &Config,
+	) -> Result<EmbeddingResponse>;
+}
+
+/// Embedding provider factory, model strings use the same "provider:model" format
+pub struct EmbeddingProviderFactory;
+
+impl EmbeddingProviderFactory {
+	/// Get the embedding provider for a model string like "openai:text-embedding-3-small"
+	pub fn get_provider_for_model(model: &str) -> Result<(Box<dyn EmbeddingProvider>, String)> {
+		let (provider_name, model_name) = ProviderFactory::parse_model(model)?;
+		match provider_name.to_lowercase().as_str() {
+			"openai" => Ok((Box::new(OpenAiProvider::new()), model_name)),
+			_ => Err(anyhow::anyhow!(
+				"Provider '{}' has no embeddings support. Supported providers: openai",
+				provider_name
+			)),
+		}
+	}
+}
+
+/// Cosine similarity of two embeddings, 0.0 for empty or mismatched vectors
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+	if a.is_empty() || a.len() != b.len() {
+		return 0.0;
+	}
+	let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+	let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+	let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+	if norm_a == 0.0 || norm_b == 0.0 {
+		return 0.0;
+	}
+	dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_cosine_similarity() {
+		assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+		assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+		assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+		assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+	}
+
+	#[test]
+	fn test_embedding_provider_for_model() {
+		let (provider, model) =
+			EmbeddingProviderFactory::get_provider_for_model("openai:text-embedding-3-small")
+				.unwrap();
+		assert_eq!(provider.name(), "openai");
+		assert_eq!(model, "text-embedding-3-small");
+		assert!(EmbeddingProviderFactory::get_provider_for_model("anthropic:claude").is_err());
+	}
+}
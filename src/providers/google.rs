@@ -48,14 +48,10 @@ const PRICING: &[(&str, f64, f64)] = &[
 
 /// Calculate cost for Google Vertex AI models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
 }
 
 /// Google Vertex AI provider implementation
@@ -160,6 +156,10 @@ impl AiProvider for GoogleVertexProvider {
 		32_768
 	}
 
+	fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+		super::model_pricing(PRICING, model)
+	}
+
 	async fn chat_completion(
 		&self,
 		messages: &[Message],
@@ -284,10 +284,11 @@ impl AiProvider for GoogleVertexProvider {
 			}
 
 			let full_error = error_details.join(" | ");
-			return Err(anyhow::anyhow!(
-				"Google Vertex AI API error: {}",
-				full_error
-			));
+			return Err(super::ProviderHttpError::new(
+				status,
+				format!("Google Vertex AI API error: {}", full_error),
+			)
+			.into());
 		}
 
 		// Extract content from response
@@ -377,6 +378,7 @@ impl AiProvider for GoogleVertexProvider {
 				output_tokens: completion_tokens,
 				total_tokens,
 				cached_tokens: 0, // Google Vertex AI doesn't support caching yet
+				cache_write_tokens: 0,
 				cost,
 				request_time_ms: Some(api_time_ms), // Track API timing for Google
 			})
@@ -413,6 +415,8 @@ impl AiProvider for GoogleVertexProvider {
 			exchange,
 			tool_calls,
 			finish_reason: None, // Vertex AI doesn't provide finish_reason in the same format
+			citations: None,
+			refusal: None,
 		})
 	}
 }
@@ -15,7 +15,7 @@
 // Provider abstraction layer for different AI providers
 
 use crate::config::Config;
-use crate::session::Message;
+use crate::session::{ContentPart, Message};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -23,6 +23,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 pub mod amazon;
 pub mod anthropic;
 pub mod cloudflare;
+pub mod embeddings;
 pub mod google;
 pub mod openai;
 pub mod openrouter;
@@ -32,6 +33,7 @@ pub mod deepseek;
 pub use amazon::AmazonBedrockProvider;
 pub use anthropic::AnthropicProvider;
 pub use cloudflare::CloudflareWorkersAiProvider;
+pub use embeddings::{EmbeddingProvider, EmbeddingProviderFactory};
 pub use google::GoogleVertexProvider;
 pub use openai::OpenAiProvider;
 pub use openrouter::OpenRouterProvider;
@@ -45,6 +47,8 @@ pub struct TokenUsage {
 	pub total_tokens: u64,  // prompt_tokens + output_tokens
 	pub cached_tokens: u64, // Subset of prompt_tokens that came from cache (discounted)
 	#[serde(default)]
+	pub cache_write_tokens: u64, // Subset of prompt_tokens written to cache (billed at a premium)
+	#[serde(default)]
 	pub cost: Option<f64>, // Pre-calculated total cost (provider handles cache pricing)
 	// Time tracking
 	#[serde(default)]
@@ -81,6 +85,39 @@ impl ProviderExchange {
 	}
 }
 
+/// Error response of a provider API, keeping the HTTP status so callers can classify it
+/// (e.g. rate limits) without parsing the message
+#[derive(Debug)]
+pub struct ProviderHttpError {
+	pub status: u16,
+	pub message: String,
+}
+
+impl ProviderHttpError {
+	pub fn new(status: reqwest::StatusCode, message: String) -> Self {
+		Self {
+			status: status.as_u16(),
+			message,
+		}
+	}
+}
+
+impl std::fmt::Display for ProviderHttpError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl std::error::Error for ProviderHttpError {}
+
+/// Source reference returned by providers that ground their answers (web search, retrieval)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Citation {
+	pub url: String,
+	#[serde(default)]
+	pub title: Option<String>,
+}
+
 /// Provider response containing the AI completion
 #[derive(Debug, Clone)]
 pub struct ProviderResponse {
@@ -88,6 +125,190 @@ pub struct ProviderResponse {
 	pub exchange: ProviderExchange,
 	pub tool_calls: Option<Vec<crate::mcp::McpToolCall>>,
 	pub finish_reason: Option<String>,
+	pub citations: Option<Vec<Citation>>,
+	/// Why the model declined, when the provider returns it apart from the content
+	pub refusal: Option<String>,
+}
+
+/// Extract citations/annotations from a raw provider response
+/// Supports OpenAI-compatible `message.annotations` (url_citation), top-level `citations`
+/// arrays (Perplexity via OpenRouter) and Anthropic content block `citations`
+pub fn extract_citations(response_json: &serde_json::Value) -> Option<Vec<Citation>> {
+	let mut citations: Vec<Citation> = Vec::new();
+	let mut push = |url: Option<&str>, title: Option<&str>| {
+		if let Some(url) = url.filter(|u| !u.is_empty()) {
+			if !citations.iter().any(|c| c.url == url) {
+				citations.push(Citation {
+					url: url.to_string(),
+					title: title.filter(|t| !t.is_empty()).map(|t| t.to_string()),
+				});
+			}
+		}
+	};
+
+	// OpenAI-compatible annotations on the first choice message
+	if let Some(annotations) = response_json
+		.get("choices")
+		.and_then(|choices| choices.get(0))
+		.and_then(|choice| choice.get("message"))
+		.and_then(|message| message.get("annotations"))
+		.and_then(|a| a.as_array())
+	{
+		for annotation in annotations {
+			let source = annotation.get("url_citation").unwrap_or(annotation);
+			push(
+				source.get("url").and_then(|u| u.as_str()),
+				source.get("title").and_then(|t| t.as_str()),
+			);
+		}
+	}
+
+	// Top-level citations array (plain URLs or objects)
+	if let Some(list) = response_json.get("citations").and_then(|c| c.as_array()) {
+		for item in list {
+			if let Some(url) = item.as_str() {
+				push(Some(url), None);
+			} else {
+				push(
+					item.get("url").and_then(|u| u.as_str()),
+					item.get("title").and_then(|t| t.as_str()),
+				);
+			}
+		}
+	}
+
+	// Anthropic content blocks with citations
+	if let Some(blocks) = response_json.get("content").and_then(|c| c.as_array()) {
+		for block in blocks {
+			if let Some(block_citations) = block.get("citations").and_then(|c| c.as_array()) {
+				for citation in block_citations {
+					push(
+						citation.get("url").and_then(|u| u.as_str()),
+						citation.get("title").and_then(|t| t.as_str()),
+					);
+				}
+			}
+		}
+	}
+
+	if citations.is_empty() {
+		None
+	} else {
+		Some(citations)
+	}
+}
+
+/// Pricing of a model as (input, output) USD per 1M tokens from a provider's pricing table:
+/// the first entry whose name is part of the model name
+pub fn model_pricing(pricing: &[(&str, f64, f64)], model: &str) -> Option<(f64, f64)> {
+	pricing
+		.iter()
+		.find(|(pricing_model, _, _)| model.contains(pricing_model))
+		.map(|(_, input_price, output_price)| (*input_price, *output_price))
+}
+
+/// URL of an API endpoint: the configured base URL or the provider's default, plus the path
+pub fn endpoint_url(base_url: Option<&str>, default_base_url: &str, path: &str) -> String {
+	format!(
+		"{}/{}",
+		base_url.unwrap_or(default_base_url).trim_end_matches('/'),
+		path.trim_start_matches('/')
+	)
+}
+
+/// Extract the refusal text of an OpenAI-compatible response (`message.refusal`)
+pub fn extract_refusal(response_json: &serde_json::Value) -> Option<String> {
+	response_json
+		.get("choices")?
+		.get(0)?
+		.get("message")?
+		.get("refusal")?
+		.as_str()
+		.filter(|refusal| !refusal.trim().is_empty())
+		.map(|refusal| refusal.to_string())
+}
+
+/// Extract the ordered text and tool call parts of an assistant response
+/// Supports Anthropic content blocks (`text` / `tool_use`) and OpenAI-compatible messages
+/// whose `content` is a string or an array of blocks, followed by `tool_calls`.
+/// Returns None unless the response contains both text and tool calls.
+pub fn extract_content_parts(response_json: &serde_json::Value) -> Option<Vec<ContentPart>> {
+	fn push_block(block: &serde_json::Value, parts: &mut Vec<ContentPart>) {
+		match block.get("type").and_then(|t| t.as_str()) {
+			Some("text") => {
+				if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+					parts.push(ContentPart::Text {
+						text: text.to_string(),
+					});
+				}
+			}
+			Some("tool_use") => {
+				if let (Some(id), Some(name)) = (
+					block.get("id").and_then(|i| i.as_str()),
+					block.get("name").and_then(|n| n.as_str()),
+				) {
+					parts.push(ContentPart::ToolCall {
+						tool_id: id.to_string(),
+						tool_name: name.to_string(),
+					});
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let mut parts: Vec<ContentPart> = Vec::new();
+	if let Some(message) = response_json
+		.get("choices")
+		.and_then(|choices| choices.get(0))
+		.and_then(|choice| choice.get("message"))
+	{
+		// OpenAI-compatible: content (string or blocks) always precedes tool_calls
+		match message.get("content") {
+			Some(serde_json::Value::String(text)) => {
+				parts.push(ContentPart::Text { text: text.clone() })
+			}
+			Some(serde_json::Value::Array(blocks)) => {
+				for block in blocks {
+					push_block(block, &mut parts);
+				}
+			}
+			_ => {}
+		}
+		if let Some(tool_calls) = message.get("tool_calls").and_then(|t| t.as_array()) {
+			for tool_call in tool_calls {
+				let name = tool_call
+					.get("function")
+					.and_then(|f| f.get("name"))
+					.or_else(|| tool_call.get("name"))
+					.and_then(|n| n.as_str());
+				if let (Some(id), Some(name)) = (tool_call.get("id").and_then(|i| i.as_str()), name)
+				{
+					parts.push(ContentPart::ToolCall {
+						tool_id: id.to_string(),
+						tool_name: name.to_string(),
+					});
+				}
+			}
+		}
+	} else if let Some(blocks) = response_json.get("content").and_then(|c| c.as_array()) {
+		// Anthropic content blocks keep the order in which they were generated
+		for block in blocks {
+			push_block(block, &mut parts);
+		}
+	}
+
+	// Drop empty text parts; only mixed responses are worth keeping
+	parts.retain(|part| !matches!(part, ContentPart::Text { text } if text.trim().is_empty()));
+	let has_text = parts.iter().any(|p| matches!(p, ContentPart::Text { .. }));
+	let has_tool_calls = parts
+		.iter()
+		.any(|p| matches!(p, ContentPart::ToolCall { .. }));
+	if has_text && has_tool_calls {
+		Some(parts)
+	} else {
+		None
+	}
 }
 
 /// Trait that all AI providers must implement
@@ -128,11 +349,24 @@ pub trait AiProvider: Send + Sync {
 	/// This is what we can send to the API - the provider handles output limits internally
 	fn get_max_input_tokens(&self, model: &str) -> usize;
 
+	/// Get pricing for a model as (input, output) USD per 1M tokens, None when unknown
+	/// Used for estimates before a request; actual costs come from the response
+	fn get_model_pricing(&self, _model: &str) -> Option<(f64, f64)> {
+		None
+	}
+
 	/// Check if the provider/model supports vision capabilities
 	fn supports_vision(&self, _model: &str) -> bool {
 		// Default implementation - providers can override
 		false
 	}
+
+	/// Check if the provider/model accepts a JSON schema in `response_format`
+	/// Without it, the schema is requested in the system message instead
+	fn supports_structured_output(&self, _model: &str) -> bool {
+		// Default implementation - providers can override
+		false
+	}
 }
 
 /// Provider factory to create the appropriate provider based on model string
@@ -248,4 +482,135 @@ mod tests {
 		let provider = ProviderFactory::create_provider("invalid");
 		assert!(provider.is_err());
 	}
+
+	#[test]
+	fn test_extract_citations() {
+		// OpenAI-compatible annotations
+		let response = serde_json::json!({
+			"choices": [{
+				"message": {
+					"content": "Answer",
+					"annotations": [
+						{"type": "url_citation", "url_citation": {"url": "https://a.example", "title": "A"}},
+						{"type": "url_citation", "url_citation": {"url": "https://a.example", "title": "A"}}
+					]
+				}
+			}],
+			"citations": ["https://b.example"]
+		});
+		let citations = extract_citations(&response).unwrap();
+		assert_eq!(citations.len(), 2);
+		assert_eq!(citations[0].title.as_deref(), Some("A"));
+		assert_eq!(citations[1].url, "https://b.example");
+
+		// Anthropic content block citations
+		let response = serde_json::json!({
+			"content": [{
+				"type": "text",
+				"text": "Answer",
+				"citations": [{"type": "web_search_result_location", "url": "https://c.example", "title": "C"}]
+			}]
+		});
+		let citations = extract_citations(&response).unwrap();
+		assert_eq!(citations[0].url, "https://c.example");
+
+		// No citations
+		assert!(extract_citations(&serde_json::json!({"choices": []})).is_none());
+	}
+
+	#[test]
+	fn test_model_pricing() {
+		let pricing = [("gpt-4o-mini", 0.15, 0.6), ("gpt-4o", 2.5, 10.0)];
+		assert_eq!(
+			model_pricing(&pricing, "gpt-4o-mini-2024-07-18"),
+			Some((0.15, 0.6))
+		);
+		assert_eq!(model_pricing(&pricing, "gpt-4o"), Some((2.5, 10.0)));
+		assert_eq!(model_pricing(&pricing, "o3"), None);
+	}
+
+	#[test]
+	fn test_extract_refusal() {
+		let response = serde_json::json!({
+			"choices": [{"message": {"content": null, "refusal": "I can't help with that."}}]
+		});
+		assert_eq!(
+			extract_refusal(&response).as_deref(),
+			Some("I can't help with that.")
+		);
+
+		let response = serde_json::json!({
+			"choices": [{"message": {"content": "Sure", "refusal": null}}]
+		});
+		assert!(extract_refusal(&response).is_none());
+	}
+
+	#[test]
+	fn test_endpoint_url() {
+		let default_base = "https://api.openai.com/v1";
+		assert_eq!(
+			endpoint_url(None, default_base, "chat/completions"),
+			"https://api.openai.com/v1/chat/completions"
+		);
+		assert_eq!(
+			endpoint_url(
+				Some("https://gateway.corp/openai/"),
+				default_base,
+				"/embeddings"
+			),
+			"https://gateway.corp/openai/embeddings"
+		);
+	}
+
+	#[test]
+	fn test_extract_content_parts() {
+		// Anthropic blocks interleave text and tool use
+		let response = serde_json::json!({
+			"content": [
+				{"type": "text", "text": "Let me check the file."},
+				{"type": "tool_use", "id": "toolu_1", "name": "view", "input": {}},
+				{"type": "text", "text": "And list the directory."},
+				{"type": "tool_use", "id": "toolu_2", "name": "list_files", "input": {}}
+			]
+		});
+		let parts = extract_content_parts(&response).unwrap();
+		assert_eq!(parts.len(), 4);
+		assert_eq!(
+			parts[2],
+			ContentPart::Text {
+				text: "And list the directory.".to_string()
+			}
+		);
+		assert_eq!(
+			parts[3],
+			ContentPart::ToolCall {
+				tool_id: "toolu_2".to_string(),
+				tool_name: "list_files".to_string()
+			}
+		);
+
+		// OpenAI-compatible text followed by tool calls
+		let response = serde_json::json!({
+			"choices": [{
+				"message": {
+					"content": [{"type": "text", "text": "Checking."}],
+					"tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "view", "arguments": "{}"}}]
+				}
+			}]
+		});
+		let parts = extract_content_parts(&response).unwrap();
+		assert_eq!(parts.len(), 2);
+		assert!(matches!(&parts[1], ContentPart::ToolCall { tool_id, .. } if tool_id == "call_1"));
+
+		// Tool calls without any text are not worth keeping
+		let response = serde_json::json!({
+			"choices": [{
+				"message": {
+					"content": "",
+					"tool_calls": [{"id": "call_1", "function": {"name": "view", "arguments": "{}"}}]
+				}
+			}]
+		});
+		assert!(extract_content_parts(&response).is_none());
+	}
 }
@@ -14,6 +14,7 @@
 
 // OpenAI provider implementation
 
+use super::embeddings::EmbeddingResponse;
 use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
 use crate::config::Config;
 use crate::log_debug;
@@ -21,7 +22,6 @@ use crate::session::Message;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
 
 /// OpenAI pricing constants (per 1M tokens in USD)
 /// Source: https://platform.openai.com/docs/pricing (as of January 2025)
@@ -66,14 +66,25 @@ const PRICING: &[(&str, f64, f64)] = &[
 
 /// Calculate cost for OpenAI models with basic pricing
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
+}
+
+/// Embedding model prices (per 1M input tokens in USD)
+const EMBEDDING_PRICING: &[(&str, f64)] = &[
+	("text-embedding-3-small", 0.02),
+	("text-embedding-3-large", 0.13),
+	("text-embedding-ada-002", 0.10),
+];
+
+/// Calculate cost for OpenAI embedding models
+fn calculate_embedding_cost(model: &str, prompt_tokens: u64) -> Option<f64> {
+	EMBEDDING_PRICING
+		.iter()
+		.find(|(pricing_model, _)| model.contains(pricing_model))
+		.map(|(_, price)| (prompt_tokens as f64 / 1_000_000.0) * price)
 }
 
 /// Calculate cost for OpenAI models with cache-aware pricing
@@ -86,21 +97,17 @@ fn calculate_cost_with_cache(
 	cache_read_tokens: u64,
 	completion_tokens: u64,
 ) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			// Regular input tokens at normal price (includes cache write - no additional cost)
-			let regular_input_cost = (regular_input_tokens as f64 / 1_000_000.0) * input_price;
+	let (input_price, output_price) = super::model_pricing(PRICING, model)?;
+	// Regular input tokens at normal price (includes cache write - no additional cost)
+	let regular_input_cost = (regular_input_tokens as f64 / 1_000_000.0) * input_price;
 
-			// Cache read tokens at 0.25x price (75% cheaper)
-			let cache_read_cost = (cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.25;
+	// Cache read tokens at 0.25x price (75% cheaper)
+	let cache_read_cost = (cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.25;
 
-			// Output tokens at normal price (never cached)
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	// Output tokens at normal price (never cached)
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
 
-			return Some(regular_input_cost + cache_read_cost + output_cost);
-		}
-	}
-	None
+	Some(regular_input_cost + cache_read_cost + output_cost)
 }
 
 /// Check if a model supports the temperature parameter
@@ -129,7 +136,7 @@ impl OpenAiProvider {
 
 // Constants
 const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
-const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 
 /// Message format for the OpenAI API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,14 +171,8 @@ impl AiProvider for OpenAiProvider {
 	}
 
 	fn get_api_key(&self, _config: &Config) -> Result<String> {
-		// API keys now only from environment variables for security
-		match env::var(OPENAI_API_KEY_ENV) {
-			Ok(key) => Ok(key),
-			Err(_) => Err(anyhow::anyhow!(
-				"OpenAI API key not found in environment variable: {}",
-				OPENAI_API_KEY_ENV
-			)),
-		}
+		// Environment variable first, then the configured secrets source
+		crate::config::secrets::require_secret(OPENAI_API_KEY_ENV, "OpenAI API key")
 	}
 
 	fn supports_caching(&self, model: &str) -> bool {
@@ -189,6 +190,16 @@ impl AiProvider for OpenAiProvider {
 			|| model.starts_with("gpt-4o-")
 	}
 
+	fn supports_structured_output(&self, model: &str) -> bool {
+		// json_schema response_format: GPT-4o and later, o-series reasoning models
+		model.starts_with("gpt-4o")
+			|| model.starts_with("gpt-4.1")
+			|| model.starts_with("gpt-5")
+			|| model.starts_with("o1")
+			|| model.starts_with("o3")
+			|| model.starts_with("o4")
+	}
+
 	fn get_max_input_tokens(&self, model: &str) -> usize {
 		// OpenAI model context window limits (what we can send as input)
 		// These are the actual context windows - API handles output limits
@@ -216,6 +227,10 @@ impl AiProvider for OpenAiProvider {
 		8_192
 	}
 
+	fn get_model_pricing(&self, model: &str) -> Option<(f64, f64)> {
+		super::model_pricing(PRICING, model)
+	}
+
 	async fn chat_completion(
 		&self,
 		messages: &[Message],
@@ -248,6 +263,14 @@ impl AiProvider for OpenAiProvider {
 			request_body["temperature"] = serde_json::json!(temperature);
 		}
 
+		// Constrain the response to the JSON schema
+		if let Some(schema) = &config.response_schema {
+			if self.supports_structured_output(model) {
+				request_body["response_format"] =
+					crate::session::response_schema::response_format(schema);
+			}
+		}
+
 		// Add tool definitions if MCP has any servers configured
 		if !config.mcp.servers.is_empty() {
 			let functions = crate::mcp::get_available_functions(config).await;
@@ -293,7 +316,11 @@ impl AiProvider for OpenAiProvider {
 
 		// Make the actual API request
 		let response = client
-			.post(OPENAI_API_URL)
+			.post(super::endpoint_url(
+				config.openai.base_url.as_deref(),
+				OPENAI_API_BASE,
+				"chat/completions",
+			))
 			.header("Authorization", format!("Bearer {}", api_key))
 			.header("Content-Type", "application/json")
 			.json(&request_body)
@@ -344,7 +371,11 @@ impl AiProvider for OpenAiProvider {
 			}
 
 			let full_error = error_details.join(" | ");
-			return Err(anyhow::anyhow!("OpenAI API error: {}", full_error));
+			return Err(super::ProviderHttpError::new(
+				status,
+				format!("OpenAI API error: {}", full_error),
+			)
+			.into());
 		}
 
 		// Check for errors in response body even with HTTP 200
@@ -478,6 +509,7 @@ impl AiProvider for OpenAiProvider {
 				output_tokens: completion_tokens,
 				total_tokens,
 				cached_tokens,                      // Simple: total tokens that came from cache
+				cache_write_tokens: 0,              // Cache writes are not billed separately
 				cost,                               // Pre-calculated with proper cache pricing
 				request_time_ms: Some(api_time_ms), // Track API timing for OpenAI
 			})
@@ -485,6 +517,12 @@ impl AiProvider for OpenAiProvider {
 			None
 		};
 
+		// Extract citations/annotations (web search models)
+		let citations = super::extract_citations(&response_json);
+
+		// Refusals come in their own field, with the content left empty
+		let refusal = super::extract_refusal(&response_json);
+
 		// Create exchange record
 		let exchange = ProviderExchange::new(request_body, response_json, usage, self.name());
 
@@ -493,6 +531,117 @@ impl AiProvider for OpenAiProvider {
 			exchange,
 			tool_calls,
 			finish_reason,
+			citations,
+			refusal,
+		})
+	}
+}
+
+// Trait path spelled out: importing it would make AiProvider's name() calls ambiguous
+#[async_trait::async_trait]
+impl super::embeddings::EmbeddingProvider for OpenAiProvider {
+	fn name(&self) -> &str {
+		"openai"
+	}
+
+	async fn embed(
+		&self,
+		texts: &[String],
+		model: &str,
+		config: &Config,
+	) -> Result<EmbeddingResponse> {
+		let api_key = self.get_api_key(config)?;
+		let request_body = serde_json::json!({
+			"model": model,
+			"input": texts,
+		});
+
+		let api_start = std::time::Instant::now();
+		let response = Client::new()
+			.post(super::endpoint_url(
+				config.openai.base_url.as_deref(),
+				OPENAI_API_BASE,
+				"embeddings",
+			))
+			.header("Authorization", format!("Bearer {}", api_key))
+			.header("Content-Type", "application/json")
+			.json(&request_body)
+			.send()
+			.await?;
+		let api_time_ms = api_start.elapsed().as_millis() as u64;
+
+		let status = response.status();
+		let response_text = response.text().await?;
+		let response_json: serde_json::Value =
+			serde_json::from_str(&response_text).map_err(|e| {
+				anyhow::anyhow!(
+					"Failed to parse embeddings response JSON: {}. Response: {}",
+					e,
+					response_text
+				)
+			})?;
+		if !status.is_success() || response_json.get("error").is_some() {
+			let message = response_json
+				.pointer("/error/message")
+				.and_then(|m| m.as_str())
+				.unwrap_or(&response_text);
+			return Err(anyhow::anyhow!(
+				"OpenAI embeddings API error: HTTP {} | Message: {}",
+				status,
+				message
+			));
+		}
+
+		// Entries carry their input index, order them by it
+		let mut data: Vec<&serde_json::Value> = response_json
+			.get("data")
+			.and_then(|d| d.as_array())
+			.ok_or_else(|| anyhow::anyhow!("Embeddings response has no data"))?
+			.iter()
+			.collect();
+		data.sort_by_key(|entry| entry.get("index").and_then(|i| i.as_u64()));
+		let embeddings: Vec<Vec<f32>> = data
+			.iter()
+			.map(|entry| {
+				entry
+					.get("embedding")
+					.and_then(|e| e.as_array())
+					.map(|values| {
+						values
+							.iter()
+							.filter_map(|v| v.as_f64())
+							.map(|v| v as f32)
+							.collect()
+					})
+					.unwrap_or_default()
+			})
+			.collect();
+		if embeddings.len() != texts.len() {
+			return Err(anyhow::anyhow!(
+				"Embeddings response has {} embeddings for {} inputs",
+				embeddings.len(),
+				texts.len()
+			));
+		}
+
+		let prompt_tokens = response_json
+			.pointer("/usage/prompt_tokens")
+			.and_then(|t| t.as_u64())
+			.unwrap_or(0);
+		let usage = TokenUsage {
+			prompt_tokens,
+			output_tokens: 0,
+			total_tokens: prompt_tokens,
+			cached_tokens: 0,
+			cache_write_tokens: 0,
+			cost: calculate_embedding_cost(model, prompt_tokens),
+			request_time_ms: Some(api_time_ms),
+		};
+		let exchange = ProviderExchange::new(request_body, response_json, Some(usage), "openai");
+
+		Ok(EmbeddingResponse {
+			embeddings,
+			exchange,
 		})
 	}
 }
@@ -21,7 +21,6 @@ use crate::session::Message;
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env;
 use std::sync::OnceLock;
 
 // Helper struct to group response processing parameters and reduce function argument count
@@ -67,7 +66,7 @@ impl OpenRouterProvider {
 
 // Constants
 const OPENROUTER_API_KEY_ENV: &str = "OPENROUTER_API_KEY";
-const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const OPENROUTER_API_BASE: &str = "https://openrouter.ai/api/v1";
 
 /// Message format for the OpenRouter API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,14 +99,8 @@ impl AiProvider for OpenRouterProvider {
 	}
 
 	fn get_api_key(&self, _config: &Config) -> Result<String> {
-		// API keys now only from environment variables for security
-		match env::var(OPENROUTER_API_KEY_ENV) {
-			Ok(key) => Ok(key),
-			Err(_) => Err(anyhow::anyhow!(
-				"OpenRouter API key not found in environment variable: {}",
-				OPENROUTER_API_KEY_ENV
-			)),
-		}
+		// Environment variable first, then the configured secrets source
+		crate::config::secrets::require_secret(OPENROUTER_API_KEY_ENV, "OpenRouter API key")
 	}
 
 	fn supports_caching(&self, model: &str) -> bool {
@@ -130,6 +123,11 @@ impl AiProvider for OpenRouterProvider {
 			|| model.contains("pixtral")
 	}
 
+	fn supports_structured_output(&self, model: &str) -> bool {
+		// OpenAI and Gemini models accept json_schema response_format through OpenRouter
+		model.starts_with("openai/") || model.starts_with("google/gemini")
+	}
+
 	fn get_max_input_tokens(&self, model: &str) -> usize {
 		// OpenRouter model input limits depend on underlying provider
 		// Claude models through OpenRouter: 200K total context
@@ -205,10 +203,32 @@ impl AiProvider for OpenRouterProvider {
 					"Google Vertex",
 					"xAI",
 				],
-				"allow_fallbacks": true,
+				"allow_fallbacks": config.openrouter.allow_fallbacks,
 			},
 		});
 
+		// Restrict routing to the configured upstream providers
+		if !config.openrouter.only.is_empty() {
+			request_body["provider"]["only"] = serde_json::json!(config.openrouter.only);
+		}
+
+		// Cost attribution tags, shown on the OpenRouter dashboard and kept in the logged request
+		if !config.attribution.user.is_empty() {
+			request_body["user"] = serde_json::json!(config.attribution.user);
+		}
+		if !config.attribution.metadata.is_empty() {
+			request_body["metadata"] = serde_json::json!(config.attribution.metadata);
+		}
+
+		// Constrain the response to the JSON schema, routed only to upstreams that support it
+		if let Some(schema) = &config.response_schema {
+			if self.supports_structured_output(model) {
+				request_body["response_format"] =
+					crate::session::response_schema::response_format(schema);
+				request_body["provider"]["require_parameters"] = serde_json::json!(true);
+			}
+		}
+
 		// Add tool definitions if MCP has any servers configured
 		if !config.mcp.servers.is_empty() {
 			let functions = crate::mcp::get_available_functions(config).await;
@@ -276,7 +296,11 @@ impl AiProvider for OpenRouterProvider {
 
 		// Create the HTTP request
 		let request_future = client
-			.post(OPENROUTER_API_URL)
+			.post(super::endpoint_url(
+				config.openrouter.base_url.as_deref(),
+				OPENROUTER_API_BASE,
+				"chat/completions",
+			))
 			.header("Authorization", format!("Bearer {}", api_key))
 			.header("Content-Type", "application/json")
 			.header("HTTP-Referer", "https://github.com/muvon/octomind")
@@ -415,7 +439,11 @@ impl OpenRouterProvider {
 				}
 			}
 
-			return Err(anyhow::anyhow!("OpenRouter API error: {}", full_error));
+			return Err(super::ProviderHttpError::new(
+				ctx.status,
+				format!("OpenRouter API error: {}", full_error),
+			)
+			.into());
 		}
 
 		// Enhanced error handling for HTTP 200 responses with errors
@@ -495,10 +523,19 @@ impl OpenRouterProvider {
 			crate::log_debug!("Finish reason: {}", reason);
 		}
 
-		// Extract content
+		// Extract content (plain string, or an array of content blocks for some models)
 		let mut content = String::new();
-		if let Some(text) = message.get("content").and_then(|c| c.as_str()) {
-			content = text.to_string();
+		match message.get("content") {
+			Some(serde_json::Value::String(text)) => content = text.to_string(),
+			Some(serde_json::Value::Array(blocks)) => {
+				content = blocks
+					.iter()
+					.filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"))
+					.filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+					.collect::<Vec<_>>()
+					.join("\n\n");
+			}
+			_ => {}
 		}
 
 		// Extract tool calls
@@ -568,39 +605,16 @@ impl OpenRouterProvider {
 		};
 
 		// Extract token usage
-		let usage: Option<TokenUsage> = if let Some(usage_obj) = ctx.response_json.get("usage") {
-			let prompt_tokens = usage_obj
-				.get("prompt_tokens")
-				.and_then(|v| v.as_u64())
-				.unwrap_or(0);
-			let completion_tokens = usage_obj
-				.get("completion_tokens")
-				.and_then(|v| v.as_u64())
-				.unwrap_or(0);
-			let total_tokens = usage_obj
-				.get("total_tokens")
-				.and_then(|v| v.as_u64())
-				.unwrap_or(0);
-			let cost = usage_obj.get("cost").and_then(|v| v.as_f64());
-
-			// Extract cached tokens from OpenRouter's detailed response
-			let cached_tokens = usage_obj
-				.get("prompt_tokens_details")
-				.and_then(|details| details.get("cached_tokens"))
-				.and_then(|v| v.as_u64())
-				.unwrap_or(0);
-
-			Some(TokenUsage {
-				prompt_tokens,
-				output_tokens: completion_tokens,
-				total_tokens,
-				cached_tokens, // OpenRouter provides cached token information
-				cost,
-				request_time_ms: Some(ctx.api_time_ms),
-			})
-		} else {
-			None
-		};
+		let usage: Option<TokenUsage> = ctx
+			.response_json
+			.get("usage")
+			.map(|usage_obj| parse_usage(usage_obj, ctx.api_time_ms));
+
+		// Extract citations/annotations (web-grounded models)
+		let citations = super::extract_citations(&ctx.response_json);
+
+		// Refusals come in their own field, with the content left empty
+		let refusal = super::extract_refusal(&ctx.response_json);
 
 		// Create exchange record
 		let exchange = ProviderExchange::new(
@@ -615,6 +629,8 @@ impl OpenRouterProvider {
 			exchange,
 			tool_calls,
 			finish_reason,
+			citations,
+			refusal,
 		})
 	}
 }
@@ -876,10 +892,94 @@ fn convert_messages(messages: &[Message], config: &Config) -> Vec<OpenRouterMess
 	result
 }
 
+// Token usage from an OpenRouter "usage" object
+// prompt_tokens includes cache reads and writes; cached tokens are reported at the top level
+// or, for Claude and other cache-capable models, in prompt_tokens_details
+fn parse_usage(usage_obj: &serde_json::Value, api_time_ms: u64) -> TokenUsage {
+	let get = |key: &str| usage_obj.get(key).and_then(|v| v.as_u64());
+	let details = usage_obj.get("prompt_tokens_details");
+	let get_detail = |key: &str| {
+		details
+			.and_then(|details| details.get(key))
+			.and_then(|v| v.as_u64())
+	};
+
+	let prompt_tokens = get("prompt_tokens").unwrap_or(0);
+	let cached_tokens = get("cached_tokens")
+		.or_else(|| get_detail("cached_tokens"))
+		.unwrap_or(0)
+		.min(prompt_tokens);
+
+	// Cache writes are billed as regular input (at a premium, already included in cost)
+	let cache_creation_tokens = get_detail("cache_creation")
+		.or_else(|| get_detail("cache_write_tokens"))
+		.unwrap_or(0);
+	if cached_tokens > 0 || cache_creation_tokens > 0 {
+		log_debug!(
+			"OpenRouter cache: {} read, {} written of {} prompt tokens",
+			cached_tokens,
+			cache_creation_tokens,
+			prompt_tokens
+		);
+	}
+
+	TokenUsage {
+		prompt_tokens,
+		output_tokens: get("completion_tokens").unwrap_or(0),
+		total_tokens: get("total_tokens").unwrap_or(0),
+		cached_tokens,
+		cache_write_tokens: cache_creation_tokens,
+		cost: usage_obj.get("cost").and_then(|v| v.as_f64()),
+		request_time_ms: Some(api_time_ms),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_parse_usage_cache_details() {
+		// Claude via OpenRouter: cache counts only in prompt_tokens_details
+		let usage = parse_usage(
+			&serde_json::json!({
+				"prompt_tokens": 12000,
+				"completion_tokens": 350,
+				"total_tokens": 12350,
+				"cost": 0.0123,
+				"prompt_tokens_details": {
+					"cached_tokens": 10500,
+					"cache_creation": 900
+				},
+				"completion_tokens_details": { "reasoning_tokens": 0 }
+			}),
+			1200,
+		);
+		assert_eq!(usage.prompt_tokens, 12000);
+		assert_eq!(usage.output_tokens, 350);
+		assert_eq!(usage.total_tokens, 12350);
+		assert_eq!(usage.cached_tokens, 10500);
+		assert_eq!(usage.cache_write_tokens, 900);
+		assert_eq!(usage.cost, Some(0.0123));
+		assert_eq!(usage.request_time_ms, Some(1200));
+
+		// A top-level cached_tokens wins over the details
+		let usage = parse_usage(
+			&serde_json::json!({
+				"prompt_tokens": 100,
+				"cached_tokens": 40,
+				"prompt_tokens_details": { "cached_tokens": 80 }
+			}),
+			0,
+		);
+		assert_eq!(usage.cached_tokens, 40);
+
+		// Without cache information nothing is cached
+		let usage = parse_usage(&serde_json::json!({ "prompt_tokens": 100 }), 0);
+		assert_eq!(usage.cached_tokens, 0);
+		assert_eq!(usage.cache_write_tokens, 0);
+	}
+
 	#[test]
 	fn test_supports_vision() {
 		let provider = OpenRouterProvider::new();
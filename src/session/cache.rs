@@ -112,6 +112,24 @@ impl CacheManager {
 		}
 	}
 
+	/// Mark the conversation prefix built by stable_prefix_order (stable_cache_prefix)
+	/// The system message and the last prefix message get markers, so the whole prefix
+	/// is one cached block that later content markers never move
+	pub fn mark_conversation_prefix(
+		&self,
+		messages: &mut [Message],
+		prefix_len: usize,
+		supports_caching: bool,
+	) {
+		if !supports_caching || prefix_len == 0 || prefix_len > messages.len() {
+			return;
+		}
+		if messages[0].role == "system" {
+			messages[0].cached = true;
+		}
+		messages[prefix_len - 1].cached = true;
+	}
+
 	/// Manage user content cache markers using 2-marker system
 	/// Returns true if a marker was added/moved, false otherwise
 	pub fn manage_content_cache_markers(
@@ -391,11 +409,14 @@ impl CacheManager {
 		input_tokens: u64,
 		output_tokens: u64,
 		cached_tokens: u64,
+		cache_write_tokens: u64,
 	) {
 		// Update session totals (lifetime statistics)
 		session.info.input_tokens += input_tokens;
 		session.info.output_tokens += output_tokens;
 		session.info.cached_tokens += cached_tokens;
+		// Cache writes are part of input_tokens, tracked separately for their premium pricing
+		session.info.cache_write_tokens += cache_write_tokens;
 
 		// CRITICAL FIX: For threshold checking, we need to track tokens correctly:
 		// - input_tokens here are the NON-CACHED input tokens processed by the API
@@ -680,6 +701,25 @@ impl CacheManager {
 	}
 }
 
+/// Order of the conversation prefix with stable_cache_prefix: system prompt, custom
+/// instructions (user), welcome (assistant). Ending on the assistant turn keeps the
+/// prefix marker out of reach of the content markers, which only move on user/tool
+/// messages. Empty parts are left out, so the same inputs always give the same prefix
+pub fn stable_prefix_order<'a>(
+	system_prompt: &'a str,
+	instructions: Option<&'a str>,
+	welcome: &'a str,
+) -> Vec<(&'static str, &'a str)> {
+	let mut prefix = vec![("system", system_prompt)];
+	if let Some(instructions) = instructions.filter(|i| !i.trim().is_empty()) {
+		prefix.push(("user", instructions));
+	}
+	if !welcome.trim().is_empty() {
+		prefix.push(("assistant", welcome));
+	}
+	prefix
+}
+
 /// Cache statistics for display and monitoring
 #[derive(Debug, Clone)]
 pub struct CacheStatistics {
@@ -769,6 +809,7 @@ mod tests {
 				input_tokens: 0,
 				output_tokens: 0,
 				cached_tokens: 0,
+				cache_write_tokens: 0,
 				total_cost: 0.0,
 				duration_seconds: 0,
 				layer_stats: Vec::new(),
@@ -776,6 +817,8 @@ mod tests {
 				total_api_time_ms: 0,
 				total_layer_time_ms: 0,
 				total_tool_time_ms: 0,
+				version: 0,
+				exit_summary: None,
 			},
 			messages: Vec::new(),
 			session_file: None,
@@ -865,6 +908,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 				images: None,
+				content_parts: None,
+				model: None,
 			},
 			Message {
 				role: "user".to_string(),
@@ -875,6 +920,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 				images: None,
+				content_parts: None,
+				model: None,
 			},
 		];
 
@@ -885,4 +932,37 @@ mod tests {
 		// User message should not be automatically cached
 		assert!(!messages[1].cached);
 	}
+
+	#[test]
+	fn test_stable_conversation_prefix() {
+		let manager = CacheManager::new();
+		let build = || {
+			let mut session = create_test_session();
+			let prefix = stable_prefix_order("System prompt", Some("Instructions"), "Welcome");
+			for (role, content) in &prefix {
+				session.add_message(role, content);
+			}
+			manager.mark_conversation_prefix(&mut session.messages, prefix.len(), true);
+			session.add_message("user", "First message");
+			session
+				.messages
+				.iter()
+				.map(|m| (m.role.clone(), m.content.clone(), m.cached))
+				.collect::<Vec<_>>()
+		};
+
+		// The same inputs give a byte-identical prefix
+		let first = build();
+		assert_eq!(first, build());
+		let roles: Vec<&str> = first.iter().map(|(role, _, _)| role.as_str()).collect();
+		assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+		let cached: Vec<bool> = first.iter().map(|(_, _, cached)| *cached).collect();
+		assert_eq!(cached, vec![true, false, true, false]);
+
+		// Missing parts are left out instead of leaving empty turns
+		assert_eq!(
+			stable_prefix_order("System prompt", None, "  "),
+			vec![("system", "System prompt")]
+		);
+	}
 }
@@ -24,6 +24,27 @@ use std::sync::Arc;
 // Animation frames for loading indicator
 const LOADING_FRAMES: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
 
+// Pause flag for the tool status line (set while a tool prompts the user)
+static TOOL_STATUS_PAUSED: AtomicBool = AtomicBool::new(false);
+
+// Pause or resume the live tool status line
+pub fn set_tool_status_paused(paused: bool) {
+	TOOL_STATUS_PAUSED.store(paused, Ordering::SeqCst);
+	if paused && std::io::stdout().is_terminal() {
+		// Clear whatever the status line last drew
+		let _ = execute!(
+			stdout(),
+			cursor::MoveToColumn(0),
+			crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+		);
+	}
+}
+
+// Whether a tool is prompting the user and the status line is paused
+pub fn tool_status_paused() -> bool {
+	TOOL_STATUS_PAUSED.load(Ordering::SeqCst)
+}
+
 // Show loading animation while waiting for response
 pub async fn show_loading_animation(cancel_flag: Arc<AtomicBool>, cost: f64) -> Result<()> {
 	let mut stdout = stdout();
@@ -61,6 +82,87 @@ pub async fn show_loading_animation(cancel_flag: Arc<AtomicBool>, cost: f64) ->
 	Ok(())
 }
 
+// Show a live status line for running tools with elapsed time, updated in place
+// Each entry is (tool_name, completed_flag); finished tools drop out of the line
+pub async fn show_tool_status_animation(
+	cancel_flag: Arc<AtomicBool>,
+	tools: Vec<(String, Arc<AtomicBool>)>,
+) -> Result<()> {
+	// Only animate in interactive terminals - piped output stays clean
+	if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+		return Ok(());
+	}
+
+	let mut stdout = stdout();
+	let mut frame_idx = 0;
+	let start = std::time::Instant::now();
+	let max_width = crossterm::terminal::size()
+		.map(|(cols, _)| cols as usize)
+		.unwrap_or(80)
+		.saturating_sub(1);
+
+	while !cancel_flag.load(Ordering::SeqCst) {
+		// Numbered like the tool headers, the number skips the tool (see tool_skip)
+		let running: Vec<String> = tools
+			.iter()
+			.enumerate()
+			.filter(|(_, (_, done))| !done.load(Ordering::SeqCst))
+			.map(|(index, (name, _))| format!("#{} {}", index + 1, name))
+			.collect();
+		if running.is_empty() {
+			break;
+		}
+
+		// Don't draw over interactive prompts from tools
+		if TOOL_STATUS_PAUSED.load(Ordering::SeqCst) {
+			tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+			continue;
+		}
+
+		let elapsed = start.elapsed().as_secs_f64();
+		let status = if tools.len() == 1 {
+			format!(
+				"Running {}... {:.1}s (s+Enter to skip)",
+				tools[0].0, elapsed
+			)
+		} else {
+			format!(
+				"Running {}/{} tools: {} {:.1}s (number+Enter to skip)",
+				running.len(),
+				tools.len(),
+				running.join(", "),
+				elapsed
+			)
+		};
+		let status: String = status.chars().take(max_width.saturating_sub(3)).collect();
+
+		execute!(
+			stdout,
+			cursor::MoveToColumn(0),
+			crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+		)?;
+		print!(
+			" {} {}",
+			LOADING_FRAMES[frame_idx].cyan(),
+			status.bright_blue()
+		);
+		stdout.flush()?;
+
+		frame_idx = (frame_idx + 1) % LOADING_FRAMES.len();
+		tokio::time::sleep(tokio::time::Duration::from_millis(80)).await;
+	}
+
+	// Clear the status line so tool output starts on a clean line
+	execute!(
+		stdout,
+		cursor::MoveToColumn(0),
+		crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+	)?;
+	stdout.flush()?;
+
+	Ok(())
+}
+
 // No-op animation for non-interactive mode (like run command)
 pub async fn show_no_animation(cancel_flag: Arc<AtomicBool>, _cost: f64) -> Result<()> {
 	// Just wait for cancellation without showing any visual animation
@@ -39,3 +39,24 @@ pub fn print_assistant_response(content: &str, config: &Config, _role: &str) {
 		println!("{}", content.bright_green());
 	}
 }
+
+// Print a "Sources:" footer for provider-returned citations
+pub fn print_citations(citations: &[crate::providers::Citation]) {
+	if citations.is_empty() {
+		return;
+	}
+
+	println!();
+	println!("{}", "Sources:".bright_cyan().bold());
+	for (index, citation) in citations.iter().enumerate() {
+		match &citation.title {
+			Some(title) => println!(
+				"  [{}] {} - {}",
+				index + 1,
+				title,
+				citation.url.bright_blue()
+			),
+			None => println!("  [{}] {}", index + 1, citation.url.bright_blue()),
+		}
+	}
+}
@@ -0,0 +1,316 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Auto context - attach snippets of files and symbols mentioned in a user message (auto_context)
+// Saves the tool round-trips the model would otherwise need for "explain this function" asks
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Token budget for all attached snippets of one message
+pub const AUTO_CONTEXT_MAX_TOKENS: usize = 4000;
+/// Lines shown for a whole file, or around a referenced line/symbol
+const MAX_FILE_LINES: usize = 200;
+const SYMBOL_LINES: usize = 40;
+const LINE_CONTEXT: usize = 10;
+/// Upper bound on snippets attached to, and references tried for, a single message
+const MAX_SNIPPETS: usize = 5;
+const MAX_REFERENCES: usize = 10;
+
+/// Something in the message that points at code
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodeReference {
+	/// File path, optionally with a 1-based line range (path:10 or path:10-20)
+	File {
+		path: String,
+		lines: Option<(usize, usize)>,
+	},
+	/// Identifier written as `name`, `name()` or `module::name`
+	Symbol(String),
+}
+
+/// A snippet ready to attach
+#[derive(Debug, Clone)]
+pub struct ContextSnippet {
+	pub label: String,
+	pub content: String,
+}
+
+/// Detect file paths and symbols mentioned in a message
+pub fn detect_references(input: &str) -> Vec<CodeReference> {
+	let mut references = Vec::new();
+
+	for word in input.split_whitespace() {
+		let word = word.trim_matches(|c: char| "`'\"()[]{}<>,;!?".contains(c));
+		let word = word.trim_end_matches(['.', ':']);
+		if let Some(reference) = parse_file_reference(word) {
+			if !references.contains(&reference) {
+				references.push(reference);
+			}
+		}
+	}
+
+	// Backticked identifiers and bare calls like process_response()
+	let backticked = input.split('`').skip(1).step_by(2);
+	let calls = input
+		.split_whitespace()
+		.filter(|w| w.trim_end_matches(['.', ',', '?', ':']).ends_with("()"));
+	for candidate in backticked.chain(calls) {
+		if let Some(symbol) = parse_symbol(candidate) {
+			let reference = CodeReference::Symbol(symbol);
+			if !references.contains(&reference) {
+				references.push(reference);
+			}
+		}
+	}
+
+	references
+}
+
+fn parse_file_reference(word: &str) -> Option<CodeReference> {
+	if word.contains("://") {
+		return None;
+	}
+
+	// Optional :line or :start-end suffix
+	let (path, lines) = match word.rsplit_once(':') {
+		Some((path, range)) if !range.is_empty() => {
+			let (start, end) = range.split_once('-').unwrap_or((range, range));
+			match (start.parse::<usize>(), end.parse::<usize>()) {
+				(Ok(start), Ok(end)) if start > 0 && end >= start => (path, Some((start, end))),
+				_ => return None,
+			}
+		}
+		_ => (word, None),
+	};
+
+	// Needs a file extension: "src/main.rs", "Cargo.toml"
+	let file_name = path.rsplit('/').next().unwrap_or(path);
+	let (stem, extension) = file_name.rsplit_once('.')?;
+	if stem.is_empty()
+		|| extension.len() > 5
+		|| !extension.starts_with(|c: char| c.is_ascii_alphabetic())
+		|| !extension.chars().all(|c| c.is_ascii_alphanumeric())
+	{
+		return None;
+	}
+
+	Some(CodeReference::File {
+		path: path.to_string(),
+		lines,
+	})
+}
+
+fn parse_symbol(candidate: &str) -> Option<String> {
+	let candidate = candidate
+		.trim_end_matches(['.', ',', '?', ':'])
+		.trim_end_matches("()");
+	// module::name and Type.method refer to the last segment
+	let name = candidate
+		.rsplit("::")
+		.next()
+		.and_then(|s| s.rsplit('.').next())
+		.unwrap_or(candidate);
+	let valid = name.len() >= 3
+		&& name
+			.chars()
+			.next()
+			.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+	valid.then(|| name.to_string())
+}
+
+/// Read snippets for the references in a message, within the token budget
+pub fn collect_snippets(input: &str, project_dir: &Path) -> Vec<ContextSnippet> {
+	let mut snippets = Vec::new();
+	let mut used_tokens = 0;
+
+	for reference in detect_references(input).into_iter().take(MAX_REFERENCES) {
+		if snippets.len() >= MAX_SNIPPETS {
+			break;
+		}
+		let snippet = match &reference {
+			CodeReference::File { path, lines } => read_file_snippet(project_dir, path, *lines),
+			CodeReference::Symbol(name) => read_symbol_snippet(project_dir, name),
+		};
+		let Some(mut snippet) = snippet else {
+			continue;
+		};
+
+		let remaining = AUTO_CONTEXT_MAX_TOKENS.saturating_sub(used_tokens);
+		if remaining < 100 {
+			break;
+		}
+		if crate::session::estimate_tokens(&snippet.content) > remaining {
+			snippet.content = crate::session::truncate_to_tokens(&snippet.content, remaining);
+		}
+		used_tokens += crate::session::estimate_tokens(&snippet.content);
+		snippets.push(snippet);
+	}
+
+	snippets
+}
+
+/// Append the snippets to the user message
+pub fn attach_snippets(input: &str, snippets: &[ContextSnippet]) -> String {
+	let mut context = String::new();
+	for snippet in snippets {
+		context.push_str(&format!(
+			"### {}\n```\n{}\n```\n\n",
+			snippet.label,
+			snippet.content.trim_end()
+		));
+	}
+	format!(
+		"{}\n\n==== AUTO CONTEXT ====\n\n{}==== END AUTO CONTEXT ====",
+		input, context
+	)
+}
+
+// Resolve a path inside the project; files outside it are never read
+fn resolve_project_file(project_dir: &Path, path: &str) -> Option<PathBuf> {
+	let project_dir = project_dir.canonicalize().ok()?;
+	let resolved = project_dir.join(path).canonicalize().ok()?;
+	(resolved.starts_with(&project_dir) && resolved.is_file()).then_some(resolved)
+}
+
+fn read_text_file(path: &Path) -> Option<String> {
+	let bytes = std::fs::read(path).ok()?;
+	let sample = &bytes[..bytes.len().min(crate::mcp::fs::binary::BINARY_SAMPLE_SIZE)];
+	if crate::mcp::fs::binary::looks_binary(sample) {
+		return None;
+	}
+	String::from_utf8(bytes).ok()
+}
+
+// Lines start..=end (1-based) of content, clamped to the file
+fn line_range(content: &str, start: usize, end: usize) -> Option<(usize, usize, String)> {
+	let lines: Vec<&str> = content.lines().collect();
+	if lines.is_empty() || start > lines.len() {
+		return None;
+	}
+	let end = end.min(lines.len());
+	Some((start, end, lines[start - 1..end].join("\n")))
+}
+
+fn read_file_snippet(
+	project_dir: &Path,
+	path: &str,
+	lines: Option<(usize, usize)>,
+) -> Option<ContextSnippet> {
+	let resolved = resolve_project_file(project_dir, path)?;
+	let content = read_text_file(&resolved)?;
+
+	let (start, end) = match lines {
+		Some((start, end)) => (
+			start.saturating_sub(LINE_CONTEXT).max(1),
+			end + LINE_CONTEXT,
+		),
+		None => (1, MAX_FILE_LINES),
+	};
+	let (start, end, snippet) = line_range(&content, start, end)?;
+	Some(ContextSnippet {
+		label: format!("{} (lines {}-{})", path, start, end),
+		content: snippet,
+	})
+}
+
+// Find the definition of a symbol with git grep (tracked files only)
+fn read_symbol_snippet(project_dir: &Path, name: &str) -> Option<ContextSnippet> {
+	let pattern = format!(
+		"(fn|struct|enum|trait|type|impl|class|def|function|func|interface|const)[[:space:]]+{}",
+		name
+	);
+	let output = Command::new("git")
+		.args(["grep", "-n", "-w", "-E", "-m", "1", &pattern])
+		.current_dir(project_dir)
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+
+	// First match: path:line:text
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let mut parts = stdout.lines().next()?.splitn(3, ':');
+	let path = parts.next()?.to_string();
+	let line: usize = parts.next()?.parse().ok()?;
+
+	let resolved = resolve_project_file(project_dir, &path)?;
+	let content = read_text_file(&resolved)?;
+	let (start, end, snippet) = line_range(&content, line, line + SYMBOL_LINES - 1)?;
+	Some(ContextSnippet {
+		label: format!("{} in {} (lines {}-{})", name, path, start, end),
+		content: snippet,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_detect_references() {
+		let references = detect_references(
+			"Why does `process_response` fail in src/session/chat/response.rs:120? See also Cargo.toml.",
+		);
+		assert_eq!(
+			references,
+			vec![
+				CodeReference::File {
+					path: "src/session/chat/response.rs".to_string(),
+					lines: Some((120, 120)),
+				},
+				CodeReference::File {
+					path: "Cargo.toml".to_string(),
+					lines: None,
+				},
+				CodeReference::Symbol("process_response".to_string()),
+			]
+		);
+
+		assert_eq!(
+			detect_references("explain config::load_config() and lib.rs:10-20"),
+			vec![
+				CodeReference::File {
+					path: "lib.rs".to_string(),
+					lines: Some((10, 20)),
+				},
+				CodeReference::Symbol("load_config".to_string()),
+			]
+		);
+
+		// Plain prose, URLs and version numbers are not references
+		assert!(detect_references("Please see https://example.com/a.html, v1.2 is out").is_empty());
+	}
+
+	#[test]
+	fn test_file_snippet_stays_in_project() {
+		let dir =
+			std::env::temp_dir().join(format!("octomind-auto-context-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let content: String = (1..=50).map(|i| format!("line {}\n", i)).collect();
+		std::fs::write(dir.join("notes.txt"), &content).unwrap();
+
+		let snippet = read_file_snippet(&dir, "notes.txt", Some((30, 30))).unwrap();
+		assert_eq!(snippet.label, "notes.txt (lines 20-40)");
+		assert!(snippet.content.starts_with("line 20\n"));
+		assert!(snippet.content.ends_with("line 40"));
+
+		assert!(read_file_snippet(&dir, "../outside.txt", None).is_none());
+		assert!(read_file_snippet(&dir, "missing.txt", None).is_none());
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmark runs - one prompt against one model in a fresh in-memory session
+// Uses the same request and response processing path as `octomind run`
+
+use super::response::process_response;
+use super::session::ChatSession;
+use crate::config::Config;
+use crate::session::create_system_prompt;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Measurements for a single prompt/model run
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct BenchRun {
+	pub model: String,
+	pub prompt_index: usize,
+	pub latency_ms: u64,
+	pub api_time_ms: u64,
+	pub tool_time_ms: u64,
+	pub input_tokens: u64,
+	pub output_tokens: u64,
+	pub cached_tokens: u64,
+	pub cost: f64,
+	pub tool_calls: u64,
+	pub response: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+/// Run one prompt against one model, executing tool calls when the config has MCP servers
+/// Failures are recorded in the returned run instead of aborting the benchmark
+pub async fn run_bench_prompt(
+	config: &Config,
+	role: &str,
+	model: &str,
+	prompt: &str,
+	prompt_index: usize,
+	temperature: Option<f32>,
+) -> BenchRun {
+	let started = Instant::now();
+	let mut run = BenchRun {
+		model: model.to_string(),
+		prompt_index,
+		..Default::default()
+	};
+
+	let temperature =
+		temperature.unwrap_or_else(|| config.get_role_config_struct(role).temperature);
+	let mut chat_session = ChatSession::new(
+		format!("bench-{}-{}", prompt_index + 1, sanitize_model(model)),
+		Some(model.to_string()),
+		Some(temperature),
+		config,
+	);
+
+	let current_dir = std::env::current_dir().unwrap_or_default();
+	let system_prompt = create_system_prompt(&current_dir, config, role).await;
+	chat_session.session.add_message("system", &system_prompt);
+	chat_session.session.add_message("user", prompt);
+
+	let operation_cancelled = Arc::new(AtomicBool::new(false));
+	let messages = chat_session.session.messages.clone();
+	let result = match crate::session::chat_completion_with_validation(
+		&messages,
+		model,
+		temperature,
+		config,
+		Some(&mut chat_session),
+		Some(operation_cancelled.clone()),
+	)
+	.await
+	{
+		Ok(response) => {
+			process_response(
+				response.content,
+				response.exchange,
+				response.tool_calls,
+				response.finish_reason,
+				&mut chat_session,
+				config,
+				role,
+				operation_cancelled,
+			)
+			.await
+		}
+		Err(e) => Err(e),
+	};
+
+	let info = &chat_session.session.info;
+	run.latency_ms = started.elapsed().as_millis() as u64;
+	run.api_time_ms = info.total_api_time_ms;
+	run.tool_time_ms = info.total_tool_time_ms;
+	run.input_tokens = info.input_tokens;
+	run.output_tokens = info.output_tokens;
+	run.cached_tokens = info.cached_tokens;
+	run.cost = info.total_cost;
+	run.tool_calls = info.tool_calls;
+	run.response = chat_session.last_response.clone();
+	if let Err(e) = result {
+		run.error = Some(e.to_string());
+	}
+
+	run
+}
+
+// Model names contain provider prefixes and slashes that do not belong in session names
+fn sanitize_model(model: &str) -> String {
+	model
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+		.collect()
+}
@@ -37,8 +37,12 @@ pub const MCP_COMMAND: &str = "/mcp";
 pub const REPORT_COMMAND: &str = "/report";
 pub const IMAGE_COMMAND: &str = "/image";
 pub const CONTEXT_COMMAND: &str = "/context";
+pub const ROLE_COMMAND: &str = "/role";
+pub const RELOAD_PROMPT_COMMAND: &str = "/reload-prompt";
+pub const ESTIMATE_COMMAND: &str = "/estimate";
+pub const SCHEMA_COMMAND: &str = "/schema";
 // List of all available commands for autocomplete
-pub const COMMANDS: [&str; 22] = [
+pub const COMMANDS: [&str; 26] = [
 	HELP_COMMAND,
 	HELP_COMMAND_ALT,
 	EXIT_COMMAND,
@@ -61,4 +65,8 @@ pub const COMMANDS: [&str; 22] = [
 	REPORT_COMMAND,
 	IMAGE_COMMAND,
 	CONTEXT_COMMAND,
+	ROLE_COMMAND,
+	RELOAD_PROMPT_COMMAND,
+	ESTIMATE_COMMAND,
+	SCHEMA_COMMAND,
 ];
@@ -169,10 +169,12 @@ pub async fn perform_context_reduction(
 				"You can now continue the conversation with optimized context".bright_cyan()
 			);
 
-			// Auto-commit with octocode if available
-			if let Err(e) = auto_commit_with_octocode().await {
-				// Don't fail the entire operation if commit fails, just warn
-				println!("{}: {}", "Warning: Auto-commit failed".bright_yellow(), e);
+			// Auto-commit with octocode if available (and the integration is on)
+			if config.octocode_integration {
+				if let Err(e) = auto_commit_with_octocode().await {
+					// Don't fail the entire operation if commit fails, just warn
+					println!("{}: {}", "Warning: Auto-commit failed".bright_yellow(), e);
+				}
 			}
 
 			// Save the updated session
@@ -15,9 +15,9 @@
 // Context truncation functionality to manage token usage
 
 use crate::config::Config;
-use crate::log_conditional;
 use crate::session::chat::session::ChatSession;
 use crate::session::SmartSummarizer;
+use crate::{log_conditional, log_info};
 use anyhow::Result;
 use colored::Colorize;
 use regex::Regex;
@@ -785,6 +785,8 @@ pub async fn perform_smart_truncation(
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		};
 		truncated_messages.push(summary_msg);
 	}
@@ -811,6 +813,278 @@ pub async fn perform_smart_truncation(
 	Ok(())
 }
 
+// Most recent user turns kept verbatim by auto-compaction (the current request is one of them)
+const AUTO_COMPACT_KEEP_TURNS: usize = 2;
+
+// Range of messages folded into a checkpoint: everything after the leading system
+// messages up to the start of the last `keep_turns` user turns. Turns start at a user
+// message, so assistant tool calls always stay together with their tool results
+fn compaction_range(
+	messages: &[crate::session::Message],
+	keep_turns: usize,
+) -> Option<(usize, usize)> {
+	let start = messages.iter().take_while(|m| m.role == "system").count();
+	let end = messages
+		.iter()
+		.enumerate()
+		.rev()
+		.filter(|(_, m)| m.role == "user")
+		.nth(keep_turns.saturating_sub(1))
+		.map(|(i, _)| i)?;
+	(start < end).then_some((start, end))
+}
+
+// Marks a tool result that was replaced by its one-line summary
+const SUMMARIZED_TOOL_RESULT_PREFIX: &str = "[Tool result summarized]";
+
+// Replace tool results older than the last `keep` with a one-line summary, once a later
+// assistant message has consumed them. Returns the number of summarized results
+fn summarize_stale_tool_results(messages: &mut [crate::session::Message], keep: usize) -> usize {
+	// Tool results after the last assistant message are still waiting for the model
+	let Some(last_assistant) = messages.iter().rposition(|m| m.role == "assistant") else {
+		return 0;
+	};
+	let tool_indices: Vec<usize> = messages
+		.iter()
+		.enumerate()
+		.filter(|(_, m)| m.role == "tool")
+		.map(|(i, _)| i)
+		.collect();
+	let stale = tool_indices.len().saturating_sub(keep);
+
+	let mut summarized = 0;
+	for &index in &tool_indices[..stale] {
+		let message = &mut messages[index];
+		if index > last_assistant || message.content.starts_with(SUMMARIZED_TOOL_RESULT_PREFIX) {
+			continue;
+		}
+
+		let summary = format!(
+			"{} {} returned {} lines ({} bytes), call it again if the details are needed",
+			SUMMARIZED_TOOL_RESULT_PREFIX,
+			message.name.as_deref().unwrap_or("tool"),
+			message
+				.content
+				.lines()
+				.filter(|l| !l.trim().is_empty())
+				.count(),
+			message.content.len()
+		);
+		// Short results cost less than their summary
+		if summary.len() >= message.content.len() {
+			continue;
+		}
+		message.content = summary;
+		message.content_parts = None;
+		summarized += 1;
+	}
+	summarized
+}
+
+/// Summarize tool results beyond the tool_result_retention window
+/// Returns true when any tool result was summarized
+pub fn apply_tool_result_retention(chat_session: &mut ChatSession, config: &Config) -> bool {
+	if config.tool_result_retention == 0 {
+		return false;
+	}
+
+	let summarized = summarize_stale_tool_results(
+		&mut chat_session.session.messages,
+		config.tool_result_retention,
+	);
+	if summarized == 0 {
+		return false;
+	}
+
+	log_info!(
+		"Summarized {} older tool results (keeping the last {} in full)",
+		summarized,
+		config.tool_result_retention
+	);
+	true
+}
+
+// Most recent user turns whose tool activity compact_tool_churn leaves verbatim
+const TOOL_CHURN_KEEP_TURNS: usize = 3;
+
+// Tool-calling assistant messages a run needs before it is worth collapsing
+const TOOL_CHURN_MIN_CALL_MESSAGES: usize = 2;
+
+// Marks the summary that replaced a run of tool calls and results
+const TOOL_CHURN_SUMMARY_PREFIX: &str = "[Tool activity summarized]";
+
+// Collapse runs of "assistant calls tool -> tool result -> assistant calls tool" older
+// than the last `keep_turns` user turns into one summary line. The summary is prepended
+// to the assistant message that ends the run, so its outcome stays verbatim; calls and
+// their results are removed together. Returns the number of collapsed runs
+fn compact_tool_churn(messages: &mut Vec<crate::session::Message>, keep_turns: usize) -> usize {
+	let Some((start, end)) = compaction_range(messages, keep_turns) else {
+		return 0;
+	};
+	let is_tool_call =
+		|m: &crate::session::Message| m.role == "assistant" && m.tool_calls.is_some();
+
+	// Runs as (first, last) index ranges of tool calls and results, oldest first
+	let mut runs = Vec::new();
+	let mut index = start;
+	while index < end {
+		if !is_tool_call(&messages[index]) {
+			index += 1;
+			continue;
+		}
+		let run_start = index;
+		while index < end && (is_tool_call(&messages[index]) || messages[index].role == "tool") {
+			index += 1;
+		}
+		let call_messages = messages[run_start..index]
+			.iter()
+			.filter(|m| is_tool_call(m))
+			.count();
+		if call_messages >= TOOL_CHURN_MIN_CALL_MESSAGES {
+			runs.push((run_start, index));
+		}
+	}
+
+	// Back to front, so earlier indices stay valid
+	for &(run_start, run_end) in runs.iter().rev() {
+		let results: Vec<String> = messages[run_start..run_end]
+			.iter()
+			.filter(|m| m.role == "tool")
+			.map(|m| {
+				format!(
+					"{} ({} lines)",
+					m.name.as_deref().unwrap_or("tool"),
+					m.content.lines().filter(|l| !l.trim().is_empty()).count()
+				)
+			})
+			.collect();
+		let summary = format!(
+			"{} {} tool calls: {}",
+			TOOL_CHURN_SUMMARY_PREFIX,
+			results.len(),
+			results.join(", ")
+		);
+
+		// The assistant message after the run holds its outcome
+		match messages.get_mut(run_end) {
+			Some(outcome) if outcome.role == "assistant" => {
+				outcome.content = format!("{}\n\n{}", summary, outcome.content);
+				messages.drain(run_start..run_end);
+			}
+			_ => {
+				let mut summary_message = messages[run_start].clone();
+				summary_message.content = summary;
+				summary_message.tool_calls = None;
+				summary_message.content_parts = None;
+				summary_message.cached = false;
+				messages.splice(run_start..run_end, [summary_message]);
+			}
+		}
+	}
+	runs.len()
+}
+
+/// Collapse older runs of tool calls into summaries when compact_tool_churn is on
+/// Returns true when any run was collapsed
+pub fn apply_tool_churn_compaction(chat_session: &mut ChatSession, config: &Config) -> bool {
+	if !config.compact_tool_churn {
+		return false;
+	}
+
+	let collapsed = compact_tool_churn(&mut chat_session.session.messages, TOOL_CHURN_KEEP_TURNS);
+	if collapsed == 0 {
+		return false;
+	}
+
+	log_info!(
+		"Collapsed {} older runs of tool calls into summaries (keeping the last {} turns verbatim)",
+		collapsed,
+		TOOL_CHURN_KEEP_TURNS
+	);
+	true
+}
+
+/// Summarize older turns into a checkpoint when the context reaches auto_compact_threshold
+/// of the model's context window. Returns true when the conversation was compacted
+pub async fn check_and_compact_context(
+	chat_session: &mut ChatSession,
+	config: &Config,
+) -> Result<bool> {
+	if !config.auto_compact {
+		return Ok(false);
+	}
+
+	let context_window =
+		crate::providers::ProviderFactory::get_provider_for_model(&chat_session.model)
+			.map(|(provider, model)| provider.get_max_input_tokens(&model))
+			.unwrap_or(0);
+	if context_window == 0 {
+		return Ok(false);
+	}
+
+	let current_tokens = crate::session::estimate_message_tokens(&chat_session.session.messages);
+	let usage = current_tokens as f64 / context_window as f64;
+	if usage < config.auto_compact_threshold {
+		return Ok(false);
+	}
+
+	let messages = &chat_session.session.messages;
+	let Some((start, end)) = compaction_range(messages, AUTO_COMPACT_KEEP_TURNS) else {
+		return Ok(false);
+	};
+
+	// System messages are pinned and stay in place; the rest of the range is summarized
+	let (pinned, older): (Vec<_>, Vec<_>) = messages[start..end]
+		.iter()
+		.cloned()
+		.partition(|m| m.role == "system");
+	let summary = SmartSummarizer::new()
+		.summarize_messages(&older)
+		.map_err(|e| anyhow::anyhow!("Auto-compaction failed: {}", e))?;
+
+	let checkpoint = crate::session::Message {
+		role: "assistant".to_string(),
+		content: format!(
+			"--- Checkpoint: summary of {} earlier messages ---\n{}\n--- End Checkpoint ---",
+			older.len(),
+			summary
+		),
+		timestamp: std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		cached: false,
+		tool_call_id: None,
+		name: None,
+		tool_calls: None,
+		images: None,
+		content_parts: None,
+		model: None,
+	};
+
+	let mut compacted = messages[..start].to_vec();
+	compacted.extend(pinned);
+	compacted.push(checkpoint);
+	compacted.extend_from_slice(&messages[end..]);
+	chat_session.session.messages = compacted;
+
+	let new_tokens = crate::session::estimate_message_tokens(&chat_session.session.messages);
+	println!(
+		"{}",
+		format!(
+			"Context at {:.0}% of the model window - compacted {} older messages into a checkpoint ({} → {} tokens)",
+			usage * 100.0,
+			older.len(),
+			current_tokens,
+			new_tokens
+		)
+		.bright_blue()
+	);
+
+	chat_session.save()?;
+	Ok(true)
+}
+
 /// Perform smart full context summarization using external crate
 /// This replaces the entire conversation with an intelligent summary
 pub async fn perform_smart_full_summarization(
@@ -886,6 +1160,8 @@ pub async fn perform_smart_full_summarization(
 		name: None,
 		tool_calls: None,
 		images: None,
+		content_parts: None,
+		model: None,
 	};
 	new_messages.push(summary_msg);
 
@@ -935,6 +1211,8 @@ mod tests {
 			name,
 			tool_calls,
 			images: None,
+			content_parts: None,
+			model: None,
 		}
 	}
 
@@ -1195,4 +1473,165 @@ mod tests {
 		assert_eq!(messages[1].role, "tool");
 		assert_eq!(messages[1].tool_call_id, Some("call_123".to_string()));
 	}
+
+	#[test]
+	fn test_compaction_range_keeps_recent_turns() {
+		let messages = vec![
+			create_test_message("system", "System", None, None, None),
+			create_test_message("user", "First", None, None, None),
+			create_test_message(
+				"assistant",
+				"",
+				Some(json!([{"id": "call_1", "type": "function"}])),
+				None,
+				None,
+			),
+			create_test_message("tool", "Result", None, Some("call_1".to_string()), None),
+			create_test_message("assistant", "Done", None, None, None),
+			create_test_message("user", "Second", None, None, None),
+			create_test_message("assistant", "Ok", None, None, None),
+			create_test_message("user", "Current", None, None, None),
+		];
+
+		// The first turn, including its tool call and result, is folded into the checkpoint
+		assert_eq!(super::compaction_range(&messages, 2), Some((1, 5)));
+		assert_eq!(super::compaction_range(&messages, 1), Some((1, 7)));
+		// Nothing older than the kept turns
+		assert_eq!(super::compaction_range(&messages, 3), None);
+		assert_eq!(super::compaction_range(&messages[..2], 1), None);
+	}
+
+	#[test]
+	fn test_summarize_stale_tool_results() {
+		let long_result = "match\n".repeat(50);
+		let mut messages = vec![
+			create_test_message("user", "Find it", None, None, None),
+			create_test_message(
+				"assistant",
+				"",
+				Some(json!([{"id": "call_1", "type": "function"}])),
+				None,
+				None,
+			),
+			create_test_message(
+				"tool",
+				&long_result,
+				None,
+				Some("call_1".to_string()),
+				Some("search_code".to_string()),
+			),
+			create_test_message("tool", "ok", None, Some("call_2".to_string()), None),
+			create_test_message(
+				"assistant",
+				"",
+				Some(json!([{"id": "call_3", "type": "function"}])),
+				None,
+				None,
+			),
+			create_test_message(
+				"tool",
+				&long_result,
+				None,
+				Some("call_3".to_string()),
+				Some("search_code".to_string()),
+			),
+			create_test_message("assistant", "Done", None, None, None),
+			create_test_message(
+				"tool",
+				&long_result,
+				None,
+				Some("call_4".to_string()),
+				Some("search_code".to_string()),
+			),
+		];
+
+		// Keeping one result leaves the newest untouched and skips the short one
+		assert_eq!(super::summarize_stale_tool_results(&mut messages, 1), 2);
+		assert_eq!(
+			messages[2].content,
+			"[Tool result summarized] search_code returned 50 lines (300 bytes), call it again if the details are needed"
+		);
+		assert_eq!(messages[3].content, "ok");
+		assert!(messages[5]
+			.content
+			.starts_with(super::SUMMARIZED_TOOL_RESULT_PREFIX));
+		assert_eq!(messages[7].content, long_result);
+		assert_eq!(messages[7].tool_call_id, Some("call_4".to_string()));
+
+		// Already summarized results are left alone
+		assert_eq!(super::summarize_stale_tool_results(&mut messages, 1), 0);
+
+		// A result the model has not answered yet is kept even outside the window
+		messages.pop();
+		messages.pop();
+		messages.push(create_test_message(
+			"tool",
+			&long_result,
+			None,
+			Some("call_5".to_string()),
+			None,
+		));
+		assert_eq!(super::summarize_stale_tool_results(&mut messages, 0), 0);
+	}
+
+	#[test]
+	fn test_compact_tool_churn() {
+		let tool_call = |id: &str| {
+			create_test_message(
+				"assistant",
+				"Checking",
+				Some(json!([{"id": id, "type": "function"}])),
+				None,
+				None,
+			)
+		};
+		let tool_result = |id: &str, content: &str| {
+			create_test_message(
+				"tool",
+				content,
+				None,
+				Some(id.to_string()),
+				Some("list_files".to_string()),
+			)
+		};
+		let mut messages = vec![
+			create_test_message("system", "System", None, None, None),
+			create_test_message("user", "First", None, None, None),
+			tool_call("call_1"),
+			tool_result("call_1", "a\nb"),
+			tool_call("call_2"),
+			tool_result("call_2", "c"),
+			create_test_message("assistant", "Found it", None, None, None),
+			create_test_message("user", "Second", None, None, None),
+			tool_call("call_3"),
+			tool_result("call_3", "d"),
+			create_test_message("assistant", "Single call", None, None, None),
+			create_test_message("user", "Current", None, None, None),
+			tool_call("call_4"),
+			tool_result("call_4", "e"),
+			tool_call("call_5"),
+			tool_result("call_5", "f"),
+		];
+
+		// Only the first turn is old and has a run of two tool-calling messages
+		assert_eq!(super::compact_tool_churn(&mut messages, 1), 1);
+		assert_eq!(messages.len(), 12);
+		assert_eq!(messages[2].role, "assistant");
+		assert_eq!(
+			messages[2].content,
+			"[Tool activity summarized] 2 tool calls: list_files (2 lines), list_files (1 lines)\n\nFound it"
+		);
+		assert!(messages[2].tool_calls.is_none());
+		// A single call and the recent turn stay as they are, calls with their results
+		assert_eq!(
+			messages[4].tool_calls,
+			Some(json!([{"id": "call_3", "type": "function"}]))
+		);
+		assert_eq!(messages[5].tool_call_id, Some("call_3".to_string()));
+		assert_eq!(messages[8].role, "assistant");
+		assert_eq!(messages[11].tool_call_id, Some("call_5".to_string()));
+
+		// Nothing left to collapse
+		assert_eq!(super::compact_tool_churn(&mut messages, 1), 0);
+	}
 }
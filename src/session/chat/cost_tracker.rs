@@ -46,6 +46,7 @@ impl CostTracker {
 				regular_prompt_tokens,
 				usage.output_tokens,
 				cached_tokens,
+				usage.cache_write_tokens,
 			);
 
 			// Update cost
@@ -70,6 +71,29 @@ impl CostTracker {
 		Ok(())
 	}
 
+	/// Warn when a single model completion exceeds completion_warning_tokens
+	/// Only for the responses the user reads, not compaction, summaries or layer calls
+	pub fn check_completion_size(usage: &crate::session::TokenUsage, config: &Config) {
+		let threshold = config.completion_warning_tokens;
+		if threshold == 0 || usage.output_tokens <= threshold {
+			return;
+		}
+
+		use colored::Colorize;
+		let cost_info = usage
+			.cost
+			.map(|cost| format!(", request cost ${:.5}", cost))
+			.unwrap_or_default();
+		println!(
+			"{}",
+			format!(
+				"⚠️  Model response used {} output tokens (threshold: {}{})",
+				usage.output_tokens, threshold, cost_info
+			)
+			.bright_yellow()
+		);
+	}
+
 	/// Display session usage statistics
 	pub fn display_session_usage(chat_session: &ChatSession) {
 		use crate::log_info;
@@ -111,6 +135,15 @@ impl CostTracker {
 			);
 		}
 
+		// Cache writes are billed above the regular input price (already included in the cost)
+		let cache_writes = chat_session.session.info.cache_write_tokens;
+		if cache_writes > 0 {
+			log_info!(
+				"cache writes: {} prompt tokens written to cache",
+				cache_writes
+			);
+		}
+
 		// Show cost breakdown
 		Self::display_cost_breakdown(chat_session);
 
@@ -0,0 +1,100 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Custom slash commands ([[custom_commands]]) - a command expands its prompt template
+// into the user message, which is then processed like typed input
+
+use super::commands::COMMANDS;
+use crate::config::Config;
+use std::path::Path;
+
+// Replaced by the text typed after the command name
+const ARGS_PLACEHOLDER: &str = "%{ARGS}";
+
+/// Whether a name (without the slash) is taken by a built-in command
+pub fn is_builtin_command(name: &str) -> bool {
+	COMMANDS
+		.iter()
+		.any(|command| command.strip_prefix('/') == Some(name))
+}
+
+/// Prompt of a custom command input such as "/review src/main.rs", with placeholders
+/// processed. None when the input is not a custom command
+pub async fn expand_custom_command(
+	input: &str,
+	config: &Config,
+	role: &str,
+	project_dir: &Path,
+) -> Option<String> {
+	let (name, args) = split_command(input)?;
+	if is_builtin_command(name) {
+		return None;
+	}
+	let command = config.custom_commands.iter().find(|c| c.name == name)?;
+	let template = crate::session::helper_functions::process_placeholders_async_with_role(
+		&command.template,
+		project_dir,
+		Some(role),
+	)
+	.await;
+	Some(with_args(&template, args))
+}
+
+// Command name without the slash and the text after it
+fn split_command(input: &str) -> Option<(&str, &str)> {
+	let rest = input.trim().strip_prefix('/')?;
+	match rest.split_once(char::is_whitespace) {
+		Some((name, args)) => Some((name, args.trim())),
+		None => Some((rest, "")),
+	}
+}
+
+// %{ARGS} in the template is replaced; without it, the text goes on its own line at the end
+fn with_args(template: &str, args: &str) -> String {
+	if template.contains(ARGS_PLACEHOLDER) {
+		template.replace(ARGS_PLACEHOLDER, args)
+	} else if args.is_empty() {
+		template.to_string()
+	} else {
+		format!("{}\n\n{}", template, args)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_custom_command_arguments() {
+		assert_eq!(split_command("/review"), Some(("review", "")));
+		assert_eq!(
+			split_command("/review  src/main.rs --strict "),
+			Some(("review", "src/main.rs --strict"))
+		);
+		assert_eq!(split_command("review"), None);
+
+		assert_eq!(
+			with_args("Review %{ARGS} for bugs", "src/lib.rs"),
+			"Review src/lib.rs for bugs"
+		);
+		assert_eq!(with_args("Explain this", ""), "Explain this");
+		assert_eq!(
+			with_args("Explain this", "fn main"),
+			"Explain this\n\nfn main"
+		);
+
+		assert!(is_builtin_command("help"));
+		assert!(!is_builtin_command("review"));
+	}
+}
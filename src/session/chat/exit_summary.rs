@@ -0,0 +1,181 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Exit summary - a recap of the session written when it ends (see exit_summary config)
+
+use super::cost_tracker::CostTracker;
+use crate::config::Config;
+use crate::session::chat::session::ChatSession;
+use crate::session::{ExitSummary, Message};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Characters of each message included in the transcript
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+// Transcript size sent for summarization, older messages are left out beyond it
+const MAX_TRANSCRIPT_CHARS: usize = 60_000;
+
+const EXIT_SUMMARY_PROMPT: &str = "You write a short recap of a coding assistant session so the user can quickly pick it up later. In 3 to 6 bullet points cover the goal, what was done (files and key changes), decisions made and what is left to do. Reply with the bullet points only.";
+
+/// Summarize the session with exit_summary_model and store the recap in the session info
+/// Errors are printed and never keep the session from ending
+pub async fn write_exit_summary(chat_session: &mut ChatSession, config: &Config) {
+	if !config.exit_summary {
+		return;
+	}
+	let messages = &chat_session.session.messages;
+	if conversation_message_count(messages) < config.exit_summary_min_messages {
+		return;
+	}
+	// Resumed and ended again without new messages - the last recap still applies
+	if let Some(summary) = &chat_session.session.info.exit_summary {
+		if summary.message_count == messages.len() {
+			return;
+		}
+	}
+
+	println!("{}", "Summarizing the session...".cyan());
+	match request_summary(chat_session, config).await {
+		Ok(text) => {
+			println!("\n{}\n{}\n", "Session summary:".bright_green(), text);
+			chat_session.session.info.exit_summary = Some(ExitSummary {
+				text,
+				created_at: SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.unwrap_or_default()
+					.as_secs(),
+				message_count: chat_session.session.messages.len(),
+			});
+			let _ = chat_session.save();
+		}
+		Err(e) => println!(
+			"{}",
+			format!("Could not summarize the session: {}", e).yellow()
+		),
+	}
+}
+
+async fn request_summary(chat_session: &mut ChatSession, config: &Config) -> Result<String> {
+	let model = if !config.exit_summary_model.is_empty() {
+		config.exit_summary_model.clone()
+	} else if !config.default_layer_model.is_empty() {
+		config.default_layer_model.clone()
+	} else {
+		chat_session.model.clone()
+	};
+
+	// No MCP servers, so no tools are sent with the request
+	let mut clean_config = config.clone();
+	clean_config.mcp.servers.clear();
+
+	let messages = [
+		plain_message("system", EXIT_SUMMARY_PROMPT),
+		plain_message("user", &build_transcript(&chat_session.session.messages)),
+	];
+	let response = crate::session::chat_completion_with_provider(
+		&messages,
+		&model,
+		chat_session.temperature,
+		&clean_config,
+	)
+	.await?;
+	CostTracker::track_exchange_cost(chat_session, &response.exchange, config)?;
+
+	let text = response.content.trim();
+	if text.is_empty() {
+		return Err(anyhow!("{} returned an empty summary", model));
+	}
+	Ok(text.to_string())
+}
+
+// User and assistant messages, what makes a session worth summarizing
+fn conversation_message_count(messages: &[Message]) -> usize {
+	messages
+		.iter()
+		.filter(|m| m.role == "user" || m.role == "assistant")
+		.count()
+}
+
+// Plain text transcript of the user and assistant messages, newest kept within the budget
+fn build_transcript(messages: &[Message]) -> String {
+	let mut entries = Vec::new();
+	let mut total = 0;
+	for message in messages.iter().rev() {
+		if message.role != "user" && message.role != "assistant" {
+			continue;
+		}
+		let content = message.content.trim();
+		if content.is_empty() {
+			continue;
+		}
+		let content = match content.char_indices().nth(MAX_MESSAGE_CHARS) {
+			Some((end, _)) => format!("{} [...]", &content[..end]),
+			None => content.to_string(),
+		};
+		let entry = format!("{}: {}", message.role.to_uppercase(), content);
+		total += entry.len();
+		if total > MAX_TRANSCRIPT_CHARS && !entries.is_empty() {
+			break;
+		}
+		entries.push(entry);
+	}
+	entries.reverse();
+	format!(
+		"Summarize this session transcript:\n\n{}",
+		entries.join("\n\n")
+	)
+}
+
+fn plain_message(role: &str, content: &str) -> Message {
+	Message {
+		role: role.to_string(),
+		content: content.to_string(),
+		timestamp: SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		cached: false,
+		tool_call_id: None,
+		name: None,
+		tool_calls: None,
+		images: None,
+		content_parts: None,
+		model: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_build_transcript() {
+		let messages = vec![
+			plain_message("system", "You are a developer"),
+			plain_message("user", "Fix the parser"),
+			plain_message("assistant", "Done, see src/parser.rs"),
+			plain_message("tool", "file contents"),
+			plain_message("assistant", &"x".repeat(MAX_MESSAGE_CHARS + 10)),
+		];
+		assert_eq!(conversation_message_count(&messages), 3);
+
+		let transcript = build_transcript(&messages);
+		assert!(!transcript.contains("You are a developer"));
+		assert!(!transcript.contains("file contents"));
+		assert!(transcript.contains("USER: Fix the parser\n\nASSISTANT: Done, see src/parser.rs"));
+		assert!(transcript.ends_with(&format!("{} [...]", "x".repeat(MAX_MESSAGE_CHARS))));
+	}
+}
@@ -57,6 +57,9 @@ pub fn remove_function_calls(content: &str) -> String {
 		r#"<(antml:)?function_calls>\s*(.+?)\s*</(antml:)?function_calls>"#,
 		r#"```(json)?\s*\[?\s*\{\s*"tool_name":.+?\}\s*\]?\s*```"#,
 		r#"^\s*\{\s*"tool_name":.+?\}\s*$"#,
+		// content_tool_call_format blocks
+		r#"(?s)<tool_call>.*?</tool_call>"#,
+		r#"(?s)```tool_call[ \t]*\r?\n.*?```"#,
 	];
 
 	let mut result = content.to_string();
@@ -0,0 +1,87 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Interrupt and inject - pause after the current tool/API step to add an instruction
+// Triggered with Ctrl+\ (SIGQUIT) while a response is being processed
+
+use colored::Colorize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INJECT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Request a pause after the current step to inject an instruction
+pub fn request_injection() {
+	INJECT_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Drop any pending injection request (e.g. when back at the input prompt)
+pub fn clear_injection_request() {
+	INJECT_REQUESTED.store(false, Ordering::SeqCst);
+}
+
+/// Consume a pending injection request
+pub fn take_injection_request() -> bool {
+	INJECT_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Listen for Ctrl+\ and turn it into an injection request (Unix only)
+pub fn spawn_inject_listener() {
+	#[cfg(unix)]
+	tokio::spawn(async {
+		use tokio::signal::unix::{signal, SignalKind};
+
+		let mut quit_signal = match signal(SignalKind::quit()) {
+			Ok(quit_signal) => quit_signal,
+			Err(e) => {
+				crate::log_debug!("Failed to register Ctrl+\\ handler: {}", e);
+				return;
+			}
+		};
+
+		while quit_signal.recv().await.is_some() {
+			request_injection();
+			println!(
+				"\n{}",
+				"✋ Will pause after the current step so you can add an instruction..."
+					.bright_cyan()
+			);
+			let _ = std::io::stdout().flush();
+		}
+	});
+}
+
+/// Ask the user for an additional instruction; None when left empty
+pub fn prompt_for_injection() -> Option<String> {
+	println!();
+	println!(
+		"{}",
+		"⏸  Paused. Type an additional instruction (empty to resume without changes):"
+			.bright_cyan()
+	);
+	print!("{} ", ">".bright_cyan());
+	let _ = std::io::stdout().flush();
+
+	let mut input = String::new();
+	std::io::stdin().read_line(&mut input).unwrap_or_default();
+	let input = input.trim();
+
+	if input.is_empty() {
+		println!("{}", "Resuming...".bright_yellow());
+		None
+	} else {
+		println!("{}", "Instruction added, resuming...".bright_green());
+		Some(input.to_string())
+	}
+}
@@ -54,6 +54,8 @@ use crate::log_info;
 // Global mutex for history file operations to prevent race conditions
 lazy_static::lazy_static! {
 	static ref HISTORY_MUTEX: Mutex<()> = Mutex::new(());
+	// Inputs of a --no-save session, kept out of the history file
+	static ref EPHEMERAL_HISTORY: Mutex<Vec<String>> = Mutex::new(Vec::new());
 }
 
 // Get the history file path
@@ -108,6 +110,10 @@ fn decode_history_line(encoded: &str) -> String {
 // Append a single line to history file in thread-safe manner
 // Encodes newlines to preserve multiline entries as single history records
 fn append_to_history_file(line: &str) -> Result<()> {
+	if crate::session::is_ephemeral() {
+		EPHEMERAL_HISTORY.lock().unwrap().push(line.to_string());
+		return Ok(());
+	}
 	let _lock = HISTORY_MUTEX.lock().unwrap();
 	let history_path = get_history_file_path()?;
 
@@ -160,8 +166,45 @@ fn load_history_from_file() -> Result<Vec<String>> {
 	Ok(history)
 }
 
+/// Live session status shown in the prompt (prompt_status)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PromptStatus {
+	pub total_cost: f64,
+	/// Input plus output tokens used by the session so far
+	pub total_tokens: u64,
+	/// Estimated tokens of the current conversation
+	pub context_tokens: usize,
+	/// Context window of the session model (0 when unknown)
+	pub context_window: usize,
+}
+
+impl PromptStatus {
+	/// Current context as a percentage of the model window
+	pub fn context_percent(&self) -> Option<f64> {
+		(self.context_window > 0)
+			.then(|| self.context_tokens as f64 * 100.0 / self.context_window as f64)
+	}
+
+	/// Plain status text, e.g. "$0.12 | 45K tok | ctx 38%"
+	pub fn label(&self) -> String {
+		let mut label = format!(
+			"${:.2} | {} tok",
+			self.total_cost,
+			super::format_number(self.total_tokens)
+		);
+		match self.context_percent() {
+			Some(percent) => label.push_str(&format!(" | ctx {:.0}%", percent)),
+			None => label.push_str(&format!(
+				" | ctx {}",
+				super::format_number(self.context_tokens as u64)
+			)),
+		}
+		label
+	}
+}
+
 // Read user input with support for multiline input, command completion, and persistent history
-pub fn read_user_input(estimated_cost: f64) -> Result<String> {
+pub fn read_user_input(estimated_cost: f64, status: Option<PromptStatus>) -> Result<String> {
 	// Configure rustyline with proper completion behavior for file completion
 	let config = RustylineConfig::builder()
 		.completion_type(CompletionType::Circular) // Cycle through completions inline, no menu
@@ -230,15 +273,32 @@ pub fn read_user_input(estimated_cost: f64) -> Result<String> {
 			log_info!("Could not load history: {}", e);
 		}
 	}
+	for line in EPHEMERAL_HISTORY.lock().unwrap().iter() {
+		let _ = editor.add_history_entry(line.clone());
+	}
 
 	// Set prompt with colors if terminal supports them and include cost estimation
-	let prompt = if estimated_cost > 0.0 {
+	// With prompt_status the prompt turns yellow/red as the context fills up
+	let prompt = if let Some(status) = status {
+		let prompt = format!("[{}] > ", status.label());
+		match status.context_percent() {
+			Some(percent) if percent >= 80.0 => prompt.bright_red().to_string(),
+			Some(percent) if percent >= 50.0 => prompt.bright_yellow().to_string(),
+			_ => prompt.bright_blue().to_string(),
+		}
+	} else if estimated_cost > 0.0 {
 		format!("[~${:.2}] > ", estimated_cost)
 			.bright_blue()
 			.to_string()
 	} else {
 		"> ".bright_blue().to_string()
 	};
+	// A --no-save session is marked on every prompt
+	let prompt = if crate::session::is_ephemeral() {
+		format!("{}{}", "[no-save] ".dimmed(), prompt)
+	} else {
+		prompt
+	};
 
 	// Read line with command completion and history search (Ctrl+R)
 	match editor.readline(&prompt) {
@@ -289,6 +349,25 @@ mod tests {
 	use super::*;
 	use std::io::Write;
 
+	#[test]
+	fn test_prompt_status_label() {
+		let status = PromptStatus {
+			total_cost: 0.1234,
+			total_tokens: 45_300,
+			context_tokens: 76_000,
+			context_window: 200_000,
+		};
+		assert_eq!(status.label(), "$0.12 | 45K tok | ctx 38%");
+
+		// Unknown context window shows the token estimate instead
+		let status = PromptStatus {
+			context_window: 0,
+			..status
+		};
+		assert_eq!(status.context_percent(), None);
+		assert_eq!(status.label(), "$0.12 | 45K tok | ctx 76K");
+	}
+
 	#[test]
 	fn test_multiline_history_encoding_decoding() {
 		// Test data with various edge cases
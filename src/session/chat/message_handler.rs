@@ -50,6 +50,7 @@ impl MessageHandler {
 	) -> Result<()> {
 		// Extract the original tool_calls from the exchange response based on provider
 		let original_tool_calls = Self::extract_original_tool_calls(exchange);
+		let content_parts = crate::providers::extract_content_parts(&exchange.response);
 
 		// Create the assistant message directly with tool_calls preserved from the exchange
 		let assistant_message = crate::session::Message {
@@ -64,6 +65,8 @@ impl MessageHandler {
 			name: None,
 			tool_calls: original_tool_calls, // Store the original tool_calls for proper reconstruction
 			images: None,
+			content_parts,
+			model: Some(chat_session.model.clone()),
 		};
 
 		// Add the assistant message to the session
@@ -15,27 +15,36 @@
 // Chat session module
 mod animation;
 pub mod assistant_output;
+mod auto_context;
+pub mod bench;
 mod command_executor;
 mod commands;
 mod context_reduction;
 mod context_truncation;
 mod cost_tracker;
+pub mod custom_commands;
+mod exit_summary;
 pub mod formatting;
+pub mod inject;
 mod input;
 mod layered_response;
 pub mod markdown;
 mod message_handler;
+mod prompt_reload;
 pub mod response;
+pub mod retry_budget;
 pub mod session;
 mod syntax;
 mod tool_error_tracker;
 mod tool_processor;
+pub mod tool_skip;
 
 // Re-export main structures and functions
 pub use animation::{
-	show_generation_message_static, show_loading_animation, show_no_animation, show_smart_animation,
+	set_tool_status_paused, show_generation_message_static, show_loading_animation,
+	show_no_animation, show_smart_animation,
 };
-pub use assistant_output::print_assistant_response;
+pub use assistant_output::{print_assistant_response, print_citations};
 pub use command_executor::{
 	command_exists, execute_command_layer, get_command_help, list_available_commands,
 };
@@ -49,13 +58,14 @@ pub use context_truncation::{
 };
 pub use cost_tracker::CostTracker;
 pub use formatting::{format_duration, remove_function_calls};
-pub use input::read_user_input;
+pub use input::{read_user_input, PromptStatus};
 pub use layered_response::process_layered_response;
 pub use markdown::{is_markdown_content, MarkdownRenderer, MarkdownTheme};
 pub use message_handler::MessageHandler;
 pub use response::process_response;
 pub use session::{
 	format_number, run_interactive_session, run_interactive_session_with_input, ChatSession,
+	RunOutcome, RunStatus,
 };
 pub use tool_processor::ToolProcessor;
 
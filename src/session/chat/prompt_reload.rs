@@ -0,0 +1,161 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Prompt reload - pick up edits to the instructions file and prompt sources mid-session
+// Used by /reload-prompt and, with auto_reload_prompt, before each user message
+
+use super::session::ChatSession;
+use crate::config::Config;
+use crate::session::create_system_prompt;
+use crate::session::helper_functions::process_placeholders_async_with_role;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Tracks modification times of the files the session prompts are built from
+pub struct PromptWatcher {
+	files: Vec<(PathBuf, Option<SystemTime>)>,
+	instructions_path: Option<PathBuf>,
+	// Processed instructions currently in the conversation (used to locate the message)
+	instructions_content: Option<String>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+	std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+async fn read_instructions(path: &Path, project_dir: &Path, role: &str) -> Option<String> {
+	let content = std::fs::read_to_string(path).ok()?;
+	Some(process_placeholders_async_with_role(&content, project_dir, Some(role)).await)
+}
+
+impl PromptWatcher {
+	/// Start watching the custom instructions file, the README used by prompt placeholders
+	/// and the role's system_file
+	pub async fn new(
+		chat_session: &ChatSession,
+		project_dir: &Path,
+		config: &Config,
+		role: &str,
+	) -> Self {
+		let mut files = Vec::new();
+
+		let instructions_path = if config.custom_instructions_file_name.is_empty() {
+			None
+		} else {
+			Some(project_dir.join(&config.custom_instructions_file_name))
+		};
+		if let Some(path) = &instructions_path {
+			files.push((path.clone(), modified_time(path)));
+		}
+
+		let readme_path = project_dir.join("README.md");
+		files.push((readme_path.clone(), modified_time(&readme_path)));
+
+		if let Some(system_file) = &config.get_role_config_struct(role).system_file {
+			if let Ok(path) = config.resolve_config_relative_path(system_file) {
+				let mtime = modified_time(&path);
+				files.push((path, mtime));
+			}
+		}
+
+		// Only track instructions that are actually part of the conversation
+		let instructions_content = match &instructions_path {
+			Some(path) => read_instructions(path, project_dir, role)
+				.await
+				.filter(|content| {
+					chat_session
+						.session
+						.messages
+						.iter()
+						.any(|m| m.role == "user" && &m.content == content)
+				}),
+			None => None,
+		};
+
+		Self {
+			files,
+			instructions_path,
+			instructions_content,
+		}
+	}
+
+	/// Files changed since the last check or reload
+	pub fn changed_files(&self) -> Vec<&Path> {
+		self.files
+			.iter()
+			.filter(|(path, mtime)| modified_time(path) != *mtime)
+			.map(|(path, _)| path.as_path())
+			.collect()
+	}
+
+	/// Rebuild the system prompt and refresh the instructions message
+	/// Returns a short description of what was updated
+	pub async fn reload(
+		&mut self,
+		chat_session: &mut ChatSession,
+		project_dir: &Path,
+		config: &Config,
+		role: &str,
+	) -> Result<Vec<String>> {
+		let mut updated = Vec::new();
+
+		let system_prompt = create_system_prompt(project_dir, config, role).await;
+		if let Some(system_msg) = chat_session
+			.session
+			.messages
+			.iter_mut()
+			.find(|m| m.role == "system")
+		{
+			if system_msg.content != system_prompt {
+				system_msg.content = system_prompt;
+				updated.push("system prompt".to_string());
+			}
+		}
+
+		if let (Some(path), Some(old_content)) =
+			(&self.instructions_path, self.instructions_content.clone())
+		{
+			if let Some(new_content) = read_instructions(path, project_dir, role).await {
+				if new_content != old_content {
+					if let Some(msg) = chat_session
+						.session
+						.messages
+						.iter_mut()
+						.find(|m| m.role == "user" && m.content == old_content)
+					{
+						msg.content = new_content.clone();
+						self.instructions_content = Some(new_content);
+						updated.push(
+							path.file_name()
+								.unwrap_or_default()
+								.to_string_lossy()
+								.to_string(),
+						);
+					}
+				}
+			}
+		}
+
+		// Remember the current state so unchanged files do not trigger again
+		for (path, mtime) in self.files.iter_mut() {
+			*mtime = modified_time(path);
+		}
+
+		if !updated.is_empty() {
+			chat_session.save()?;
+		}
+		Ok(updated)
+	}
+}
@@ -14,16 +14,17 @@
 
 // Response processing module - main orchestrator
 
+mod result_similarity;
 pub mod tool_execution;
 mod tool_result_processor;
 
 use super::{CostTracker, MessageHandler, ToolProcessor};
 use crate::config::Config;
 use crate::log_debug;
-use crate::session::chat::assistant_output::print_assistant_response;
+use crate::session::chat::assistant_output::{print_assistant_response, print_citations};
 use crate::session::chat::formatting::remove_function_calls;
 use crate::session::chat::session::ChatSession;
-use crate::session::ProviderExchange;
+use crate::session::{ContentPart, ProviderExchange};
 use anyhow::Result;
 use colored::Colorize;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -43,17 +44,130 @@ fn log_response_debug(
 	}
 }
 
+// Check if a finish_reason means the completion was cut off by the output limit
+fn is_length_finish(finish_reason: Option<&str>) -> bool {
+	matches!(
+		finish_reason.map(|r| r.to_lowercase()).as_deref(),
+		Some("length") | Some("max_tokens")
+	)
+}
+
+// Build a transient message used only for continuation requests
+fn continuation_message(role: &str, content: &str) -> crate::session::Message {
+	crate::session::Message {
+		role: role.to_string(),
+		content: content.to_string(),
+		timestamp: std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		cached: false,
+		tool_call_id: None,
+		name: None,
+		tool_calls: None,
+		images: None,
+		content_parts: None,
+		model: None,
+	}
+}
+
+// Request continuations for a response cut off by the output limit and stitch them together
+// Continuation requests are not stored in the session; their cost is tracked
+async fn continue_truncated_response(
+	partial: String,
+	chat_session: &mut ChatSession,
+	config: &Config,
+	operation_cancelled: Arc<AtomicBool>,
+) -> String {
+	let mut stitched = partial;
+
+	// A continuation is only a fragment of the response, not checked against the response schema
+	let without_schema = config.response_schema.is_some().then(|| {
+		let mut config = config.clone();
+		config.response_schema = None;
+		config
+	});
+	let request_config = without_schema.as_ref().unwrap_or(config);
+
+	for attempt in 1..=config.auto_continue_on_length {
+		if operation_cancelled.load(Ordering::SeqCst) {
+			break;
+		}
+
+		println!(
+			"{}",
+			format!(
+				"⚠️  Response cut off by length limit - continuing ({}/{})...",
+				attempt, config.auto_continue_on_length
+			)
+			.yellow()
+		);
+
+		let mut messages = chat_session.session.messages.clone();
+		messages.push(continuation_message("assistant", &stitched));
+		messages.push(continuation_message(
+			"user",
+			"Continue exactly where you left off. Do not repeat anything already written.",
+		));
+
+		let response = match crate::session::chat_completion_with_validation(
+			&messages,
+			&chat_session.model,
+			chat_session.temperature,
+			request_config,
+			None,
+			Some(operation_cancelled.clone()),
+		)
+		.await
+		{
+			Ok(response) => response,
+			Err(e) => {
+				log_debug!("Auto-continue request failed: {}", e);
+				break;
+			}
+		};
+
+		// Track the extra cost of the continuation request
+		let _ = CostTracker::track_exchange_cost(chat_session, &response.exchange, config);
+
+		stitched.push_str(&response.content);
+
+		if !is_length_finish(response.finish_reason.as_deref()) {
+			break;
+		}
+	}
+
+	stitched
+}
+
 // Helper function to handle final response when no tool calls are present
-fn handle_final_response(
+#[allow(clippy::too_many_arguments)]
+async fn handle_final_response(
 	content: &str,
 	current_content: &str,
 	current_exchange: ProviderExchange,
+	finish_reason: Option<&str>,
 	chat_session: &mut ChatSession,
 	config: &Config,
 	role: &str,
+	operation_cancelled: Arc<AtomicBool>,
 ) -> Result<()> {
 	// Remove any function_calls blocks if they exist but weren't processed earlier
-	let clean_content = remove_function_calls(current_content);
+	let mut clean_content = remove_function_calls(current_content);
+
+	// Recover responses cut off by the output limit when auto-continue is enabled
+	if config.auto_continue_on_length > 0 && is_length_finish(finish_reason) {
+		clean_content =
+			continue_truncated_response(clean_content, chat_session, config, operation_cancelled)
+				.await;
+	}
+
+	// Extract citations before the exchange is consumed
+	let citations = if config.show_citations {
+		crate::providers::extract_citations(&current_exchange.response)
+	} else {
+		None
+	};
 
 	// When adding the final assistant message for a response that involved tool calls,
 	// we've already tracked the cost and tokens in the loop above, so we pass None for exchange
@@ -73,6 +187,11 @@ fn handle_final_response(
 	// Print assistant response with color
 	print_assistant_response(&clean_content, config, role);
 
+	// Render sources footer if the provider returned citations
+	if let Some(ref citations) = citations {
+		print_citations(citations);
+	}
+
 	// Display cumulative token usage using CostTracker
 	CostTracker::display_session_usage(chat_session);
 
@@ -91,40 +210,8 @@ async fn display_tool_parameters_only(config: &Config, tool_calls: &[crate::mcp:
 		// Always log debug info if debug enabled
 		log_debug!("Found {} tool calls in response", tool_calls.len());
 
-		let is_single_tool = tool_calls.len() == 1;
-
-		// Show headers upfront - with indices for multiple tools, without for single tool
 		for (index, call) in tool_calls.iter().enumerate() {
-			let tool_index = index + 1;
-
-			// Get server name using same logic as execution
-			let server_name = get_tool_server_name_async(&call.tool_name, config).await;
-
-			// Create formatted header - with or without index based on tool count
-			let title = if is_single_tool {
-				format!(
-					" {} | {} ",
-					call.tool_name.bright_cyan(),
-					server_name.bright_blue()
-				)
-			} else {
-				format!(
-					" [{}] {} | {} ",
-					tool_index,
-					call.tool_name.bright_cyan(),
-					server_name.bright_blue()
-				)
-			};
-			let separator_length = 70.max(title.len() + 4);
-			let dashes = "─".repeat(separator_length - title.len());
-			let separator = format!("──{}{}──", title, dashes.dimmed());
-			println!("{}", separator);
-
-			// Show parameters based on log level
-			if config.get_log_level().is_debug_enabled() || config.get_log_level().is_info_enabled()
-			{
-				display_tool_parameters_full(call, config);
-			}
+			display_tool_header(config, call, index, tool_calls.len()).await;
 
 			// Add spacing between tools (except for the last one)
 			if index < tool_calls.len() - 1 {
@@ -137,11 +224,96 @@ async fn display_tool_parameters_only(config: &Config, tool_calls: &[crate::mcp:
 	}
 }
 
+// Display a single tool header with its parameters
+async fn display_tool_header(
+	config: &Config,
+	call: &crate::mcp::McpToolCall,
+	index: usize,
+	total: usize,
+) {
+	// Get server name using same logic as execution
+	let server_name = get_tool_server_name_async(&call.tool_name, config).await;
+
+	// Create formatted header - with index for multiple tools, without for a single tool
+	let title = if total == 1 {
+		format!(
+			" {} | {} ",
+			call.tool_name.bright_cyan(),
+			server_name.bright_blue()
+		)
+	} else {
+		format!(
+			" [{}] {} | {} ",
+			index + 1,
+			call.tool_name.bright_cyan(),
+			server_name.bright_blue()
+		)
+	};
+	let separator_length = 70.max(title.len() + 4);
+	let dashes = "─".repeat(separator_length - title.len());
+	let separator = format!("──{}{}──", title, dashes.dimmed());
+	println!("{}", separator);
+
+	// Show parameters based on log level
+	if config.get_log_level().is_debug_enabled() || config.get_log_level().is_info_enabled() {
+		display_tool_parameters_full(call, config);
+	}
+}
+
+// Display text and tool headers in the order the model produced them
+// Returns false (nothing printed) when the parts do not line up with the resolved tool calls
+async fn display_interleaved_response(
+	parts: &[ContentPart],
+	tool_calls: &[crate::mcp::McpToolCall],
+	config: &Config,
+	role: &str,
+) -> bool {
+	let part_calls: Vec<&str> = parts
+		.iter()
+		.filter_map(|part| match part {
+			ContentPart::ToolCall { tool_id, .. } => Some(tool_id.as_str()),
+			ContentPart::Text { .. } => None,
+		})
+		.collect();
+	let matches_calls = part_calls.len() == tool_calls.len()
+		&& part_calls
+			.iter()
+			.all(|id| tool_calls.iter().any(|call| call.tool_id == *id));
+	if !matches_calls {
+		return false;
+	}
+
+	log_debug!("Found {} tool calls in response", tool_calls.len());
+
+	for part in parts {
+		match part {
+			ContentPart::Text { text } => {
+				let clean_text = remove_function_calls(text);
+				print_assistant_response(&clean_text, config, role);
+			}
+			ContentPart::ToolCall { tool_id, .. } => {
+				if let Some((index, call)) = tool_calls
+					.iter()
+					.enumerate()
+					.find(|(_, call)| call.tool_id == *tool_id)
+				{
+					display_tool_header(config, call, index, tool_calls.len()).await;
+					println!();
+				}
+			}
+		}
+	}
+
+	true
+}
+
 // Display tool parameters in full detail (for info/debug modes)
+// Long string values are elided per tool_param_preview_len (display only, the tool gets full params)
 pub fn display_tool_parameters_full(tool_call: &crate::mcp::McpToolCall, config: &Config) {
-	if let Ok(params_obj) = serde_json::from_value::<serde_json::Map<String, serde_json::Value>>(
-		tool_call.parameters.clone(),
-	) {
+	let parameters = elide_long_strings(&tool_call.parameters, config.tool_param_preview_len);
+	if let Ok(params_obj) =
+		serde_json::from_value::<serde_json::Map<String, serde_json::Value>>(parameters.clone())
+	{
 		if !params_obj.is_empty() {
 			// Find the longest key for column alignment (max 20 chars to prevent excessive spacing)
 			let max_key_length = params_obj
@@ -170,7 +342,7 @@ pub fn display_tool_parameters_full(tool_call: &crate::mcp::McpToolCall, config:
 		}
 	} else {
 		// Fallback for non-object parameters (arrays, primitives, etc.)
-		let params_str = serde_json::to_string(&tool_call.parameters).unwrap_or_default();
+		let params_str = serde_json::to_string(&parameters).unwrap_or_default();
 		if params_str != "null" {
 			if config.get_log_level().is_debug_enabled() {
 				// Debug mode: Show full params
@@ -186,6 +358,35 @@ pub fn display_tool_parameters_full(tool_call: &crate::mcp::McpToolCall, config:
 	}
 }
 
+// Elide string values longer than max_chars as "<prefix>[... N chars]" (0 = keep full values)
+fn elide_long_strings(value: &serde_json::Value, max_chars: usize) -> serde_json::Value {
+	if max_chars == 0 {
+		return value.clone();
+	}
+	match value {
+		serde_json::Value::String(s) => {
+			let total = s.chars().count();
+			if total > max_chars {
+				let prefix: String = s.chars().take(max_chars).collect();
+				serde_json::Value::String(format!("{}[... {} chars]", prefix, total - max_chars))
+			} else {
+				value.clone()
+			}
+		}
+		serde_json::Value::Array(arr) => serde_json::Value::Array(
+			arr.iter()
+				.map(|item| elide_long_strings(item, max_chars))
+				.collect(),
+		),
+		serde_json::Value::Object(obj) => serde_json::Value::Object(
+			obj.iter()
+				.map(|(k, v)| (k.clone(), elide_long_strings(v, max_chars)))
+				.collect(),
+		),
+		_ => value.clone(),
+	}
+}
+
 // Format a parameter value for smart display (info mode)
 fn format_parameter_value_smart(value: &serde_json::Value) -> String {
 	match value {
@@ -274,17 +475,18 @@ fn format_parameter_value_full(value: &serde_json::Value) -> String {
 fn resolve_tool_calls(
 	current_tool_calls_param: &mut Option<Vec<crate::mcp::McpToolCall>>,
 	current_content: &str,
+	format: crate::config::ContentToolCallFormat,
 ) -> Vec<crate::mcp::McpToolCall> {
 	if let Some(calls) = current_tool_calls_param.take() {
 		// Use the tool calls from the API response only once
 		if !calls.is_empty() {
 			calls
 		} else {
-			crate::mcp::parse_tool_calls(current_content) // Fallback
+			crate::mcp::parse_tool_calls(current_content, format) // Fallback
 		}
 	} else {
 		// For follow-up iterations, parse from content if any new tool calls exist
-		crate::mcp::parse_tool_calls(current_content)
+		crate::mcp::parse_tool_calls(current_content, format)
 	}
 }
 
@@ -311,6 +513,8 @@ fn add_assistant_message_with_tool_calls(
 
 	// Extract the original tool_calls from the exchange response based on provider
 	let original_tool_calls = MessageHandler::extract_original_tool_calls(current_exchange);
+	// Keep the text/tool call order so the display can show what preceded each call
+	let content_parts = crate::providers::extract_content_parts(&current_exchange.response);
 
 	// Create the assistant message directly with tool_calls preserved from the exchange
 	let assistant_message = crate::session::Message {
@@ -325,6 +529,8 @@ fn add_assistant_message_with_tool_calls(
 		name: None,
 		tool_calls: original_tool_calls, // Store the original tool_calls for proper reconstruction
 		images: None,
+		content_parts,
+		model: Some(chat_session.model.clone()),
 	};
 
 	// Add the assistant message to the session
@@ -379,6 +585,7 @@ pub async fn process_response(
 	let mut current_content = content.clone();
 	let mut current_exchange = exchange;
 	let mut current_tool_calls_param = tool_calls.clone(); // Track the tool_calls parameter
+	let mut current_finish_reason = finish_reason;
 
 	loop {
 		// Check for cancellation at the start of each loop iteration
@@ -387,8 +594,11 @@ pub async fn process_response(
 		// Check for tool calls if MCP has any servers configured
 		if !config.mcp.servers.is_empty() {
 			// Resolve current tool calls for this iteration
-			let current_tool_calls =
-				resolve_tool_calls(&mut current_tool_calls_param, &current_content);
+			let current_tool_calls = resolve_tool_calls(
+				&mut current_tool_calls_param,
+				&current_content,
+				config.content_tool_call_format,
+			);
 
 			if !current_tool_calls.is_empty() {
 				// Add assistant message with tool calls preserved
@@ -400,12 +610,28 @@ pub async fn process_response(
 					role,
 				)?;
 
-				// Display the clean content (without function calls) to the user FIRST
-				let clean_content = remove_function_calls(&current_content);
-				print_assistant_response(&clean_content, config, role);
+				// Show text interleaved with tool headers when the response kept its order
+				let content_parts = chat_session
+					.session
+					.messages
+					.last()
+					.and_then(|msg| msg.content_parts.clone());
+				let interleaved = match content_parts {
+					Some(parts) => {
+						display_interleaved_response(&parts, &current_tool_calls, config, role)
+							.await
+					}
+					None => false,
+				};
 
-				// Display tool parameters upfront (headers will be shown per-tool during execution)
-				display_tool_parameters_only(config, &current_tool_calls).await;
+				if !interleaved {
+					// Display the clean content (without function calls) to the user FIRST
+					let clean_content = remove_function_calls(&current_content);
+					print_assistant_response(&clean_content, config, role);
+
+					// Display tool parameters upfront (headers will be shown per-tool during execution)
+					display_tool_parameters_only(config, &current_tool_calls).await;
+				}
 
 				// Early exit if cancellation was requested
 				if operation_cancelled.load(Ordering::SeqCst) {
@@ -449,7 +675,7 @@ pub async fn process_response(
 				// Process tool results if any exist
 				if !tool_results.is_empty() {
 					// Process tool results and handle follow-up API calls using the new module
-					if let Some((new_content, new_exchange, new_tool_calls)) =
+					if let Some((new_content, new_exchange, new_tool_calls, new_finish_reason)) =
 						tool_result_processor::process_tool_results(
 							tool_results,
 							total_tool_time_ms,
@@ -464,6 +690,7 @@ pub async fn process_response(
 						current_content = new_content;
 						current_exchange = new_exchange;
 						current_tool_calls_param = new_tool_calls;
+						current_finish_reason = new_finish_reason;
 
 						// Check if there are more tools to process
 						if current_tool_calls_param.is_some()
@@ -473,7 +700,10 @@ pub async fn process_response(
 							continue;
 						} else {
 							// Check if there are more tool calls in the content itself
-							let more_tools = crate::mcp::parse_tool_calls(&current_content);
+							let more_tools = crate::mcp::parse_tool_calls(
+								&current_content,
+								config.content_tool_call_format,
+							);
 							if !more_tools.is_empty() {
 								// Log if debug mode is enabled
 								log_debug!(
@@ -492,7 +722,10 @@ pub async fn process_response(
 					}
 				} else {
 					// No tool results - check if there were more tools to execute directly
-					let more_tools = crate::mcp::parse_tool_calls(&current_content);
+					let more_tools = crate::mcp::parse_tool_calls(
+						&current_content,
+						config.content_tool_call_format,
+					);
 					if !more_tools.is_empty() {
 						// Log if debug mode is enabled
 						log_debug!(
@@ -521,8 +754,11 @@ pub async fn process_response(
 		&content,
 		&current_content,
 		current_exchange,
+		current_finish_reason.as_deref(),
 		chat_session,
 		config,
 		role,
+		operation_cancelled,
 	)
+	.await
 }
@@ -0,0 +1,191 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Near-duplicate tool results for dedupe_tool_results, found by embedding similarity
+// Only used when dedupe_similarity_model is set and no identical result exists
+
+use super::tool_result_processor::{content_hash, DEDUPE_MIN_CONTENT_LEN};
+use crate::config::Config;
+use crate::providers::embeddings::cosine_similarity;
+use crate::providers::EmbeddingProviderFactory;
+use crate::session::chat::session::ChatSession;
+use crate::session::chat::CostTracker;
+use crate::session::Message;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+// Characters of a result that are embedded (about 6k tokens)
+const MAX_EMBEDDED_CHARS: usize = 24_000;
+
+// Results whose sizes differ by more than this factor are never near-duplicates
+const MAX_SIZE_RATIO: f64 = 1.25;
+
+// Embeddings by content hash, so each result is embedded once
+// Limited to the results of the current conversation, see prune_embeddings
+static EMBEDDINGS: OnceLock<Mutex<HashMap<u64, Vec<f32>>>> = OnceLock::new();
+
+/// Most similar earlier tool message to a new result, as (message index, similarity),
+/// when the similarity reaches dedupe_similarity_threshold
+pub async fn find_similar_tool_result(
+	chat_session: &mut ChatSession,
+	content: &str,
+	config: &Config,
+) -> Result<Option<(usize, f32)>> {
+	if content.len() < DEDUPE_MIN_CONTENT_LEN {
+		return Ok(None);
+	}
+	let candidates = similarity_candidates(&chat_session.session.messages, content);
+	if candidates.is_empty() {
+		return Ok(None);
+	}
+
+	// Embed whatever is not cached yet in one request
+	let content_key = content_hash(content);
+	let cache = EMBEDDINGS.get_or_init(|| Mutex::new(HashMap::new()));
+	let mut missing: Vec<(u64, String)> = Vec::new();
+	{
+		let cache = cache.lock().unwrap();
+		for (key, text) in std::iter::once((content_key, content)).chain(
+			candidates
+				.iter()
+				.map(|&index| chat_session.session.messages[index].content.as_str())
+				.map(|text| (content_hash(text), text)),
+		) {
+			if !cache.contains_key(&key) && !missing.iter().any(|(k, _)| *k == key) {
+				missing.push((key, embedded_text(text)));
+			}
+		}
+	}
+	if !missing.is_empty() {
+		let (provider, model) =
+			EmbeddingProviderFactory::get_provider_for_model(&config.dedupe_similarity_model)?;
+		let texts: Vec<String> = missing.iter().map(|(_, text)| text.clone()).collect();
+		let response = provider.embed(&texts, &model, config).await?;
+		CostTracker::track_exchange_cost(chat_session, &response.exchange, config)?;
+
+		let mut cache = cache.lock().unwrap();
+		for ((key, _), embedding) in missing.into_iter().zip(response.embeddings) {
+			cache.insert(key, embedding);
+		}
+		prune_embeddings(&mut cache, &chat_session.session.messages, content_key);
+	}
+
+	let cache = cache.lock().unwrap();
+	let Some(embedding) = cache.get(&content_key) else {
+		return Ok(None);
+	};
+	let best = candidates
+		.into_iter()
+		.filter_map(|index| {
+			let key = content_hash(&chat_session.session.messages[index].content);
+			let similarity = cosine_similarity(embedding, cache.get(&key)?);
+			Some((index, similarity))
+		})
+		.max_by(|a, b| a.1.total_cmp(&b.1));
+	Ok(best.filter(|(_, similarity)| *similarity as f64 >= config.dedupe_similarity_threshold))
+}
+
+// Earlier tool results close enough in size to be near-duplicates of content
+fn similarity_candidates(messages: &[Message], content: &str) -> Vec<usize> {
+	let size = content.len() as f64;
+	messages
+		.iter()
+		.enumerate()
+		.filter(|(_, msg)| {
+			let other = msg.content.len() as f64;
+			msg.role == "tool"
+				&& msg.content.len() >= DEDUPE_MIN_CONTENT_LEN
+				&& other.max(size) / other.min(size) <= MAX_SIZE_RATIO
+		})
+		.map(|(index, _)| index)
+		.collect()
+}
+
+// Drop embeddings of results no longer in the conversation (truncated, compacted or
+// from an earlier session), so the cache never outgrows the current context
+fn prune_embeddings(cache: &mut HashMap<u64, Vec<f32>>, messages: &[Message], content_key: u64) {
+	let live: HashSet<u64> = messages
+		.iter()
+		.filter(|msg| msg.role == "tool" && msg.content.len() >= DEDUPE_MIN_CONTENT_LEN)
+		.map(|msg| content_hash(&msg.content))
+		.chain(std::iter::once(content_key))
+		.collect();
+	cache.retain(|key, _| live.contains(key));
+}
+
+fn embedded_text(text: &str) -> String {
+	match text.char_indices().nth(MAX_EMBEDDED_CHARS) {
+		Some((end, _)) => text[..end].to_string(),
+		None => text.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(role: &str, content: String) -> Message {
+		Message {
+			role: role.to_string(),
+			content,
+			timestamp: 0,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			content_parts: None,
+			model: None,
+		}
+	}
+
+	#[test]
+	fn test_similarity_candidates() {
+		let content = "a".repeat(1000);
+		let messages = vec![
+			message("user", "b".repeat(1000)),
+			message("tool", "c".repeat(1000)),
+			message("tool", "d".repeat(1200)),
+			message("tool", "e".repeat(1300)),
+			message("tool", "f".repeat(810)),
+			message("tool", "g".repeat(790)),
+			message("tool", "h".repeat(100)),
+		];
+
+		// Tool results within 1.25x of the size either way; user messages and results
+		// too short to dedupe are never candidates
+		assert_eq!(similarity_candidates(&messages, &content), vec![1, 2, 4]);
+		assert!(similarity_candidates(&messages[..1], &content).is_empty());
+	}
+
+	#[test]
+	fn test_prune_embeddings() {
+		let kept = "a".repeat(600);
+		let new_result = "b".repeat(600);
+		let messages = vec![message("tool", kept.clone())];
+
+		let mut cache = HashMap::new();
+		for text in [&kept, &new_result, &"c".repeat(600)] {
+			cache.insert(content_hash(text), vec![1.0]);
+		}
+		prune_embeddings(&mut cache, &messages, content_hash(&new_result));
+
+		let mut keys: Vec<u64> = cache.into_keys().collect();
+		keys.sort();
+		let mut expected = vec![content_hash(&kept), content_hash(&new_result)];
+		expected.sort();
+		assert_eq!(keys, expected);
+	}
+}
@@ -21,9 +21,83 @@ use crate::session::chat::ToolProcessor;
 use crate::{log_debug, log_info};
 use anyhow::Result;
 use colored::Colorize;
+use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+/// When tool calls need interactive confirmation before they are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ToolConfirmMode {
+	/// Execute tool calls without asking
+	#[default]
+	Off,
+	/// Confirm the first tool call of the session, then proceed normally
+	First,
+	/// Confirm every tool call
+	Always,
+}
+
+/// Tool confirmation gate of a session (--confirm-tools / confirm_first_tool_call)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ToolConfirmation {
+	mode: ToolConfirmMode,
+	approved_once: bool,
+}
+
+impl ToolConfirmation {
+	pub fn new(mode: ToolConfirmMode) -> Self {
+		Self {
+			mode,
+			approved_once: false,
+		}
+	}
+
+	/// Whether the next tool call must be confirmed by the user
+	pub fn needs_confirmation(&self) -> bool {
+		match self.mode {
+			ToolConfirmMode::Off => false,
+			ToolConfirmMode::First => !self.approved_once,
+			ToolConfirmMode::Always => true,
+		}
+	}
+
+	// The first approval clears the gate unless every call must be confirmed
+	fn record_approval(&mut self) {
+		self.approved_once = true;
+	}
+}
+
+// Show the tool name and arguments and ask for approval
+fn confirm_tool_call(tool_call: &crate::mcp::McpToolCall) -> bool {
+	println!(
+		"{}",
+		format!(
+			"⏸  Confirm tool call: {}",
+			tool_call.tool_name.bright_cyan()
+		)
+		.bright_yellow()
+	);
+	println!(
+		"{}",
+		serde_json::to_string_pretty(&tool_call.parameters).unwrap_or_default()
+	);
+	print!("{}", "Execute this tool call? [y/N]: ".bright_cyan());
+	std::io::stdout().flush().unwrap();
+
+	let mut input = String::new();
+	std::io::stdin().read_line(&mut input).unwrap_or_default();
+
+	if input.trim().to_lowercase().starts_with('y') {
+		true
+	} else {
+		println!(
+			"{}",
+			format!("Tool call '{}' declined by user", tool_call.tool_name).bright_red()
+		);
+		false
+	}
+}
+
 /// Context for tool execution - can be either main session or layer context
 pub enum ToolExecutionContext<'a> {
 	/// Main session context with full session access
@@ -135,6 +209,13 @@ pub async fn execute_tools_parallel(
 	tool_processor: &mut ToolProcessor,
 	operation_cancelled: Arc<AtomicBool>,
 ) -> Result<(Vec<crate::mcp::McpToolResult>, u64)> {
+	// Large tool results are judged against the context room left in this session
+	let status = chat_session.prompt_status();
+	crate::mcp::context_budget::set_context_usage(Some(crate::mcp::context_budget::ContextUsage {
+		used_tokens: status.context_tokens,
+		context_window: status.context_window,
+	}));
+
 	let mut context = ToolExecutionContext::MainSession {
 		chat_session,
 		tool_processor,
@@ -167,9 +248,45 @@ async fn execute_tools_parallel_internal(
 	config: &Config,
 	operation_cancelled: Arc<AtomicBool>,
 ) -> Result<(Vec<crate::mcp::McpToolResult>, u64)> {
+	// Confirmation gate of the session (tool calls made by layers are not gated):
+	// declined calls are not executed and report an error to the model
+	let mut declined_results = Vec::new();
+	let current_tool_calls: Vec<_> = match &mut *context {
+		ToolExecutionContext::MainSession { chat_session, .. }
+			if chat_session.tool_confirmation.needs_confirmation() =>
+		{
+			let mut approved = Vec::new();
+			for tool_call in current_tool_calls {
+				let gate = &mut chat_session.tool_confirmation;
+				if !gate.needs_confirmation() {
+					approved.push(tool_call);
+				} else if confirm_tool_call(&tool_call) {
+					gate.record_approval();
+					approved.push(tool_call);
+				} else {
+					declined_results.push(crate::mcp::McpToolResult::error(
+						tool_call.tool_name.clone(),
+						tool_call.tool_id.clone(),
+						format!("User declined to execute tool '{}'. Do not retry the same call; ask the user how to proceed or take a different approach.", tool_call.tool_name),
+					));
+				}
+			}
+			approved
+		}
+		_ => current_tool_calls,
+	};
+	if current_tool_calls.is_empty() {
+		return Ok((declined_results, 0));
+	}
+
 	let mut tool_tasks = Vec::new();
 	let is_single_tool = current_tool_calls.len() == 1;
 
+	// Completion flags for the live status line (one per tool)
+	let mut status_entries = Vec::new();
+	// Per-tool cancellation: a skipped tool ends with a "cancelled by user" result
+	let mut skip_targets = Vec::new();
+
 	for (index, tool_call) in current_tool_calls.clone().iter().enumerate() {
 		// Increment tool call counter
 		context.increment_tool_calls();
@@ -197,6 +314,16 @@ async fn execute_tools_parallel_internal(
 		let tool_id_for_task = original_tool_id.clone();
 		let tool_call_clone = tool_call.clone(); // Clone for async move
 		let cancel_token_for_task = operation_cancelled.clone(); // Pass cancellation token
+		let completed_flag = Arc::new(AtomicBool::new(false));
+		let completed_for_task = completed_flag.clone();
+		let skipped_flag = Arc::new(AtomicBool::new(false));
+		let skipped_for_task = skipped_flag.clone();
+		skip_targets.push(crate::session::chat::tool_skip::SkipTarget {
+			tool_name: tool_name.clone(),
+			completed: completed_flag.clone(),
+			skipped: skipped_flag,
+		});
+		status_entries.push((tool_name.clone(), completed_flag));
 
 		// Create the appropriate execution task based on context
 		let task = match context {
@@ -205,12 +332,24 @@ async fn execute_tools_parallel_internal(
 					let mut call_with_id = tool_call_clone.clone();
 					// CRITICAL: Use the original tool_id, don't change it
 					call_with_id.tool_id = tool_id_for_task.clone();
-					crate::mcp::execute_tool_call(
+					let started = std::time::Instant::now();
+					let result = run_skippable(
 						&call_with_id,
-						&config_clone,
-						Some(cancel_token_for_task),
+						skipped_for_task,
+						crate::mcp::execute_tool_call(
+							&call_with_id,
+							&config_clone,
+							Some(cancel_token_for_task),
+						),
 					)
-					.await
+					.await;
+					completed_for_task.store(true, Ordering::SeqCst);
+					crate::mcp::trace::record(
+						&call_with_id,
+						&result,
+						started.elapsed().as_millis() as u64,
+					);
+					result
 				})
 			}
 			ToolExecutionContext::Layer { layer_config, .. } => {
@@ -219,12 +358,24 @@ async fn execute_tools_parallel_internal(
 					let mut call_with_id = tool_call_clone.clone();
 					// CRITICAL: Use the original tool_id, don't change it
 					call_with_id.tool_id = tool_id_for_task.clone();
-					crate::mcp::execute_layer_tool_call(
+					let started = std::time::Instant::now();
+					let result = run_skippable(
 						&call_with_id,
-						&config_clone,
-						&layer_config_clone,
+						skipped_for_task,
+						crate::mcp::execute_layer_tool_call(
+							&call_with_id,
+							&config_clone,
+							&layer_config_clone,
+						),
 					)
-					.await
+					.await;
+					completed_for_task.store(true, Ordering::SeqCst);
+					crate::mcp::trace::record(
+						&call_with_id,
+						&result,
+						started.elapsed().as_millis() as u64,
+					);
+					result
 				})
 			}
 		};
@@ -233,7 +384,7 @@ async fn execute_tools_parallel_internal(
 	}
 
 	// FIXED: Proper parallel awaiting with immediate cancellation support
-	let mut tool_results = Vec::new();
+	let mut tool_results = declined_results;
 	let mut _has_error = false;
 	let mut total_tool_time_ms = 0; // Track cumulative tool execution time
 
@@ -248,9 +399,40 @@ async fn execute_tools_parallel_internal(
 	// Extract just the tasks for parallel execution
 	let tasks: Vec<_> = tool_tasks.into_iter().map(|(_, task, _, _)| task).collect();
 
+	// Live status line showing running tools, stopped on completion or cancellation
+	let status_cancel = Arc::new(AtomicBool::new(false));
+	let status_cancel_flag = status_cancel.clone();
+	let status_operation_cancelled = operation_cancelled.clone();
+	let status_task = tokio::spawn(async move {
+		let monitor_flag = status_cancel_flag.clone();
+		let _monitor = tokio::spawn(async move {
+			while !monitor_flag.load(Ordering::SeqCst) {
+				if status_operation_cancelled.load(Ordering::SeqCst) {
+					monitor_flag.store(true, Ordering::SeqCst);
+					break;
+				}
+				tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+			}
+		});
+		let _ = crate::session::chat::animation::show_tool_status_animation(
+			status_cancel_flag,
+			status_entries,
+		)
+		.await;
+	});
+
+	// Typed tool numbers skip single tools while they run
+	let skip_stop = Arc::new(AtomicBool::new(false));
+	let skip_listener =
+		crate::session::chat::tool_skip::spawn_skip_listener(skip_targets, skip_stop.clone());
+
 	// Use tokio::select! for immediate cancellation response
-	tokio::select! {
+	let outcome = tokio::select! {
 		task_results = futures::future::join_all(tasks) => {
+			// Stop the status line before displaying results
+			status_cancel.store(true, Ordering::SeqCst);
+			let _ = status_task.await;
+
 			// All tasks completed before cancellation
 			for ((tool_name, tool_id, tool_index), task_result) in task_info.into_iter().zip(task_results) {
 				// Store tool call info for consolidated display after execution
@@ -400,6 +582,8 @@ async fn execute_tools_parallel_internal(
 			}
 		}
 			}
+
+			Ok((tool_results, total_tool_time_ms))
 		},
 		_ = async {
 			loop {
@@ -409,6 +593,10 @@ async fn execute_tools_parallel_internal(
 				tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
 			}
 		} => {
+			// Stop the status line before printing cancellation feedback
+			status_cancel.store(true, Ordering::SeqCst);
+			let _ = status_task.await;
+
 			// Cancellation occurred - provide immediate feedback
 			use colored::*;
 			println!(
@@ -425,11 +613,42 @@ async fn execute_tools_parallel_internal(
 			}
 
 			// Return empty results for cancelled execution
-			return Ok((Vec::new(), total_tool_time_ms));
+			Ok((Vec::new(), total_tool_time_ms))
 		}
+	};
+
+	// Stop reading the terminal before the input prompt takes it back
+	skip_stop.store(true, Ordering::SeqCst);
+	if let Some(skip_listener) = skip_listener {
+		let _ = skip_listener.await;
 	}
 
-	Ok((tool_results, total_tool_time_ms))
+	outcome
+}
+
+// Run a tool until it finishes or the user skips it
+// Dropping a skipped tool's future stops it (shell commands are killed on drop)
+async fn run_skippable(
+	tool_call: &crate::mcp::McpToolCall,
+	skipped: Arc<AtomicBool>,
+	tool_future: impl std::future::Future<Output = Result<(crate::mcp::McpToolResult, u64)>>,
+) -> Result<(crate::mcp::McpToolResult, u64)> {
+	let started = std::time::Instant::now();
+	tokio::select! {
+		result = tool_future => result,
+		_ = async {
+			while !skipped.load(Ordering::SeqCst) {
+				tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+			}
+		} => Ok((
+			crate::mcp::McpToolResult::error(
+				tool_call.tool_name.clone(),
+				tool_call.tool_id.clone(),
+				format!("Cancelled by user: '{}' was stopped before it finished, so there is no result. Other tool calls were not affected. Do not run it again unless the user asks for it.", tool_call.tool_name),
+			),
+			started.elapsed().as_millis() as u64,
+		)),
+	}
 }
 
 // Parameters for tool display functions
@@ -465,8 +684,16 @@ async fn display_tool_success(
 	if config.get_log_level().is_info_enabled() || config.get_log_level().is_debug_enabled() {
 		// Extract content using MCP protocol
 		let content = crate::mcp::extract_mcp_content(&res.result);
-
-		if !content.trim().is_empty() {
+		let edit_diff = res
+			.result
+			.get("diff")
+			.and_then(|d| d.as_str())
+			.filter(|d| !d.is_empty());
+
+		if let Some(diff) = edit_diff {
+			// show_edit_diffs: the diff says more than the JSON result
+			display_edit_diff(diff);
+		} else if !content.trim().is_empty() {
 			if config.get_log_level().is_debug_enabled() {
 				// Debug mode: Show full content
 				println!("{}", content);
@@ -548,6 +775,23 @@ fn display_tool_output_smart(output_str: &str) {
 	}
 }
 
+// Display a unified diff from a file edit with added/removed lines colorized
+fn display_edit_diff(diff: &str) {
+	for line in diff.lines() {
+		if line.starts_with("+++") || line.starts_with("---") {
+			println!("{}", line.bold());
+		} else if line.starts_with("@@") {
+			println!("{}", line.bright_cyan());
+		} else if line.starts_with('+') {
+			println!("{}", line.bright_green());
+		} else if line.starts_with('-') {
+			println!("{}", line.bright_red());
+		} else {
+			println!("{}", line.dimmed());
+		}
+	}
+}
+
 // Display tool error in consolidated format
 fn display_tool_error(
 	stored_tool_call: &Option<crate::mcp::McpToolCall>,
@@ -624,6 +868,9 @@ pub async fn execute_layer_tool_calls_parallel(
 	config: &Config,
 	operation_cancelled: Option<Arc<AtomicBool>>,
 ) -> Result<(Vec<crate::mcp::McpToolResult>, u64)> {
+	// Layers have their own context, so their results use the configured threshold
+	crate::mcp::context_budget::set_context_usage(None);
+
 	let mut context = ToolExecutionContext::Layer {
 		session_name,
 		layer_config,
@@ -696,3 +943,28 @@ fn fix_assistant_message_tool_calls(
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_needs_confirmation() {
+		assert!(!ToolConfirmation::default().needs_confirmation());
+		assert!(!ToolConfirmation::new(ToolConfirmMode::Off).needs_confirmation());
+
+		// The first approval clears the gate
+		let mut first = ToolConfirmation::new(ToolConfirmMode::First);
+		assert!(first.needs_confirmation());
+		first.record_approval();
+		assert!(!first.needs_confirmation());
+
+		// Every call is confirmed
+		let mut always = ToolConfirmation::new(ToolConfirmMode::Always);
+		always.record_approval();
+		assert!(always.needs_confirmation());
+
+		// A new session starts with its own gate
+		assert!(ToolConfirmation::new(ToolConfirmMode::First).needs_confirmation());
+	}
+}
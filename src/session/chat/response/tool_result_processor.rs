@@ -16,6 +16,7 @@
 
 use crate::config::Config;
 use crate::session::chat::animation::show_smart_animation;
+use crate::session::chat::retry_budget;
 use crate::session::chat::session::ChatSession;
 use crate::{log_debug, log_info};
 use anyhow::Result;
@@ -36,6 +37,7 @@ pub async fn process_tool_results(
 		String,
 		crate::session::ProviderExchange,
 		Option<Vec<crate::mcp::McpToolCall>>,
+		Option<String>,
 	)>,
 > {
 	// Add the accumulated tool execution time to the session total
@@ -48,6 +50,14 @@ pub async fn process_tool_results(
 		return Ok(None);
 	}
 
+	// Interrupt and inject: ask for the extra instruction now, before the animation starts;
+	// it is added after the tool results so the tool call/result pairing stays intact
+	let injected_instruction = if crate::session::chat::inject::take_injection_request() {
+		crate::session::chat::inject::prompt_for_injection()
+	} else {
+		None
+	};
+
 	// Create separate animation flag but monitor global cancellation
 	let animation_cancel = Arc::new(AtomicBool::new(false));
 
@@ -90,9 +100,15 @@ pub async fn process_tool_results(
 	let mut accumulated_content_size = 0;
 	let mut needs_truncation_check = false;
 
+	// Results of calls parsed from content go back together as one user message
+	let mut content_call_results = Vec::new();
+
 	for tool_result in &tool_results {
 		// CRITICAL FIX: Extract ONLY the actual tool output, not our custom JSON wrapper
-		let tool_content = extract_tool_content(tool_result);
+		let tool_content = format_json_content(
+			extract_tool_content(tool_result),
+			config.get_tool_result_json_mode(&tool_result.tool_name),
+		);
 
 		// PERFORMANCE OPTIMIZATION: Check size before moving content
 		let content_size = tool_content.len();
@@ -104,6 +120,70 @@ pub async fn process_tool_results(
 			needs_truncation_check = true;
 		}
 
+		// Repeated identical results (e.g. re-reading the same file) become a short reference
+		let tool_content = if config.dedupe_tool_results {
+			match find_duplicate_tool_result(&chat_session.session.messages, &tool_content) {
+				Some(index) => {
+					let reference = duplicate_result_reference(
+						&chat_session.session.messages,
+						index,
+						&tool_result.tool_name,
+					);
+					log_debug!(
+						"Tool result for '{}' duplicates message {}, storing reference",
+						tool_result.tool_name,
+						index
+					);
+					reference
+				}
+				// Near-duplicates (e.g. the same file read with other arguments) by embedding
+				None if !config.dedupe_similarity_model.is_empty() => {
+					match super::result_similarity::find_similar_tool_result(
+						chat_session,
+						&tool_content,
+						config,
+					)
+					.await
+					{
+						Ok(Some((index, similarity))) => {
+							log_debug!(
+								"Tool result for '{}' is {:.3} similar to message {}, storing reference",
+								tool_result.tool_name,
+								similarity,
+								index
+							);
+							near_duplicate_result_reference(
+								&chat_session.session.messages,
+								index,
+								&tool_result.tool_name,
+								similarity,
+							)
+						}
+						Ok(None) => tool_content,
+						Err(e) => {
+							log_debug!(
+								"Similarity check for '{}' failed, keeping the full result: {}",
+								tool_result.tool_name,
+								e
+							);
+							tool_content
+						}
+					}
+				}
+				None => tool_content,
+			}
+		} else {
+			tool_content
+		};
+
+		if crate::mcp::content_tool_calls::is_content_tool_call(&tool_result.tool_id) {
+			content_call_results.push(crate::mcp::content_tool_calls::format_content_tool_result(
+				&tool_result.tool_name,
+				&tool_content,
+			));
+			continue;
+		}
+
 		// Use the new add_tool_message method which handles token tracking properly
 		chat_session.add_tool_message(
 			&tool_content,
@@ -134,6 +214,10 @@ pub async fn process_tool_results(
 		}
 	}
 
+	if !content_call_results.is_empty() {
+		chat_session.add_user_message(&content_call_results.join("\n\n"))?;
+	}
+
 	// BATCH TRUNCATION: Check once after all small tool results are processed
 	if needs_truncation_check {
 		let batch_truncate_cancelled = Arc::new(AtomicBool::new(false));
@@ -151,6 +235,14 @@ pub async fn process_tool_results(
 		truncation_time += truncation_start.elapsed().as_millis();
 	}
 
+	// Append the injected instruction as a user message for the follow-up call
+	if let Some(instruction) = injected_instruction {
+		chat_session.add_user_message(&instruction)?;
+	}
+
+	// Results the model already answered in earlier rounds shrink to one-line summaries
+	crate::session::chat::context_truncation::apply_tool_result_retention(chat_session, config);
+
 	// FINAL SAFETY CHECK: Truncate context before making follow-up API call
 	// This ensures we don't send an oversized context to the API after processing
 	// multiple large tool results
@@ -243,14 +335,52 @@ pub async fn process_tool_results(
 	let _ = animation_task.await;
 
 	match follow_up_result {
-		Ok(response) => {
+		Ok(mut response) => {
 			// Store direct tool calls for efficient processing if they exist
-			let has_more_tools = if let Some(ref calls) = response.tool_calls {
-				!calls.is_empty()
-			} else {
-				// Fall back to parsing if no direct tool calls
-				!crate::mcp::parse_tool_calls(&response.content).is_empty()
-			};
+			let mut has_more_tools =
+				has_actionable_tool_calls(&response, config.content_tool_call_format);
+
+			// Provider quirk: finish_reason says tool_calls but nothing actionable came back.
+			// Optionally retry the follow-up once before giving up on this turn.
+			if is_tool_calls_finish_mismatch(response.finish_reason.as_deref(), has_more_tools)
+				&& config.retry_on_empty_tool_calls
+				&& !operation_cancelled.load(Ordering::SeqCst)
+			{
+				// Without retry budget left, give up on this turn like without the retry
+				match retry_budget::take_retry("the empty tool_calls response") {
+					Err(e) => println!("{}", format!("⚠️  {}", e).yellow()),
+					Ok(()) => {
+						println!(
+							"{}",
+							"⚠️  Model reported tool calls but returned none - retrying once..."
+								.yellow()
+						);
+
+						// Track cost of the discarded response before retrying
+						handle_follow_up_cost_tracking(chat_session, &response.exchange, config);
+
+						match make_follow_up_api_call(
+							chat_session,
+							config,
+							operation_cancelled.clone(),
+						)
+						.await
+						{
+							Ok(retry_response) => {
+								response = retry_response;
+								has_more_tools = has_actionable_tool_calls(
+									&response,
+									config.content_tool_call_format,
+								);
+							}
+							Err(e) => {
+								log_info!("Retry after empty tool_calls failed: {}", e);
+								return Err(e);
+							}
+						}
+					}
+				}
+			}
 
 			// Debug logging for follow-up finish_reason
 			if let Some(ref reason) = response.finish_reason {
@@ -269,10 +399,16 @@ pub async fn process_tool_results(
 					response.content,
 					response.exchange,
 					response.tool_calls,
+					response.finish_reason,
 				)))
 			} else {
 				// If no more tools, return None to break out of the loop
-				Ok(Some((response.content, response.exchange, None)))
+				Ok(Some((
+					response.content,
+					response.exchange,
+					None,
+					response.finish_reason,
+				)))
 			}
 		}
 		Err(e) => {
@@ -326,6 +462,94 @@ fn extract_tool_content(tool_result: &crate::mcp::McpToolResult) -> String {
 	}
 }
 
+// Reformat content that parses as a JSON object or array; anything else is left unchanged
+fn format_json_content(content: String, mode: crate::config::ToolResultJsonMode) -> String {
+	use crate::config::ToolResultJsonMode;
+
+	if mode == ToolResultJsonMode::Raw {
+		return content;
+	}
+	let trimmed = content.trim_start();
+	if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+		return content;
+	}
+	let value: serde_json::Value = match serde_json::from_str(&content) {
+		Ok(value) => value,
+		Err(_) => return content,
+	};
+	let formatted = match mode {
+		ToolResultJsonMode::Pretty => serde_json::to_string_pretty(&value),
+		_ => serde_json::to_string(&value),
+	};
+	formatted.unwrap_or(content)
+}
+
+// Results shorter than this are cheaper to repeat than to reference
+pub(super) const DEDUPE_MIN_CONTENT_LEN: usize = 512;
+
+pub(super) fn content_hash(content: &str) -> u64 {
+	use std::hash::{Hash, Hasher};
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	content.hash(&mut hasher);
+	hasher.finish()
+}
+
+// Find the earliest tool message still in the session with identical content
+fn find_duplicate_tool_result(
+	messages: &[crate::session::Message],
+	content: &str,
+) -> Option<usize> {
+	if content.len() < DEDUPE_MIN_CONTENT_LEN {
+		return None;
+	}
+	let hash = content_hash(content);
+	messages.iter().position(|msg| {
+		msg.role == "tool"
+			&& msg.content.len() == content.len()
+			&& content_hash(&msg.content) == hash
+	})
+}
+
+// Short stand-in for a duplicated result, pointing at the original by tool call id and turn
+fn duplicate_result_reference(
+	messages: &[crate::session::Message],
+	index: usize,
+	tool_name: &str,
+) -> String {
+	let (original_id, turn) = result_origin(messages, index);
+	format!(
+		"[Same as earlier result for {} (tool call {}, see turn {}); content omitted as it is unchanged]",
+		tool_name, original_id, turn
+	)
+}
+
+// Stand-in for a result nearly identical to an earlier one (dedupe_similarity_model)
+fn near_duplicate_result_reference(
+	messages: &[crate::session::Message],
+	index: usize,
+	tool_name: &str,
+	similarity: f32,
+) -> String {
+	let (original_id, turn) = result_origin(messages, index);
+	format!(
+		"[Nearly the same as earlier result for {} (tool call {}, see turn {}, {:.0}% similar); content omitted, run the tool again if the exact output matters]",
+		tool_name,
+		original_id,
+		turn,
+		similarity * 100.0
+	)
+}
+
+// Tool call id and user turn of an earlier tool message
+fn result_origin(messages: &[crate::session::Message], index: usize) -> (&str, usize) {
+	let turn = messages[..=index]
+		.iter()
+		.filter(|msg| msg.role == "user")
+		.count();
+	let original_id = messages[index].tool_call_id.as_deref().unwrap_or("unknown");
+	(original_id, turn)
+}
+
 // Make follow-up API call with cancellation support
 async fn make_follow_up_api_call(
 	chat_session: &ChatSession,
@@ -347,6 +571,24 @@ async fn make_follow_up_api_call(
 	.await
 }
 
+// Check if the response carries tool calls we can actually execute
+fn has_actionable_tool_calls(
+	response: &crate::providers::ProviderResponse,
+	format: crate::config::ContentToolCallFormat,
+) -> bool {
+	if let Some(ref calls) = response.tool_calls {
+		!calls.is_empty()
+	} else {
+		// Fall back to parsing if no direct tool calls
+		!crate::mcp::parse_tool_calls(&response.content, format).is_empty()
+	}
+}
+
+// Detect provider inconsistency: tool_calls finish reason without any actionable calls
+fn is_tool_calls_finish_mismatch(finish_reason: Option<&str>, has_more_tools: bool) -> bool {
+	matches!(finish_reason, Some("tool_calls") | Some("tool_use")) && !has_more_tools
+}
+
 // Check if conversation should continue based on finish_reason
 fn check_should_continue(
 	response: &crate::providers::ProviderResponse,
@@ -354,6 +596,22 @@ fn check_should_continue(
 	has_more_tools: bool,
 ) -> bool {
 	match response.finish_reason.as_deref() {
+		Some("tool_calls") | Some("tool_use") if !has_more_tools => {
+			// Inconsistent response - nothing to execute, so stop instead of looping
+			println!(
+				"{}",
+				format!(
+					"⚠️  Model returned finish_reason '{}' without any tool calls - ending turn.",
+					response.finish_reason.as_deref().unwrap()
+				)
+				.yellow()
+			);
+			log_info!(
+				"Provider inconsistency: finish_reason '{}' with empty or unparseable tool_calls",
+				response.finish_reason.as_deref().unwrap()
+			);
+			false
+		}
 		Some("tool_calls") | Some("tool_use") => {
 			// Model wants to make more tool calls
 			log_debug!("finish_reason is 'tool_calls', continuing conversation");
@@ -384,9 +642,11 @@ fn check_should_continue(
 fn handle_follow_up_cost_tracking(
 	chat_session: &mut ChatSession,
 	exchange: &crate::session::ProviderExchange,
-	_config: &Config,
+	config: &Config,
 ) {
 	if let Some(usage) = &exchange.usage {
+		crate::session::chat::CostTracker::check_completion_size(usage, config);
+
 		// Simple token extraction with clean provider interface
 		let cached_tokens = usage.cached_tokens;
 		let regular_prompt_tokens = usage.prompt_tokens.saturating_sub(cached_tokens);
@@ -398,6 +658,7 @@ fn handle_follow_up_cost_tracking(
 			regular_prompt_tokens,
 			usage.output_tokens,
 			cached_tokens,
+			usage.cache_write_tokens,
 		);
 
 		// Track API time from the follow-up exchange
@@ -509,3 +770,144 @@ fn handle_follow_up_cost_tracking(
 		);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::loading::get_test_default_config;
+	use crate::providers::{ProviderExchange, ProviderResponse};
+
+	fn response_with(
+		finish_reason: &str,
+		tool_calls: Option<Vec<crate::mcp::McpToolCall>>,
+	) -> ProviderResponse {
+		ProviderResponse {
+			content: String::new(),
+			exchange: ProviderExchange::new(
+				serde_json::json!({}),
+				serde_json::json!({}),
+				None,
+				"test",
+			),
+			tool_calls,
+			finish_reason: Some(finish_reason.to_string()),
+			citations: None,
+			refusal: None,
+		}
+	}
+
+	#[test]
+	fn test_find_duplicate_tool_result() {
+		let message =
+			|role: &str, content: &str, tool_call_id: Option<&str>| crate::session::Message {
+				role: role.to_string(),
+				content: content.to_string(),
+				timestamp: 0,
+				cached: false,
+				tool_call_id: tool_call_id.map(|id| id.to_string()),
+				name: None,
+				tool_calls: None,
+				images: None,
+				content_parts: None,
+				model: None,
+			};
+		let file_content = "fn main() {}\n".repeat(100);
+		let messages = vec![
+			message("system", "system prompt", None),
+			message("user", "read main.rs", None),
+			message("tool", &file_content, Some("call_1")),
+			message("user", "read it again", None),
+		];
+
+		let index = find_duplicate_tool_result(&messages, &file_content).unwrap();
+		assert_eq!(index, 2);
+		let reference = duplicate_result_reference(&messages, index, "text_editor");
+		assert!(reference.contains("call_1"));
+		assert!(reference.contains("turn 1"));
+
+		// Different or short content is never deduplicated
+		assert!(find_duplicate_tool_result(&messages, &"other\n".repeat(200)).is_none());
+		assert!(find_duplicate_tool_result(&messages, "system prompt").is_none());
+	}
+
+	#[test]
+	fn test_format_json_content() {
+		use crate::config::ToolResultJsonMode;
+
+		let json = "{\n  \"files\": [\"a.rs\", \"b.rs\"],\n  \"count\": 2\n}".to_string();
+		assert_eq!(
+			format_json_content(json.clone(), ToolResultJsonMode::Raw),
+			json
+		);
+		assert_eq!(
+			format_json_content(json.clone(), ToolResultJsonMode::Compact),
+			r#"{"count":2,"files":["a.rs","b.rs"]}"#
+		);
+		assert_eq!(
+			format_json_content("[1,2]".to_string(), ToolResultJsonMode::Pretty),
+			"[\n  1,\n  2\n]"
+		);
+
+		// Plain text, scalars and invalid JSON are passed through
+		for content in ["hello", "42", "{not json"] {
+			assert_eq!(
+				format_json_content(content.to_string(), ToolResultJsonMode::Compact),
+				content
+			);
+		}
+
+		// Per-tool overrides win over the global mode
+		let mut config = get_test_default_config();
+		config.tool_result_json_mode = ToolResultJsonMode::Pretty;
+		config
+			.tool_result_json_modes
+			.insert("list_files".to_string(), ToolResultJsonMode::Compact);
+		assert_eq!(
+			config.get_tool_result_json_mode("list_files"),
+			ToolResultJsonMode::Compact
+		);
+		assert_eq!(
+			config.get_tool_result_json_mode("shell"),
+			ToolResultJsonMode::Pretty
+		);
+	}
+
+	#[test]
+	fn test_empty_tool_calls_with_tool_calls_finish_reason_stops() {
+		let config = get_test_default_config();
+
+		// Provider says tool_calls but returns an empty array
+		let response = response_with("tool_calls", Some(Vec::new()));
+		let has_more_tools = has_actionable_tool_calls(&response, config.content_tool_call_format);
+		assert!(!has_more_tools);
+		assert!(is_tool_calls_finish_mismatch(
+			response.finish_reason.as_deref(),
+			has_more_tools
+		));
+		assert!(!check_should_continue(&response, &config, has_more_tools));
+
+		// Same for missing tool_calls and no parseable calls in content
+		let response = response_with("tool_use", None);
+		let has_more_tools = has_actionable_tool_calls(&response, config.content_tool_call_format);
+		assert!(!check_should_continue(&response, &config, has_more_tools));
+	}
+
+	#[test]
+	fn test_tool_calls_finish_reason_with_calls_continues() {
+		let config = get_test_default_config();
+		let response = response_with(
+			"tool_calls",
+			Some(vec![crate::mcp::McpToolCall {
+				tool_name: "list_files".to_string(),
+				parameters: serde_json::json!({}),
+				tool_id: "call_1".to_string(),
+			}]),
+		);
+		let has_more_tools = has_actionable_tool_calls(&response, config.content_tool_call_format);
+		assert!(!is_tool_calls_finish_mismatch(
+			response.finish_reason.as_deref(),
+			has_more_tools
+		));
+		assert!(check_should_continue(&response, &config, has_more_tools));
+	}
+}
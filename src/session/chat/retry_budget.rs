@@ -0,0 +1,79 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Turn-level retry budget shared by provider retries and tool (agent) retries
+
+use anyhow::{anyhow, Result};
+use std::sync::Mutex;
+
+// Retries allowed and used in the current turn
+#[derive(Debug)]
+struct RetryBudget {
+	max: u32, // 0 = unlimited
+	used: u32,
+}
+
+impl RetryBudget {
+	// Take one retry, or fail once the turn has used max retries
+	fn take(&mut self, what: &str) -> Result<()> {
+		if self.max > 0 && self.used >= self.max {
+			return Err(anyhow!(
+				"Retry budget exhausted: {} retries already made this turn (max_retries_per_turn = {}), not retrying {}",
+				self.used,
+				self.max,
+				what
+			));
+		}
+		self.used += 1;
+		Ok(())
+	}
+}
+
+// Tool execution has no session context, so the budget of the running turn is global
+static TURN_BUDGET: Mutex<RetryBudget> = Mutex::new(RetryBudget { max: 0, used: 0 });
+
+/// Start a new turn with max_retries retries available (0 = unlimited)
+pub fn start_turn(max_retries: u32) {
+	*TURN_BUDGET.lock().unwrap() = RetryBudget {
+		max: max_retries,
+		used: 0,
+	};
+}
+
+/// Take one retry of `what` from the turn's budget
+/// Fails with a message naming the limit once max_retries_per_turn retries were made
+pub fn take_retry(what: &str) -> Result<()> {
+	TURN_BUDGET.lock().unwrap().take(what)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_retry_budget() {
+		let mut budget = RetryBudget { max: 2, used: 0 };
+		assert!(budget.take("request").is_ok());
+		assert!(budget.take("agent 'a'").is_ok());
+		let err = budget.take("request").unwrap_err().to_string();
+		assert!(err.contains("2 retries already made this turn"));
+		assert!(err.contains("not retrying request"));
+
+		// 0 = unlimited
+		let mut budget = RetryBudget { max: 0, used: 0 };
+		for _ in 0..100 {
+			assert!(budget.take("request").is_ok());
+		}
+	}
+}
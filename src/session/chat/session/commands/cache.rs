@@ -68,6 +68,55 @@ pub async fn handle_cache(
 					cache_manager.get_cache_statistics_with_config(&session.session, Some(config));
 				println!("{}", stats.format_for_display());
 			}
+			"show" => {
+				show_cache_markers(session);
+			}
+			"here" => {
+				// Manual checkpoint at the current conversation boundary (last message)
+				let supports_caching =
+					crate::session::model_supports_caching(&session.session.info.model);
+				let last_index = session
+					.session
+					.messages
+					.iter()
+					.rposition(|msg| msg.role != "system");
+				if !supports_caching {
+					println!("{}", "This model does not support caching.".bright_yellow());
+					return Ok(false);
+				}
+				match last_index {
+					None => {
+						println!(
+							"{}",
+							"No conversation messages to cache yet.".bright_yellow()
+						);
+					}
+					Some(index) => {
+						let cache_manager = crate::session::cache::CacheManager::new();
+						if cache_manager.apply_cache_to_message(
+							&mut session.session,
+							index,
+							supports_caching,
+						)? {
+							println!(
+								"{}",
+								format!(
+									"Cache checkpoint set at message {} ({})",
+									index, session.session.messages[index].role
+								)
+								.bright_green()
+							);
+							let _ = session.save();
+						} else {
+							println!(
+								"{}",
+								format!("Message {} is already a cache checkpoint", index)
+									.bright_yellow()
+							);
+						}
+					}
+				}
+			}
 			"clear" => {
 				// Clear content cache markers (but keep system markers)
 				let cache_manager = crate::session::cache::CacheManager::new();
@@ -143,6 +192,14 @@ pub async fn handle_cache(
 					"{}",
 					"  /cache stats - Show detailed cache statistics".cyan()
 				);
+				println!(
+					"{}",
+					"  /cache show - List messages carrying cache markers".cyan()
+				);
+				println!(
+					"{}",
+					"  /cache here - Set a cache checkpoint at the latest message".cyan()
+				);
 				println!("{}", "  /cache clear - Clear content cache markers".cyan());
 				println!(
 					"{}",
@@ -153,3 +210,46 @@ pub async fn handle_cache(
 	}
 	Ok(false)
 }
+
+// List the messages that carry a cache marker, with a short content preview
+fn show_cache_markers(session: &ChatSession) {
+	let cached: Vec<(usize, &crate::session::Message)> = session
+		.session
+		.messages
+		.iter()
+		.enumerate()
+		.filter(|(_, msg)| msg.cached)
+		.collect();
+
+	if cached.is_empty() {
+		println!("{}", "No messages are marked for caching.".bright_yellow());
+		return;
+	}
+
+	println!(
+		"{}",
+		format!(
+			"Cache markers ({} of {} messages):",
+			cached.len(),
+			session.session.messages.len()
+		)
+		.bright_cyan()
+	);
+	for (index, msg) in cached {
+		let preview: String = msg
+			.content
+			.split_whitespace()
+			.collect::<Vec<_>>()
+			.join(" ")
+			.chars()
+			.take(60)
+			.collect();
+		println!(
+			"  {:>4}  {:<9}  {:>7} tokens  {}",
+			index,
+			msg.role.bright_blue(),
+			crate::session::estimate_tokens(&msg.content),
+			preview.dimmed()
+		);
+	}
+}
@@ -14,12 +14,79 @@
 
 // Clear command handler
 
+use super::super::core::ChatSession;
 use anyhow::Result;
+use colored::Colorize;
 use std::io::{self, Write};
 
-pub fn handle_clear() -> Result<bool> {
+// Returns true when the session loop should start the conversation over (after confirmation)
+// `/clear all` also resets the session's token and cost totals, `/clear screen` only clears the screen
+pub fn handle_clear(session: &ChatSession, params: &[&str]) -> Result<bool> {
+	let reset_stats = match params.first().copied() {
+		None => false,
+		Some("all") => true,
+		Some("screen") => {
+			clear_screen()?;
+			return Ok(false);
+		}
+		Some(other) => {
+			println!(
+				"{}: {}",
+				"Unknown /clear option".bright_red(),
+				other.bright_yellow()
+			);
+			println!("{}", "Usage: /clear [all|screen]".bright_yellow());
+			return Ok(false);
+		}
+	};
+
+	// Dropping the conversation cannot be undone, so ask first
+	let messages = session
+		.session
+		.messages
+		.iter()
+		.filter(|message| message.role != "system")
+		.count();
+	println!(
+		"{}",
+		format!(
+			"This drops all {} messages of session '{}' and starts over with a fresh system prompt.",
+			messages, session.session.info.name
+		)
+		.bright_yellow()
+	);
+	if reset_stats {
+		println!(
+			"{}",
+			"Token and cost totals are reset as well.".bright_yellow()
+		);
+	} else {
+		println!(
+			"{}",
+			format!(
+				"Token and cost totals (${:.5}) are kept; use '/clear all' to reset them too.",
+				session.session.info.total_cost
+			)
+			.dimmed()
+		);
+	}
+	print!("{}", "Clear the conversation? [y/N]: ".bright_cyan());
+	io::stdout().flush()?;
+	let mut answer = String::new();
+	io::stdin().read_line(&mut answer).unwrap_or_default();
+	if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+		println!("{}", "Conversation kept".bright_green());
+		return Ok(false);
+	}
+
+	let command_line = if reset_stats { "/clear all" } else { "/clear" };
+	let _ = crate::session::logger::log_session_command(&session.session.info.name, command_line);
+	Ok(true)
+}
+
+pub fn clear_screen() -> Result<()> {
 	// ANSI escape code to clear screen and move cursor to top-left
 	print!("\x1B[2J\x1B[1;1H");
 	io::stdout().flush()?;
-	Ok(false)
+	Ok(())
 }
@@ -18,26 +18,65 @@ use anyhow::Result;
 use arboard::Clipboard;
 use colored::Colorize;
 
-pub fn handle_copy(last_response: &str) -> Result<bool> {
+pub fn handle_copy(last_response: &str, params: &[&str]) -> Result<bool> {
 	if last_response.is_empty() {
 		println!(
 			"{}",
 			"No response to copy. Send a message first.".bright_yellow()
 		);
+		return Ok(false);
+	}
+
+	let (text, what) = if params.first() == Some(&"code") {
+		match last_code_block(last_response) {
+			Some(code) => (code, "Last code block"),
+			None => {
+				println!(
+					"{}",
+					"The last response has no code block to copy.".bright_yellow()
+				);
+				return Ok(false);
+			}
+		}
 	} else {
-		match Clipboard::new() {
-			Ok(mut clipboard) => match clipboard.set_text(last_response) {
-				Ok(_) => {
-					println!("{}", "Last response copied to clipboard.".bright_green());
-				}
-				Err(e) => {
-					println!("{}: {}", "Failed to copy to clipboard".bright_red(), e);
+		(last_response, "Last response")
+	};
+
+	match copy_to_clipboard(text) {
+		Ok(()) => println!(
+			"{}",
+			format!("{} copied to clipboard.", what).bright_green()
+		),
+		Err(e) => println!("{}", e.to_string().bright_red()),
+	}
+	Ok(false)
+}
+
+/// Put text on the system clipboard
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+	let mut clipboard =
+		Clipboard::new().map_err(|e| anyhow::anyhow!("Failed to access clipboard: {}", e))?;
+	clipboard
+		.set_text(text)
+		.map_err(|e| anyhow::anyhow!("Failed to copy to clipboard: {}", e))
+}
+
+// Content of the last fenced (```) code block, without the fences and language tag
+fn last_code_block(text: &str) -> Option<&str> {
+	let mut last = None;
+	let mut open: Option<usize> = None;
+	let mut offset = 0;
+	for line in text.split_inclusive('\n') {
+		if line.trim_start().starts_with("```") {
+			match open {
+				None => open = Some(offset + line.len()),
+				Some(start) => {
+					last = Some(&text[start..offset]);
+					open = None;
 				}
-			},
-			Err(e) => {
-				println!("{}: {}", "Failed to access clipboard".bright_red(), e);
 			}
 		}
+		offset += line.len();
 	}
-	Ok(false)
+	last.map(|code| code.strip_suffix('\n').unwrap_or(code))
 }
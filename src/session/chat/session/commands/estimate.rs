@@ -0,0 +1,118 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Estimate command handler - prompt size and cost of the next request, without calling the provider
+
+use super::super::core::ChatSession;
+use super::utils::format_number;
+use crate::config::Config;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn handle_estimate(session: &ChatSession, config: &Config, params: &[&str]) -> Result<bool> {
+	let message = params.join(" ");
+	let context_tokens = crate::session::estimate_message_tokens(&session.session.messages);
+	let message_tokens = crate::session::estimate_tokens(&message);
+	let tool_tokens = config.mcp.servers.len() * crate::session::TOOL_DEFINITION_TOKENS_PER_SERVER;
+	let prompt_tokens = context_tokens + message_tokens + tool_tokens;
+
+	println!(
+		"{}",
+		"───────────── Request Estimate ─────────────".bright_cyan()
+	);
+	println!("{} {}", "Model:".yellow(), session.model.bright_white());
+	println!(
+		"{} {} tokens ({} messages)",
+		"Context:".yellow(),
+		format_number(context_tokens as u64).bright_white(),
+		session.session.messages.len()
+	);
+	println!(
+		"{} {} tokens",
+		"Message:".yellow(),
+		format_number(message_tokens as u64).bright_white()
+	);
+	if tool_tokens > 0 {
+		println!(
+			"{} ~{} tokens",
+			"Tool definitions:".yellow(),
+			format_number(tool_tokens as u64).bright_white()
+		);
+	}
+
+	let context_window = crate::providers::ProviderFactory::get_provider_for_model(&session.model)
+		.map(|(provider, model)| provider.get_max_input_tokens(&model))
+		.unwrap_or(0);
+	if context_window > 0 {
+		println!(
+			"{} {} tokens ({:.0}% of the {} context window)",
+			"Prompt total:".yellow(),
+			format_number(prompt_tokens as u64).bright_white(),
+			prompt_tokens as f64 * 100.0 / context_window as f64,
+			format_number(context_window as u64)
+		);
+	} else {
+		println!(
+			"{} {} tokens",
+			"Prompt total:".yellow(),
+			format_number(prompt_tokens as u64).bright_white()
+		);
+	}
+
+	match config.get_model_pricing(&session.model) {
+		Some(pricing) => {
+			let input_cost = prompt_tokens as f64 / 1_000_000.0 * pricing.input;
+			println!(
+				"{} ~${:.5} (${:.2} per 1M input tokens)",
+				"Input cost:".yellow(),
+				input_cost,
+				pricing.input
+			);
+			println!(
+				"{} ~${:.5} per 1K output tokens (${:.2} per 1M)",
+				"Output cost:".yellow(),
+				pricing.output / 1_000.0,
+				pricing.output
+			);
+			if crate::session::model_supports_caching(&session.model)
+				&& session.session.messages.iter().any(|m| m.cached)
+			{
+				println!(
+					"{}",
+					"Cached prefixes are billed at a lower rate, so the input cost is an upper bound."
+						.bright_black()
+				);
+			}
+		}
+		None => {
+			println!(
+				"{}",
+				format!(
+					"No pricing known for {}. Add it to model_pricing in the config to estimate cost.",
+					session.model
+				)
+				.bright_yellow()
+			);
+		}
+	}
+
+	if message.is_empty() {
+		println!(
+			"{}",
+			"Tip: /estimate <message> includes a proposed message in the estimate.".bright_black()
+		);
+	}
+
+	Ok(false)
+}
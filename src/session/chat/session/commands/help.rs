@@ -23,11 +23,17 @@ use colored::Colorize;
 pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 	println!("{}", "\nAvailable commands:\n".bright_cyan());
 	println!("{} - Show this help message", HELP_COMMAND.cyan());
-	println!("{} - Copy last response to clipboard", COPY_COMMAND.cyan());
-	println!("{} - Clear the screen", CLEAR_COMMAND.cyan());
+	println!(
+		"{} [code] - Copy last response (or its last code block) to clipboard",
+		COPY_COMMAND.cyan()
+	);
+	println!(
+		"{} [all|screen] - Start the conversation over in this session (all: reset token/cost totals too, screen: only clear the screen)",
+		CLEAR_COMMAND.cyan()
+	);
 	println!("{} - Save the session", SAVE_COMMAND.cyan());
 	println!(
-		"{} - Manage cache checkpoints: /cache [stats|clear|threshold]",
+		"{} - Manage cache checkpoints: /cache [stats|show|here|clear|threshold]",
 		CACHE_COMMAND.cyan()
 	);
 	println!(
@@ -40,7 +46,11 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		INFO_COMMAND.cyan()
 	);
 	println!(
-		"{} - Toggle layered processing architecture on/off",
+		"{} [message] - Estimate prompt tokens and cost of the next request without sending it",
+		ESTIMATE_COMMAND.cyan()
+	);
+	println!(
+		"{} [presets|on-next|status] - Toggle layered processing on/off, list layer presets, send the next message through layers or show the layer state",
 		LAYERS_COMMAND.cyan()
 	);
 	println!("{} - Finalize task with memorization, summarization, and auto-commit (resets layered processing for next task)", DONE_COMMAND.cyan());
@@ -65,7 +75,19 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		MODEL_COMMAND.cyan()
 	);
 	println!(
-		"{} [list|info|full] - Show MCP server status and tools (info is default)",
+		"{} [role] - Show current role or switch to another role, keeping conversation history",
+		ROLE_COMMAND.cyan()
+	);
+	println!(
+		"{} [file|off] - Show the response schema, require JSON responses matching a schema file, or turn it off (runtime only)",
+		SCHEMA_COMMAND.cyan()
+	);
+	println!(
+		"{} - Rebuild the system prompt and reload the instructions file after editing them",
+		RELOAD_PROMPT_COMMAND.cyan()
+	);
+	println!(
+		"{} [list|info|full|logs <server>|refresh <server>] - Show MCP server status and tools (info is default)",
 		MCP_COMMAND.cyan()
 	);
 	println!(
@@ -95,6 +117,14 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 	println!("{} - Accept hint/completion", "Ctrl+E".bright_green());
 	println!("{} - Cancel input", "Ctrl+C".bright_green());
 	println!("{} - Exit session", "Ctrl+D".bright_green());
+	println!(
+		"{} - While a response is running, pause after the current step to add an instruction",
+		"Ctrl+\\".bright_green()
+	);
+	println!(
+		"{} - While tools run, skip tool N (s = the first still running); the turn continues",
+		"N or s + Enter".bright_green()
+	);
 	println!();
 
 	// Additional info about caching
@@ -104,6 +134,8 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 	);
 	println!("Use '/cache' to mark your last user message for caching.");
 	println!("Use '/cache stats' to view detailed cache statistics and efficiency.");
+	println!("Use '/cache show' to list the messages that currently carry cache markers.");
+	println!("Use '/cache here' to set a checkpoint at the latest message right away.");
 	println!("Use '/cache clear' to remove content cache markers (keeps system/tool caches).");
 	println!("Use '/cache threshold' to view auto-cache settings.");
 	println!("Supports 2-marker system: when you add a 3rd marker, the first one moves to the new position.");
@@ -145,5 +177,19 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		println!();
 	}
 
+	// Custom commands from [[custom_commands]]
+	if !config.custom_commands.is_empty() {
+		println!("{}", "** Custom Commands **".bright_yellow());
+		println!("Each expands its prompt template into your message (text after the command fills %{{ARGS}}).");
+		for command in &config.custom_commands {
+			println!(
+				"  {} - {}",
+				format!("/{}", command.name).cyan(),
+				command.description
+			);
+		}
+		println!();
+	}
+
 	Ok(false)
 }
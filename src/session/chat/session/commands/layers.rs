@@ -23,7 +23,36 @@ pub async fn handle_layers(
 	session: &mut ChatSession,
 	config: &mut Config,
 	role: &str,
+	params: &[&str],
 ) -> Result<bool> {
+	// Subcommand: list configured layer presets
+	if params.first() == Some(&"presets") {
+		show_layer_presets(config, role);
+		return Ok(false);
+	}
+
+	// Subcommand: send the next message through the layer pipeline
+	if params.first() == Some(&"on-next") {
+		session.run_layers_next = true;
+		println!(
+			"{}",
+			"The next message will be processed through the layer pipeline.".bright_green()
+		);
+		if !config.get_enable_layers(role) {
+			println!(
+				"{}",
+				"Layers are disabled for this role - run /layers to enable them.".bright_yellow()
+			);
+		}
+		return Ok(false);
+	}
+
+	// Subcommand: show whether layers are on and what the next message goes through
+	if params.first() == Some(&"status") {
+		show_layers_status(session, config, role);
+		return Ok(false);
+	}
+
 	// Toggle layered processing (RUNTIME ONLY - no config file changes)
 	let current_role = role; // Use the passed role parameter
 
@@ -77,3 +106,66 @@ pub async fn handle_layers(
 	// Return false since we don't need to reload config (runtime-only change)
 	Ok(false)
 }
+
+// Display the layer state of the role and the session
+fn show_layers_status(session: &ChatSession, config: &Config, role: &str) {
+	let enabled = config.get_enable_layers(role);
+	println!("{}", "Layered processing:".bright_cyan().bold());
+	println!(
+		"  {} {}",
+		"Role:".dimmed(),
+		if enabled {
+			format!("{} (enabled)", role).bright_green()
+		} else {
+			format!("{} (disabled)", role).bright_yellow()
+		}
+	);
+
+	if enabled {
+		let orchestrator = crate::session::layers::LayeredOrchestrator::from_config(config, role);
+		let names: Vec<&str> = orchestrator.layers.iter().map(|l| l.name()).collect();
+		println!("  {} {}", "Pipeline:".dimmed(), names.join(" → "));
+	}
+
+	let next = if !enabled {
+		"main model only (layers disabled)".to_string()
+	} else if session.run_layers_next {
+		"layer pipeline, then the main model".to_string()
+	} else {
+		"main model only (/layers on-next to use layers)".to_string()
+	};
+	println!("  {} {}", "Next message:".dimmed(), next);
+}
+
+// Display configured layer presets and which roles use them
+fn show_layer_presets(config: &Config, role: &str) {
+	if config.layer_presets.is_empty() {
+		println!("{}", "No layer presets configured.".bright_yellow());
+		println!(
+			"{}",
+			"Define them in config with [[layer_presets]] name = \"...\" layers = [...]".dimmed()
+		);
+		return;
+	}
+
+	println!("{}", "Layer presets:".bright_cyan().bold());
+	for preset in &config.layer_presets {
+		let used_by: Vec<&str> = config
+			.roles
+			.iter()
+			.filter(|r| r.layer_preset.as_deref() == Some(preset.name.as_str()))
+			.map(|r| r.name.as_str())
+			.collect();
+		let marker = if used_by.contains(&role) { "*" } else { " " };
+
+		println!(
+			"{} {} {}",
+			marker.bright_green(),
+			preset.name.bright_yellow(),
+			format!("[{}]", preset.layers.join(" → ")).dimmed()
+		);
+		if !used_by.is_empty() {
+			println!("    {} {}", "roles:".dimmed(), used_by.join(", "));
+		}
+	}
+}
@@ -21,7 +21,7 @@ use colored::Colorize;
 
 pub async fn handle_mcp(config: &Config, role: &str, params: &[&str]) -> Result<bool> {
 	// Handle /mcp command for showing MCP server status and tools
-	// Support subcommands: list, info, full
+	// Support subcommands: list, info, full, health, dump, validate, logs, refresh
 	let subcommand = if params.is_empty() { "info" } else { params[0] };
 
 	match subcommand {
@@ -31,6 +31,8 @@ pub async fn handle_mcp(config: &Config, role: &str, params: &[&str]) -> Result<
 		"health" => handle_mcp_health(config, role).await,
 		"dump" => handle_mcp_dump(config, role).await,
 		"validate" => handle_mcp_validate(config, role).await,
+		"logs" => handle_mcp_logs(config, role, params.get(1).copied()),
+		"refresh" => handle_mcp_refresh(config, role, params.get(1).copied()).await,
 		_ => handle_mcp_invalid(),
 	}
 }
@@ -99,7 +101,7 @@ async fn handle_mcp_info(config: &Config, role: &str) -> Result<bool> {
 					Default::default(),
 				)
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				// External servers - get from status report or check on-demand
 				if let Some((h, r)) = server_report.get(server.name()) {
 					(*h, r.clone())
@@ -219,7 +221,7 @@ async fn handle_mcp_full(config: &Config, role: &str) -> Result<bool> {
 				crate::mcp::process::ServerHealth::Running,
 				Default::default(),
 			),
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse => {
 				if let Some((h, r)) = server_report.get(server_name) {
 					(*h, r.clone())
 				} else {
@@ -430,7 +432,9 @@ async fn handle_mcp_health(config: &Config, role: &str) -> Result<bool> {
 	let server_report = crate::mcp::server::get_server_status_report();
 
 	for server in &config_for_role.mcp.servers {
-		if let McpConnectionType::Http | McpConnectionType::Stdin = server.connection_type() {
+		if let McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::Sse =
+			server.connection_type()
+		{
 			let (health, restart_info) = if let Some((h, r)) = server_report.get(server.name()) {
 				(*h, r.clone())
 			} else {
@@ -590,6 +594,151 @@ async fn handle_mcp_validate(config: &Config, role: &str) -> Result<bool> {
 	Ok(false)
 }
 
+// Number of captured stderr lines shown by /mcp logs
+const MCP_LOGS_LINES: usize = 50;
+
+fn handle_mcp_logs(config: &Config, role: &str, server_name: Option<&str>) -> Result<bool> {
+	// Show recent stderr output captured from a server process
+	let config_for_role = config.get_merged_config_for_role(role);
+	let process_servers: Vec<&str> = config_for_role
+		.mcp
+		.servers
+		.iter()
+		.filter(|s| s.connection_type() != McpConnectionType::Builtin)
+		.map(|s| s.name())
+		.collect();
+
+	let Some(server_name) = server_name else {
+		println!();
+		println!("{}", "Usage: /mcp logs <server>".bright_blue());
+		if process_servers.is_empty() {
+			println!(
+				"{}",
+				"No external MCP servers configured for this role.".yellow()
+			);
+		} else {
+			println!("Servers: {}", process_servers.join(", "));
+		}
+		return Ok(false);
+	};
+
+	println!();
+	println!(
+		"{}",
+		format!("MCP Server Logs: {}", server_name)
+			.bright_cyan()
+			.bold()
+	);
+	println!("{}", "─".repeat(50).dimmed());
+
+	let lines = crate::mcp::process::get_server_stderr(server_name, MCP_LOGS_LINES);
+	if lines.is_empty() {
+		if process_servers.contains(&server_name) {
+			println!("{}", "No stderr output captured yet.".yellow());
+		} else {
+			println!(
+				"{}",
+				format!(
+					"'{}' is not an external MCP server for this role.",
+					server_name
+				)
+				.yellow()
+			);
+		}
+		return Ok(false);
+	}
+
+	for line in &lines {
+		println!("{}", line);
+	}
+	println!();
+	println!(
+		"{}",
+		format!("Last {} stderr lines (oldest first).", lines.len()).dimmed()
+	);
+	Ok(false)
+}
+
+async fn handle_mcp_refresh(
+	config: &Config,
+	role: &str,
+	server_name: Option<&str>,
+) -> Result<bool> {
+	// Drop a server's cached tool definitions, re-probe it and rebuild the tool map
+	let config_for_role = config.get_merged_config_for_role(role);
+	let server_names: Vec<&str> = config_for_role
+		.mcp
+		.servers
+		.iter()
+		.map(|s| s.name())
+		.collect();
+
+	let Some(server_name) = server_name else {
+		println!();
+		println!("{}", "Usage: /mcp refresh <server>".bright_blue());
+		if server_names.is_empty() {
+			println!("{}", "No MCP servers configured for this role.".yellow());
+		} else {
+			println!("Servers: {}", server_names.join(", "));
+		}
+		return Ok(false);
+	};
+	let Some(server) = config_for_role
+		.mcp
+		.servers
+		.iter()
+		.find(|s| s.name() == server_name)
+	else {
+		println!();
+		println!(
+			"{}",
+			format!("'{}' is not an MCP server for this role.", server_name).yellow()
+		);
+		if !server_names.is_empty() {
+			println!("Servers: {}", server_names.join(", "));
+		}
+		return Ok(false);
+	};
+
+	println!();
+	println!(
+		"{}",
+		format!("MCP Refresh: {}", server_name).bright_cyan().bold()
+	);
+	println!("{}", "─".repeat(50).dimmed());
+
+	let before = crate::mcp::tool_map::get_tool_names_for_server(server_name);
+	if server.connection_type() == McpConnectionType::Builtin {
+		crate::mcp::clear_internal_function_cache_for_server(server_name);
+	} else if let Err(e) = crate::mcp::server::refresh_server_functions(server).await {
+		println!("{}: {}", "Failed to re-probe server".bright_red(), e);
+		return Ok(false);
+	}
+	if let Err(e) = crate::mcp::tool_map::rebuild_tool_map(&config_for_role).await {
+		println!("{}: {}", "Failed to rebuild the tool map".bright_red(), e);
+		return Ok(false);
+	}
+	let after = crate::mcp::tool_map::get_tool_names_for_server(server_name);
+
+	let added: Vec<&String> = after.iter().filter(|t| !before.contains(t)).collect();
+	let removed: Vec<&String> = before.iter().filter(|t| !after.contains(t)).collect();
+	if added.is_empty() && removed.is_empty() {
+		println!("{}", "No tool changes.".bright_green());
+	}
+	for tool in added {
+		println!("  {} {}", "+".bright_green(), tool.bright_green());
+	}
+	for tool in removed {
+		println!("  {} {}", "-".bright_red(), tool.bright_red());
+	}
+	println!();
+	println!(
+		"{}",
+		format!("{} tools available from '{}'.", after.len(), server_name).dimmed()
+	);
+	Ok(false)
+}
+
 fn handle_mcp_invalid() -> Result<bool> {
 	// Invalid subcommand
 	println!();
@@ -618,10 +767,18 @@ fn handle_mcp_invalid() -> Result<bool> {
 		"  {} - Validate tool schema definitions",
 		"/mcp validate".cyan()
 	);
+	println!(
+		"  {} - Show recent stderr output of a server process",
+		"/mcp logs <server>".cyan()
+	);
+	println!(
+		"  {} - Re-probe a server's tools after it changed, showing added and removed tools",
+		"/mcp refresh <server>".cyan()
+	);
 	println!();
 	println!(
 		"{}",
-		"Usage: /mcp [list|info|full|health|dump|validate]".bright_blue()
+		"Usage: /mcp [list|info|full|health|dump|validate|logs|refresh]".bright_blue()
 	);
 	Ok(false)
 }
@@ -655,6 +812,14 @@ async fn check_server_health_on_demand(
 				}
 			}
 		}
+		McpConnectionType::Sse => {
+			// SSE server - running while its event stream is open
+			if crate::mcp::sse::is_connected(server.name()) {
+				crate::mcp::process::ServerHealth::Running
+			} else {
+				crate::mcp::process::ServerHealth::Dead
+			}
+		}
 		McpConnectionType::Builtin => {
 			// Builtin servers are always running
 			crate::mcp::process::ServerHealth::Running
@@ -17,7 +17,8 @@
 mod cache;
 mod clear;
 mod context;
-mod copy;
+pub(super) mod copy;
+mod estimate;
 mod exit;
 mod help;
 mod image;
@@ -28,8 +29,10 @@ mod loglevel;
 mod mcp;
 mod model;
 mod report;
+mod role;
 mod run;
 mod save;
+mod schema;
 mod session;
 mod summarize;
 mod truncate;
@@ -59,19 +62,24 @@ pub async fn process_command(
 	match command {
 		EXIT_COMMAND | QUIT_COMMAND => exit::handle_exit(),
 		HELP_COMMAND => help::handle_help(config, role).await,
-		COPY_COMMAND => copy::handle_copy(&session.last_response),
-		CLEAR_COMMAND => clear::handle_clear(),
+		COPY_COMMAND => copy::handle_copy(&session.last_response, params),
+		CLEAR_COMMAND => clear::handle_clear(session, params),
 		SAVE_COMMAND => save::handle_save(session),
 		INFO_COMMAND => info::handle_info(session),
+		ESTIMATE_COMMAND => estimate::handle_estimate(session, config, params),
 		REPORT_COMMAND => report::handle_report(session, config),
 		CONTEXT_COMMAND => context::handle_context(session, config, params),
-		LAYERS_COMMAND => layers::handle_layers(session, config, role).await,
+		LAYERS_COMMAND => layers::handle_layers(session, config, role, params).await,
 		LOGLEVEL_COMMAND => loglevel::handle_loglevel(config, params),
 		TRUNCATE_COMMAND => truncate::handle_truncate(session, config).await,
 		SUMMARIZE_COMMAND => summarize::handle_summarize(session, config).await,
 		CACHE_COMMAND => cache::handle_cache(session, config, params).await,
 		LIST_COMMAND => list::handle_list(session, config, params),
 		MODEL_COMMAND => model::handle_model(session, config, params),
+		SCHEMA_COMMAND => schema::handle_schema(config, params),
+		ROLE_COMMAND => role::handle_role(session, config, role, params),
+		// Handled by the session runner, which owns the project dir and prompt watcher
+		RELOAD_PROMPT_COMMAND => Ok(true),
 		SESSION_COMMAND => session::handle_session(session, params),
 		MCP_COMMAND => mcp::handle_mcp(config, role, params).await,
 		RUN_COMMAND => run::handle_run(session, config, role, params).await,
@@ -97,13 +105,32 @@ async fn handle_unknown_command(command: &str, config: &Config, role: &str) -> R
 	// Basic session commands
 	println!("{} - Show help and available commands", HELP_COMMAND.cyan());
 	println!("{} - Display token usage and costs", INFO_COMMAND.cyan());
+	println!(
+		"{} - Estimate tokens and cost of the next request",
+		ESTIMATE_COMMAND.cyan()
+	);
 	println!("{} - Generate detailed usage report", REPORT_COMMAND.cyan());
-	println!("{} - Copy last response to clipboard", COPY_COMMAND.cyan());
-	println!("{} - Clear the screen", CLEAR_COMMAND.cyan());
+	println!(
+		"{} [code] - Copy last response (or its last code block) to clipboard",
+		COPY_COMMAND.cyan()
+	);
+	println!(
+		"{} - Clear the conversation, keeping the session",
+		CLEAR_COMMAND.cyan()
+	);
 	println!("{} - Save the session", SAVE_COMMAND.cyan());
 	println!("{} - List all sessions", LIST_COMMAND.cyan());
 	println!("{} - Switch to another session", SESSION_COMMAND.cyan());
 	println!("{} - Show/change current model", MODEL_COMMAND.cyan());
+	println!("{} - Show/switch session role", ROLE_COMMAND.cyan());
+	println!(
+		"{} - Show/set the JSON schema for responses",
+		SCHEMA_COMMAND.cyan()
+	);
+	println!(
+		"{} - Rebuild system prompt and instructions",
+		RELOAD_PROMPT_COMMAND.cyan()
+	);
 	println!("{} - Set logging level", LOGLEVEL_COMMAND.cyan());
 
 	// Advanced commands
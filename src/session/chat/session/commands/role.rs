@@ -0,0 +1,83 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Role command handler
+
+use super::super::core::ChatSession;
+use crate::config::Config;
+use anyhow::Result;
+use colored::Colorize;
+
+// Returns true when the session loop should switch to the requested role
+pub fn handle_role(
+	session: &ChatSession,
+	config: &Config,
+	role: &str,
+	params: &[&str],
+) -> Result<bool> {
+	if params.is_empty() {
+		println!(
+			"{}",
+			format!("Current session role: {}", role).bright_cyan()
+		);
+		println!();
+		println!("{}", "Available roles:".bright_blue());
+		for available in &config.roles {
+			let marker = if available.name == role { "*" } else { " " };
+			println!(
+				"  {} {}",
+				marker.bright_green(),
+				available.name.bright_yellow()
+			);
+		}
+		println!();
+		println!(
+			"{}",
+			"Use '/role <name>' to switch roles while keeping the conversation history."
+				.bright_yellow()
+		);
+		return Ok(false);
+	}
+
+	let new_role = params[0];
+	if new_role == role {
+		println!(
+			"{}",
+			format!("Session is already using role '{}'", role).bright_yellow()
+		);
+		return Ok(false);
+	}
+
+	if !config.role_map.contains_key(new_role) {
+		println!(
+			"{}: {}",
+			"Unknown role".bright_red(),
+			new_role.bright_yellow()
+		);
+		let names: Vec<&str> = config.roles.iter().map(|r| r.name.as_str()).collect();
+		println!("Available roles: {}", names.join(", ").bright_cyan());
+		return Ok(false);
+	}
+
+	// Log the command execution
+	if let Some(session_file) = &session.session.session_file {
+		if let Some(session_name) = session_file.file_stem().and_then(|s| s.to_str()) {
+			let command_line = format!("/role {}", new_role);
+			let _ = crate::session::logger::log_session_command(session_name, &command_line);
+		}
+	}
+
+	// Actual switch (config, tools, system prompt) is performed by the session loop
+	Ok(true)
+}
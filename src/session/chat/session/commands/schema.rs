@@ -0,0 +1,75 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Response schema command handler
+
+use crate::config::Config;
+use crate::session::response_schema;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+pub fn handle_schema(config: &mut Config, params: &[&str]) -> Result<bool> {
+	// Runtime only, like --response-schema
+	if params.is_empty() {
+		match &config.response_schema {
+			Some(schema) => {
+				println!(
+					"{}",
+					"Responses must be JSON matching this schema:".bright_cyan()
+				);
+				println!("{}", serde_json::to_string_pretty(schema)?);
+				println!(
+					"{}",
+					"Use /schema off to return to free-form responses.".bright_blue()
+				);
+			}
+			None => {
+				println!("{}", "No response schema set.".bright_cyan());
+				println!(
+					"{}",
+					"Use /schema <file> to require JSON responses matching a JSON schema."
+						.bright_blue()
+				);
+			}
+		}
+		return Ok(false);
+	}
+
+	if params == ["off"] {
+		config.response_schema = None;
+		println!(
+			"{}",
+			"Response schema removed, responses are free-form again.".bright_green()
+		);
+		return Ok(false);
+	}
+
+	let path = params.join(" ");
+	match response_schema::load_schema(Path::new(&path)) {
+		Ok(schema) => {
+			config.response_schema = Some(schema);
+			println!(
+				"{}",
+				format!(
+					"Responses must now be JSON matching the schema in {} (runtime only).",
+					path
+				)
+				.bright_green()
+			);
+		}
+		Err(e) => println!("{}", e.to_string().bright_red()),
+	}
+	Ok(false)
+}
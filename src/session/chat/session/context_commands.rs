@@ -0,0 +1,125 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Context commands ([developer] context_commands) - run when a session starts, their
+// output primes the conversation with e.g. the current build error
+
+use crate::config::Config;
+use colored::Colorize;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command as TokioCommand;
+
+// Longest a single context command may run
+const CONTEXT_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Bytes of output kept per command - the end, where build errors usually are
+const MAX_OUTPUT_BYTES: usize = 8000;
+
+/// Run the context commands and return the message with their output
+/// None when none are configured or the user declined to run them
+pub async fn run_context_commands(config: &Config, project_dir: &Path) -> Option<String> {
+	let commands = &config.developer.context_commands;
+	if commands.is_empty() {
+		return None;
+	}
+	if config.developer.confirm_context_commands && !confirm_context_commands(commands) {
+		return None;
+	}
+
+	let mut sections = Vec::new();
+	for command in commands {
+		println!("{}", format!("$ {}", command).dimmed());
+		sections.push(run_context_command(command, project_dir).await);
+	}
+	Some(format!(
+		"Output of commands run in the project at session start:\n\n{}",
+		sections.join("\n\n")
+	))
+}
+
+fn confirm_context_commands(commands: &[String]) -> bool {
+	if !std::io::stdin().is_terminal() {
+		println!(
+			"{}",
+			"Context commands skipped: no terminal to confirm them on (confirm_context_commands = false runs them anyway)"
+				.bright_yellow()
+		);
+		return false;
+	}
+
+	println!("{}", "Context commands for this session:".bright_yellow());
+	for command in commands {
+		println!("  {}", command.bright_cyan());
+	}
+	print!(
+		"{}",
+		"Run them and add their output to the conversation? [y/N]: ".bright_cyan()
+	);
+	let _ = std::io::stdout().flush();
+	let mut answer = String::new();
+	std::io::stdin().read_line(&mut answer).unwrap_or_default();
+	matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+// One command's section of the message: the command line, how it ended and its output
+async fn run_context_command(command: &str, project_dir: &Path) -> String {
+	let mut cmd = if cfg!(target_os = "windows") {
+		let mut cmd = TokioCommand::new("cmd");
+		cmd.args(["/C", command]);
+		cmd
+	} else {
+		let mut cmd = TokioCommand::new("sh");
+		cmd.args(["-c", command]);
+		cmd
+	};
+	cmd.current_dir(project_dir)
+		.stdin(std::process::Stdio::null())
+		.kill_on_drop(true);
+
+	match tokio::time::timeout(CONTEXT_COMMAND_TIMEOUT, cmd.output()).await {
+		Ok(Ok(output)) => {
+			let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+			text.push_str(&String::from_utf8_lossy(&output.stderr));
+			let status = match output.status.code() {
+				Some(code) => format!("exit code {}", code),
+				None => "killed by a signal".to_string(),
+			};
+			format!(
+				"$ {} ({})\n```\n{}\n```",
+				command,
+				status,
+				output_tail(text.trim_end())
+			)
+		}
+		Ok(Err(e)) => format!("$ {} (failed to run: {})", command, e),
+		Err(_) => format!(
+			"$ {} (timed out after {} seconds)",
+			command,
+			CONTEXT_COMMAND_TIMEOUT.as_secs()
+		),
+	}
+}
+
+fn output_tail(text: &str) -> String {
+	if text.len() <= MAX_OUTPUT_BYTES {
+		return text.to_string();
+	}
+	let mut start = text.len() - MAX_OUTPUT_BYTES;
+	while !text.is_char_boundary(start) {
+		start += 1;
+	}
+	format!("[... earlier output cut]\n{}", &text[start..])
+}
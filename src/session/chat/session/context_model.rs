@@ -0,0 +1,82 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Context model selection - switch the session model by context size (context_models)
+
+use super::core::ChatSession;
+use super::utils::format_number;
+use crate::config::Config;
+use colored::Colorize;
+
+/// Context size based model tracking for context_models
+#[derive(Debug, Clone, Default)]
+pub struct ContextModelState {
+	/// Session model before the first switch, used below the lowest min_tokens
+	pub base_model: Option<String>,
+	/// Model the session was last switched to
+	pub selected: Option<String>,
+	/// The model was changed by hand since - no more switching this session
+	pub manual: bool,
+}
+
+impl ChatSession {
+	/// Switch to the context_models entry for the current context size
+	/// Returns the new model when the session switched
+	pub fn select_model_for_context(&mut self, config: &Config) -> Option<String> {
+		if config.context_models.is_empty() || self.context_model.manual {
+			return None;
+		}
+		// The rate limit fallback owns the model until it switches back
+		if self.rate_limit.switched_from.is_some() {
+			return None;
+		}
+		// The model was changed by hand (/model) since the last switch - leave it alone
+		if let Some(selected) = &self.context_model.selected {
+			if self.model != *selected {
+				self.context_model.manual = true;
+				return None;
+			}
+		}
+
+		let context_tokens = crate::session::estimate_message_tokens(&self.session.messages);
+		let target = config
+			.context_models
+			.iter()
+			.filter(|entry| entry.min_tokens <= context_tokens)
+			.max_by_key(|entry| entry.min_tokens)
+			.map(|entry| entry.model.clone())
+			.or_else(|| self.context_model.base_model.clone())
+			.unwrap_or_else(|| self.model.clone());
+		if target == self.model {
+			return None;
+		}
+
+		println!(
+			"{}",
+			format!(
+				"Context at ~{} tokens, switching from {} to {} (context_models)",
+				format_number(context_tokens as u64),
+				self.model,
+				target
+			)
+			.bright_cyan()
+		);
+		if self.context_model.base_model.is_none() {
+			self.context_model.base_model = Some(self.model.clone());
+		}
+		self.context_model.selected = Some(target.clone());
+		self.set_model(&target);
+		Some(target)
+	}
+}
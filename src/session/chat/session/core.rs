@@ -16,6 +16,7 @@
 
 use super::utils::format_number;
 use crate::config::Config;
+use crate::session::chat::response::tool_execution::ToolConfirmation;
 use crate::session::{get_sessions_dir, load_session, Session};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -54,7 +55,12 @@ pub struct ChatSession {
 	pub estimated_cost: f64,
 	pub cache_next_user_message: bool, // Flag to cache the next user message
 	pub spending_threshold_checkpoint: f64, // Track spending at last threshold check
+	pub spending_limit_reached: bool,  // Declined to continue past the spending threshold
 	pub pending_image: Option<crate::session::image::ImageAttachment>, // Pending image attachment
+	pub tool_confirmation: ToolConfirmation, // Confirmation gate before tool calls run
+	pub rate_limit: super::RateLimitState, // Rate limit tracking for rate_limit_fallback_model
+	pub context_model: super::ContextModelState, // Model switching by context size (context_models)
+	pub run_layers_next: bool,         // Send the next user message through the layer pipeline
 }
 
 impl ChatSession {
@@ -81,6 +87,7 @@ impl ChatSession {
 			input_tokens: 0,
 			output_tokens: 0,
 			cached_tokens: 0,
+			cache_write_tokens: 0,
 			total_cost: 0.0,
 			duration_seconds: 0,
 			layer_stats: Vec::new(), // Initialize empty layer stats
@@ -89,6 +96,8 @@ impl ChatSession {
 			total_api_time_ms: 0,
 			total_tool_time_ms: 0,
 			total_layer_time_ms: 0,
+			version: crate::session::migrations::CURRENT_SESSION_VERSION,
+			exit_summary: None,
 		};
 
 		Self {
@@ -109,7 +118,12 @@ impl ChatSession {
 			estimated_cost: 0.0,                // Initialize estimated cost as zero
 			cache_next_user_message: false,     // Initialize cache flag
 			spending_threshold_checkpoint: 0.0, // Initialize spending checkpoint
+			spending_limit_reached: false,      // Initialize spending limit flag
 			pending_image: None,                // Initialize pending image
+			tool_confirmation: ToolConfirmation::default(),
+			rate_limit: Default::default(),    // Initialize rate limit tracking
+			context_model: Default::default(), // Initialize context model tracking
+			run_layers_next: false,            // Set by the runner for the first message
 		}
 	}
 
@@ -117,6 +131,7 @@ impl ChatSession {
 	pub fn initialize(
 		name: Option<String>,
 		resume: Option<String>,
+		resume_latest: bool,
 		model: Option<String>,
 		temperature: Option<f32>,
 		config: &Config,
@@ -124,6 +139,20 @@ impl ChatSession {
 	) -> Result<Self> {
 		let sessions_dir = get_sessions_dir()?;
 
+		// Resolve --resume-latest to the most recently modified session
+		let resume = if resume_latest {
+			match crate::session::latest_session_name()? {
+				Some(latest) => Some(latest),
+				None => {
+					return Err(anyhow::anyhow!(
+						"No previous session found to resume. Start a new one with 'octomind session'."
+					))
+				}
+			}
+		} else {
+			resume
+		};
+
 		// Determine session name
 		let session_name = if let Some(name_arg) = &name {
 			name_arg.clone()
@@ -135,6 +164,7 @@ impl ChatSession {
 		};
 
 		let session_file = sessions_dir.join(format!("{}.jsonl", session_name));
+		crate::mcp::dev::scratchpad::set_session(&session_name);
 
 		// Get temperature from role config if not provided via command line
 		let effective_temperature = if let Some(temp) = temperature {
@@ -155,8 +185,8 @@ impl ChatSession {
 				));
 			}
 			true
-		} else if name.is_some() && session_file.exists() {
-			// Named session that exists - resume it
+		} else if name.is_some() && session_file.exists() && !crate::session::is_ephemeral() {
+			// Named session that exists - resume it (--no-save always starts fresh)
 			true
 		} else {
 			// Create new session
@@ -215,6 +245,14 @@ impl ChatSession {
 						"Cost:".blue(),
 						session.info.total_cost.to_string().bright_magenta()
 					);
+					if let Some(summary) = &session.info.exit_summary {
+						let summary_time =
+							DateTime::<Utc>::from_timestamp(summary.created_at as i64, 0)
+								.map(|dt| dt.naive_local().format("%Y-%m-%d %H:%M:%S").to_string())
+								.unwrap_or_else(|| "Unknown".to_string());
+						println!("{} {}", "Last summary:".blue(), summary_time.white());
+						println!("{}", summary.text.dimmed());
+					}
 
 					// Create chat session from loaded session
 					let restored_model = session.info.model.clone(); // Extract model before moving session
@@ -226,7 +264,12 @@ impl ChatSession {
 						estimated_cost: 0.0,
 						cache_next_user_message: false,     // Initialize cache flag
 						spending_threshold_checkpoint: 0.0, // Initialize spending checkpoint
+						spending_limit_reached: false,      // Initialize spending limit flag
 						pending_image: None,                // Initialize pending image
+						tool_confirmation: ToolConfirmation::default(),
+						rate_limit: Default::default(), // Initialize rate limit tracking
+						context_model: Default::default(), // Initialize context model tracking
+						run_layers_next: false,         // Set by the runner for the first message
 					};
 
 					// Update the estimated cost from the loaded session
@@ -268,6 +311,7 @@ impl ChatSession {
 					// Generate a new unique session name using the new format
 					let new_session_name = generate_session_name();
 					let new_session_file = sessions_dir.join(format!("{}.jsonl", new_session_name));
+					crate::mcp::dev::scratchpad::set_session(&new_session_name);
 
 					println!(
 						"{}",
@@ -275,7 +319,7 @@ impl ChatSession {
 					);
 
 					// Create file if it doesn't exist
-					if !new_session_file.exists() {
+					if !new_session_file.exists() && !crate::session::is_ephemeral() {
 						let file = File::create(&new_session_file)?;
 						drop(file);
 					}
@@ -312,9 +356,15 @@ impl ChatSession {
 				"{}",
 				format!("Starting new session: {}", session_name).bright_green()
 			);
+			if crate::session::is_ephemeral() {
+				println!(
+					"{}",
+					"Ephemeral session (--no-save): nothing will be written to disk".yellow()
+				);
+			}
 
 			// Create session file if it doesn't exist
-			if !session_file.exists() {
+			if !session_file.exists() && !crate::session::is_ephemeral() {
 				let file = File::create(&session_file)?;
 				drop(file);
 			}
@@ -350,6 +400,19 @@ impl ChatSession {
 		&self.session.info.model
 	}
 
+	/// Cost, token totals and context usage for the interactive prompt (prompt_status)
+	pub fn prompt_status(&self) -> crate::session::chat::PromptStatus {
+		let context_window = crate::providers::ProviderFactory::get_provider_for_model(&self.model)
+			.map(|(provider, model)| provider.get_max_input_tokens(&model))
+			.unwrap_or(0);
+		crate::session::chat::PromptStatus {
+			total_cost: self.session.info.total_cost,
+			total_tokens: self.session.info.input_tokens + self.session.info.output_tokens,
+			context_tokens: crate::session::estimate_message_tokens(&self.session.messages),
+			context_window,
+		}
+	}
+
 	/// Attach image from file path
 	pub async fn attach_image_from_path(&mut self, path: &str) -> Result<()> {
 		use crate::session::image::ImageProcessor;
@@ -56,6 +56,13 @@ impl ChatSession {
 			format_number(self.session.info.output_tokens).bright_green(),
 			format_number(self.session.info.cached_tokens).bright_magenta()
 		);
+		if self.session.info.cache_write_tokens > 0 {
+			println!(
+				"{} {} input tokens written to cache",
+				"Cache writes:".yellow(),
+				format_number(self.session.info.cache_write_tokens).bright_magenta()
+			);
+		}
 
 		// Cost information
 		println!(
@@ -504,6 +511,11 @@ impl ChatSession {
 				markdown_content.push_str(&format!("**Tool:** {}\n", name));
 			}
 
+			// Add the model that produced an assistant message
+			if let Some(ref model) = message.model {
+				markdown_content.push_str(&format!("**Model:** {}\n", model));
+			}
+
 			// Add content
 			let content = if let Some(limit) = content_limit {
 				if message.content.chars().count() > limit {
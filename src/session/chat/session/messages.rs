@@ -88,6 +88,7 @@ impl ChatSession {
 					"{}",
 					"✗ Session cancelled by user due to spending threshold.".bright_red()
 				);
+				self.spending_limit_reached = true;
 				Ok(false)
 			}
 		} else {
@@ -95,6 +96,45 @@ impl ChatSession {
 		}
 	}
 
+	// Drop all messages to start the conversation over (/clear)
+	// Token and cost totals are kept for the session unless reset_stats
+	pub fn clear_conversation(&mut self, reset_stats: bool) -> Result<()> {
+		// Loading the session skips everything before a restoration point
+		crate::session::logger::log_restoration_point(
+			&self.session.info.name,
+			"/clear - Conversation cleared",
+			"",
+		)?;
+
+		self.session.messages.clear();
+		self.session.current_non_cached_tokens = 0;
+		self.session.current_total_tokens = 0;
+		self.session.last_cache_checkpoint_time = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs();
+		self.last_response.clear();
+		self.cache_next_user_message = false;
+		self.pending_image = None;
+
+		if reset_stats {
+			let info = &mut self.session.info;
+			info.input_tokens = 0;
+			info.output_tokens = 0;
+			info.cached_tokens = 0;
+			info.total_cost = 0.0;
+			info.layer_stats.clear();
+			info.tool_calls = 0;
+			info.total_api_time_ms = 0;
+			info.total_tool_time_ms = 0;
+			info.total_layer_time_ms = 0;
+			self.estimated_cost = 0.0;
+			self.spending_threshold_checkpoint = 0.0;
+			self.spending_limit_reached = false;
+		}
+		Ok(())
+	}
+
 	// Add a system message
 	pub fn add_system_message(&mut self, content: &str) -> Result<()> {
 		// Log to raw session log
@@ -192,6 +232,8 @@ impl ChatSession {
 			name: Some(tool_name.to_string()),
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		};
 
 		// Add message to session
@@ -239,8 +281,11 @@ impl ChatSession {
 			);
 		}
 
-		// Add message to session
-		let message = self.session.add_message("assistant", content);
+		// Add message to session, recording the model that produced it
+		self.session.add_message("assistant", content);
+		let message = self.session.messages.last_mut().unwrap();
+		message.model = Some(self.model.clone());
+		let message = message.clone();
 		self.last_response = content.to_string();
 
 		// Log the raw exchange if available (legacy)
@@ -251,6 +296,8 @@ impl ChatSession {
 		// Update token counts and estimated costs if we have usage data
 		if let Some(ex) = &exchange {
 			if let Some(usage) = &ex.usage {
+				crate::session::chat::CostTracker::check_completion_size(usage, config);
+
 				// Simple token extraction with clean provider interface
 				let cached_tokens = usage.cached_tokens;
 				let regular_prompt_tokens = usage.prompt_tokens.saturating_sub(cached_tokens);
@@ -267,6 +314,7 @@ impl ChatSession {
 					regular_prompt_tokens,
 					usage.output_tokens,
 					cached_tokens,
+					usage.cache_write_tokens,
 				);
 
 				// Check if we should automatically move the cache marker
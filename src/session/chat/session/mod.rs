@@ -14,12 +14,19 @@
 
 // Session module implementation
 mod commands;
+mod context_commands;
+mod context_model;
 mod core;
 mod display;
 mod messages;
+mod rate_limit;
 mod runner;
 mod utils;
 
+pub use context_model::ContextModelState;
 pub use core::ChatSession;
-pub use runner::{run_interactive_session, run_interactive_session_with_input};
+pub use rate_limit::{chat_completion_with_fallback, is_rate_limit_error, RateLimitState};
+pub use runner::{
+	run_interactive_session, run_interactive_session_with_input, RunOutcome, RunStatus,
+};
 pub use utils::format_number;
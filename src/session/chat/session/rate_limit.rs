@@ -0,0 +1,225 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Rate limit fallback - switch the session model after repeated HTTP 429 errors
+
+use super::core::ChatSession;
+use crate::config::Config;
+use crate::providers::{AiProvider, ProviderFactory, ProviderHttpError, ProviderResponse};
+use crate::session::chat::retry_budget;
+use crate::session::Message;
+use anyhow::Result;
+use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Wait before retrying a rate limited request on the same model
+const RATE_LIMIT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Rate limit tracking for the session model
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitState {
+	/// Consecutive requests rejected with HTTP 429
+	pub consecutive_errors: u32,
+	/// Original model and when the session switched away from it
+	pub switched_from: Option<(String, Instant)>,
+}
+
+/// Whether a provider error is a rate limit (HTTP 429) response
+pub fn is_rate_limit_error(error: &anyhow::Error) -> bool {
+	error.chain().any(|cause| {
+		cause
+			.downcast_ref::<ProviderHttpError>()
+			.is_some_and(|http_error| http_error.status == 429)
+	})
+}
+
+impl ChatSession {
+	/// Switch back from the fallback model once rate_limit_restore_seconds have passed
+	/// Returns the restored model
+	pub fn restore_model_after_rate_limit(&mut self, config: &Config) -> Option<String> {
+		let (original, since) = self.rate_limit.switched_from.as_ref()?;
+
+		// The model was changed by hand (/model) since the switch - nothing to restore
+		if self.model != config.rate_limit_fallback_model {
+			self.rate_limit.switched_from = None;
+			return None;
+		}
+		if config.rate_limit_restore_seconds == 0
+			|| since.elapsed().as_secs() < config.rate_limit_restore_seconds
+		{
+			return None;
+		}
+
+		let original = original.clone();
+		println!(
+			"{}",
+			format!(
+				"Rate limit cooldown passed, switching back from {} to {}",
+				self.model, original
+			)
+			.bright_cyan()
+		);
+		self.set_model(&original);
+		self.rate_limit = RateLimitState::default();
+		Some(original)
+	}
+
+	/// Switch to rate_limit_fallback_model, unless the session is already using it
+	/// Returns the fallback model
+	fn switch_to_rate_limit_fallback(&mut self, config: &Config) -> Option<String> {
+		let fallback = &config.rate_limit_fallback_model;
+		if fallback.is_empty() || self.model == *fallback {
+			return None;
+		}
+
+		println!(
+			"{}",
+			format!(
+				"⚠️  {} is rate limited ({} consecutive 429 errors), switching to {}{}",
+				self.model,
+				self.rate_limit.consecutive_errors,
+				fallback,
+				if config.rate_limit_restore_seconds > 0 {
+					format!(" for {}s", config.rate_limit_restore_seconds)
+				} else {
+					" for the rest of the session".to_string()
+				}
+			)
+			.bright_yellow()
+		);
+		self.rate_limit = RateLimitState {
+			consecutive_errors: 0,
+			switched_from: Some((self.model.clone(), Instant::now())),
+		};
+		self.set_model(fallback);
+		Some(fallback.clone())
+	}
+
+	// Runtime model change, same as /model
+	pub(super) fn set_model(&mut self, model: &str) {
+		self.model = model.to_string();
+		self.session.info.model = model.to_string();
+	}
+}
+
+/// Send a request for the session, retrying rate limited requests and switching to
+/// rate_limit_fallback_model after rate_limit_fallback_after consecutive 429 errors
+pub async fn chat_completion_with_fallback(
+	chat_session: &mut ChatSession,
+	mut provider: Box<dyn AiProvider>,
+	mut model: String,
+	messages: &[Message],
+	temperature: f32,
+	config: &Config,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<ProviderResponse> {
+	loop {
+		let result = provider
+			.chat_completion(
+				messages,
+				&model,
+				temperature,
+				config,
+				cancellation_token.clone(),
+			)
+			.await;
+
+		let rate_limited = matches!(&result, Err(e) if is_rate_limit_error(e));
+		if !rate_limited {
+			if result.is_ok() {
+				chat_session.rate_limit.consecutive_errors = 0;
+			}
+			return result;
+		}
+		if config.rate_limit_fallback_model.is_empty() {
+			return result;
+		}
+
+		chat_session.rate_limit.consecutive_errors += 1;
+		let attempts = chat_session.rate_limit.consecutive_errors;
+		if attempts < config.rate_limit_fallback_after {
+			retry_budget::take_retry("the rate limited request")?;
+			println!(
+				"{}",
+				format!(
+					"Rate limited by the provider ({}/{}), retrying in {}s...",
+					attempts,
+					config.rate_limit_fallback_after,
+					RATE_LIMIT_RETRY_DELAY.as_secs()
+				)
+				.yellow()
+			);
+			if !wait_for_retry(&cancellation_token).await {
+				return result;
+			}
+			continue;
+		}
+
+		match chat_session.switch_to_rate_limit_fallback(config) {
+			Some(fallback) => {
+				retry_budget::take_retry(&format!("the request on fallback model {}", fallback))?;
+				(provider, model) = ProviderFactory::get_provider_for_model(&fallback)?;
+			}
+			None => {
+				// Already on the fallback model - give up on this request
+				chat_session.rate_limit.consecutive_errors = 0;
+				return result;
+			}
+		}
+	}
+}
+
+// Sleep before a retry; false when the operation was cancelled meanwhile
+async fn wait_for_retry(cancellation_token: &Option<Arc<AtomicBool>>) -> bool {
+	let deadline = Instant::now() + RATE_LIMIT_RETRY_DELAY;
+	while Instant::now() < deadline {
+		if let Some(token) = cancellation_token {
+			if token.load(Ordering::SeqCst) {
+				return false;
+			}
+		}
+		tokio::time::sleep(Duration::from_millis(100)).await;
+	}
+	true
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn http_error(status: u16) -> anyhow::Error {
+		ProviderHttpError::new(
+			reqwest::StatusCode::from_u16(status).unwrap(),
+			format!("OpenAI API error: HTTP {}", status),
+		)
+		.into()
+	}
+
+	#[test]
+	fn test_is_rate_limit_error() {
+		assert!(is_rate_limit_error(&http_error(429)));
+		assert!(is_rate_limit_error(
+			&http_error(429).context("Request failed")
+		));
+
+		// Other statuses and errors without a status are not rate limits
+		assert!(!is_rate_limit_error(&http_error(500)));
+		assert!(!is_rate_limit_error(&http_error(503)));
+		assert!(!is_rate_limit_error(&anyhow::anyhow!(
+			"Request failed: HTTP 429 Too Many Requests"
+		)));
+	}
+}
@@ -16,12 +16,22 @@
 
 use super::super::animation::{show_loading_animation, show_no_animation};
 use super::super::commands::*;
-use super::super::context_truncation::check_and_truncate_context;
+use super::super::context_truncation::{
+	apply_tool_churn_compaction, apply_tool_result_retention, check_and_compact_context,
+	check_and_truncate_context,
+};
+use super::super::custom_commands::expand_custom_command;
+use super::super::exit_summary::write_exit_summary;
 use super::super::input::read_user_input;
+use super::super::prompt_reload::PromptWatcher;
 use super::super::response::process_response;
+use super::super::response::tool_execution::{ToolConfirmMode, ToolConfirmation};
+use super::super::retry_budget;
 use super::core::ChatSession;
-use crate::config::Config;
+use crate::config::{Config, LayerFailurePolicy};
 use crate::session::create_system_prompt;
+use crate::session::layers::orchestrator::{abort_after_layer_failure, layers_modified_session};
+use crate::session::layers::LayeredOrchestrator;
 use crate::{log_debug, log_info};
 use anyhow::Result;
 use std::io::Write; // Added for stdout flushing
@@ -47,6 +57,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		#[arg(long, short)]
 		resume: Option<String>,
 
+		/// Resume the most recently modified session
+		#[arg(long)]
+		resume_latest: bool,
+
+		/// Send the first message straight to the model, without layers
+		#[arg(long)]
+		skip_layers: bool,
+
 		/// Model to use instead of the one configured in config
 		#[arg(long)]
 		model: Option<String>,
@@ -58,11 +76,15 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		/// Session role: developer (default with layers and tools) or assistant (simple chat without tools)
 		#[arg(long, default_value = "developer")]
 		role: String,
+
+		/// Ask before executing tool calls: first, always or off
+		#[arg(long)]
+		confirm_tools: Option<ToolConfirmMode>,
 	}
 
 	// Read args as SessionArgs
 	let args_str = format!("{:?}", args);
-	let session_args: SessionArgs = {
+	let mut session_args: SessionArgs = {
 		// Get model
 		let model = if args_str.contains("model: Some(\"") {
 			let start = args_str.find("model: Some(\"").unwrap() + 13;
@@ -90,6 +112,12 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			None
 		};
 
+		// Get resume_latest
+		let resume_latest = args_str.contains("resume_latest: true");
+
+		// Get skip_layers
+		let skip_layers = args_str.contains("skip_layers: true");
+
 		// Get role
 		let role = if args_str.contains("role: \"") {
 			let start = args_str.find("role: \"").unwrap() + 7;
@@ -112,18 +140,30 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			0.7 // Default temperature
 		};
 
+		// Get tool confirmation mode
+		let confirm_tools = [
+			ToolConfirmMode::Off,
+			ToolConfirmMode::First,
+			ToolConfirmMode::Always,
+		]
+		.into_iter()
+		.find(|mode| args_str.contains(&format!("confirm_tools: Some({:?})", mode)));
+
 		SessionArgs {
 			name,
 			resume,
+			resume_latest,
+			skip_layers,
 			model,
 			temperature,
 			role,
+			confirm_tools,
 		}
 	};
 
-	// For developer role, show MCP server status
+	// For developer role, show MCP server status (octocode hints unless turned off)
 	let current_dir = std::env::current_dir()?;
-	if session_args.role == "developer" {
+	if session_args.role == "developer" && config.octocode_integration {
 		// Check if external MCP server is configured
 		let role_config = config.get_role_config(&session_args.role);
 		let mcp_config = &role_config.1;
@@ -182,6 +222,7 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	let mut chat_session = ChatSession::initialize(
 		session_args.name,
 		session_args.resume,
+		session_args.resume_latest,
 		session_args.model.clone(),
 		Some(session_args.temperature),
 		&config_for_role,
@@ -193,12 +234,22 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		chat_session.model = runtime_model.clone();
 		log_info!("Using runtime model override: {}", runtime_model);
 	}
+	ensure_runtime_model(&chat_session.model, &session_args.role)?;
 
 	// Always set the temperature from the command line (runtime only)
 	chat_session.temperature = session_args.temperature;
 
-	// Track if the first message has been processed through layers
-	let mut first_message_processed = !chat_session.session.messages.is_empty();
+	// Confirmation gate before tool execution (interactive sessions only)
+	let confirm_mode = match session_args.confirm_tools {
+		Some(mode) => mode,
+		None if config_for_role.confirm_first_tool_call => ToolConfirmMode::First,
+		None => ToolConfirmMode::Off,
+	};
+	chat_session.tool_confirmation = ToolConfirmation::new(confirm_mode);
+
+	// The first message of a new session goes through layers, unless --skip-layers
+	chat_session.run_layers_next =
+		!session_args.skip_layers && chat_session.session.messages.is_empty();
 	println!("Interactive coding session started. Type your questions/requests.");
 	println!("Type /help for available commands.");
 
@@ -209,120 +260,24 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			"{}",
 			"💡 Tip: Use ↑/↓ arrows or Ctrl+R for command history search".bright_yellow()
 		);
+		#[cfg(unix)]
+		println!(
+			"{}",
+			"💡 Tip: Press Ctrl+\\ during a response to add an instruction after the current step"
+				.bright_yellow()
+		);
 	}
 
 	// Initialize with system prompt if new session
 	if chat_session.session.messages.is_empty() {
-		// Create system prompt based on role
-		let system_prompt = create_system_prompt(&current_dir, config, &session_args.role).await;
-		chat_session.add_system_message(&system_prompt)?;
-
-		// Process layer system prompts during session initialization
-		// This ensures layer system prompts are processed once and cached for the entire session
-		let (role_config, _, _, _, _) = config.get_role_config(&session_args.role);
-		if role_config.enable_layers {
-			use crate::session::layers::LayeredOrchestrator;
-			// Create orchestrator with processed system prompts
-			let _orchestrator = LayeredOrchestrator::from_config_with_processed_prompts(
-				config,
-				&session_args.role,
-				&current_dir,
-			)
-			.await;
-			log_info!("Layer system prompts processed and cached for session");
-		}
-
-		// CRITICAL FIX: Apply automatic cache markers for system messages AND tool definitions
-		// This ensures consistent caching behavior across all supported models
-		let supports_caching = crate::session::model_supports_caching(&chat_session.model);
-		let has_tools = !config.mcp.servers.is_empty();
-
-		if supports_caching {
-			let cache_manager = crate::session::cache::CacheManager::new();
-			cache_manager.add_automatic_cache_markers(
-				&mut chat_session.session.messages,
-				has_tools,
-				supports_caching,
-			);
-
-			log_info!("System prompt has been automatically marked for caching to save tokens in future interactions.");
-			// Save the session to ensure the cached status is persisted
-			let _ = chat_session.save();
-		} else {
-			// Don't show warning for models that don't support caching
-			log_info!(
-				"Note: This model doesn't support caching, but system prompt is still optimized."
-			);
-		}
-
-		// Add assistant welcome message with variable processing
-		let role_config = config.get_role_config_struct(&session_args.role);
-		let welcome_message =
-			crate::session::helper_functions::process_placeholders_async_with_role(
-				&role_config.welcome,
-				&current_dir,
-				Some(&session_args.role),
-			)
-			.await;
-
-		chat_session.add_assistant_message(
-			&welcome_message,
-			None,
+		start_conversation(
+			&mut chat_session,
+			config,
 			&config_for_role,
 			&session_args.role,
-		)?;
-
-		// Apply cache marker to welcome message as well for consistency
-		if supports_caching {
-			let cache_manager = crate::session::cache::CacheManager::new();
-			cache_manager.add_automatic_cache_markers(
-				&mut chat_session.session.messages,
-				has_tools,
-				supports_caching,
-			);
-		}
-
-		// Check for custom instructions file in current working directory
-		let instructions_filename = &config.custom_instructions_file_name;
-		if !instructions_filename.is_empty() {
-			let instructions_path = current_dir.join(instructions_filename);
-			if instructions_path.exists() {
-				match std::fs::read_to_string(&instructions_path) {
-					Ok(instructions_content) => {
-						// Process placeholders in instructions content using the same pattern as welcome message
-						let processed_instructions =
-							crate::session::helper_functions::process_placeholders_async_with_role(
-								&instructions_content,
-								&current_dir,
-								Some(&session_args.role),
-							)
-							.await;
-
-						// Add as user message after welcome message
-						chat_session.add_user_message(&processed_instructions)?;
-
-						// Apply cache marker to the assistant message (welcome message) if caching is supported
-						// This ensures the assistant message before the instructions is cached
-						if supports_caching {
-							let cache_manager = crate::session::cache::CacheManager::new();
-							cache_manager.add_automatic_cache_markers(
-								&mut chat_session.session.messages,
-								has_tools,
-								supports_caching,
-							);
-						}
-
-						log_info!(
-							"Added {} content as user message with variable processing",
-							instructions_filename
-						);
-					}
-					Err(e) => {
-						log_debug!("Failed to read {}: {}", instructions_filename, e);
-					}
-				}
-			}
-		}
+			&current_dir,
+		)
+		.await?;
 	} else {
 		// Print the last few messages for context with colors if terminal supports them
 		let last_messages = chat_session
@@ -345,10 +300,19 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		}
 	}
 
+	// Track prompt source files so edits can be picked up without restarting
+	let mut prompt_watcher =
+		PromptWatcher::new(&chat_session, &current_dir, config, &session_args.role).await;
+
 	// Set up advanced cancellation system for proper CTRL+C handling
 	let ctrl_c_pressed = Arc::new(AtomicBool::new(false));
 	let ctrl_c_pressed_clone = ctrl_c_pressed.clone();
 
+	// Set by a second Ctrl+C: save and clean up at the next check, then exit
+	let force_exit = Arc::new(AtomicBool::new(false));
+	let force_exit_clone = force_exit.clone();
+	let force_exit_grace_seconds = config.force_exit_grace_seconds;
+
 	// Enhanced processing state tracking for smart cancellation
 	#[derive(Debug, Clone, PartialEq)]
 	enum ProcessingState {
@@ -378,10 +342,24 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 
 	// Set up sophisticated Ctrl+C handler with immediate feedback
 	ctrlc::set_handler(move || {
-		// Double Ctrl+C forces immediate exit
+		// Double Ctrl+C forces exit - after a quick save within the grace period,
+		// a third Ctrl+C (or force_exit_grace_seconds = 0) exits right away
 		if ctrl_c_pressed_clone.load(Ordering::SeqCst) {
-			println!("\n🛑 Forcing exit due to repeated Ctrl+C...");
-			std::process::exit(130); // 130 is standard exit code for SIGINT
+			if force_exit_grace_seconds == 0 || force_exit_clone.swap(true, Ordering::SeqCst) {
+				println!("\n🛑 Forcing exit due to repeated Ctrl+C...");
+				std::process::exit(130); // 130 is standard exit code for SIGINT
+			}
+			println!("\n🛑 Saving before exit... (Ctrl+C again to exit now)");
+			std::io::stdout().flush().unwrap();
+			std::thread::spawn(move || {
+				std::thread::sleep(std::time::Duration::from_secs(force_exit_grace_seconds));
+				println!(
+					"\n🛑 Save did not finish within {}s, exiting anyway",
+					force_exit_grace_seconds
+				);
+				std::process::exit(130);
+			});
+			return;
 		}
 
 		// Set the flag immediately
@@ -419,6 +397,9 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	})
 	.expect("Error setting Ctrl+C handler");
 
+	// Ctrl+\ pauses after the current tool/API step to inject an extra instruction
+	crate::session::chat::inject::spawn_inject_listener();
+
 	// We need to handle configuration reloading, so keep our own copy that we can update
 	let mut current_config = config_for_role.clone();
 
@@ -487,6 +468,15 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 				log_debug!("Warning: Failed to save session after smart cleanup: {}", e);
 			}
 
+			// Second Ctrl+C - saved, stop the servers and exit within the grace period
+			if force_exit.load(Ordering::SeqCst) {
+				if let Err(e) = crate::mcp::server::cleanup_servers() {
+					eprintln!("Warning: Error cleaning up MCP servers: {}", e);
+				}
+				println!("🛑 Session saved, exiting");
+				std::process::exit(130);
+			}
+
 			// Reset for next iteration
 			ctrl_c_pressed.store(false, Ordering::SeqCst);
 			*current_operation.lock().unwrap() = None;
@@ -499,8 +489,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		// Create a fresh cancellation flag for this iteration
 		let operation_cancelled = Arc::new(AtomicBool::new(false));
 
-		// Read user input with command completion and cost estimation
-		let mut input = read_user_input(chat_session.estimated_cost)?;
+		// Read user input with command completion and cost estimation (or full status)
+		let prompt_status = current_config
+			.prompt_status
+			.then(|| chat_session.prompt_status());
+		let mut input = read_user_input(chat_session.estimated_cost, prompt_status)?;
+
+		// A pause requested after the last step finished no longer applies
+		crate::session::chat::inject::clear_injection_request();
 
 		// Check if the input is an exit command from Ctrl+D
 		if input == "/exit" || input == "/quit" {
@@ -513,12 +509,19 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			continue;
 		}
 
+		// Custom commands expand into their prompt and go on as a normal message
+		if let Some(prompt) =
+			expand_custom_command(&input, &current_config, &session_args.role, &current_dir).await
+		{
+			input = prompt;
+		}
+
 		// Check if this is a command
 		if input.starts_with('/') {
 			// Handle special /done command separately
 			if input.trim() == "/done" {
-				// Reset first_message_processed to false so that the next message goes through layers again
-				first_message_processed = false;
+				// The next message starts a new task and goes through layers again
+				chat_session.run_layers_next = true;
 
 				// Apply reducer functionality to optimize context
 				let result = super::super::context_reduction::perform_context_reduction(
@@ -566,6 +569,7 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					let new_chat_session = ChatSession::initialize(
 						Some(new_session_name), // Use the name from the command
 						None,
+						false,
 						None, // Keep using the default model
 						None, // Use config temperature
 						&current_config,
@@ -575,8 +579,8 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					// Replace the current chat session
 					chat_session = new_chat_session;
 
-					// Reset first message flag for new session
-					first_message_processed = !chat_session.session.messages.is_empty();
+					// Only a new session sends its first message through layers
+					chat_session.run_layers_next = chat_session.session.messages.is_empty();
 
 					// Print the last few messages for context with colors
 					if !chat_session.session.messages.is_empty() {
@@ -602,12 +606,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					continue;
 				} else if input.starts_with(LAYERS_COMMAND) {
 					// This is a command that requires config reload
-					// Reload the configuration
-					match crate::config::Config::load() {
+					// Reload the configuration, keeping the command-line adjustments
+					match config.reload() {
 						Ok(updated_config) => {
 							// Update our current config with the new role-specific config
+							let response_schema = current_config.response_schema.take();
 							current_config =
 								updated_config.get_merged_config_for_role(&session_args.role);
+							current_config.response_schema = response_schema;
 							// Update thread config for logging macros
 							crate::config::set_thread_config(&current_config);
 							log_info!("Configuration reloaded successfully");
@@ -618,6 +624,109 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					}
 					// Continue with the session
 					continue;
+				} else if input.starts_with(ROLE_COMMAND) {
+					// Switch role: re-resolve config and tools, keep conversation history
+					use colored::*;
+					let new_role = match input.split_whitespace().nth(1) {
+						Some(name) if config.role_map.contains_key(name) => name.to_string(),
+						_ => {
+							println!(
+								"{}",
+								"Usage: /role <name> (run /role to list the available roles)"
+									.bright_yellow()
+							);
+							continue;
+						}
+					};
+					let old_role = std::mem::replace(&mut session_args.role, new_role);
+
+					let response_schema = current_config.response_schema.take();
+					current_config = config.get_merged_config_for_role(&session_args.role);
+					current_config.response_schema = response_schema;
+					crate::config::set_thread_config(&current_config);
+
+					// Make sure servers and tool map match the new role
+					if let Err(e) = crate::mcp::initialize_servers_for_role(&current_config).await {
+						log_info!("Warning: Failed to initialize MCP servers: {}", e);
+					}
+					if let Err(e) = crate::mcp::tool_map::initialize_tool_map(&current_config).await
+					{
+						log_info!("Warning: Failed to initialize tool map: {}", e);
+					}
+
+					println!(
+						"{}",
+						format!("Role changed from {} to {}", old_role, session_args.role)
+							.bright_green()
+					);
+
+					// Updating the system message alters the context, so ask first
+					print!(
+						"{}",
+						"Replace the system prompt with the one for the new role? [y/N]: "
+							.bright_cyan()
+					);
+					std::io::stdout().flush().unwrap();
+					let mut answer = String::new();
+					std::io::stdin().read_line(&mut answer).unwrap_or_default();
+
+					if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+						let system_prompt =
+							create_system_prompt(&current_dir, config, &session_args.role).await;
+						if let Some(system_msg) = chat_session
+							.session
+							.messages
+							.iter_mut()
+							.find(|m| m.role == "system")
+						{
+							system_msg.content = system_prompt;
+						}
+						if let Err(e) = chat_session.save() {
+							println!("{}: {}", "Failed to save session".bright_red(), e);
+						}
+						println!(
+							"{}",
+							"System prompt updated for the new role".bright_green()
+						);
+					} else {
+						println!(
+							"{}",
+							"System prompt unchanged; only config and tools were switched"
+								.bright_yellow()
+						);
+					}
+					continue;
+				} else if input.starts_with(CLEAR_COMMAND) {
+					// Confirmed /clear: start the conversation over in the same session
+					let reset_stats = input.split_whitespace().nth(1) == Some("all");
+					chat_session.clear_conversation(reset_stats)?;
+					start_conversation(
+						&mut chat_session,
+						config,
+						&current_config,
+						&session_args.role,
+						&current_dir,
+					)
+					.await?;
+					chat_session.run_layers_next = !session_args.skip_layers;
+					chat_session.save()?;
+					use colored::*;
+					println!(
+						"{}",
+						"Conversation cleared - the session continues with a fresh context"
+							.bright_green()
+					);
+					continue;
+				} else if input.starts_with(RELOAD_PROMPT_COMMAND) {
+					reload_prompt(
+						&mut prompt_watcher,
+						&mut chat_session,
+						&current_dir,
+						config,
+						&session_args.role,
+					)
+					.await;
+					continue;
 				} else {
 					// It's a regular exit command
 					break;
@@ -626,6 +735,18 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			continue;
 		}
 
+		// Pick up edits to the instructions file or prompt sources before sending
+		if current_config.auto_reload_prompt && !prompt_watcher.changed_files().is_empty() {
+			reload_prompt(
+				&mut prompt_watcher,
+				&mut chat_session,
+				&current_dir,
+				config,
+				&session_args.role,
+			)
+			.await;
+		}
+
 		// Check for cancellation before starting layered processing
 		if ctrl_c_pressed.load(Ordering::SeqCst) {
 			continue;
@@ -635,8 +756,12 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		// 1. Process through layers if needed (first message with layers enabled)
 		// 2. Use the processed input for the main model chat
 
-		// If layers are enabled and this is the first message, process it through layers first
-		if current_config.get_enable_layers(&session_args.role) && !first_message_processed {
+		// Provider and agent retries of this turn share one budget
+		retry_budget::start_turn(current_config.max_retries_per_turn);
+
+		// If layers are enabled and this message is due for them (first message of a new
+		// session, after /done or /layers on-next), process it through layers first
+		if current_config.get_enable_layers(&session_args.role) && chat_session.run_layers_next {
 			// Set processing state to layers
 			*processing_state.lock().unwrap() = ProcessingState::ProcessingLayers;
 
@@ -655,7 +780,7 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			// Process using layered architecture to get improved input
 			// Each layer processes function calls with its own model internally,
 			// so the final output already incorporates all function call results
-			let layered_result = super::super::layered_response::process_layered_response(
+			let layered_result = process_layers(
 				&input,
 				&mut chat_session,
 				&current_config,
@@ -671,20 +796,25 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 						continue;
 					}
 
-					// Check if layers modified the session (added messages via output_mode)
+					// Check if layers modified the session (wrote messages via output_mode)
 					let messages_after_layers = chat_session.session.messages.len();
-					let layers_modified_session = messages_after_layers > messages_before_layers;
+					let layers_modified_session = layers_modified_session(
+						&LayeredOrchestrator::from_config(&current_config, &session_args.role)
+							.output_modes(),
+						messages_before_layers,
+						messages_after_layers,
+					);
 
 					if layers_modified_session {
-						// Layers used output_mode append/replace and added messages to session
+						// Layers used output_mode append/replace and wrote messages to session
 						// Skip adding user message to avoid duplicates
 						log_info!(
-							"Layers modified session ({} messages added). Skipping user message addition.",
-							messages_after_layers - messages_before_layers
+							"Layers modified session ({} messages now). Skipping user message addition.",
+							messages_after_layers
 						);
 
-						// Mark that we've processed the first message through layers
-						first_message_processed = true;
+						// Later messages skip layers until /layers on-next or /done
+						chat_session.run_layers_next = false;
 
 						// Continue to next iteration - session already has the layer messages
 						continue;
@@ -693,8 +823,8 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 						// Use the processed input from layers instead of the original input
 						input = processed_input;
 
-						// Mark that we've processed the first message through layers
-						first_message_processed = true;
+						// Later messages skip layers until /layers on-next or /done
+						chat_session.run_layers_next = false;
 
 						log_info!(
 							"{}",
@@ -708,16 +838,29 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 						continue;
 					}
 
-					// Print colorful error message and continue with original input
 					use colored::*;
 					println!(
 						"\n{}: {}",
 						"Error processing through layers".bright_red(),
 						e
 					);
+					if abort_after_layer_failure(
+						current_config
+							.get_role_config_struct(&session_args.role)
+							.layer_failure_policy,
+						&mut chat_session.session.messages,
+						messages_before_layers,
+					) {
+						// Drop the message; the next one goes through the layers again
+						println!(
+							"{}",
+							"Message not sent (layer_failure_policy = \"abort\").".yellow()
+						);
+						continue;
+					}
 					println!("{}", "Continuing with original input.".yellow());
 					// Still mark as processed to avoid infinite retry loops
-					first_message_processed = true;
+					chat_session.run_layers_next = false;
 				}
 			}
 		}
@@ -731,6 +874,16 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 				.as_millis()
 		);
 
+		// Guard against a single oversized message before it reaches the context
+		input = match enforce_user_message_limit(input, &current_config, true) {
+			Some(capped) => capped,
+			None => continue,
+		};
+
+		if current_config.auto_context {
+			input = attach_auto_context(input, &current_dir);
+		}
+
 		let user_message_index = chat_session.session.messages.len();
 
 		// UNIFIED STANDARD PROCESSING FLOW
@@ -754,6 +907,32 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			user_message_index
 		);
 
+		// Shrink older tool results to one-line summaries beyond tool_result_retention
+		if apply_tool_result_retention(&mut chat_session, &current_config) {
+			let _ = chat_session.save();
+		}
+
+		// Collapse older runs of tool calls into summaries (compact_tool_churn)
+		if apply_tool_churn_compaction(&mut chat_session, &current_config) {
+			// The current user message is still last, at a new index
+			if let Some(ref mut op) = *current_operation.lock().unwrap() {
+				op.user_message_index = Some(chat_session.session.messages.len() - 1);
+			}
+			let _ = chat_session.save();
+		}
+
+		// Fold older turns into a checkpoint when the context nears the model's window
+		match check_and_compact_context(&mut chat_session, &current_config).await {
+			Ok(true) => {
+				// The current user message is still last, at a new index
+				if let Some(ref mut op) = *current_operation.lock().unwrap() {
+					op.user_message_index = Some(chat_session.session.messages.len() - 1);
+				}
+			}
+			Ok(false) => {}
+			Err(e) => log_debug!("Auto-compaction skipped: {}", e),
+		}
+
 		// Check if we need to truncate the context to stay within token limits
 		let truncate_cancelled = Arc::new(AtomicBool::new(false));
 		check_and_truncate_context(
@@ -792,6 +971,9 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		// Set processing state to calling API
 		*processing_state.lock().unwrap() = ProcessingState::CallingAPI;
 
+		// Pick the session model for the current context size (context_models)
+		chat_session.select_model_for_context(&current_config);
+
 		// Call OpenRouter in a separate task
 		let model = chat_session.model.clone();
 		let temperature = chat_session.temperature;
@@ -877,7 +1059,9 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 
 		// Process the response
 		match api_result {
-			Ok(response) => {
+			Ok(mut response) => {
+				show_refusal(&mut response);
+
 				// Update operation context with assistant message info
 				if let Some(ref mut op) = *current_operation.lock().unwrap() {
 					op.assistant_message_index = Some(chat_session.session.messages.len());
@@ -966,6 +1150,15 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					// Print colorful error message
 					use colored::*;
 					println!("\n{}: {}", "Error processing response".bright_red(), e);
+				} else if current_config.copy_response_to_clipboard
+					&& !chat_session.last_response.is_empty()
+				{
+					// Opt-in: put the final response on the clipboard
+					use colored::*;
+					match super::commands::copy::copy_to_clipboard(&chat_session.last_response) {
+						Ok(()) => println!("{}", "Response copied to clipboard.".dimmed()),
+						Err(e) => log_debug!("{}", e),
+					}
 				}
 			}
 			Err(e) => {
@@ -1033,9 +1226,67 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		*current_operation.lock().unwrap() = None;
 	}
 
+	write_exit_summary(&mut chat_session, &current_config).await;
+
 	Ok(())
 }
 
+// Fail fast on a missing or malformed session model instead of a cryptic provider error
+fn ensure_runtime_model(model: &str, role: &str) -> Result<()> {
+	if model.trim().is_empty() {
+		return Err(anyhow::anyhow!(
+			"No model configured for role '{}'. Set model = \"provider:model\" in the config or pass --model provider:model",
+			role
+		));
+	}
+	crate::providers::ProviderFactory::parse_model(model)
+		.map(|_| ())
+		.map_err(|e| anyhow::anyhow!("Invalid model '{}' for role '{}': {}", model, role, e))
+}
+
+/// How a non-interactive run ended, mapped to the process exit code by the run command
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RunStatus {
+	#[default]
+	Completed,
+	/// The API call (or a tool follow-up) failed
+	ApiError,
+	/// Continuing past the spending threshold was declined
+	SpendingLimit,
+	/// The run was stopped by session_max_duration_seconds
+	TimeLimit,
+}
+
+/// Result of a non-interactive run
+#[derive(Debug, Clone, Default)]
+pub struct RunOutcome {
+	pub status: RunStatus,
+	/// Final assistant message, empty when no request was made
+	pub final_response: String,
+}
+
+impl RunOutcome {
+	fn from_session(
+		chat_session: &ChatSession,
+		api_failed: bool,
+		time_limit_reached: bool,
+	) -> Self {
+		let status = if time_limit_reached {
+			RunStatus::TimeLimit
+		} else if api_failed {
+			RunStatus::ApiError
+		} else if chat_session.spending_limit_reached {
+			RunStatus::SpendingLimit
+		} else {
+			RunStatus::Completed
+		};
+		Self {
+			status,
+			final_response: chat_session.last_response.clone(),
+		}
+	}
+}
+
 // Run a single non-interactive session with provided input
 // THIS IS just helper and USED as simplified version of interactive session
 // That used for run command THAT is not interactive and get request and process it
@@ -1044,7 +1295,7 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 	args: &T,
 	config: &Config,
 	initial_input: &str,
-) -> Result<()> {
+) -> Result<RunOutcome> {
 	use clap::Args;
 	use std::fmt::Debug;
 
@@ -1059,6 +1310,14 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 		#[arg(long, short)]
 		resume: Option<String>,
 
+		/// Resume the most recently modified session
+		#[arg(long)]
+		resume_latest: bool,
+
+		/// Send the first message straight to the model, without layers
+		#[arg(long)]
+		skip_layers: bool,
+
 		/// Model to use instead of the one configured in config
 		#[arg(long)]
 		model: Option<String>,
@@ -1102,6 +1361,12 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 			None
 		};
 
+		// Get resume_latest
+		let resume_latest = args_str.contains("resume_latest: true");
+
+		// Get skip_layers
+		let skip_layers = args_str.contains("skip_layers: true");
+
 		// Get role
 		let role = if args_str.contains("role: \"") {
 			let start = args_str.find("role: \"").unwrap() + 7;
@@ -1127,6 +1392,8 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 		SessionArgs {
 			name,
 			resume,
+			resume_latest,
+			skip_layers,
 			model,
 			temperature,
 			role,
@@ -1143,6 +1410,7 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 	let mut chat_session = ChatSession::initialize(
 		session_args.name,
 		session_args.resume,
+		session_args.resume_latest,
 		session_args.model.clone(),
 		Some(session_args.temperature),
 		&config_for_role,
@@ -1154,112 +1422,23 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 		chat_session.model = runtime_model.clone();
 		log_info!("Using runtime model override: {}", runtime_model);
 	}
+	ensure_runtime_model(&chat_session.model, &session_args.role)?;
 	chat_session.temperature = session_args.temperature;
 
-	// Track if the first message has been processed through layers
-	let first_message_processed = !chat_session.session.messages.is_empty();
+	// The first message of a new session goes through layers, unless --skip-layers
+	chat_session.run_layers_next =
+		!session_args.skip_layers && chat_session.session.messages.is_empty();
 
 	// Initialize with system prompt if new session - same as interactive
 	if chat_session.session.messages.is_empty() {
-		let system_prompt = create_system_prompt(&current_dir, config, &session_args.role).await;
-		chat_session.add_system_message(&system_prompt)?;
-
-		// Process layer system prompts - same as interactive
-		let (role_config, _, _, _, _) = config.get_role_config(&session_args.role);
-		if role_config.enable_layers {
-			use crate::session::layers::LayeredOrchestrator;
-			let _orchestrator = LayeredOrchestrator::from_config_with_processed_prompts(
-				config,
-				&session_args.role,
-				&current_dir,
-			)
-			.await;
-			log_info!("Layer system prompts processed and cached for session");
-		}
-
-		// Apply automatic cache markers - same as interactive
-		let supports_caching = crate::session::model_supports_caching(&chat_session.model);
-		let has_tools = !config.mcp.servers.is_empty();
-
-		if supports_caching {
-			let cache_manager = crate::session::cache::CacheManager::new();
-			cache_manager.add_automatic_cache_markers(
-				&mut chat_session.session.messages,
-				has_tools,
-				supports_caching,
-			);
-			log_info!("System prompt has been automatically marked for caching to save tokens in future interactions.");
-			let _ = chat_session.save();
-		} else {
-			log_info!(
-				"Note: This model doesn't support caching, but system prompt is still optimized."
-			);
-		}
-
-		// Add assistant welcome message - same as interactive
-		let role_config = config.get_role_config_struct(&session_args.role);
-		let welcome_message =
-			crate::session::helper_functions::process_placeholders_async_with_role(
-				&role_config.welcome,
-				&current_dir,
-				Some(&session_args.role),
-			)
-			.await;
-
-		chat_session.add_assistant_message(
-			&welcome_message,
-			None,
+		start_conversation(
+			&mut chat_session,
+			config,
 			&config_for_role,
 			&session_args.role,
-		)?;
-
-		// Apply cache marker to welcome message - same as interactive
-		if supports_caching {
-			let cache_manager = crate::session::cache::CacheManager::new();
-			cache_manager.add_automatic_cache_markers(
-				&mut chat_session.session.messages,
-				has_tools,
-				supports_caching,
-			);
-		}
-
-		// Check for custom instructions file - same as interactive
-		let instructions_filename = &config.custom_instructions_file_name;
-		if !instructions_filename.is_empty() {
-			let instructions_path = current_dir.join(instructions_filename);
-			if instructions_path.exists() {
-				match std::fs::read_to_string(&instructions_path) {
-					Ok(instructions_content) => {
-						let processed_instructions =
-							crate::session::helper_functions::process_placeholders_async_with_role(
-								&instructions_content,
-								&current_dir,
-								Some(&session_args.role),
-							)
-							.await;
-
-						chat_session.add_user_message(&processed_instructions)?;
-
-						if supports_caching {
-							let cache_manager = crate::session::cache::CacheManager::new();
-							cache_manager.add_automatic_cache_markers(
-								&mut chat_session.session.messages,
-								has_tools,
-								supports_caching,
-							);
-						}
-
-						log_info!(
-							"Added {} content as user message with variable processing",
-							instructions_filename
-						);
-					}
-					Err(e) => {
-						log_debug!("Failed to read {}: {}", instructions_filename, e);
-					}
-				}
-			}
-		}
+			&current_dir,
+		)
+		.await?;
 	}
 
 	// Set up cancellation handling for non-interactive mode (simplified)
@@ -1282,6 +1461,26 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 	let mut input = initial_input.to_string();
 	let operation_cancelled = Arc::new(AtomicBool::new(false));
 
+	// Wall-clock limit for the whole run: cancel whatever is in flight on expiry
+	let time_limit_reached = Arc::new(AtomicBool::new(false));
+	if config.session_max_duration_seconds > 0 {
+		let limit = tokio::time::Duration::from_secs(config.session_max_duration_seconds);
+		let reached = time_limit_reached.clone();
+		let cancel = operation_cancelled.clone();
+		tokio::spawn(async move {
+			tokio::time::sleep(limit).await;
+			reached.store(true, Ordering::SeqCst);
+			cancel.store(true, Ordering::SeqCst);
+		});
+	}
+
+	// Custom commands - same as interactive
+	if let Some(prompt) =
+		expand_custom_command(&input, &current_config, &session_args.role, &current_dir).await
+	{
+		input = prompt;
+	}
+
 	// Check if this is a command (same logic as interactive session)
 	if input.starts_with('/') {
 		use colored::*;
@@ -1293,7 +1492,7 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 				"✓ Session optimized and ready for next message".bright_green()
 			);
 			let _ = chat_session.save();
-			return Ok(());
+			return Ok(RunOutcome::default());
 		}
 
 		// Process the command
@@ -1305,21 +1504,37 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 			// Check if it's a session switch command
 			if input.starts_with(crate::session::chat::commands::SESSION_COMMAND) {
 				println!("{}", "Note: Session switching is not supported in run mode. Use 'octomind session' for interactive session management.".yellow());
+			} else if input.starts_with(CLEAR_COMMAND) {
+				// Confirmed /clear - same as interactive
+				let reset_stats = input.split_whitespace().nth(1) == Some("all");
+				chat_session.clear_conversation(reset_stats)?;
+				start_conversation(
+					&mut chat_session,
+					config,
+					&current_config,
+					&session_args.role,
+					&current_dir,
+				)
+				.await?;
+				println!("{}", "Conversation cleared".bright_green());
 			}
 		}
 
 		// Save session after command execution
 		let _ = chat_session.save();
-		return Ok(());
+		return Ok(RunOutcome::default());
 	}
 
+	// Provider and agent retries of this turn share one budget
+	retry_budget::start_turn(current_config.max_retries_per_turn);
+
 	// Layer processing if enabled and first message - same as interactive
-	if current_config.get_enable_layers(&session_args.role) && !first_message_processed {
+	if current_config.get_enable_layers(&session_args.role) && chat_session.run_layers_next {
 		// Track session message count before layer processing
 		let messages_before_layers = chat_session.session.messages.len();
 
 		// Process using layered architecture - same as interactive
-		let layered_result = super::super::layered_response::process_layered_response(
+		let layered_result = process_layers(
 			&input,
 			&mut chat_session,
 			&current_config,
@@ -1328,21 +1543,31 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 		)
 		.await;
 
+		if time_limit_reached.load(Ordering::SeqCst) {
+			return Ok(time_limit_outcome(&chat_session, config));
+		}
+
 		match layered_result {
 			Ok(processed_input) => {
 				// Check if layers modified the session
 				let messages_after_layers = chat_session.session.messages.len();
-				let layers_modified_session = messages_after_layers > messages_before_layers;
+				let layers_modified_session = layers_modified_session(
+					&LayeredOrchestrator::from_config(&current_config, &session_args.role)
+						.output_modes(),
+					messages_before_layers,
+					messages_after_layers,
+				);
 
 				if layers_modified_session {
 					// Layers used output_mode append/replace - session already has the messages
 					log_info!(
-						"Layers modified session ({} messages added). Skipping user message addition.",
-						messages_after_layers - messages_before_layers
+						"Layers modified session ({} messages now). Skipping user message addition.",
+						messages_after_layers
 					);
 					// Save session and exit - processing is complete
 					let _ = chat_session.save();
-					return Ok(());
+					write_exit_summary(&mut chat_session, &current_config).await;
+					return Ok(RunOutcome::from_session(&chat_session, false, false));
 				} else {
 					// Use processed input from layers
 					input = processed_input;
@@ -1350,6 +1575,16 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 				}
 			}
 			Err(e) => {
+				if abort_after_layer_failure(
+					current_config
+						.get_role_config_struct(&session_args.role)
+						.layer_failure_policy,
+					&mut chat_session.session.messages,
+					messages_before_layers,
+				) {
+					let _ = chat_session.save();
+					return Err(anyhow::anyhow!("Layer processing failed: {}", e));
+				}
 				use colored::*;
 				println!(
 					"\n{}: {}",
@@ -1361,10 +1596,39 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 		}
 	}
 
+	// Guard against a single oversized message (auto-truncate in non-interactive mode)
+	let input = match enforce_user_message_limit(input, &current_config, false) {
+		Some(capped) => capped,
+		None => return Ok(RunOutcome::default()),
+	};
+	let input = if current_config.auto_context {
+		attach_auto_context(input, &current_dir)
+	} else {
+		input
+	};
+
 	// Add user message - same as interactive
-	let user_message_index = chat_session.session.messages.len();
+	let mut user_message_index = chat_session.session.messages.len();
 	chat_session.add_user_message(&input)?;
 
+	// Tool result retention - same as interactive
+	if apply_tool_result_retention(&mut chat_session, &current_config) {
+		let _ = chat_session.save();
+	}
+
+	// Tool churn compaction - same as interactive
+	if apply_tool_churn_compaction(&mut chat_session, &current_config) {
+		user_message_index = chat_session.session.messages.len() - 1;
+		let _ = chat_session.save();
+	}
+
+	// Auto-compaction - same as interactive
+	match check_and_compact_context(&mut chat_session, &current_config).await {
+		Ok(true) => user_message_index = chat_session.session.messages.len() - 1,
+		Ok(false) => {}
+		Err(e) => log_debug!("Auto-compaction skipped: {}", e),
+	}
+
 	// Check and truncate context - same as interactive
 	let truncate_cancelled = Arc::new(AtomicBool::new(false));
 	check_and_truncate_context(
@@ -1406,7 +1670,8 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 	// Auto-accept spending threshold for non-interactive mode
 	// Skip the spending threshold check - auto-proceed in non-interactive mode
 
-	// Make API call - same as interactive
+	// Make API call - same as interactive, on the model for the current context size
+	chat_session.select_model_for_context(&current_config);
 	let model = chat_session.model.clone();
 	let temperature = chat_session.temperature;
 	let config_clone = current_config.clone();
@@ -1427,10 +1692,12 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 	let _ = animation_task.await;
 
 	// Process response - same as interactive
-	match api_result {
-		Ok(response) => {
-			// Process the response with tool calls - same as interactive
-			let tool_process_cancelled = Arc::new(AtomicBool::new(false));
+	let api_failed = match api_result {
+		Ok(mut response) => {
+			show_refusal(&mut response);
+
+			// Process the response with tool calls - tool calls stop with the time limit
+			let tool_process_cancelled = operation_cancelled.clone();
 			let legacy_exchange = response.exchange;
 
 			let process_result = process_response(
@@ -1445,10 +1712,21 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 			)
 			.await;
 
-			if let Err(e) = process_result {
+			if let Err(e) = &process_result {
+				if time_limit_reached.load(Ordering::SeqCst) {
+					return Ok(time_limit_outcome(&chat_session, config));
+				}
 				use colored::*;
 				println!("\n{}: {}", "Error processing response".bright_red(), e);
 			}
+			process_result.is_err()
+		}
+		Err(_) if time_limit_reached.load(Ordering::SeqCst) => {
+			// Remove the unanswered user message, as on any failed request
+			if user_message_index < chat_session.session.messages.len() {
+				chat_session.session.messages.truncate(user_message_index);
+			}
+			return Ok(time_limit_outcome(&chat_session, config));
 		}
 		Err(e) => {
 			// Remove user message on API failure - same as interactive
@@ -1499,11 +1777,433 @@ pub async fn run_interactive_session_with_input<T: clap::Args + std::fmt::Debug>
 					);
 				}
 			}
+			true
 		}
+	};
+
+	// Tool processing ends quietly when the time limit cancels it, so check the timer
+	// whatever the outcome of the response
+	if time_limit_reached.load(Ordering::SeqCst) {
+		return Ok(time_limit_outcome(&chat_session, config));
 	}
 
 	// Save session before exit
 	let _ = chat_session.save();
+	if !api_failed {
+		write_exit_summary(&mut chat_session, &current_config).await;
+	}
+
+	Ok(RunOutcome::from_session(&chat_session, api_failed, false))
+}
+
+// Process the input through the role's layers. With layer_failure_policy = "retry" a
+// failed run is repeated once, after dropping the messages it left in the session
+async fn process_layers(
+	input: &str,
+	chat_session: &mut ChatSession,
+	config: &Config,
+	role: &str,
+	operation_cancelled: Arc<AtomicBool>,
+) -> Result<String> {
+	let messages_before_layers = chat_session.session.messages.len();
+	let result = super::super::layered_response::process_layered_response(
+		input,
+		chat_session,
+		config,
+		role,
+		operation_cancelled.clone(),
+	)
+	.await;
+
+	match result {
+		Err(e)
+			if config.get_role_config_struct(role).layer_failure_policy
+				== LayerFailurePolicy::Retry
+				&& !operation_cancelled.load(Ordering::SeqCst) =>
+		{
+			// The retry draws from the turn's budget like provider and agent retries
+			if let Err(budget) = retry_budget::take_retry("the layers") {
+				return Err(anyhow::anyhow!("{}. {}", e, budget));
+			}
+			use colored::*;
+			println!(
+				"\n{}: {}",
+				"Error processing through layers".bright_red(),
+				e
+			);
+			println!("{}", "Retrying the layers once.".yellow());
+			chat_session
+				.session
+				.messages
+				.truncate(messages_before_layers);
+			super::super::layered_response::process_layered_response(
+				input,
+				chat_session,
+				config,
+				role,
+				operation_cancelled,
+			)
+			.await
+		}
+		result => result,
+	}
+}
+
+// Report a run stopped by session_max_duration_seconds, saving the session first
+fn time_limit_outcome(chat_session: &ChatSession, config: &Config) -> RunOutcome {
+	use colored::*;
+	println!(
+		"\n{}",
+		format!(
+			"⏱  Session time limit reached ({}s), stopping the run",
+			config.session_max_duration_seconds
+		)
+		.bright_yellow()
+	);
+	let _ = chat_session.save();
+	RunOutcome::from_session(chat_session, false, true)
+}
+
+// Enforce max_user_message_tokens on a single user message
+// Interactive mode asks whether to trim, send as-is or cancel; non-interactive always trims
+// Returns None when the user cancels the message
+fn enforce_user_message_limit(input: String, config: &Config, interactive: bool) -> Option<String> {
+	use colored::*;
+
+	let limit = config.max_user_message_tokens;
+	if limit == 0 {
+		return Some(input);
+	}
+
+	let tokens = crate::session::estimate_tokens(&input);
+	if tokens <= limit {
+		return Some(input);
+	}
+
+	println!(
+		"{}",
+		format!(
+			"⚠️  Message is {} tokens, exceeding max_user_message_tokens ({})",
+			tokens, limit
+		)
+		.bright_yellow()
+	);
+
+	if interactive {
+		print!(
+			"{}",
+			"[t]rim to limit, [s]end anyway or [c]ancel? [T/s/c]: ".bright_cyan()
+		);
+		std::io::stdout().flush().unwrap();
+		let mut answer = String::new();
+		std::io::stdin().read_line(&mut answer).unwrap_or_default();
+		match answer.trim().to_lowercase().as_str() {
+			"s" | "send" => return Some(input),
+			"c" | "cancel" => {
+				println!("{}", "Message cancelled".bright_yellow());
+				return None;
+			}
+			_ => {}
+		}
+	}
+
+	let trimmed = crate::session::truncate_to_tokens(&input, limit);
+	let trimmed_tokens = crate::session::estimate_tokens(&trimmed);
+	println!(
+		"{}",
+		format!(
+			"✂️  Trimmed message from {} to {} tokens ({} removed)",
+			tokens,
+			trimmed_tokens,
+			tokens.saturating_sub(trimmed_tokens)
+		)
+		.bright_green()
+	);
+
+	Some(trimmed)
+}
+
+// Attach snippets of files and symbols the message mentions (auto_context)
+fn attach_auto_context(input: String, project_dir: &std::path::Path) -> String {
+	use colored::*;
+
+	let snippets = crate::session::chat::auto_context::collect_snippets(&input, project_dir);
+	if snippets.is_empty() {
+		return input;
+	}
+	for snippet in &snippets {
+		println!(
+			"{}",
+			format!("📎 Auto context: {}", snippet.label).bright_blue()
+		);
+	}
+	crate::session::chat::auto_context::attach_snippets(&input, &snippets)
+}
+
+// Announce a refusal the provider returned apart from the content. With empty content the
+// refusal becomes the assistant turn, so it is shown and kept instead of a blank response
+fn show_refusal(response: &mut crate::providers::ProviderResponse) {
+	use colored::*;
+
+	let Some(refusal) = response.refusal.take() else {
+		return;
+	};
+	println!(
+		"{}",
+		"🚫 The model refused this request".bright_red().bold()
+	);
+	if response.content.trim().is_empty() {
+		response.content = refusal;
+	} else {
+		println!("{}", refusal.bright_red());
+	}
+}
+
+// Start the conversation of a new (or cleared) session: system prompt, welcome message
+// and the custom instructions file, with cache markers where supported
+async fn start_conversation(
+	chat_session: &mut ChatSession,
+	config: &Config,
+	config_for_role: &Config,
+	role: &str,
+	current_dir: &std::path::Path,
+) -> Result<()> {
+	// Create system prompt based on role
+	let system_prompt = create_system_prompt(current_dir, config, role).await;
+
+	// Process layer system prompts during session initialization
+	// This ensures layer system prompts are processed once and cached for the entire session
+	let (role_config, _, _, _, _) = config.get_role_config(role);
+	if role_config.enable_layers {
+		// Create orchestrator with processed system prompts
+		let _orchestrator =
+			LayeredOrchestrator::from_config_with_processed_prompts(config, role, current_dir)
+				.await;
+		log_info!("Layer system prompts processed and cached for session");
+	}
+
+	let supports_caching = crate::session::model_supports_caching(&chat_session.model);
+	let has_tools = !config.mcp.servers.is_empty();
+
+	// Welcome message with variable processing - an empty welcome adds no assistant turn
+	let role_config = config.get_role_config_struct(role);
+	let welcome_template = crate::session::prompt_template::render_prompt_template(
+		&role_config.welcome,
+		current_dir,
+		config,
+		role,
+	)
+	.await;
+	let welcome_message = crate::session::helper_functions::process_placeholders_async_with_role(
+		&welcome_template,
+		current_dir,
+		Some(role),
+	)
+	.await;
+
+	let instructions = read_custom_instructions(config, role, current_dir).await;
+
+	if config.stable_cache_prefix {
+		// The whole prefix is added first and cached as one block, see stable_prefix_order
+		let prefix = crate::session::cache::stable_prefix_order(
+			&system_prompt,
+			instructions.as_deref(),
+			&welcome_message,
+		);
+		for (message_role, content) in &prefix {
+			match *message_role {
+				"system" => chat_session.add_system_message(content)?,
+				"user" => chat_session.add_user_message(content)?,
+				_ => chat_session.add_assistant_message(content, None, config_for_role, role)?,
+			}
+		}
+		let cache_manager = crate::session::cache::CacheManager::new();
+		cache_manager.mark_conversation_prefix(
+			&mut chat_session.session.messages,
+			prefix.len(),
+			supports_caching,
+		);
+		if supports_caching {
+			log_info!("Conversation prefix has been marked for caching as one stable block.");
+			let _ = chat_session.save();
+		}
+	} else {
+		chat_session.add_system_message(&system_prompt)?;
+
+		// CRITICAL FIX: Apply automatic cache markers for system messages AND tool definitions
+		// This ensures consistent caching behavior across all supported models
+		if supports_caching {
+			let cache_manager = crate::session::cache::CacheManager::new();
+			cache_manager.add_automatic_cache_markers(
+				&mut chat_session.session.messages,
+				has_tools,
+				supports_caching,
+			);
+
+			log_info!("System prompt has been automatically marked for caching to save tokens in future interactions.");
+			// Save the session to ensure the cached status is persisted
+			let _ = chat_session.save();
+		} else {
+			// Don't show warning for models that don't support caching
+			log_info!(
+				"Note: This model doesn't support caching, but system prompt is still optimized."
+			);
+		}
+
+		if !welcome_message.trim().is_empty() {
+			chat_session.add_assistant_message(&welcome_message, None, config_for_role, role)?;
+
+			// Apply cache marker to welcome message as well for consistency
+			if supports_caching {
+				let cache_manager = crate::session::cache::CacheManager::new();
+				cache_manager.add_automatic_cache_markers(
+					&mut chat_session.session.messages,
+					has_tools,
+					supports_caching,
+				);
+			}
+		}
+
+		if let Some(processed_instructions) = &instructions {
+			// Add as user message after welcome message
+			chat_session.add_user_message(processed_instructions)?;
+
+			// Apply cache marker to the assistant message (welcome message) if caching is supported
+			// This ensures the assistant message before the instructions is cached
+			if supports_caching {
+				let cache_manager = crate::session::cache::CacheManager::new();
+				cache_manager.add_automatic_cache_markers(
+					&mut chat_session.session.messages,
+					has_tools,
+					supports_caching,
+				);
+			}
+		}
+	}
+	if instructions.is_some() {
+		log_info!(
+			"Added {} content as user message with variable processing",
+			config.custom_instructions_file_name
+		);
+	}
+
+	// Output of the [developer] context_commands, e.g. the current build error
+	if let Some(context) = super::context_commands::run_context_commands(config, current_dir).await
+	{
+		chat_session.add_user_message(&context)?;
+		log_info!(
+			"Added the output of {} context commands as user message",
+			config.developer.context_commands.len()
+		);
+	}
 
 	Ok(())
 }
+
+// Custom instructions file in the working directory with placeholders processed,
+// None when it is not configured, missing or unreadable
+async fn read_custom_instructions(
+	config: &Config,
+	role: &str,
+	current_dir: &std::path::Path,
+) -> Option<String> {
+	let instructions_filename = &config.custom_instructions_file_name;
+	if instructions_filename.is_empty() {
+		return None;
+	}
+	let instructions_path = current_dir.join(instructions_filename);
+	if !instructions_path.exists() {
+		return None;
+	}
+	match std::fs::read_to_string(&instructions_path) {
+		Ok(instructions_content) => {
+			// Process placeholders in instructions content using the same pattern as welcome message
+			Some(
+				crate::session::helper_functions::process_placeholders_async_with_role(
+					&instructions_content,
+					current_dir,
+					Some(role),
+				)
+				.await,
+			)
+		}
+		Err(e) => {
+			log_debug!("Failed to read {}: {}", instructions_filename, e);
+			None
+		}
+	}
+}
+
+// Rebuild the system prompt and instructions message, reporting what changed
+async fn reload_prompt(
+	prompt_watcher: &mut PromptWatcher,
+	chat_session: &mut ChatSession,
+	project_dir: &std::path::Path,
+	config: &Config,
+	role: &str,
+) {
+	use colored::*;
+
+	match prompt_watcher
+		.reload(chat_session, project_dir, config, role)
+		.await
+	{
+		Ok(updated) if updated.is_empty() => {
+			println!(
+				"{}",
+				"Prompt is up to date - nothing changed".bright_yellow()
+			);
+		}
+		Ok(updated) => {
+			println!(
+				"{}",
+				format!("Reloaded {}", updated.join(" and ")).bright_green()
+			);
+		}
+		Err(e) => {
+			println!("{}: {}", "Error reloading prompt".bright_red(), e);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::loading::get_test_default_config;
+
+	#[test]
+	fn test_run_outcome_status() {
+		let config = get_test_default_config();
+		let mut chat_session = ChatSession::new(
+			"test_run_outcome".to_string(),
+			Some("openrouter:test/model".to_string()),
+			Some(0.2),
+			&config,
+		);
+		chat_session.last_response = "done".to_string();
+
+		let outcome = RunOutcome::from_session(&chat_session, false, false);
+		assert_eq!(outcome.status, RunStatus::Completed);
+		assert_eq!(outcome.final_response, "done");
+		assert_eq!(
+			RunOutcome::from_session(&chat_session, true, false).status,
+			RunStatus::ApiError
+		);
+
+		// The timer wins even when the response itself was processed
+		assert_eq!(
+			RunOutcome::from_session(&chat_session, false, true).status,
+			RunStatus::TimeLimit
+		);
+
+		chat_session.spending_limit_reached = true;
+		assert_eq!(
+			RunOutcome::from_session(&chat_session, false, false).status,
+			RunStatus::SpendingLimit
+		);
+		assert_eq!(
+			RunOutcome::from_session(&chat_session, true, true).status,
+			RunStatus::TimeLimit
+		);
+	}
+}
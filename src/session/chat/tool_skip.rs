@@ -0,0 +1,113 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Skip a single running tool - type its number (or s for the first one still running) and
+// press Enter while tools execute. The tool reports "cancelled by user", the rest and the turn go on
+
+use colored::Colorize;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A running tool the user can skip
+pub struct SkipTarget {
+	pub tool_name: String,
+	pub completed: Arc<AtomicBool>,
+	pub skipped: Arc<AtomicBool>,
+}
+
+impl SkipTarget {
+	fn is_running(&self) -> bool {
+		!self.completed.load(Ordering::SeqCst) && !self.skipped.load(Ordering::SeqCst)
+	}
+}
+
+/// Read skip requests from the terminal until `stop` is set
+/// None when stdin is not a terminal
+pub fn spawn_skip_listener(
+	targets: Vec<SkipTarget>,
+	stop: Arc<AtomicBool>,
+) -> Option<tokio::task::JoinHandle<()>> {
+	if !std::io::stdin().is_terminal() {
+		return None;
+	}
+	Some(tokio::task::spawn_blocking(move || listen(&targets, &stop)))
+}
+
+fn listen(targets: &[SkipTarget], stop: &AtomicBool) {
+	let mut typed = String::new();
+	while !stop.load(Ordering::SeqCst) {
+		// A tool is prompting the user (e.g. large output confirmation) - the input is theirs
+		if crate::session::chat::animation::tool_status_paused() {
+			std::thread::sleep(Duration::from_millis(50));
+			continue;
+		}
+		match event::poll(Duration::from_millis(100)) {
+			Ok(true) => {}
+			Ok(false) => continue,
+			Err(_) => return,
+		}
+		let Ok(Event::Key(key)) = event::read() else {
+			continue;
+		};
+		if key.kind != KeyEventKind::Press {
+			continue;
+		}
+		match key.code {
+			KeyCode::Char(c) => typed.push(c),
+			KeyCode::Enter => {
+				skip(targets, typed.trim());
+				typed.clear();
+			}
+			_ => {}
+		}
+	}
+}
+
+// Skip the tool named by the input: its number, or s for the first one still running
+fn skip(targets: &[SkipTarget], input: &str) {
+	if input.is_empty() {
+		return;
+	}
+	let target = if input.eq_ignore_ascii_case("s") {
+		targets.iter().find(|target| target.is_running())
+	} else {
+		input
+			.parse::<usize>()
+			.ok()
+			.and_then(|number| targets.get(number.checked_sub(1)?))
+			.filter(|target| target.is_running())
+	};
+
+	match target {
+		Some(target) => {
+			target.skipped.store(true, Ordering::SeqCst);
+			println!(
+				"\n{}",
+				format!("⏭  Skipping {}, the turn continues...", target.tool_name).bright_yellow()
+			);
+		}
+		None => println!(
+			"\n{}",
+			format!(
+				"No running tool '{}' to skip - type a tool number or s, then Enter",
+				input
+			)
+			.yellow()
+		),
+	}
+	let _ = std::io::stdout().flush();
+}
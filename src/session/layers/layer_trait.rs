@@ -244,6 +244,9 @@ pub struct LayerConfig {
 	// Cached processed system prompt (not serialized - computed at session initialization)
 	#[serde(skip)]
 	pub processed_system_prompt: Option<String>,
+	// Global default_layer_model (not serialized - applied when the config role map is built)
+	#[serde(skip)]
+	pub default_model: Option<String>,
 }
 
 fn default_temperature() -> f32 {
@@ -251,10 +254,12 @@ fn default_temperature() -> f32 {
 }
 
 impl LayerConfig {
-	/// Get the effective model for this layer (fallback to session model if not specified)
+	/// Get the effective model for this layer
+	/// Fallback order: layer model, then default_layer_model, then session model
 	pub fn get_effective_model(&self, session_model: &str) -> String {
 		self.model
 			.clone()
+			.or_else(|| self.default_model.clone())
 			.unwrap_or_else(|| session_model.to_string())
 	}
 
@@ -272,12 +277,12 @@ impl LayerConfig {
 			let mut layer_servers = Vec::new();
 
 			for server_name in &self.mcp.server_refs {
-				// Get from loaded registry
+				// Get from loaded registry - disabled servers are not started for layers either
 				let server_config = base_config
 					.mcp
 					.servers
 					.iter()
-					.find(|s| s.name() == *server_name)
+					.find(|s| s.name() == *server_name && !s.is_disabled())
 					.cloned();
 
 				if let Some(mut server) = server_config {
@@ -291,35 +296,68 @@ impl LayerConfig {
 							McpServerConfig::Builtin {
 								name,
 								timeout_seconds,
+								disabled,
 								..
 							} => McpServerConfig::Builtin {
 								name,
 								timeout_seconds,
 								tools: filtered_tools,
+								disabled,
 							},
 							McpServerConfig::Http {
 								name,
 								connection,
+								headers,
 								timeout_seconds,
+								tool_timeouts,
+								disabled,
 								..
 							} => McpServerConfig::Http {
 								name,
 								connection,
+								headers,
 								timeout_seconds,
+								tool_timeouts,
 								tools: filtered_tools,
+								disabled,
 							},
 							McpServerConfig::Stdin {
 								name,
 								command,
 								args,
 								timeout_seconds,
+								tool_timeouts,
+								heartbeat_seconds,
+								disabled,
 								..
 							} => McpServerConfig::Stdin {
 								name,
 								command,
 								args,
 								timeout_seconds,
+								tool_timeouts,
 								tools: filtered_tools,
+								heartbeat_seconds,
+								disabled,
+							},
+							McpServerConfig::Sse {
+								name,
+								url,
+								auth_token,
+								headers,
+								timeout_seconds,
+								tool_timeouts,
+								disabled,
+								..
+							} => McpServerConfig::Sse {
+								name,
+								url,
+								auth_token,
+								headers,
+								timeout_seconds,
+								tool_timeouts,
+								tools: filtered_tools,
+								disabled,
 							},
 						};
 					}
@@ -331,6 +369,8 @@ impl LayerConfig {
 			merged_config.mcp = crate::config::McpConfig {
 				servers: layer_servers,
 				allowed_tools: self.mcp.allowed_tools.clone(),
+				servers_file: None,
+				included_servers: Vec::new(),
 			};
 		} else {
 			// No server_refs means MCP is disabled for this layer
@@ -417,6 +457,7 @@ impl LayerConfig {
 				},
 				parameters: std::collections::HashMap::new(),
 				processed_system_prompt: None, // Will be processed during session initialization
+				default_model: None,
 			},
 			"context_generator" => Self {
 				name: layer_type.to_string(),
@@ -431,6 +472,7 @@ impl LayerConfig {
 				},
 				parameters: std::collections::HashMap::new(),
 				processed_system_prompt: None, // Will be processed during session initialization
+				default_model: None,
 			},
 			"reducer" => Self {
 				name: layer_type.to_string(),
@@ -445,6 +487,7 @@ impl LayerConfig {
 				},
 				parameters: std::collections::HashMap::new(),
 				processed_system_prompt: None, // Will be processed during session initialization
+				default_model: None,
 			},
 			_ => Self {
 				name: layer_type.to_string(),
@@ -456,6 +499,7 @@ impl LayerConfig {
 				mcp: LayerMcpConfig::default(),
 				parameters: std::collections::HashMap::new(),
 				processed_system_prompt: None, // Will be processed during session initialization
+				default_model: None,
 			},
 		}
 	}
@@ -553,3 +597,26 @@ pub trait Layer {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::loading::get_test_default_config;
+
+	#[test]
+	fn test_layer_skips_disabled_servers() {
+		let mut config = get_test_default_config();
+		let mut filesystem = McpServerConfig::builtin("filesystem", 30, vec![]);
+		filesystem.set_disabled(true);
+		config.mcp.servers = vec![
+			McpServerConfig::builtin("developer", 30, vec![]),
+			filesystem,
+		];
+
+		// context_generator references both developer and filesystem
+		let layer = LayerConfig::create_system_layer("context_generator");
+		let merged = layer.get_merged_config_for_layer(&config);
+		let names: Vec<&str> = merged.mcp.servers.iter().map(|s| s.name()).collect();
+		assert_eq!(names, vec!["developer"]);
+	}
+}
@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::layer_trait::{Layer, LayerConfig};
+use super::layer_trait::{Layer, LayerConfig, OutputMode};
 use super::types::GenericLayer;
-use crate::config::Config;
-use crate::session::Session;
+use crate::config::{Config, LayerFailurePolicy};
+use crate::session::{Message, Session};
 use anyhow::Result;
 use colored::*;
 use std::io::IsTerminal;
@@ -99,7 +99,29 @@ impl LayeredOrchestrator {
 			}
 		}
 
-		Self { layers }
+		let orchestrator = Self { layers };
+		orchestrator.warn_about_output_modes();
+		orchestrator
+	}
+
+	// Output mode of each layer in pipeline order
+	pub fn output_modes(&self) -> Vec<OutputMode> {
+		self.layers
+			.iter()
+			.map(|layer| layer.config().output_mode)
+			.collect()
+	}
+
+	// Print a warning for each output_mode combination that discards layer output
+	fn warn_about_output_modes(&self) {
+		let layers: Vec<(&str, OutputMode)> = self
+			.layers
+			.iter()
+			.map(|layer| (layer.name(), layer.config().output_mode))
+			.collect();
+		for warning in output_mode_warnings(&layers) {
+			println!("{} {}", "Warning:".bright_yellow(), warning.yellow());
+		}
 	}
 
 	// Create default system layers using the new generic layer approach
@@ -314,7 +336,6 @@ impl LayeredOrchestrator {
 			}
 
 			// Handle output_mode to determine how this layer's output affects the session
-			use crate::session::layers::OutputMode;
 			match layer.config().output_mode {
 				OutputMode::None => {
 					// Intermediate layer - just pass output to next layer, don't modify session
@@ -397,3 +418,154 @@ impl LayeredOrchestrator {
 		Ok(current_input)
 	}
 }
+
+// Describe output_mode combinations where a layer's session messages get thrown away
+// Layers run in order and a replace layer clears the whole session, so only the
+// messages written by the last replace layer and the append layers after it remain
+pub fn output_mode_warnings(layers: &[(&str, OutputMode)]) -> Vec<String> {
+	let mut warnings = Vec::new();
+	let last_replace = match layers
+		.iter()
+		.rposition(|(_, mode)| *mode == OutputMode::Replace)
+	{
+		Some(index) => index,
+		None => return warnings,
+	};
+	let (replace_name, _) = layers[last_replace];
+
+	for (name, mode) in &layers[..last_replace] {
+		match mode {
+			OutputMode::Replace => warnings.push(format!(
+				"layers '{}' and '{}' both use output_mode = \"replace\"; only the output of '{}' is kept in the session",
+				name, replace_name, replace_name
+			)),
+			OutputMode::Append => warnings.push(format!(
+				"layer '{}' uses output_mode = \"append\" but the later layer '{}' replaces the session, so its output is discarded",
+				name, replace_name
+			)),
+			OutputMode::None => {}
+		}
+	}
+
+	warnings
+}
+
+// Whether the message is dropped after the layers failed on it (layer_failure_policy =
+// "abort"). Messages the failed layers already wrote are removed, so the session is left
+// as it was before the layers ran
+pub fn abort_after_layer_failure(
+	policy: LayerFailurePolicy,
+	messages: &mut Vec<Message>,
+	messages_before_layers: usize,
+) -> bool {
+	if policy != LayerFailurePolicy::Abort {
+		return false;
+	}
+	messages.truncate(messages_before_layers);
+	true
+}
+
+// Whether the layer pipeline wrote its output to the session, so the runner must not add
+// the user message again. A replace layer always rewrites the session, even when the
+// result has no more messages than before; append layers only count if they added any
+pub fn layers_modified_session(
+	output_modes: &[OutputMode],
+	messages_before: usize,
+	messages_after: usize,
+) -> bool {
+	if output_modes.contains(&OutputMode::Replace) {
+		return true;
+	}
+	output_modes.contains(&OutputMode::Append) && messages_after > messages_before
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_output_mode_warnings() {
+		use OutputMode::*;
+
+		assert!(output_mode_warnings(&[]).is_empty());
+		assert!(output_mode_warnings(&[("query", None), ("context", Replace)]).is_empty());
+		assert!(output_mode_warnings(&[("context", Replace), ("notes", Append)]).is_empty());
+		assert!(output_mode_warnings(&[("a", Append), ("b", Append)]).is_empty());
+
+		let warnings = output_mode_warnings(&[("first", Replace), ("second", Replace)]);
+		assert_eq!(warnings.len(), 1);
+		assert!(warnings[0].contains("'first' and 'second' both use output_mode = \"replace\""));
+
+		let warnings =
+			output_mode_warnings(&[("notes", Append), ("query", None), ("context", Replace)]);
+		assert_eq!(warnings.len(), 1);
+		assert!(warnings[0].contains("layer 'notes' uses output_mode = \"append\""));
+
+		// Every layer before the last replace layer is reported
+		let warnings =
+			output_mode_warnings(&[("a", Replace), ("b", Append), ("c", Replace), ("d", Append)]);
+		assert_eq!(warnings.len(), 2);
+		assert!(warnings.iter().all(|w| w.contains("'c'")));
+	}
+
+	#[test]
+	fn test_layers_modified_session() {
+		use OutputMode::*;
+
+		// Only intermediate layers - the runner sends the processed input itself
+		assert!(!layers_modified_session(&[], 1, 1));
+		assert!(!layers_modified_session(&[None, None], 1, 1));
+
+		// Append layers count only when they added messages
+		assert!(layers_modified_session(&[None, Append], 1, 2));
+		assert!(!layers_modified_session(&[None, Append], 1, 1));
+
+		// Replace layers rewrite the session whatever the resulting message count
+		assert!(layers_modified_session(&[None, Replace], 1, 2));
+		assert!(layers_modified_session(&[None, Replace], 5, 1));
+		assert!(layers_modified_session(&[Replace, Replace], 3, 3));
+		assert!(layers_modified_session(&[Append, Replace], 1, 0));
+		assert!(layers_modified_session(&[Replace, Append], 4, 2));
+	}
+
+	#[test]
+	fn test_abort_after_layer_failure() {
+		let message = |role: &str, content: &str| Message {
+			role: role.to_string(),
+			content: content.to_string(),
+			timestamp: 0,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			content_parts: None,
+			model: None,
+		};
+		let session = vec![message("system", "prompt"), message("user", "earlier")];
+		// A failed append layer already wrote its output
+		let mut after_layers = session.clone();
+		after_layers.push(message("assistant", "partial layer output"));
+
+		// Abort leaves the session as it was before the layers
+		let mut messages = after_layers.clone();
+		assert!(abort_after_layer_failure(
+			LayerFailurePolicy::Abort,
+			&mut messages,
+			session.len()
+		));
+		let contents: Vec<&str> = messages.iter().map(|m| m.content.as_str()).collect();
+		assert_eq!(contents, vec!["prompt", "earlier"]);
+
+		// Continue and retry go on with the session untouched
+		for policy in [LayerFailurePolicy::Continue, LayerFailurePolicy::Retry] {
+			let mut messages = after_layers.clone();
+			assert!(!abort_after_layer_failure(
+				policy,
+				&mut messages,
+				session.len()
+			));
+			assert_eq!(messages.len(), after_layers.len());
+		}
+	}
+}
@@ -54,6 +54,8 @@ impl LayerProcessor {
 			name: None,           // No name for system messages
 			tool_calls: None,     // No tool_calls for system messages
 			images: None,         // No images for system messages
+			content_parts: None,
+			model: None,
 		});
 
 		// Prepare input based on input_mode using the trait's prepare_input method
@@ -72,6 +74,8 @@ impl LayerProcessor {
 			name: None,         // No name for user messages
 			tool_calls: None,   // No tool_calls for user messages
 			images: None,       // No images for user messages
+			content_parts: None,
+			model: None,
 		});
 
 		messages
@@ -129,7 +133,7 @@ impl Layer for LayerProcessor {
 			let tool_calls = if let Some(ref calls) = direct_tool_calls {
 				calls
 			} else {
-				&crate::mcp::parse_tool_calls(&output)
+				&crate::mcp::parse_tool_calls(&output, config.content_tool_call_format)
 			};
 
 			// If there are tool calls, process them
@@ -198,10 +202,26 @@ impl Layer for LayerProcessor {
 						name: None,         // No name for assistant messages
 						tool_calls: None,   // No tool_calls for assistant messages
 						images: None,       // No images for assistant messages
+						content_parts: None,
+						model: None,
 					});
 
 					// Add each tool result as a tool message in standard OpenRouter format
+					// (results of calls parsed from content go back as one user message)
+					let mut content_call_results = Vec::new();
 					for tool_result in &tool_results {
+						if crate::mcp::content_tool_calls::is_content_tool_call(
+							&tool_result.tool_id,
+						) {
+							content_call_results.push(
+								crate::mcp::content_tool_calls::format_content_tool_result(
+									&tool_result.tool_name,
+									&serde_json::to_string(&tool_result.result).unwrap_or_default(),
+								),
+							);
+							continue;
+						}
+
 						// Use standard OpenRouter format for tool messages
 						layer_session.push(crate::session::Message {
 							role: "tool".to_string(),
@@ -215,6 +235,25 @@ impl Layer for LayerProcessor {
 							name: Some(tool_result.tool_name.clone()),       // Include the tool name
 							tool_calls: None,                                // No tool_calls for tool messages
 							images: None,                                    // No images for tool messages
+							content_parts: None,
+							model: None,
+						});
+					}
+					if !content_call_results.is_empty() {
+						layer_session.push(crate::session::Message {
+							role: "user".to_string(),
+							content: content_call_results.join("\n\n"),
+							timestamp: std::time::SystemTime::now()
+								.duration_since(std::time::UNIX_EPOCH)
+								.unwrap_or_default()
+								.as_secs(),
+							cached: false,
+							tool_call_id: None,
+							name: None,
+							tool_calls: None,
+							images: None,
+							content_parts: None,
+							model: None,
 						});
 					}
 
@@ -57,6 +57,8 @@ impl GenericLayer {
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		});
 
 		// Prepare input based on input_mode using the trait's prepare_input method
@@ -75,6 +77,8 @@ impl GenericLayer {
 			name: None,
 			tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 		});
 
 		messages
@@ -122,8 +126,11 @@ impl GenericLayer {
 			// Check for tool calls if MCP has any servers configured for this layer
 			if !self.config.mcp.server_refs.is_empty() {
 				// Resolve current tool calls for this iteration (same logic as main sessions)
-				let current_tool_calls =
-					self.resolve_layer_tool_calls(&mut current_tool_calls_param, &current_content);
+				let current_tool_calls = self.resolve_layer_tool_calls(
+					&mut current_tool_calls_param,
+					&current_content,
+					layer_config.content_tool_call_format,
+				);
 
 				if !current_tool_calls.is_empty() {
 					// Add assistant message with tool calls preserved
@@ -186,7 +193,10 @@ impl GenericLayer {
 								continue;
 							} else {
 								// Check if there are more tool calls in the content itself
-								let more_tools = crate::mcp::parse_tool_calls(&current_content);
+								let more_tools = crate::mcp::parse_tool_calls(
+									&current_content,
+									layer_config.content_tool_call_format,
+								);
 								if !more_tools.is_empty() {
 									continue;
 								} else {
@@ -200,7 +210,10 @@ impl GenericLayer {
 						}
 					} else {
 						// No tool results - check if there were more tools to execute directly
-						let more_tools = crate::mcp::parse_tool_calls(&current_content);
+						let more_tools = crate::mcp::parse_tool_calls(
+							&current_content,
+							layer_config.content_tool_call_format,
+						);
 						if !more_tools.is_empty() {
 							// If there are more tool calls later in the response, continue processing
 							continue;
@@ -243,17 +256,18 @@ impl GenericLayer {
 		&self,
 		current_tool_calls_param: &mut Option<Vec<crate::mcp::McpToolCall>>,
 		current_content: &str,
+		format: crate::config::ContentToolCallFormat,
 	) -> Vec<crate::mcp::McpToolCall> {
 		if let Some(calls) = current_tool_calls_param.take() {
 			// Use the tool calls from the API response only once
 			if !calls.is_empty() {
 				calls
 			} else {
-				crate::mcp::parse_tool_calls(current_content) // Fallback
+				crate::mcp::parse_tool_calls(current_content, format) // Fallback
 			}
 		} else {
 			// For follow-up iterations, parse from content if any new tool calls exist
-			crate::mcp::parse_tool_calls(current_content)
+			crate::mcp::parse_tool_calls(current_content, format)
 		}
 	}
 
@@ -281,6 +295,8 @@ impl GenericLayer {
 			name: None,
 			tool_calls: original_tool_calls,
 			images: None,
+			content_parts: None,
+			model: None,
 		};
 
 		// Add the assistant message to the session
@@ -313,6 +329,11 @@ impl GenericLayer {
 			cache_next_user_message: false,
 			pending_image: None,
 			spending_threshold_checkpoint: 0.0,
+			tool_confirmation: Default::default(),
+			spending_limit_reached: false,
+			rate_limit: Default::default(),
+			context_model: Default::default(),
+			run_layers_next: false,
 		}
 	}
 
@@ -331,7 +352,9 @@ impl GenericLayer {
 			Option<Vec<crate::mcp::McpToolCall>>,
 		)>,
 	> {
-		// Add each tool result as a tool message
+		// Add each tool result as a tool message - results of calls parsed from content
+		// go back together as one user message
+		let mut content_call_results = Vec::new();
 		for tool_result in &tool_results {
 			let tool_content = if let Some(output) = tool_result.result.get("output") {
 				if let Some(output_str) = output.as_str() {
@@ -343,6 +366,16 @@ impl GenericLayer {
 				serde_json::to_string(&tool_result.result).unwrap_or_default()
 			};
 
+			if crate::mcp::content_tool_calls::is_content_tool_call(&tool_result.tool_id) {
+				content_call_results.push(
+					crate::mcp::content_tool_calls::format_content_tool_result(
+						&tool_result.tool_name,
+						&tool_content,
+					),
+				);
+				continue;
+			}
+
 			layer_session.session.messages.push(Message {
 				role: "tool".to_string(),
 				content: tool_content,
@@ -355,6 +388,25 @@ impl GenericLayer {
 				name: Some(tool_result.tool_name.clone()),
 				tool_calls: None,
 				images: None,
+				content_parts: None,
+				model: None,
+			});
+		}
+		if !content_call_results.is_empty() {
+			layer_session.session.messages.push(Message {
+				role: "user".to_string(),
+				content: content_call_results.join("\n\n"),
+				timestamp: std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap_or_default()
+					.as_secs(),
+				cached: false,
+				tool_call_id: None,
+				name: None,
+				tool_calls: None,
+				images: None,
+				content_parts: None,
+				model: None,
 			});
 		}
 
@@ -377,7 +429,11 @@ impl GenericLayer {
 				let has_more_tools = if let Some(ref calls) = response.tool_calls {
 					!calls.is_empty()
 				} else {
-					!crate::mcp::parse_tool_calls(&response.content).is_empty()
+					!crate::mcp::parse_tool_calls(
+						&response.content,
+						layer_config.content_tool_call_format,
+					)
+					.is_empty()
 				};
 
 				if has_more_tools {
@@ -464,7 +520,7 @@ impl Layer for GenericLayer {
 			let tool_calls = if let Some(ref calls) = direct_tool_calls {
 				calls
 			} else {
-				&crate::mcp::parse_tool_calls(&output)
+				&crate::mcp::parse_tool_calls(&output, layer_config.content_tool_call_format)
 			};
 
 			// If there are tool calls, process them using this layer's MCP configuration
@@ -18,8 +18,42 @@ use anyhow::Result;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Whether events are also emitted as structured JSON (set from `[logging] format`)
+static JSON_EVENTS: AtomicBool = AtomicBool::new(false);
+
+/// Name of the structured event log inside the logs directory
+const EVENTS_LOG_FILE: &str = "events.jsonl";
+
+/// Whether full tool results go to a sidecar directory (set from `[logging] tool_results`)
+static SIDECAR_TOOL_RESULTS: AtomicBool = AtomicBool::new(false);
+
+/// Longest logged string in tool call parameters, 0 = unlimited (set from `[logging] max_param_bytes`)
+static MAX_PARAM_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Configure the logger output format (call once at startup)
+pub fn set_log_format(format: crate::config::LogFormat) {
+	JSON_EVENTS.store(
+		matches!(format, crate::config::LogFormat::Json),
+		Ordering::Relaxed,
+	);
+}
+
+/// Configure where full tool results are stored (call once at startup)
+pub fn set_tool_result_storage(storage: crate::config::ToolResultStorage) {
+	SIDECAR_TOOL_RESULTS.store(
+		matches!(storage, crate::config::ToolResultStorage::Sidecar),
+		Ordering::Relaxed,
+	);
+}
+
+/// Configure truncation of logged tool call parameters (call once at startup)
+pub fn set_max_param_bytes(max_bytes: usize) {
+	MAX_PARAM_BYTES.store(max_bytes, Ordering::Relaxed);
+}
+
 /// Get the session file path for a specific session (unified JSONL approach)
 pub fn get_session_log_file(session_name: &str) -> Result<PathBuf> {
 	let sessions_dir = crate::directories::get_sessions_dir()?;
@@ -49,7 +83,7 @@ pub fn log_session_stats(
 		"model": session_info.model,
 		"provider": session_info.provider
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -61,7 +95,7 @@ pub fn log_system_message(session_name: &str, content: &str) -> Result<()> {
 		"timestamp": get_timestamp(),
 		"content": content
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -73,7 +107,7 @@ pub fn log_user_input(session_name: &str, content: &str) -> Result<()> {
 		"timestamp": get_timestamp(),
 		"content": content
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -85,7 +119,7 @@ pub fn log_api_request(session_name: &str, request: &serde_json::Value) -> Resul
 		"timestamp": get_timestamp(),
 		"data": request
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -102,7 +136,7 @@ pub fn log_api_response(
 		"data": response,
 		"usage": usage
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -114,15 +148,70 @@ pub fn log_tool_call(
 	parameters: &serde_json::Value,
 ) -> Result<()> {
 	let log_file = get_session_log_file(session_name)?;
-	let log_entry = serde_json::json!({
+	let log_entry = tool_call_entry(
+		tool_name,
+		tool_id,
+		parameters,
+		MAX_PARAM_BYTES.load(Ordering::Relaxed),
+	);
+	write_entry(session_name, &log_file, log_entry)?;
+	Ok(())
+}
+
+// TOOL_CALL entry with the parameters cut to max_bytes per string. Cut parameters are
+// marked, since --replay-tools-from can no longer match them against a new call
+fn tool_call_entry(
+	tool_name: &str,
+	tool_id: &str,
+	parameters: &serde_json::Value,
+	max_bytes: usize,
+) -> serde_json::Value {
+	let logged_parameters = truncate_parameters(parameters, max_bytes);
+	let truncated = logged_parameters != *parameters;
+	let mut log_entry = serde_json::json!({
 		"type": "TOOL_CALL",
 		"timestamp": get_timestamp(),
 		"tool_name": tool_name,
 		"tool_id": tool_id,
-		"parameters": parameters
+		"parameters": logged_parameters
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
-	Ok(())
+	if truncated {
+		log_entry["parameters_truncated"] = serde_json::Value::Bool(true);
+	}
+	log_entry
+}
+
+// Copy of tool parameters with every string longer than max_bytes cut at a char boundary
+fn truncate_parameters(parameters: &serde_json::Value, max_bytes: usize) -> serde_json::Value {
+	use serde_json::Value;
+	if max_bytes == 0 {
+		return parameters.clone();
+	}
+	match parameters {
+		Value::String(s) if s.len() > max_bytes => {
+			let mut end = max_bytes;
+			while !s.is_char_boundary(end) {
+				end -= 1;
+			}
+			Value::String(format!(
+				"{}[... {} bytes truncated]",
+				&s[..end],
+				s.len() - end
+			))
+		}
+		Value::Array(items) => Value::Array(
+			items
+				.iter()
+				.map(|item| truncate_parameters(item, max_bytes))
+				.collect(),
+		),
+		Value::Object(map) => Value::Object(
+			map.iter()
+				.map(|(key, value)| (key.clone(), truncate_parameters(value, max_bytes)))
+				.collect(),
+		),
+		other => other.clone(),
+	}
 }
 
 /// Log tool response result with execution timing
@@ -132,18 +221,109 @@ pub fn log_tool_result(
 	result: &serde_json::Value,
 	execution_time_ms: u64,
 ) -> Result<()> {
+	// Nor a sidecar file for --no-save sessions
+	if crate::session::is_ephemeral() {
+		return Ok(());
+	}
 	let log_file = get_session_log_file(session_name)?;
-	let log_entry = serde_json::json!({
-		"type": "TOOL_RESULT",
-		"timestamp": get_timestamp(),
-		"tool_id": tool_id,
-		"result": result,
-		"execution_time_ms": execution_time_ms
-	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	let log_entry = if SIDECAR_TOOL_RESULTS.load(Ordering::Relaxed) {
+		store_sidecar_tool_result(session_name, tool_id, result)?;
+		serde_json::json!({
+			"type": "TOOL_RESULT",
+			"timestamp": get_timestamp(),
+			"tool_id": tool_id,
+			"result_ref": tool_id,
+			"execution_time_ms": execution_time_ms
+		})
+	} else {
+		serde_json::json!({
+			"type": "TOOL_RESULT",
+			"timestamp": get_timestamp(),
+			"tool_id": tool_id,
+			"result": result,
+			"execution_time_ms": execution_time_ms
+		})
+	};
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
+/// Get the sidecar directory holding full tool results for a session
+pub fn get_tool_results_dir(session_name: &str) -> Result<PathBuf> {
+	let sessions_dir = crate::directories::get_sessions_dir()?;
+	Ok(sessions_dir.join(format!("{}.tool_results", session_name)))
+}
+
+fn sidecar_result_path(session_name: &str, tool_id: &str) -> Result<PathBuf> {
+	let file_name: String = tool_id
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	Ok(get_tool_results_dir(session_name)?.join(format!("{}.json", file_name)))
+}
+
+/// Persist a tool result in the sidecar store
+/// The first result for an id is the full tool output, so later copies never overwrite it
+fn store_sidecar_tool_result(
+	session_name: &str,
+	tool_id: &str,
+	result: &serde_json::Value,
+) -> Result<()> {
+	let path = sidecar_result_path(session_name, tool_id)?;
+	if path.exists() {
+		return Ok(());
+	}
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(&path, serde_json::to_string(result)?)?;
+	Ok(())
+}
+
+/// Resolve the full result of a TOOL_RESULT log entry, inline or from the sidecar store
+pub fn load_tool_result(
+	session_name: &str,
+	log_entry: &serde_json::Value,
+) -> Option<serde_json::Value> {
+	if let Some(result) = log_entry.get("result") {
+		return Some(result.clone());
+	}
+	let tool_id = log_entry.get("result_ref").and_then(|r| r.as_str())?;
+	let content = std::fs::read_to_string(sidecar_result_path(session_name, tool_id).ok()?).ok()?;
+	serde_json::from_str(&content).ok()
+}
+
+/// Full results of the tool calls recorded in a session log, by tool id
+pub fn load_tool_results(
+	session_name: &str,
+) -> Result<std::collections::HashMap<String, serde_json::Value>> {
+	let log_file = get_session_log_file(session_name)?;
+	let content = std::fs::read_to_string(&log_file)?;
+
+	let mut results = std::collections::HashMap::new();
+	for line in content.lines() {
+		let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+			continue;
+		};
+		if entry.get("type").and_then(|t| t.as_str()) != Some("TOOL_RESULT") {
+			continue;
+		}
+		let Some(tool_id) = entry.get("tool_id").and_then(|id| id.as_str()) else {
+			continue;
+		};
+		if let Some(result) = load_tool_result(session_name, &entry) {
+			results.insert(tool_id.to_string(), result);
+		}
+	}
+	Ok(results)
+}
+
 /// Log assistant response (final cleaned response shown to user)
 pub fn log_assistant_response(session_name: &str, content: &str) -> Result<()> {
 	let log_file = get_session_log_file(session_name)?;
@@ -152,7 +332,7 @@ pub fn log_assistant_response(session_name: &str, content: &str) -> Result<()> {
 		"timestamp": get_timestamp(),
 		"content": content
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -169,7 +349,7 @@ pub fn log_restoration_point(
 		"user_message": user_message,
 		"assistant_response": assistant_response
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -181,7 +361,7 @@ pub fn log_session_command(session_name: &str, command_line: &str) -> Result<()>
 		"timestamp": get_timestamp(),
 		"command": command_line
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -194,7 +374,7 @@ pub fn log_cache_operation(session_name: &str, operation: &str, details: &str) -
 		"operation": operation,
 		"details": details
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -206,7 +386,7 @@ pub fn log_error(session_name: &str, error: &str) -> Result<()> {
 		"timestamp": get_timestamp(),
 		"error": error
 	});
-	append_to_log(&log_file, &serde_json::to_string(&log_entry)?)?;
+	write_entry(session_name, &log_file, log_entry)?;
 	Ok(())
 }
 
@@ -218,6 +398,43 @@ fn get_timestamp() -> u64 {
 		.as_secs()
 }
 
+/// Write a session log entry and, in JSON format mode, mirror it as a structured event
+fn write_entry(session_name: &str, log_file: &PathBuf, log_entry: serde_json::Value) -> Result<()> {
+	// --no-save sessions leave no log behind
+	if crate::session::is_ephemeral() {
+		return Ok(());
+	}
+	append_to_log(log_file, &serde_json::to_string(&log_entry)?)?;
+
+	if JSON_EVENTS.load(Ordering::Relaxed) {
+		emit_json_event(session_name, log_entry)?;
+	}
+	Ok(())
+}
+
+/// Emit one JSON object per event: timestamp, event type, session and payload
+fn emit_json_event(session_name: &str, mut log_entry: serde_json::Value) -> Result<()> {
+	let (event, timestamp) = match log_entry.as_object_mut() {
+		Some(fields) => (
+			fields.remove("type").unwrap_or(serde_json::Value::Null),
+			fields
+				.remove("timestamp")
+				.unwrap_or_else(|| serde_json::json!(get_timestamp())),
+		),
+		None => (serde_json::Value::Null, serde_json::json!(get_timestamp())),
+	};
+
+	let event_entry = serde_json::json!({
+		"timestamp": timestamp,
+		"event": event,
+		"session": session_name,
+		"payload": log_entry
+	});
+
+	let events_file = crate::directories::get_logs_dir()?.join(EVENTS_LOG_FILE);
+	append_to_log(&events_file, &serde_json::to_string(&event_entry)?)
+}
+
 /// Helper to append to log file ensuring single lines
 fn append_to_log(log_file: &PathBuf, content: &str) -> Result<()> {
 	let mut file = OpenOptions::new()
@@ -260,3 +477,49 @@ pub fn get_log_file() -> Result<PathBuf> {
 	let log_file = logs_dir.join(format!("session_{}.jsonl", now.format("%Y-%m-%d")));
 	Ok(log_file)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_truncate_parameters() {
+		let parameters = serde_json::json!({
+			"command": "create",
+			"path": "src/main.rs",
+			"content": "é".repeat(10),
+			"lines": [1, 2],
+			"nested": { "text": "abcdefghij" }
+		});
+
+		let truncated = truncate_parameters(&parameters, 8);
+		assert_eq!(truncated["command"], "create");
+		assert_eq!(truncated["lines"], serde_json::json!([1, 2]));
+		// Cut at a char boundary: 4 two-byte characters fit into 8 bytes
+		assert_eq!(truncated["content"], "éééé[... 12 bytes truncated]");
+		assert_eq!(
+			truncated["nested"]["text"],
+			"abcdefgh[... 2 bytes truncated]"
+		);
+
+		// 0 keeps the parameters unchanged
+		assert_eq!(truncate_parameters(&parameters, 0), parameters);
+	}
+
+	#[test]
+	fn test_truncated_tool_call_not_replayable() {
+		let parameters = serde_json::json!({"command": "create", "content": "abcdefghij"});
+
+		let entry = tool_call_entry("text_editor", "call_1", &parameters, 8);
+		assert_eq!(entry["parameters_truncated"], true);
+		assert!(crate::mcp::replay::has_truncated_parameters(&entry));
+
+		// Nothing cut: the full parameters are logged and stay replayable
+		for max_bytes in [0, 10] {
+			let entry = tool_call_entry("text_editor", "call_1", &parameters, max_bytes);
+			assert_eq!(entry["parameters"], parameters);
+			assert!(entry.get("parameters_truncated").is_none());
+			assert!(!crate::mcp::replay::has_truncated_parameters(&entry));
+		}
+	}
+}
@@ -0,0 +1,384 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Session migration system
+//!
+//! This module upgrades stored session files when the session schema changes.
+//! Like config migrations, each version increment has a corresponding step in
+//! `migrate_session_content`; the rewrite is line-based so unrelated log
+//! entries are preserved untouched.
+
+use super::{load_session, Message, SessionInfo};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Current session schema version (stored in SUMMARY `session_info.version`)
+/// Version 2 records the model that produced each assistant message
+pub const CURRENT_SESSION_VERSION: u32 = 2;
+
+/// Legacy prefixed message lines that are converted to plain message JSON
+const LEGACY_MESSAGE_PREFIXES: [&str; 3] = ["SYSTEM: ", "USER: ", "ASSISTANT: "];
+
+/// Outcome of migrating a single session file
+#[derive(Debug, Default)]
+pub struct SessionMigrationReport {
+	pub name: String,
+	pub path: PathBuf,
+	pub from_version: u32,
+	/// Human readable list of changes (empty when the session is up to date)
+	pub changes: Vec<String>,
+	/// Set when the file was rewritten (never in dry-run mode)
+	pub backup_path: Option<PathBuf>,
+}
+
+impl SessionMigrationReport {
+	pub fn is_up_to_date(&self) -> bool {
+		self.changes.is_empty()
+	}
+}
+
+/// Migrate every stored session in the sessions directory
+pub fn migrate_all_sessions(dry_run: bool) -> Result<Vec<SessionMigrationReport>> {
+	let sessions_dir = super::get_sessions_dir()?;
+	let mut paths: Vec<PathBuf> = fs::read_dir(&sessions_dir)?
+		.filter_map(|entry| entry.ok().map(|e| e.path()))
+		.filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "jsonl"))
+		.collect();
+	paths.sort();
+
+	paths
+		.iter()
+		.map(|path| migrate_session_file(path, dry_run))
+		.collect()
+}
+
+/// Migrate a single session file, writing a `.jsonl.backup` copy before rewriting
+pub fn migrate_session_file(path: &Path, dry_run: bool) -> Result<SessionMigrationReport> {
+	let content = fs::read_to_string(path)
+		.context(format!("Failed to read session file {}", path.display()))?;
+
+	let mut report = SessionMigrationReport {
+		name: path
+			.file_stem()
+			.and_then(|s| s.to_str())
+			.unwrap_or_default()
+			.to_string(),
+		path: path.to_path_buf(),
+		from_version: detect_session_version(&content),
+		..Default::default()
+	};
+
+	let (mut lines, changes) = migrate_session_content(&content)?;
+	report.changes = changes;
+
+	// Repair: sessions without any SUMMARY load with reconstructed metadata every
+	// time; persist that metadata once so future loads see it
+	if !lines.iter().any(|line| is_summary_line(line)) {
+		let mut session = load_session(&path.to_path_buf())?;
+		session.info.version = CURRENT_SESSION_VERSION;
+		let summary = serde_json::json!({
+			"type": "SUMMARY",
+			"timestamp": session.info.created_at,
+			"session_info": &session.info
+		});
+		lines.insert(0, serde_json::to_string(&summary)?);
+		report
+			.changes
+			.push("reconstructed missing SUMMARY entry".to_string());
+	}
+
+	if report.changes.is_empty() || dry_run {
+		return Ok(report);
+	}
+
+	let backup_path = path.with_extension("jsonl.backup");
+	fs::copy(path, &backup_path).context("Failed to create session backup")?;
+
+	let mut upgraded = lines.join("\n");
+	upgraded.push('\n');
+	fs::write(path, upgraded).context("Failed to write upgraded session")?;
+	report.backup_path = Some(backup_path);
+
+	Ok(report)
+}
+
+/// Lowest schema version found in the session's SUMMARY entries (0 when unversioned)
+fn detect_session_version(content: &str) -> u32 {
+	content
+		.lines()
+		.filter_map(|line| serde_json::from_str::<Value>(line).ok())
+		.filter(|entry| entry.get("type").and_then(|t| t.as_str()) == Some("SUMMARY"))
+		.map(|entry| {
+			entry
+				.pointer("/session_info/version")
+				.and_then(|v| v.as_u64())
+				.unwrap_or(0) as u32
+		})
+		.min()
+		.unwrap_or(0)
+}
+
+fn is_summary_line(line: &str) -> bool {
+	serde_json::from_str::<Value>(line)
+		.ok()
+		.and_then(|entry| {
+			entry
+				.get("type")
+				.and_then(|t| t.as_str())
+				.map(|t| t == "SUMMARY")
+		})
+		.unwrap_or(false)
+}
+
+/// Upgrade session content line by line, returning the new lines and a change list
+fn migrate_session_content(content: &str) -> Result<(Vec<String>, Vec<String>)> {
+	let mut lines = Vec::new();
+	let mut legacy_converted = 0;
+	let mut summaries_upgraded = 0;
+	let mut messages_upgraded = 0;
+	let mut models_backfilled = 0;
+
+	// Before version 2 assistant messages had no model - take it from the API request
+	// logged right before each of them
+	let backfill_models = detect_session_version(content) < 2;
+	let mut session_model: Option<String> = None;
+	let mut request_model: Option<String> = None;
+
+	for line in content.lines() {
+		// Legacy prefixed entries become structured JSON entries
+		if let Some(converted) = convert_legacy_line(line)? {
+			legacy_converted += 1;
+			lines.push(converted);
+			continue;
+		}
+
+		let Ok(entry) = serde_json::from_str::<Value>(line) else {
+			lines.push(line.to_string());
+			continue;
+		};
+
+		if let Some(log_type) = entry.get("type").and_then(|t| t.as_str()) {
+			if log_type == "API_REQUEST" {
+				request_model = entry
+					.pointer("/data/model")
+					.and_then(|m| m.as_str())
+					.map(str::to_string);
+			}
+			if log_type == "SUMMARY" {
+				if let Some(model) = entry
+					.pointer("/session_info/model")
+					.and_then(|m| m.as_str())
+				{
+					session_model = Some(model.to_string());
+				}
+				let upgraded = upgrade_summary_entry(&entry)?;
+				if upgraded != entry {
+					summaries_upgraded += 1;
+					lines.push(serde_json::to_string(&upgraded)?);
+					continue;
+				}
+			}
+		} else if entry.get("role").is_some() && entry.get("content").is_some() {
+			// Round-trip messages so newly added fields get their defaults
+			if let Ok(mut message) = serde_json::from_value::<Message>(entry.clone()) {
+				if backfill_models && message.role == "assistant" && message.model.is_none() {
+					if let Some(model) = &request_model {
+						message.model = Some(qualified_model(model, session_model.as_deref()));
+						models_backfilled += 1;
+					}
+				}
+				let upgraded = serde_json::to_value(&message)?;
+				if upgraded != entry {
+					messages_upgraded += 1;
+					lines.push(serde_json::to_string(&upgraded)?);
+					continue;
+				}
+			}
+		}
+
+		lines.push(line.to_string());
+	}
+
+	let mut changes = Vec::new();
+	if legacy_converted > 0 {
+		changes.push(format!(
+			"converted {} legacy prefixed entries to JSON",
+			legacy_converted
+		));
+	}
+	if summaries_upgraded > 0 {
+		changes.push(format!(
+			"upgraded {} SUMMARY entries to version {}",
+			summaries_upgraded, CURRENT_SESSION_VERSION
+		));
+	}
+	if messages_upgraded > 0 {
+		changes.push(format!(
+			"filled defaults for new fields in {} messages",
+			messages_upgraded
+		));
+	}
+	if models_backfilled > 0 {
+		changes.push(format!(
+			"recorded the model of {} assistant messages",
+			models_backfilled
+		));
+	}
+
+	Ok((lines, changes))
+}
+
+/// Session model ("provider:model") for a model name from a request body, when they match
+fn qualified_model(request_model: &str, session_model: Option<&str>) -> String {
+	match session_model {
+		Some(session_model)
+			if session_model
+				.split_once(':')
+				.is_some_and(|(_, model)| model == request_model) =>
+		{
+			session_model.to_string()
+		}
+		_ => request_model.to_string(),
+	}
+}
+
+/// Convert a legacy `PREFIX: {json}` line into the current JSON format
+fn convert_legacy_line(line: &str) -> Result<Option<String>> {
+	if let Some(content) = line.strip_prefix("SUMMARY: ") {
+		let mut info: SessionInfo = serde_json::from_str(content)?;
+		info.version = CURRENT_SESSION_VERSION;
+		let entry = serde_json::json!({
+			"type": "SUMMARY",
+			"timestamp": info.created_at,
+			"session_info": info
+		});
+		return Ok(Some(serde_json::to_string(&entry)?));
+	}
+
+	if let Some(content) = line.strip_prefix("INFO: ") {
+		// Legacy INFO entries carry no usable stats (same handling as load_session)
+		let mut info: SessionInfo = serde_json::from_str(content)?;
+		info.input_tokens = 0;
+		info.output_tokens = 0;
+		info.cached_tokens = 0;
+		info.total_cost = 0.0;
+		info.duration_seconds = 0;
+		info.layer_stats = Vec::new();
+		info.tool_calls = 0;
+		info.total_api_time_ms = 0;
+		info.total_tool_time_ms = 0;
+		info.total_layer_time_ms = 0;
+		info.version = CURRENT_SESSION_VERSION;
+		let entry = serde_json::json!({
+			"type": "SUMMARY",
+			"timestamp": info.created_at,
+			"session_info": info
+		});
+		return Ok(Some(serde_json::to_string(&entry)?));
+	}
+
+	if let Some(content) = line.strip_prefix("RESTORATION_POINT: ") {
+		let mut entry = serde_json::from_str::<Value>(content).unwrap_or(Value::Null);
+		if !entry.is_object() {
+			entry = serde_json::json!({});
+		}
+		entry["type"] = "RESTORATION_POINT".into();
+		return Ok(Some(serde_json::to_string(&entry)?));
+	}
+
+	for prefix in LEGACY_MESSAGE_PREFIXES {
+		if let Some(content) = line.strip_prefix(prefix) {
+			if let Ok(message) = serde_json::from_str::<Message>(content) {
+				return Ok(Some(serde_json::to_string(&message)?));
+			}
+		}
+	}
+
+	Ok(None)
+}
+
+/// Re-serialize a SUMMARY entry's session info so missing fields get defaults
+fn upgrade_summary_entry(entry: &Value) -> Result<Value> {
+	let mut upgraded = entry.clone();
+	if let Some(info_value) = entry.get("session_info") {
+		let mut info: SessionInfo = serde_json::from_value(info_value.clone())?;
+		info.version = info.version.max(CURRENT_SESSION_VERSION);
+		upgraded["session_info"] = serde_json::to_value(&info)?;
+	}
+	Ok(upgraded)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_migrate_session_content() {
+		let content = [
+			r#"SUMMARY: {"name":"old","created_at":1,"model":"openai:gpt-4o","provider":"openai","input_tokens":5,"output_tokens":6,"cached_tokens":0,"total_cost":0.1,"duration_seconds":2,"layer_stats":[]}"#,
+			r#"USER: {"role":"user","content":"hi","timestamp":1}"#,
+			r#"{"role":"assistant","content":"hello","timestamp":2}"#,
+			r#"{"type":"TOOL_CALL","tool_id":"t1"}"#,
+		]
+		.join("\n");
+
+		let (lines, changes) = migrate_session_content(&content).unwrap();
+		assert_eq!(lines.len(), 4);
+		assert_eq!(changes.len(), 2);
+
+		let summary: Value = serde_json::from_str(&lines[0]).unwrap();
+		assert_eq!(summary["type"], "SUMMARY");
+		assert_eq!(summary["session_info"]["version"], CURRENT_SESSION_VERSION);
+		assert_eq!(summary["session_info"]["tool_calls"], 0);
+
+		let user: Value = serde_json::from_str(&lines[1]).unwrap();
+		assert_eq!(user["role"], "user");
+		assert_eq!(user["cached"], false);
+
+		// Unrelated log entries are kept verbatim
+		assert_eq!(lines[3], r#"{"type":"TOOL_CALL","tool_id":"t1"}"#);
+
+		// Migrated content is already up to date
+		let (_, changes) = migrate_session_content(&lines.join("\n")).unwrap();
+		assert!(changes.is_empty());
+		assert_eq!(
+			detect_session_version(&lines.join("\n")),
+			CURRENT_SESSION_VERSION
+		);
+	}
+
+	#[test]
+	fn test_backfill_message_models() {
+		let content = [
+			r#"{"type":"SUMMARY","timestamp":1,"session_info":{"name":"s","created_at":1,"model":"openai:gpt-4o","provider":"openai","input_tokens":0,"output_tokens":0,"cached_tokens":0,"total_cost":0.0,"duration_seconds":0,"layer_stats":[],"version":1}}"#,
+			r#"{"role":"assistant","content":"welcome","timestamp":1,"cached":false}"#,
+			r#"{"type":"API_REQUEST","timestamp":2,"data":{"model":"gpt-4o"}}"#,
+			r#"{"role":"assistant","content":"first","timestamp":2,"cached":false}"#,
+			r#"{"type":"API_REQUEST","timestamp":3,"data":{"model":"o3-mini"}}"#,
+			r#"{"role":"assistant","content":"second","timestamp":3,"cached":false}"#,
+		]
+		.join("\n");
+
+		let (lines, changes) = migrate_session_content(&content).unwrap();
+		assert!(changes.contains(&"recorded the model of 2 assistant messages".to_string()));
+
+		let model = |line: &str| serde_json::from_str::<Value>(line).unwrap()["model"].clone();
+		// No request precedes the welcome message
+		assert_eq!(model(&lines[1]), Value::Null);
+		assert_eq!(model(&lines[3]), "openai:gpt-4o");
+		assert_eq!(model(&lines[5]), "o3-mini");
+	}
+}
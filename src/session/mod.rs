@@ -21,10 +21,14 @@ pub mod helper_functions; // Helper functions for layers and other components
 pub mod image; // Image processing and attachment utilities
 pub mod layers; // Layered architecture implementation
 pub mod logger; // Request/response logging utilities
+pub mod migrations; // Session file schema migrations
 mod model_utils; // Model-specific utility functions
+pub mod placeholders; // Placeholder registry and template validation
 mod project_context; // Project context collection and management
 					 // Provider abstraction layer moved to src/providers
+pub mod prompt_template; // Conditional prompt templates (template_engine = "handlebars")
 pub mod report; // Session usage reporting
+pub mod response_schema; // JSON schema constrained responses
 pub mod smart_summarizer; // Smart text summarization for context management
 mod token_counter; // Token counting utilities // Comprehensive caching system
 
@@ -38,7 +42,7 @@ pub use layers::{process_with_layers, InputMode, Layer, LayerConfig, LayerMcpCon
 pub use model_utils::model_supports_caching;
 pub use project_context::ProjectContext;
 pub use smart_summarizer::SmartSummarizer;
-pub use token_counter::{estimate_message_tokens, estimate_tokens}; // Export token counting functions // Export cache management
+pub use token_counter::{estimate_message_tokens, estimate_tokens, truncate_to_tokens}; // Export token counting functions // Export cache management
 
 // Re-export constants
 // Constants moved to config
@@ -69,6 +73,20 @@ pub struct Message {
 	pub tool_calls: Option<serde_json::Value>, // For assistant messages: original tool calls from API response
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub images: Option<Vec<crate::session::image::ImageAttachment>>, // For messages with image attachments
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub content_parts: Option<Vec<ContentPart>>, // For assistant messages: text and tool calls in response order
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub model: Option<String>, // For assistant messages: the model that produced the response
+}
+
+/// Ordered piece of an assistant response that interleaves text with tool calls
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ContentPart {
+	#[serde(rename = "text")]
+	Text { text: String },
+	#[serde(rename = "tool_call")]
+	ToolCall { tool_id: String, tool_name: String },
 }
 
 fn default_cache_marker() -> bool {
@@ -91,6 +109,8 @@ pub struct SessionInfo {
 	pub input_tokens: u64,
 	pub output_tokens: u64,
 	pub cached_tokens: u64, // Added to track cached tokens separately
+	#[serde(default)]
+	pub cache_write_tokens: u64, // Subset of input_tokens written to cache
 	pub total_cost: f64,
 	pub duration_seconds: u64,
 	pub layer_stats: Vec<LayerStats>, // Added to track per-layer statistics
@@ -103,6 +123,19 @@ pub struct SessionInfo {
 	pub total_tool_time_ms: u64, // Total time spent executing tools
 	#[serde(default)]
 	pub total_layer_time_ms: u64, // Total time spent in layer processing
+	// Session schema version (0 = written before versioning, see session::migrations)
+	#[serde(default)]
+	pub version: u32,
+	// Recap generated when the session ended (see exit_summary), shown on resume
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub exit_summary: Option<ExitSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExitSummary {
+	pub text: String,
+	pub created_at: u64,
+	pub message_count: usize, // Session messages when the summary was made
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -152,6 +185,7 @@ impl Session {
 				input_tokens: 0,
 				output_tokens: 0,
 				cached_tokens: 0,
+				cache_write_tokens: 0,
 				total_cost: 0.0,
 				duration_seconds: 0,
 				layer_stats: Vec::new(), // Initialize empty layer stats
@@ -160,6 +194,8 @@ impl Session {
 				total_api_time_ms: 0,
 				total_tool_time_ms: 0,
 				total_layer_time_ms: 0,
+				version: migrations::CURRENT_SESSION_VERSION,
+				exit_summary: None,
 			},
 			messages: Vec::new(),
 			session_file: None,
@@ -183,6 +219,8 @@ impl Session {
 			name: None,         // Default to no name
 			tool_calls: None,   // Default to no tool_calls
 			images: None,       // Default to no images
+			content_parts: None,
+			model: None,
 		};
 
 		self.messages.push(message.clone());
@@ -368,6 +406,33 @@ pub fn list_available_sessions() -> Result<Vec<(String, SessionInfo)>, anyhow::E
 	Ok(sessions)
 }
 
+// Name of the most recently modified session, by session file mtime
+pub fn latest_session_name() -> Result<Option<String>, anyhow::Error> {
+	let sessions_dir = get_sessions_dir()?;
+	if !sessions_dir.exists() {
+		return Ok(None);
+	}
+
+	let mut latest: Option<(std::time::SystemTime, String)> = None;
+	for entry in std_fs::read_dir(sessions_dir)? {
+		let path = entry?.path();
+		if !path.is_file() || path.extension().is_none_or(|ext| ext != "jsonl") {
+			continue;
+		}
+		let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+			continue;
+		};
+		let Ok(modified) = path.metadata().and_then(|meta| meta.modified()) else {
+			continue;
+		};
+		if latest.as_ref().is_none_or(|(time, _)| modified > *time) {
+			latest = Some((modified, name.to_string()));
+		}
+	}
+
+	Ok(latest.map(|(_, name)| name))
+}
+
 // Helper function to load a session from file - optimized to use streams
 pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 	// Ensure the file exists
@@ -656,6 +721,7 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 			input_tokens: 0,
 			output_tokens: 0,
 			cached_tokens: 0,
+			cache_write_tokens: 0,
 			total_cost: 0.0,
 			duration_seconds: 0,
 			layer_stats: Vec::new(),
@@ -663,6 +729,8 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 			total_api_time_ms: 0,
 			total_tool_time_ms: 0,
 			total_layer_time_ms: 0,
+			version: 0,
+			exit_summary: None,
 		};
 
 		// Extract runtime state from log file
@@ -822,8 +890,24 @@ fn apply_command_to_runtime_state(state: &mut SessionRuntimeState, command_line:
 	}
 }
 
+// --no-save: nothing of the session is written to disk (set once at startup)
+static EPHEMERAL: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Keep sessions in memory only, skipping every session file write (--no-save)
+pub fn set_ephemeral(ephemeral: bool) {
+	EPHEMERAL.store(ephemeral, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Whether sessions are kept in memory only (--no-save)
+pub fn is_ephemeral() -> bool {
+	EPHEMERAL.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 // Helper function to append to session file ensuring single lines
 pub fn append_to_session_file(session_file: &PathBuf, content: &str) -> Result<(), anyhow::Error> {
+	if is_ephemeral() {
+		return Ok(());
+	}
 	let mut file = OpenOptions::new()
 		.create(true)
 		.append(true)
@@ -843,37 +927,204 @@ pub async fn create_system_prompt(
 	// Get mode-specific configuration
 	let (_, mcp_config, _, _, system_prompt_opt) = config.get_role_config(mode);
 
+	// A role's system_file is read on every call, so edits apply to the next session
+	let system_prompt = match config.get_role_system_prompt(mode) {
+		Ok(system_prompt) => system_prompt.unwrap_or_default(),
+		Err(e) => {
+			crate::log_error!("{:#} - using the inline system prompt", e);
+			system_prompt_opt.cloned().unwrap_or_default()
+		}
+	};
+
+	let system_prompt =
+		prompt_template::render_prompt_template(&system_prompt, project_dir, config, mode).await;
+
 	// For developer role, process placeholders to add project context
 	let mut prompt =
-		helper_functions::process_placeholders_async(system_prompt_opt.unwrap(), project_dir).await;
+		helper_functions::process_placeholders_async(&system_prompt, project_dir).await;
+
+	// Git status section, unless the prompt already places it with %{GIT_STATUS}
+	if config.include_git_status && !system_prompt.contains("%{GIT_STATUS}") {
+		if let Some(summary) = ProjectContext::git_status_summary(project_dir) {
+			prompt.push_str(&format!(
+				"\n\n==== GIT STATUS ====\n\n{}\n==== END GIT STATUS ====",
+				summary
+			));
+		}
+	}
+
+	// Project file tree section, unless the prompt already places one with %{GIT_TREE}
+	if config.inject_file_tree && !system_prompt.contains("%{GIT_TREE}") {
+		if let Some(tree) = ProjectContext::file_tree_summary(
+			project_dir,
+			config.file_tree_max_depth,
+			config.file_tree_max_files,
+		)
+		.await
+		{
+			prompt.push_str(&format!(
+				"\n\n==== PROJECT FILES ====\n\n{}\n==== END PROJECT FILES ====",
+				tree
+			));
+		}
+	}
 
 	// Add MCP tools information if enabled
 	if !mcp_config.server_refs.is_empty() {
 		let config_for_role = config.get_merged_config_for_role(mode);
 		let functions = crate::mcp::get_available_functions(&config_for_role).await;
 		if !functions.is_empty() {
-			prompt.push_str("\n\nYou have access to the following tools:");
-
-			for function in &functions {
-				prompt.push_str(&format!(
-					"\n\n- {} - {}",
-					function.name, function.description
-				));
-			}
+			prompt.push_str("\n\n");
+			prompt.push_str(&crate::mcp::format_prompt_tool_listing(
+				&functions,
+				config.prompt_tool_listing,
+				config.sort_prompt_tools,
+			));
 		}
 	}
 
 	prompt
 }
 
+/// Estimated tokens of the tool definitions of one MCP server, added to request size estimates
+pub const TOOL_DEFINITION_TOKENS_PER_SERVER: usize = 150;
+
+// Copy of the messages with the scratchpad notes appended to the system message
+fn with_scratchpad_notes(messages: &[Message], max_bytes: usize) -> Option<Vec<Message>> {
+	let section = crate::mcp::dev::scratchpad::prompt_section(max_bytes)?;
+	let mut messages = messages.to_vec();
+	let system = messages
+		.iter_mut()
+		.find(|message| message.role == "system")?;
+	system.content.push_str(&section);
+	Some(messages)
+}
+
+// Corrections asked for before a response that does not match the response schema fails
+const RESPONSE_SCHEMA_RETRIES: usize = 2;
+
 /// High-level function to send a chat completion with input validation and context management
 /// This function checks input size and prompts user for handling when limits are exceeded
+/// With a response schema set, final session responses are checked against it and the
+/// model is asked to correct a mismatching response up to RESPONSE_SCHEMA_RETRIES times
 pub async fn chat_completion_with_validation(
 	messages: &[Message],
 	model: &str,
 	temperature: f32,
 	config: &Config,
-	chat_session: Option<&mut crate::session::chat::session::ChatSession>,
+	mut chat_session: Option<&mut crate::session::chat::session::ChatSession>,
+	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+) -> Result<ProviderResponse> {
+	let schema = match &config.response_schema {
+		Some(schema) => schema,
+		None => {
+			return chat_completion_within_limits(
+				messages,
+				model,
+				temperature,
+				config,
+				chat_session,
+				cancellation_token,
+			)
+			.await
+		}
+	};
+
+	let mut attempt_messages = messages.to_vec();
+	let mut retries = 0;
+	loop {
+		let response = chat_completion_within_limits(
+			&attempt_messages,
+			model,
+			temperature,
+			config,
+			chat_session.as_deref_mut(),
+			cancellation_token.clone(),
+		)
+		.await?;
+
+		// Tool calls come before the final answer, only the final answer is checked
+		// A refusal is no answer to correct, so it is passed on as is
+		if response.refusal.is_some()
+			|| response
+				.tool_calls
+				.as_ref()
+				.is_some_and(|calls| !calls.is_empty())
+		{
+			return Ok(response);
+		}
+		let errors = response_schema::check_response(&response.content, schema);
+		if errors.is_empty() {
+			return Ok(response);
+		}
+
+		let mismatch = anyhow::anyhow!(
+			"Response does not match the response schema: {}",
+			errors.join("; ")
+		);
+		if retries == RESPONSE_SCHEMA_RETRIES {
+			return Err(mismatch);
+		}
+		crate::session::chat::retry_budget::take_retry(
+			"the response that does not match the schema",
+		)
+		.map_err(|e| anyhow::anyhow!("{}. {}", mismatch, e))?;
+		retries += 1;
+
+		use colored::Colorize;
+		println!(
+			"{}",
+			format!(
+				"⚠️  Response does not match the response schema ({} errors), asking for a correction ({}/{})...",
+				errors.len(),
+				retries,
+				RESPONSE_SCHEMA_RETRIES
+			)
+			.yellow()
+		);
+
+		// The rejected response is not kept in the session, but its cost is
+		if let Some(session) = chat_session.as_deref_mut() {
+			let _ = crate::session::chat::CostTracker::track_exchange_cost(
+				session,
+				&response.exchange,
+				config,
+			);
+		}
+		attempt_messages.push(plain_message("assistant", &response.content));
+		attempt_messages.push(plain_message(
+			"user",
+			&response_schema::correction_message(&errors),
+		));
+	}
+}
+
+// Request messages that are not part of the session history
+fn plain_message(role: &str, content: &str) -> Message {
+	Message {
+		role: role.to_string(),
+		content: content.to_string(),
+		timestamp: SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		cached: false,
+		tool_call_id: None,
+		name: None,
+		tool_calls: None,
+		images: None,
+		content_parts: None,
+		model: None,
+	}
+}
+
+// Send a chat completion after checking the input fits the model's context window
+async fn chat_completion_within_limits(
+	messages: &[Message],
+	model: &str,
+	temperature: f32,
+	config: &Config,
+	mut chat_session: Option<&mut crate::session::chat::session::ChatSession>,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<ProviderResponse> {
 	// Check for cancellation before starting
@@ -883,9 +1134,32 @@ pub async fn chat_completion_with_validation(
 		}
 	}
 
+	// Switch back from the rate limit fallback model once its cooldown has passed
+	let restored_model = chat_session
+		.as_deref_mut()
+		.and_then(|session| session.restore_model_after_rate_limit(config));
+	let model = restored_model.as_deref().unwrap_or(model);
+
+	// Session requests carry the current scratchpad notes in the system message
+	let with_scratchpad = if chat_session.is_some() && config.scratchpad_in_prompt {
+		with_scratchpad_notes(messages, config.scratchpad_prompt_max_bytes)
+	} else {
+		None
+	};
+	let messages = with_scratchpad.as_deref().unwrap_or(messages);
+
 	// Parse the model string and get the appropriate provider
 	let (provider, actual_model) = ProviderFactory::get_provider_for_model(model)?;
 
+	// Without native structured outputs, the response schema goes into the system message
+	let with_schema_instruction = match &config.response_schema {
+		Some(schema) if !provider.supports_structured_output(&actual_model) => {
+			response_schema::with_schema_instruction(messages, schema)
+		}
+		_ => None,
+	};
+	let messages = with_schema_instruction.as_deref().unwrap_or(messages);
+
 	// Get maximum input tokens for this provider/model (actual context window)
 	let max_input_tokens = provider.get_max_input_tokens(&actual_model);
 
@@ -894,9 +1168,8 @@ pub async fn chat_completion_with_validation(
 
 	// Add estimated tokens for tool definitions if MCP is configured
 	if !config.mcp.servers.is_empty() {
-		// More accurate estimate: ~150 tokens per tool definition on average
 		let tool_count = config.mcp.servers.len();
-		total_input_tokens += tool_count * 150;
+		total_input_tokens += tool_count * TOOL_DEFINITION_TOKENS_PER_SERVER;
 	}
 
 	// Check if our total input exceeds what the provider can handle
@@ -940,15 +1213,32 @@ pub async fn chat_completion_with_validation(
 	}
 
 	// Input size is acceptable, proceed with API call
-	provider
-		.chat_completion(
-			messages,
-			&actual_model,
-			temperature,
-			config,
-			cancellation_token,
-		)
-		.await
+	// Session requests retry and fall back to rate_limit_fallback_model on repeated 429s
+	match chat_session {
+		Some(session) => {
+			crate::session::chat::session::chat_completion_with_fallback(
+				session,
+				provider,
+				actual_model,
+				messages,
+				temperature,
+				config,
+				cancellation_token,
+			)
+			.await
+		}
+		None => {
+			provider
+				.chat_completion(
+					messages,
+					&actual_model,
+					temperature,
+					config,
+					cancellation_token,
+				)
+				.await
+		}
+	}
 }
 
 /// Handle context limit exceeded by prompting user for action
@@ -1069,6 +1359,14 @@ pub async fn chat_completion_with_provider(
 	temperature: f32,
 	config: &Config,
 ) -> Result<ProviderResponse> {
+	// The response schema applies to session responses, not layer or command requests
+	let without_schema = config.response_schema.is_some().then(|| {
+		let mut config = config.clone();
+		config.response_schema = None;
+		config
+	});
+	let config = without_schema.as_ref().unwrap_or(config);
+
 	// Parse the model string and get the appropriate provider
 	let (provider, actual_model) = ProviderFactory::get_provider_for_model(model)?;
 
@@ -0,0 +1,190 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Placeholder registry - the %{NAME} variables resolved in prompts and welcome messages
+// Resolution lives in helper_functions; this module describes and validates them
+
+use anyhow::{anyhow, Result};
+
+/// Description of a prompt placeholder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaceholderInfo {
+	/// Name without the %{} wrapper, e.g. "CWD"
+	pub name: &'static str,
+	pub description: &'static str,
+	/// Value is gathered asynchronously (runs external commands)
+	pub is_async: bool,
+	pub example: &'static str,
+}
+
+impl PlaceholderInfo {
+	/// Placeholder as written in templates, e.g. "%{CWD}"
+	pub fn token(&self) -> String {
+		format!("%{{{}}}", self.name)
+	}
+}
+
+/// All placeholders known to the prompt resolver
+pub const PLACEHOLDERS: &[PlaceholderInfo] = &[
+	PlaceholderInfo {
+		name: "DATE",
+		description: "Current date and time with timezone",
+		is_async: true,
+		example: "2025-06-01 14:30:00 UTC",
+	},
+	PlaceholderInfo {
+		name: "SHELL",
+		description: "Current shell name and version",
+		is_async: true,
+		example: "zsh 5.9",
+	},
+	PlaceholderInfo {
+		name: "OS",
+		description: "Operating system information",
+		is_async: true,
+		example: "macOS 14.5 (arm64)",
+	},
+	PlaceholderInfo {
+		name: "BINARIES",
+		description: "Available development tools and their versions",
+		is_async: true,
+		example: "git: 2.45.0, cargo: 1.80.0, ...",
+	},
+	PlaceholderInfo {
+		name: "CWD",
+		description: "Current working directory",
+		is_async: false,
+		example: "/home/user/project",
+	},
+	PlaceholderInfo {
+		name: "ROLE",
+		description: "Current session role (developer, assistant, etc.)",
+		is_async: false,
+		example: "developer",
+	},
+	PlaceholderInfo {
+		name: "SYSTEM",
+		description: "Complete system information (date, shell, OS, binaries, CWD)",
+		is_async: true,
+		example: "==== SYSTEM INFORMATION ==== ...",
+	},
+	PlaceholderInfo {
+		name: "CONTEXT",
+		description: "Project context information (README, git status, git tree)",
+		is_async: false,
+		example: "==== PROJECT CONTEXT ==== ...",
+	},
+	PlaceholderInfo {
+		name: "GIT_STATUS",
+		description: "Git repository status",
+		is_async: false,
+		example: "==== GIT STATUS ==== ...",
+	},
+	PlaceholderInfo {
+		name: "GIT_TREE",
+		description: "Git file tree",
+		is_async: false,
+		example: "==== FILE TREE ==== ...",
+	},
+	PlaceholderInfo {
+		name: "README",
+		description: "Project README content",
+		is_async: false,
+		example: "==== README ==== ...",
+	},
+];
+
+/// Look up a placeholder by name ("CWD") or token ("%{CWD}")
+pub fn find_placeholder(name: &str) -> Option<&'static PlaceholderInfo> {
+	let name = name
+		.strip_prefix("%{")
+		.and_then(|n| n.strip_suffix('}'))
+		.unwrap_or(name);
+	PLACEHOLDERS.iter().find(|p| p.name == name)
+}
+
+/// Placeholder tokens referenced in a template, in order of first appearance
+pub fn referenced_placeholders(template: &str) -> Vec<String> {
+	let mut found = Vec::new();
+	let mut rest = template;
+	while let Some(start) = rest.find("%{") {
+		let after = &rest[start + 2..];
+		let name_len = after
+			.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+			.unwrap_or(after.len());
+		if name_len > 0 && after[name_len..].starts_with('}') {
+			let token = format!("%{{{}}}", &after[..name_len]);
+			if !found.contains(&token) {
+				found.push(token);
+			}
+		}
+		rest = after;
+	}
+	found
+}
+
+/// Referenced placeholders that are neither built in nor in `extra_known`
+/// (layer parameters are passed as extra names)
+pub fn unknown_placeholders(template: &str, extra_known: &[&str]) -> Vec<String> {
+	referenced_placeholders(template)
+		.into_iter()
+		.filter(|token| {
+			let name = &token[2..token.len() - 1];
+			find_placeholder(name).is_none() && !extra_known.contains(&name)
+		})
+		.collect()
+}
+
+/// Fail when a template references unknown placeholders, which would otherwise stay literal
+pub fn validate_placeholders(template: &str, extra_known: &[&str], source: &str) -> Result<()> {
+	let unknown = unknown_placeholders(template, extra_known);
+	if unknown.is_empty() {
+		return Ok(());
+	}
+	Err(anyhow!(
+		"{} references unknown placeholder{} {} (run `octomind vars` to list available placeholders)",
+		source,
+		if unknown.len() == 1 { "" } else { "s" },
+		unknown.join(", ")
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_referenced_placeholders() {
+		assert_eq!(
+			referenced_placeholders("Dir %{CWD}, role %{ROLE}, again %{CWD}"),
+			vec!["%{CWD}", "%{ROLE}"]
+		);
+		// Not placeholders: empty name, unterminated, invalid characters
+		assert!(referenced_placeholders("%{} %{CWD %{A-B} 100%").is_empty());
+	}
+
+	#[test]
+	fn test_unknown_placeholders() {
+		assert_eq!(
+			unknown_placeholders("%{CWDD} in %{CWD} for %{team_size}", &[]),
+			vec!["%{CWDD}", "%{team_size}"]
+		);
+		assert_eq!(
+			unknown_placeholders("%{CWDD} for %{team_size}", &["team_size"]),
+			vec!["%{CWDD}"]
+		);
+		assert!(validate_placeholders("%{SYSTEM}%{CONTEXT}", &[], "Role 'x'").is_ok());
+		assert!(validate_placeholders("%{CWDD}", &[], "Role 'x'").is_err());
+	}
+}
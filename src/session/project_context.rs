@@ -14,8 +14,10 @@
 
 // Project context module for gathering and managing contextual information
 
+use crate::mcp::McpToolCall;
 use anyhow::Result;
 use colored::*;
+use serde_json::json;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -287,6 +289,121 @@ impl ProjectContext {
 		None
 	}
 
+	/// Branch, upstream ahead/behind and a short list of changed files
+	/// None outside a git repository
+	pub fn git_status_summary(project_dir: &Path) -> Option<String> {
+		let output = Command::new("git")
+			.args(["status", "--porcelain=v1", "--branch"])
+			.current_dir(project_dir)
+			.output()
+			.ok()?;
+		if !output.status.success() {
+			return None;
+		}
+		Some(Self::format_git_status_summary(&String::from_utf8_lossy(
+			&output.stdout,
+		)))
+	}
+
+	/// Summarize `git status --porcelain=v1 --branch` output
+	fn format_git_status_summary(porcelain: &str) -> String {
+		const MAX_LISTED_FILES: usize = 20;
+
+		let mut lines = porcelain.lines();
+		let mut result = String::new();
+
+		// "## main...origin/main [ahead 1, behind 2]"
+		if let Some(header) = lines.next().and_then(|l| l.strip_prefix("## ")) {
+			let (refs, tracking) = match header.split_once(" [") {
+				Some((refs, tracking)) => (refs, Some(tracking.trim_end_matches(']'))),
+				None => (header, None),
+			};
+			match refs.split_once("...") {
+				Some((branch, upstream)) => {
+					result.push_str(&format!("Branch: {} (upstream {}", branch, upstream));
+					match tracking {
+						Some(tracking) => result.push_str(&format!(", {})\n", tracking)),
+						None => result.push_str(", up to date)\n"),
+					}
+				}
+				None => result.push_str(&format!("Branch: {}\n", refs)),
+			}
+		}
+
+		let files: Vec<&str> = lines.filter(|l| !l.trim().is_empty()).collect();
+		if files.is_empty() {
+			result.push_str("Working tree clean\n");
+			return result;
+		}
+
+		let untracked = files.iter().filter(|l| l.starts_with("??")).count();
+		result.push_str(&format!(
+			"Changes: {} changed, {} untracked\n",
+			files.len() - untracked,
+			untracked
+		));
+		for file in files.iter().take(MAX_LISTED_FILES) {
+			result.push_str(file);
+			result.push('\n');
+		}
+		if files.len() > MAX_LISTED_FILES {
+			result.push_str(&format!(
+				"... and {} more\n",
+				files.len() - MAX_LISTED_FILES
+			));
+		}
+		result
+	}
+
+	/// Tree of the project files, listed like the list_files tool (.gitignore respected)
+	/// up to max_depth directory levels (0 = no limit) and max_files files
+	/// None when no files could be listed
+	pub async fn file_tree_summary(
+		project_dir: &Path,
+		max_depth: usize,
+		max_files: usize,
+	) -> Option<String> {
+		let mut parameters = json!({ "directory": project_dir.to_string_lossy() });
+		if max_depth > 0 {
+			parameters["max_depth"] = json!(max_depth);
+		}
+		let call = McpToolCall {
+			tool_name: "list_files".to_string(),
+			parameters,
+			tool_id: String::new(),
+		};
+		let listing = crate::mcp::fs::execute_list_files(&call, None)
+			.await
+			.ok()?
+			.result;
+		if !listing["success"].as_bool().unwrap_or(false) {
+			return None;
+		}
+		let files: Vec<&str> = listing["files"]
+			.as_array()?
+			.iter()
+			.filter_map(|file| file.as_str())
+			.collect();
+		Self::format_file_tree_summary(files, max_files)
+	}
+
+	/// Render at most max_files of the listed files as a tree
+	fn format_file_tree_summary(mut files: Vec<&str>, max_files: usize) -> Option<String> {
+		if files.is_empty() {
+			return None;
+		}
+		// Shallow files first, so a capped tree still shows every top-level entry
+		files.sort_by_key(|file| (file.matches('/').count(), *file));
+		let total = files.len();
+		files.truncate(max_files);
+
+		let mut tree = Self::build_tree_structure(&files.join("\n"));
+		if total > max_files {
+			tree.push_str(&format!("... and {} more files\n", total - max_files));
+		}
+		Some(tree)
+	}
+
 	/// Format the project context as a string for inclusion in system prompts
 	pub fn format_for_prompt(&self) -> String {
 		let mut result = String::new();
@@ -326,3 +443,54 @@ impl ProjectContext {
 		result
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_format_git_status_summary() {
+		let summary = ProjectContext::format_git_status_summary(
+			"## main...origin/main [ahead 1, behind 2]\n M src/lib.rs\nA  new.rs\n?? notes.txt\n",
+		);
+		assert_eq!(
+			summary,
+			"Branch: main (upstream origin/main, ahead 1, behind 2)\n\
+			 Changes: 2 changed, 1 untracked\n M src/lib.rs\nA  new.rs\n?? notes.txt\n"
+		);
+
+		assert_eq!(
+			ProjectContext::format_git_status_summary("## feature\n"),
+			"Branch: feature\nWorking tree clean\n"
+		);
+
+		let many: String = (0..25).map(|i| format!(" M file{}.rs\n", i)).collect();
+		let summary = ProjectContext::format_git_status_summary(&format!("## main\n{}", many));
+		assert!(summary.contains("Changes: 25 changed, 0 untracked"));
+		assert!(summary.contains("file19.rs"));
+		assert!(!summary.contains("file20.rs"));
+		assert!(summary.ends_with("... and 5 more\n"));
+	}
+
+	#[test]
+	fn test_format_file_tree_summary() {
+		let files = vec![
+			"src/session/mod.rs",
+			"Cargo.toml",
+			"src/main.rs",
+			"README.md",
+		];
+		assert_eq!(
+			ProjectContext::format_file_tree_summary(files.clone(), 10).unwrap(),
+			"├─ Cargo.toml\n├─ README.md\n└─ src/\n   ├─ main.rs\n   └─ session/\n      └─ mod.rs\n"
+		);
+
+		// The deepest files are left out first
+		let summary = ProjectContext::format_file_tree_summary(files, 3).unwrap();
+		assert!(summary.contains("main.rs"));
+		assert!(!summary.contains("session/"));
+		assert!(summary.ends_with("... and 1 more files\n"));
+
+		assert!(ProjectContext::format_file_tree_summary(Vec::new(), 10).is_none());
+	}
+}
@@ -0,0 +1,594 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Prompt templates for template_engine = "handlebars" - a minimal handlebars-like syntax
+// for role system prompts and welcome messages: {{name}}, {{#if name}}...{{else}}...{{/if}},
+// {{#unless name}}...{{/unless}}, {{#each name}}...{{this}}...{{/each}} and {{! comments }}
+// %{NAME} placeholders are resolved afterwards as usual
+
+use crate::config::{Config, TemplateEngine};
+use crate::session::helper_functions::gather_system_info;
+use crate::session::project_context::ProjectContext;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Value of a template variable
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+	Text(String),
+	List(Vec<String>),
+}
+
+impl TemplateValue {
+	fn flag(value: bool) -> Self {
+		TemplateValue::Text(if value { "true" } else { "" }.to_string())
+	}
+}
+
+/// Variables available to a template by name
+pub type TemplateContext = HashMap<String, TemplateValue>;
+
+/// Variables known to prompt templates, with descriptions (lists can be iterated with #each)
+pub const TEMPLATE_VARIABLES: &[(&str, &str)] = &[
+	("role", "Current session role"),
+	("cwd", "Current working directory"),
+	("date", "Current date and time with timezone"),
+	("shell", "Current shell name and version"),
+	("os", "Operating system information"),
+	("binaries", "List of development tools and their versions"),
+	(
+		"git_status",
+		"Git repository status (empty outside a repository)",
+	),
+	("git_tree", "Git file tree (empty outside a repository)"),
+	("readme", "Project README content (empty without a README)"),
+	("servers", "List of MCP servers enabled for the role"),
+	("tools", "List of MCP tool names available to the role"),
+	(
+		"layers",
+		"Set when layered processing is enabled for the role",
+	),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+	Text(String),
+	Variable(String),
+	If {
+		name: String,
+		negate: bool,
+		then: Vec<Node>,
+		otherwise: Vec<Node>,
+	},
+	Each {
+		name: String,
+		body: Vec<Node>,
+		otherwise: Vec<Node>,
+	},
+}
+
+enum Token {
+	Text(String),
+	Tag(String),
+}
+
+// Block tags alone on their line take the whole line with them, so they leave no blank lines
+fn is_block_tag(tag: &str) -> bool {
+	tag.starts_with('#') || tag.starts_with('/') || tag.starts_with('!') || tag == "else"
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>> {
+	let mut tokens = Vec::new();
+	let mut rest = template;
+	// The text between the start of the current line and rest is whitespace only
+	let mut line_clean = true;
+	while let Some(start) = rest.find("{{") {
+		let end = rest[start..]
+			.find("}}")
+			.map(|end| start + end)
+			.ok_or_else(|| anyhow!("Unclosed '{{{{' in template"))?;
+		let tag = rest[start + 2..end].trim();
+		let before = &rest[..start];
+		let after = &rest[end + 2..];
+
+		let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+		let line_end = after.find('\n');
+		let standalone = is_block_tag(tag)
+			&& (line_clean || line_start > 0)
+			&& before[line_start..].trim().is_empty()
+			&& after[..line_end.unwrap_or(after.len())].trim().is_empty();
+
+		if standalone {
+			tokens.push(Token::Text(before[..line_start].to_string()));
+			tokens.push(Token::Tag(tag.to_string()));
+			rest = line_end.map(|i| &after[i + 1..]).unwrap_or("");
+			line_clean = true;
+		} else {
+			tokens.push(Token::Text(before.to_string()));
+			tokens.push(Token::Tag(tag.to_string()));
+			rest = after;
+			line_clean = false;
+		}
+	}
+	tokens.push(Token::Text(rest.to_string()));
+	Ok(tokens)
+}
+
+fn is_variable_name(name: &str) -> bool {
+	let name = name.strip_prefix('@').unwrap_or(name);
+	!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Nodes up to the closing tag of block (the whole template when None),
+// split at {{else}} into (nodes, else nodes)
+fn parse_nodes(
+	tokens: &mut impl Iterator<Item = Token>,
+	block: Option<&str>,
+) -> Result<(Vec<Node>, Vec<Node>)> {
+	let mut nodes = Vec::new();
+	let mut otherwise: Option<Vec<Node>> = None;
+	while let Some(token) = tokens.next() {
+		let target = match otherwise.as_mut() {
+			Some(otherwise) => otherwise,
+			None => &mut nodes,
+		};
+		let tag = match token {
+			Token::Text(text) => {
+				if !text.is_empty() {
+					target.push(Node::Text(text));
+				}
+				continue;
+			}
+			Token::Tag(tag) => tag,
+		};
+
+		if tag.starts_with('!') {
+			continue;
+		}
+		if tag == "else" {
+			if block.is_none() || otherwise.is_some() {
+				return Err(anyhow!("Unexpected {{{{else}}}} in template"));
+			}
+			otherwise = Some(Vec::new());
+			continue;
+		}
+		if let Some(name) = tag.strip_prefix('/') {
+			let name = name.trim();
+			return match block {
+				Some(block) if block == name => Ok((nodes, otherwise.unwrap_or_default())),
+				Some(block) => Err(anyhow!(
+					"{{{{/{}}}}} closes {{{{#{}}}}} in template",
+					name,
+					block
+				)),
+				None => Err(anyhow!("Unexpected {{{{/{}}}}} in template", name)),
+			};
+		}
+		if let Some(block_tag) = tag.strip_prefix('#') {
+			let (helper, name) = block_tag
+				.split_once(char::is_whitespace)
+				.unwrap_or((block_tag, ""));
+			let name = name.trim().to_string();
+			if !matches!(helper, "if" | "unless" | "each") {
+				return Err(anyhow!(
+					"Unknown block helper {{{{#{}}}}} in template (supported: if, unless, each)",
+					helper
+				));
+			}
+			if !is_variable_name(&name) {
+				return Err(anyhow!(
+					"{{{{#{}}}}} needs a variable name in template",
+					block_tag
+				));
+			}
+			let (body, block_otherwise) = parse_nodes(tokens, Some(helper))?;
+			target.push(match helper {
+				"if" | "unless" => Node::If {
+					name,
+					negate: helper == "unless",
+					then: body,
+					otherwise: block_otherwise,
+				},
+				"each" => Node::Each {
+					name,
+					body,
+					otherwise: block_otherwise,
+				},
+				_ => unreachable!("helpers are checked above"),
+			});
+			continue;
+		}
+		if !is_variable_name(&tag) {
+			return Err(anyhow!("Invalid tag {{{{{}}}}} in template", tag));
+		}
+		target.push(Node::Variable(tag));
+	}
+
+	match block {
+		Some(block) => Err(anyhow!("Unclosed {{{{#{}}}}} in template", block)),
+		None => Ok((nodes, Vec::new())),
+	}
+}
+
+fn parse(template: &str) -> Result<Vec<Node>> {
+	let tokens = tokenize(template)?;
+	Ok(parse_nodes(&mut tokens.into_iter(), None)?.0)
+}
+
+// Current item of the innermost #each
+struct Scope<'a> {
+	item: &'a str,
+	index: usize,
+	len: usize,
+}
+
+enum Resolved<'a> {
+	Text(String),
+	List(&'a [String]),
+}
+
+impl Resolved<'_> {
+	fn is_truthy(&self) -> bool {
+		match self {
+			Resolved::Text(text) => {
+				let text = text.trim();
+				!text.is_empty() && text != "false" && text != "0"
+			}
+			Resolved::List(items) => !items.is_empty(),
+		}
+	}
+}
+
+fn resolve<'a>(name: &str, context: &'a TemplateContext, scope: Option<&Scope>) -> Resolved<'a> {
+	if let Some(scope) = scope {
+		let value = match name {
+			"this" => Some(scope.item.to_string()),
+			"@index" => Some(scope.index.to_string()),
+			"@first" => Some((scope.index == 0).to_string()),
+			"@last" => Some((scope.index + 1 == scope.len).to_string()),
+			_ => None,
+		};
+		if let Some(value) = value {
+			return Resolved::Text(value);
+		}
+	}
+	match context.get(name) {
+		Some(TemplateValue::Text(text)) => Resolved::Text(text.clone()),
+		Some(TemplateValue::List(items)) => Resolved::List(items),
+		// Unknown variables render empty and are false, as in handlebars
+		None => Resolved::Text(String::new()),
+	}
+}
+
+fn render_nodes(
+	nodes: &[Node],
+	context: &TemplateContext,
+	scope: Option<&Scope>,
+	output: &mut String,
+) {
+	for node in nodes {
+		match node {
+			Node::Text(text) => output.push_str(text),
+			Node::Variable(name) => match resolve(name, context, scope) {
+				Resolved::Text(text) => output.push_str(&text),
+				Resolved::List(items) => output.push_str(&items.join("\n")),
+			},
+			Node::If {
+				name,
+				negate,
+				then,
+				otherwise,
+			} => {
+				let branch = if resolve(name, context, scope).is_truthy() != *negate {
+					then
+				} else {
+					otherwise
+				};
+				render_nodes(branch, context, scope, output);
+			}
+			Node::Each {
+				name,
+				body,
+				otherwise,
+			} => {
+				let items: Vec<String> = match resolve(name, context, scope) {
+					Resolved::List(items) => items.to_vec(),
+					// A text value iterates over its non-empty lines
+					Resolved::Text(text) => text
+						.lines()
+						.filter(|line| !line.trim().is_empty())
+						.map(str::to_string)
+						.collect(),
+				};
+				render_items(&items, body, otherwise, context, output);
+			}
+		}
+	}
+}
+
+fn render_items(
+	items: &[String],
+	body: &[Node],
+	otherwise: &[Node],
+	context: &TemplateContext,
+	output: &mut String,
+) {
+	if items.is_empty() {
+		render_nodes(otherwise, context, None, output);
+		return;
+	}
+	for (index, item) in items.iter().enumerate() {
+		let scope = Scope {
+			item,
+			index,
+			len: items.len(),
+		};
+		render_nodes(body, context, Some(&scope), output);
+	}
+}
+
+/// Render a template with the given variables
+pub fn render(template: &str, context: &TemplateContext) -> Result<String> {
+	let nodes = parse(template)?;
+	let mut output = String::new();
+	render_nodes(&nodes, context, None, &mut output);
+	Ok(output)
+}
+
+fn add_variable(name: &str, found: &mut Vec<String>) {
+	if !name.starts_with('@') && name != "this" && !found.iter().any(|n| n == name) {
+		found.push(name.to_string());
+	}
+}
+
+fn collect_variables(nodes: &[Node], found: &mut Vec<String>) {
+	for node in nodes {
+		match node {
+			Node::Text(_) => {}
+			Node::Variable(name) => add_variable(name, found),
+			Node::If {
+				name,
+				then,
+				otherwise,
+				..
+			} => {
+				add_variable(name, found);
+				collect_variables(then, found);
+				collect_variables(otherwise, found);
+			}
+			Node::Each {
+				name,
+				body,
+				otherwise,
+			} => {
+				add_variable(name, found);
+				collect_variables(body, found);
+				collect_variables(otherwise, found);
+			}
+		}
+	}
+}
+
+/// Variables referenced in a template, in order of first appearance
+pub fn referenced_variables(template: &str) -> Result<Vec<String>> {
+	let mut found = Vec::new();
+	collect_variables(&parse(template)?, &mut found);
+	Ok(found)
+}
+
+/// Fail on template syntax errors and on variables that are not in TEMPLATE_VARIABLES
+pub fn validate_template(template: &str, source: &str) -> Result<()> {
+	let variables = referenced_variables(template).map_err(|e| anyhow!("{}: {}", source, e))?;
+	let unknown: Vec<String> = variables
+		.into_iter()
+		.filter(|name| !TEMPLATE_VARIABLES.iter().any(|(known, _)| known == name))
+		.collect();
+	if unknown.is_empty() {
+		return Ok(());
+	}
+	Err(anyhow!(
+		"{} references unknown template variable{} {}",
+		source,
+		if unknown.len() == 1 { "" } else { "s" },
+		unknown.join(", ")
+	))
+}
+
+// Values of the variables a template references - only those are gathered
+async fn template_context(
+	variables: &[String],
+	project_dir: &Path,
+	config: &Config,
+	role: &str,
+) -> TemplateContext {
+	let needs = |names: &[&str]| variables.iter().any(|v| names.contains(&v.as_str()));
+	let mut context = TemplateContext::new();
+
+	context.insert("role".to_string(), TemplateValue::Text(role.to_string()));
+	context.insert(
+		"cwd".to_string(),
+		TemplateValue::Text(project_dir.to_string_lossy().to_string()),
+	);
+
+	if needs(&["date", "shell", "os", "binaries"]) {
+		let info = gather_system_info().await;
+		context.insert(
+			"date".to_string(),
+			TemplateValue::Text(info.date_with_timezone),
+		);
+		context.insert("shell".to_string(), TemplateValue::Text(info.shell_info));
+		context.insert("os".to_string(), TemplateValue::Text(info.os_info));
+		context.insert(
+			"binaries".to_string(),
+			TemplateValue::List(info.binaries.lines().map(str::to_string).collect()),
+		);
+	}
+
+	if needs(&["git_status", "git_tree", "readme"]) {
+		let project = ProjectContext::collect(project_dir);
+		context.insert(
+			"git_status".to_string(),
+			TemplateValue::Text(project.git_status.unwrap_or_default()),
+		);
+		context.insert(
+			"git_tree".to_string(),
+			TemplateValue::Text(project.file_tree.unwrap_or_default()),
+		);
+		context.insert(
+			"readme".to_string(),
+			TemplateValue::Text(project.readme_content.unwrap_or_default()),
+		);
+	}
+
+	let (role_config, mcp_config, _, _, _) = config.get_role_config(role);
+	context.insert(
+		"layers".to_string(),
+		TemplateValue::flag(role_config.enable_layers),
+	);
+	if needs(&["servers"]) {
+		let servers = config
+			.get_enabled_servers_for_role(mcp_config)
+			.iter()
+			.map(|server| server.name().to_string())
+			.collect();
+		context.insert("servers".to_string(), TemplateValue::List(servers));
+	}
+	if needs(&["tools"]) {
+		let tools = if mcp_config.is_enabled() {
+			let config_for_role = config.get_merged_config_for_role(role);
+			crate::mcp::get_available_functions(&config_for_role)
+				.await
+				.into_iter()
+				.map(|function| function.name)
+				.collect()
+		} else {
+			Vec::new()
+		};
+		context.insert("tools".to_string(), TemplateValue::List(tools));
+	}
+
+	context
+}
+
+/// Render a role prompt (system prompt, welcome message) with template_engine
+/// The prompt is returned as is with the simple engine, or when it fails to render
+pub async fn render_prompt_template(
+	prompt: &str,
+	project_dir: &Path,
+	config: &Config,
+	role: &str,
+) -> String {
+	if config.template_engine != TemplateEngine::Handlebars || !prompt.contains("{{") {
+		return prompt.to_string();
+	}
+	let rendered = match referenced_variables(prompt) {
+		Ok(variables) => {
+			let context = template_context(&variables, project_dir, config, role).await;
+			render(prompt, &context)
+		}
+		Err(e) => Err(e),
+	};
+	match rendered {
+		Ok(rendered) => rendered,
+		Err(e) => {
+			crate::log_error!("{:#} - using the prompt as is", e);
+			prompt.to_string()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn context() -> TemplateContext {
+		let mut context = TemplateContext::new();
+		context.insert(
+			"role".to_string(),
+			TemplateValue::Text("developer".to_string()),
+		);
+		context.insert(
+			"tools".to_string(),
+			TemplateValue::List(vec!["text_editor".to_string(), "shell".to_string()]),
+		);
+		context.insert("servers".to_string(), TemplateValue::List(Vec::new()));
+		context.insert("layers".to_string(), TemplateValue::flag(false));
+		context
+	}
+
+	#[test]
+	fn test_render_variables_and_conditionals() {
+		let context = context();
+		assert_eq!(
+			render("Role {{ role }}{{! not shown }}", &context).unwrap(),
+			"Role developer"
+		);
+		assert_eq!(
+			render("{{#if tools}}tools{{else}}none{{/if}}", &context).unwrap(),
+			"tools"
+		);
+		assert_eq!(
+			render("{{#if servers}}servers{{else}}none{{/if}}", &context).unwrap(),
+			"none"
+		);
+		assert_eq!(
+			render("{{#unless layers}}no layers{{/unless}}", &context).unwrap(),
+			"no layers"
+		);
+		assert_eq!(render("[{{missing}}]", &context).unwrap(), "[]");
+	}
+
+	#[test]
+	fn test_render_each() {
+		let context = context();
+		assert_eq!(
+			render(
+				"{{#each tools}}{{@index}}:{{this}}{{#unless @last}}, {{/unless}}{{/each}}",
+				&context
+			)
+			.unwrap(),
+			"0:text_editor, 1:shell"
+		);
+		assert_eq!(
+			render(
+				"{{#each servers}}{{this}}{{else}}no servers{{/each}}",
+				&context
+			)
+			.unwrap(),
+			"no servers"
+		);
+	}
+
+	#[test]
+	fn test_standalone_block_lines() {
+		let template =
+			"Intro\n{{#if tools}}\nTools:\n{{#each tools}}\n- {{this}}\n{{/each}}\n{{/if}}\nEnd";
+		assert_eq!(
+			render(template, &context()).unwrap(),
+			"Intro\nTools:\n- text_editor\n- shell\nEnd"
+		);
+	}
+
+	#[test]
+	fn test_template_errors() {
+		assert!(render("{{#if tools}}open", &context()).is_err());
+		assert!(render("{{#if tools}}x{{/each}}", &context()).is_err());
+		assert!(render("{{/if}}", &context()).is_err());
+		assert!(render("{{#with tools}}x{{/with}}", &context()).is_err());
+		assert!(render("{{role", &context()).is_err());
+		assert!(validate_template("{{#if tools}}{{cwd}}{{/if}}", "Role 'x'").is_ok());
+		assert!(validate_template("{{#if tool}}x{{/if}}", "Role 'x'").is_err());
+	}
+}
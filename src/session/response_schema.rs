@@ -0,0 +1,315 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// JSON schema constrained responses (--response-schema and /schema)
+// Validation covers the common subset of JSON Schema: type, properties, required,
+// additionalProperties, items, enum, const, minItems/maxItems and anyOf/oneOf
+
+use super::Message;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+
+// Errors listed in a re-prompt, the rest are counted
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Read a JSON schema file, the schema must be a JSON object
+pub fn load_schema(path: &Path) -> Result<Value> {
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| anyhow!("Failed to read response schema {}: {}", path.display(), e))?;
+	let schema: Value = serde_json::from_str(&content).map_err(|e| {
+		anyhow!(
+			"Response schema {} is not valid JSON: {}",
+			path.display(),
+			e
+		)
+	})?;
+	if !schema.is_object() {
+		return Err(anyhow!(
+			"Response schema {} must be a JSON object",
+			path.display()
+		));
+	}
+	Ok(schema)
+}
+
+/// `response_format` request field for providers with native structured outputs
+/// Not strict: the response is validated against the schema afterwards anyway
+pub fn response_format(schema: &Value) -> Value {
+	let name: String = schema
+		.get("title")
+		.and_then(|t| t.as_str())
+		.unwrap_or("response")
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '-' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.take(64)
+		.collect();
+	json!({
+		"type": "json_schema",
+		"json_schema": {
+			"name": name,
+			"strict": false,
+			"schema": schema
+		}
+	})
+}
+
+/// Copy of the messages with the schema instruction appended to the system message,
+/// for providers without native structured outputs
+pub fn with_schema_instruction(messages: &[Message], schema: &Value) -> Option<Vec<Message>> {
+	let mut messages = messages.to_vec();
+	let system = messages
+		.iter_mut()
+		.find(|message| message.role == "system")?;
+	system.content.push_str(&format!(
+		"\n\n==== RESPONSE FORMAT ====\nYour final response MUST be a single JSON value that matches this JSON schema:\n{}\nRespond with the JSON only: no explanations and no markdown code fences.\n==== END RESPONSE FORMAT ====",
+		serde_json::to_string_pretty(schema).unwrap_or_default()
+	));
+	Some(messages)
+}
+
+/// Schema mismatches of a response, empty when it matches
+pub fn check_response(content: &str, schema: &Value) -> Vec<String> {
+	match serde_json::from_str::<Value>(strip_code_fence(content)) {
+		Ok(value) => validate(&value, schema),
+		Err(e) => vec![format!("the response is not valid JSON: {}", e)],
+	}
+}
+
+/// User message asking the model to answer again, listing the mismatches
+pub fn correction_message(errors: &[String]) -> String {
+	let mut message = String::from("Your response does not match the required JSON schema:\n");
+	for error in errors.iter().take(MAX_REPORTED_ERRORS) {
+		message.push_str(&format!("- {}\n", error));
+	}
+	if errors.len() > MAX_REPORTED_ERRORS {
+		message.push_str(&format!(
+			"- ... and {} more\n",
+			errors.len() - MAX_REPORTED_ERRORS
+		));
+	}
+	message.push_str("Respond again with only the corrected JSON.");
+	message
+}
+
+// Models without native support often wrap JSON in a ```json fence
+fn strip_code_fence(content: &str) -> &str {
+	let content = content.trim();
+	match content.strip_prefix("```") {
+		Some(rest) => {
+			let rest = rest.trim_start_matches(|c: char| c.is_ascii_alphanumeric());
+			rest.strip_suffix("```").unwrap_or(rest).trim()
+		}
+		None => content,
+	}
+}
+
+/// Validate a value against a JSON schema, one message per mismatch
+pub fn validate(value: &Value, schema: &Value) -> Vec<String> {
+	let mut errors = Vec::new();
+	validate_at(value, schema, "$", &mut errors);
+	errors
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+	let Some(schema) = schema.as_object() else {
+		// `true`/`false` schemas
+		if schema == &Value::Bool(false) {
+			errors.push(format!("{}: no value is allowed here", path));
+		}
+		return;
+	};
+
+	if let Some(expected) = schema.get("type") {
+		let types: Vec<&str> = match expected {
+			Value::String(t) => vec![t.as_str()],
+			Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+			_ => Vec::new(),
+		};
+		if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+			errors.push(format!(
+				"{}: expected {}, got {}",
+				path,
+				types.join(" or "),
+				type_name(value)
+			));
+			return;
+		}
+	}
+
+	if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+		if !allowed.contains(value) {
+			errors.push(format!(
+				"{}: {} is not one of {}",
+				path,
+				value,
+				Value::from(allowed.clone())
+			));
+		}
+	}
+	if let Some(expected) = schema.get("const") {
+		if value != expected {
+			errors.push(format!("{}: expected {}", path, expected));
+		}
+	}
+
+	for key in ["anyOf", "oneOf"] {
+		if let Some(options) = schema.get(key).and_then(|o| o.as_array()) {
+			let matching = options
+				.iter()
+				.filter(|option| validate(value, option).is_empty())
+				.count();
+			if matching == 0 || (key == "oneOf" && matching > 1) {
+				errors.push(format!(
+					"{}: must match {} of the {} schemas",
+					path,
+					if key == "oneOf" {
+						"exactly one"
+					} else {
+						"at least one"
+					},
+					key
+				));
+			}
+		}
+	}
+
+	match value {
+		Value::Object(object) => {
+			let properties = schema.get("properties").and_then(|p| p.as_object());
+			if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+				for name in required.iter().filter_map(|n| n.as_str()) {
+					if !object.contains_key(name) {
+						errors.push(format!("{}: missing required property '{}'", path, name));
+					}
+				}
+			}
+			for (name, property_value) in object {
+				let property_path = format!("{}.{}", path, name);
+				match properties.and_then(|p| p.get(name)) {
+					Some(property_schema) => {
+						validate_at(property_value, property_schema, &property_path, errors)
+					}
+					None => match schema.get("additionalProperties") {
+						Some(Value::Bool(false)) => {
+							errors.push(format!("{}: property is not allowed", property_path))
+						}
+						Some(additional @ Value::Object(_)) => {
+							validate_at(property_value, additional, &property_path, errors)
+						}
+						_ => {}
+					},
+				}
+			}
+		}
+		Value::Array(items) => {
+			if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+				if (items.len() as u64) < min {
+					errors.push(format!("{}: expected at least {} items", path, min));
+				}
+			}
+			if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+				if items.len() as u64 > max {
+					errors.push(format!("{}: expected at most {} items", path, max));
+				}
+			}
+			if let Some(item_schema) = schema.get("items") {
+				for (index, item) in items.iter().enumerate() {
+					validate_at(item, item_schema, &format!("{}[{}]", path, index), errors);
+				}
+			}
+		}
+		_ => {}
+	}
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+	match expected {
+		"integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+		"number" => value.is_number(),
+		other => type_name(value) == other,
+	}
+}
+
+fn type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "boolean",
+		Value::Number(_) => "number",
+		Value::String(_) => "string",
+		Value::Array(_) => "array",
+		Value::Object(_) => "object",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn person_schema() -> Value {
+		json!({
+			"type": "object",
+			"properties": {
+				"name": { "type": "string" },
+				"age": { "type": "integer" },
+				"role": { "enum": ["admin", "user"] },
+				"tags": { "type": "array", "items": { "type": "string" } }
+			},
+			"required": ["name", "age"],
+			"additionalProperties": false
+		})
+	}
+
+	#[test]
+	fn test_validate() {
+		let schema = person_schema();
+		assert!(validate(&json!({"name": "Ann", "age": 30, "tags": ["a"]}), &schema).is_empty());
+
+		let mut errors = validate(
+			&json!({"name": 1, "role": "root", "tags": ["a", 2], "extra": true}),
+			&schema,
+		);
+		errors.sort();
+		assert_eq!(
+			errors,
+			vec![
+				"$.extra: property is not allowed",
+				"$.name: expected string, got number",
+				"$.role: \"root\" is not one of [\"admin\",\"user\"]",
+				"$.tags[1]: expected string, got number",
+				"$: missing required property 'age'",
+			]
+		);
+		assert_eq!(
+			validate(&json!(1.5), &json!({"type": "integer"})),
+			vec!["$: expected integer, got number"]
+		);
+	}
+
+	#[test]
+	fn test_check_response() {
+		let schema = person_schema();
+		assert!(
+			check_response("```json\n{\"name\": \"Ann\", \"age\": 3}\n```", &schema).is_empty()
+		);
+		assert!(check_response("Here it is: {}", &schema)[0]
+			.starts_with("the response is not valid JSON"));
+	}
+}
@@ -399,6 +399,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 			},
 			Message {
 				role: "assistant".to_string(),
@@ -409,6 +411,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 			images: None,
+			content_parts: None,
+			model: None,
 			},
 		];
 
@@ -38,6 +38,26 @@ pub fn estimate_tokens(text: &str) -> usize {
 	tokens.len()
 }
 
+// Truncate text to at most max_tokens tokens (returns the text unchanged if it fits)
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+	let tokenizer = get_tokenizer();
+	let mut tokens = tokenizer.encode_ordinary(text);
+	if tokens.len() <= max_tokens {
+		return text.to_string();
+	}
+
+	tokens.truncate(max_tokens);
+	// The cut may land inside a multi-byte character - drop tokens until it decodes
+	while !tokens.is_empty() {
+		if let Ok(decoded) = tokenizer.decode(tokens.clone()) {
+			return decoded;
+		}
+		tokens.pop();
+	}
+
+	String::new()
+}
+
 // Estimate tokens for a full message list
 pub fn estimate_message_tokens(messages: &[crate::session::Message]) -> usize {
 	let mut total = 0;